@@ -1,3 +1,5 @@
 fn main() {
+  // 헤드리스 빌드에는 Tauri 설정과 아이콘이 필요하지 않습니다
+  #[cfg(feature = "gui")]
   tauri_build::build()
 }
@@ -0,0 +1,75 @@
+// Tauri 없이 라이브러리만으로 디스플레이 목록을 출력하고 프로필을 적용하는 예제
+//
+//   cargo run --no-default-features --example headless
+//   cargo run --no-default-features --example headless -- <profiles.json> <프로필 id>
+//
+// DSM_MOCK_BACKEND에 fixture 경로를 주면 실제 장치 대신 mock 백엔드를 사용합니다
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use display_sound_manager_lib::audio::{AudioBackend, SystemAudioBackend};
+use display_sound_manager_lib::display::layout::DEFAULT_DESKTOP_LIMIT;
+use display_sound_manager_lib::display::{DisplayBackend, SystemDisplayBackend};
+use display_sound_manager_lib::error::AppError;
+use display_sound_manager_lib::mock::MockBackend;
+use display_sound_manager_lib::profiles::{
+    apply_profile_with_events, read_profiles_file, validate_profile, CancellationToken,
+};
+use display_sound_manager_lib::store_lock::StoreLock;
+
+type Backends = (Arc<dyn DisplayBackend>, Arc<dyn AudioBackend>);
+
+fn backends() -> Result<Backends, AppError> {
+    match MockBackend::from_env() {
+        Some(mock) => {
+            let mock = mock?;
+            Ok((Arc::new(mock.clone()), Arc::new(mock)))
+        }
+        None => Ok((Arc::new(SystemDisplayBackend), Arc::new(SystemAudioBackend))),
+    }
+}
+
+fn run(args: &[String]) -> Result<(), AppError> {
+    let (displays, audio) = backends()?;
+    for display in displays.enumerate()? {
+        println!(
+            "{} (id {}): {}x{} at ({}, {}){}",
+            display.name,
+            display.id,
+            display.width,
+            display.height,
+            display.x,
+            display.y,
+            if display.is_primary { " primary" } else { "" }
+        );
+    }
+
+    let [profiles_file, profile_id] = args else {
+        return Ok(());
+    };
+    let profiles_file = PathBuf::from(profiles_file);
+    let profiles = {
+        let _lock = StoreLock::acquire(&profiles_file)?;
+        read_profiles_file(&profiles_file)?
+    };
+    let profile = profiles
+        .into_iter()
+        .find(|profile| &profile.id == profile_id)
+        .ok_or_else(|| AppError::ProfileNotFound { profile_id: profile_id.clone() })?;
+    validate_profile(&profile, DEFAULT_DESKTOP_LIMIT)?;
+
+    let report = apply_profile_with_events(&profile, &*displays, &*audio, &CancellationToken::new(), &|event| {
+        println!("{}: {}", event.name(), event.payload());
+    })?;
+    println!("backends: {:?}", report.backend_used);
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&args) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
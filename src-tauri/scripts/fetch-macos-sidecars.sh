@@ -0,0 +1,24 @@
+#!/bin/sh
+# macOS 빌드에 포함할 사이드카(displayplacer, SwitchAudioSource)를 준비합니다.
+# Tauri는 externalBin 파일 이름 끝에 타깃 트리플이 붙어 있기를 요구합니다.
+#   예: binaries/displayplacer-aarch64-apple-darwin
+set -e
+
+cd "$(dirname "$0")/.."
+
+TARGET_TRIPLE="${TARGET_TRIPLE:-$(rustc -vV | sed -n 's/^host: //p')}"
+mkdir -p binaries
+
+for tool in displayplacer SwitchAudioSource; do
+  src="$(command -v "$tool" || true)"
+  if [ -z "$src" ]; then
+    echo "$tool 을(를) 찾을 수 없습니다. 먼저 Homebrew로 설치하세요." >&2
+    exit 1
+  fi
+  dest="binaries/$tool-$TARGET_TRIPLE"
+  cp "$src" "$dest"
+  chmod 755 "$dest"
+  # 다운로드한 바이너리의 격리 속성이 번들에 상속되지 않도록 제거합니다
+  xattr -d com.apple.quarantine "$dest" 2>/dev/null || true
+  echo "$dest"
+done
@@ -0,0 +1,308 @@
+// Tauri 앱 초기화 (로그, 설정, 프로필, 트레이, 외부 연동)
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Listener, Manager};
+
+use crate::apply_queue::{ApplyQueue, ApplyRequest};
+use crate::audio::{set_audio_backend_preference, AudioBackend, SystemAudioBackend};
+use crate::commands::{
+    apply_profile_by_id, apply_profile_with_cancel, auto_apply_on_audio_device, auto_apply_on_display_change,
+    restart_remote_server, retain_last_events, run_schedules,
+};
+use crate::display::registry::StableDisplayBackend;
+use crate::display::{wait_for_stable_displays, DisplayBackend, SystemDisplayBackend};
+use crate::error::AppError;
+use crate::instance::{self, INSTANCE_FILE_NAME};
+use crate::locale::{detect_system_locale, set_current_locale, Locale};
+use crate::mock::{MockBackend, MOCK_BACKEND_ENV};
+use crate::monitor::{DeviceChange, DeviceMonitor, DEFAULT_POLL_INTERVAL_SECS};
+use crate::profile_store::ProfileStore;
+use crate::profiles::resolve_profile;
+use crate::schedules::read_schedules_file;
+use crate::state::{AppSettings, AppState, LOG_FILE_NAME, LOG_MAX_FILE_SIZE, LOG_RETAINED_FILES};
+use crate::sync::LockExt;
+use crate::tools::{set_command_templates_override, set_command_timeout, set_retry_policy};
+use crate::{hotkeys, midi, tray, ws};
+
+// 선택한 백엔드와, 실제 OS 백엔드이면 안정 id 등록부 (프로필 id 이전에 사용)
+type Backends = (Arc<dyn DisplayBackend>, Arc<dyn AudioBackend>, Option<Arc<StableDisplayBackend>>);
+
+// 디스플레이/오디오 백엔드 선택 (통합 테스트에서는 DSM_MOCK_BACKEND로 mock 사용)
+// 실제 디스플레이 백엔드는 OS id 대신 안정 id를 쓰도록 등록부로 감쌉니다 (mock은 fixture의 id 그대로)
+fn select_backends(registry_file: PathBuf) -> Backends {
+    let system = || {
+        let displays = Arc::new(StableDisplayBackend::load(SystemDisplayBackend, registry_file.clone()));
+        let backends: Backends = (displays.clone(), Arc::new(SystemAudioBackend), Some(displays));
+        backends
+    };
+    match MockBackend::from_env() {
+        Some(Ok(mock)) => {
+            log::warn!("{} 설정으로 mock 백엔드를 사용합니다", MOCK_BACKEND_ENV);
+            (Arc::new(mock.clone()), Arc::new(mock), None)
+        }
+        Some(Err(e)) => {
+            log::warn!("mock 백엔드를 사용할 수 없어 실제 백엔드를 사용합니다: {}", e);
+            system()
+        }
+        None => system(),
+    }
+}
+
+// 앱 시작 시 초기화 (로그, 설정, 프로필, 트레이, 외부 연동)
+pub fn setup(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    // 프로필 파일 경로 설정
+    let app_data_dir = app.path().app_data_dir()
+        .expect("Failed to get app data directory");
+    let profiles_file = app_data_dir.join("profiles.json");
+    let settings_file = app_data_dir.join("settings.json");
+    let log_dir = app_data_dir.join("logs");
+
+    // 파일 로그 설정 (크기 기준으로 교체, 이전 파일은 일부만 보관)
+    // 레벨은 log::set_max_level로 조절하므로 플러그인은 모든 레벨을 받습니다
+    app.handle().plugin(
+        tauri_plugin_log::Builder::default()
+            .level(log::LevelFilter::Trace)
+            .targets([
+                tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Stdout),
+                tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Folder {
+                    path: log_dir.clone(),
+                    file_name: Some(LOG_FILE_NAME.to_string()),
+                }),
+            ])
+            .max_file_size(LOG_MAX_FILE_SIZE)
+            .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepSome(LOG_RETAINED_FILES))
+            .build(),
+    )?;
+
+    // 앱 상태 초기화
+    let (display_backend, audio_backend, display_registry) =
+        select_backends(app_data_dir.join("display-registry.json"));
+    let app_state = AppState {
+        display_backend,
+        audio_backend,
+        profiles: ProfileStore::new(profiles_file),
+        schedules: Mutex::new(Vec::new()),
+        schedules_file: app_data_dir.join("schedules.json"),
+        settings: Mutex::new(AppSettings::default()),
+        settings_file,
+        log_file: log_dir.join(format!("{}.log", LOG_FILE_NAME)),
+        active_profile: Mutex::new(None),
+        active_profile_file: app_data_dir.join("active-profile.json"),
+        event_bus: ws::EventBus::new(app.handle()),
+        remote_server: Mutex::new(None),
+        midi: midi::MidiListener::start(app.handle().clone(), Vec::new()),
+        hotkeys: hotkeys::HotkeyManager::start(app.handle().clone()),
+        last_displays: Mutex::new(None),
+        last_audio_devices: Mutex::new(None),
+        journal_file: app_data_dir.join("apply-journal.json"),
+        pending_recovery: Mutex::new(None),
+        revert_point: Mutex::new(None),
+        last_known_good_file: app_data_dir.join("last-known-good.json"),
+        layout_slots: Mutex::new(Default::default()),
+        apply_cancel: Mutex::new(None),
+        apply_queue: ApplyQueue::new(),
+        device_monitor: DeviceMonitor::new(DEFAULT_POLL_INTERVAL_SECS),
+    };
+
+    // 설정 로드 및 로그 레벨 적용
+    match app_state.load_settings() {
+        Ok(settings) => *app_state.settings.lock_or_recover() = settings,
+        Err(e) => log::warn!("{}", e),
+    }
+    let (log_level, locale) = {
+        let settings = app_state.settings.lock_or_recover();
+        set_command_templates_override(settings.command_templates.clone());
+        set_command_timeout(settings.command_timeout_secs);
+        set_retry_policy(settings.retry_policy.clone());
+        set_audio_backend_preference(settings.audio_backend);
+        app_state.apply_queue.set_mode(settings.apply_queue_mode);
+        app_state.device_monitor.set_poll_interval(settings.monitor_poll_interval_secs);
+        app_state.midi.set_mappings(settings.midi_mappings.clone());
+        (settings.log_level.clone(), settings.locale.clone())
+    };
+    log::set_max_level(log_level.parse().unwrap_or(log::LevelFilter::Info));
+    set_current_locale(
+        locale
+            .as_deref()
+            .and_then(Locale::parse)
+            .unwrap_or_else(detect_system_locale),
+    );
+
+    // 기존 프로필 로드
+    if let Err(e) = app_state.profiles.reload() {
+        log::warn!("프로필 로드 실패: {}", e);
+    }
+    match read_schedules_file(&app_state.schedules_file) {
+        Ok(schedules) => *app_state.schedules.lock_or_recover() = schedules,
+        Err(e) => log::warn!("시각별 규칙 로드 실패: {}", e),
+    }
+
+    // 업데이트 후 처음 실행하면 OS id로 저장된 프로필의 디스플레이를 안정 id로 바꿉니다
+    if let Some(registry) = display_registry.filter(|registry| !registry.profiles_migrated()) {
+        match app_state.profiles.update(|profiles| registry.migrate_profile_ids(profiles)) {
+            Ok(true) => log::info!("프로필의 디스플레이 id를 안정 id로 바꿨습니다"),
+            Ok(false) => {}
+            Err(e) => log::warn!("프로필 디스플레이 id 변환 실패: {}", e),
+        }
+    }
+
+    // 프로필의 전역 단축키 등록 (실패한 단축키는 기록만 하고 계속, save_profile에서는 오류로 알림)
+    if let Err(e) = app_state.hotkeys.sync(&app_state.profiles.list()) {
+        log::warn!("단축키 등록 실패: {}", e);
+    }
+
+    // 이전 실행에서 마지막으로 적용한 프로필 (그사이 삭제되었으면 무시)
+    if let Some(record) = app_state.read_active_profile() {
+        if app_state.profiles.list().iter().any(|p| p.id == record.profile_id) {
+            *app_state.active_profile.lock_or_recover() = Some(record.profile_id);
+        }
+    }
+
+    // 이전 실행에서 적용 도중 종료되었으면 복구 대기
+    if let Some(journal) = app_state.read_apply_journal() {
+        log::warn!(
+            "중단된 적용 기록 발견 (id={}, 시작 {})",
+            journal.profile_id,
+            journal.started_at
+        );
+        *app_state.pending_recovery.lock_or_recover() = Some(journal);
+    }
+
+    // 복구를 기다리는 동안에는 시작 시 적용을 건너뜁니다
+    let startup_profile = app_state
+        .settings
+        .lock_or_recover()
+        .apply_on_startup
+        .clone()
+        .filter(|_| app_state.pending_recovery.lock_or_recover().is_none());
+    app.manage(app_state);
+
+    // 시작할 때 프로필 파일을 백업으로 되돌렸으면 알림 (창이 늦게 뜨면 get_profiles_recovery로 확인)
+    if let Some(recovery) = app.state::<AppState>().profiles.recovery() {
+        let _ = app.emit("profiles-recovered", recovery);
+    }
+
+    // 대기열이 바뀔 때마다 프론트엔드에 알림
+    let app_handle = app.handle().clone();
+    app.state::<AppState>().apply_queue.set_listener(move |queue| {
+        let _ = app_handle.emit("apply-queue-changed", queue);
+    });
+
+    // 장치 변경은 바뀐 목록과 함께 displays-changed / audio-devices-changed 이벤트로 알림
+    let app_handle = app.handle().clone();
+    let state = app.state::<AppState>();
+    state.device_monitor.subscribe(move |change| {
+        let _ = match change {
+            DeviceChange::Displays(displays) => app_handle.emit("displays-changed", displays),
+            DeviceChange::AudioDevices(devices) => app_handle.emit("audio-devices-changed", devices),
+        };
+    });
+    state.device_monitor.start(state.display_backend.clone(), state.audio_backend.clone());
+
+    // 시작 시 프로필 적용 (디스플레이 구성이 안정된 뒤)
+    if let Some(profile_id) = startup_profile {
+        let app_handle = app.handle().clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app_handle.state::<AppState>();
+            // 대기 중에도 cancel_apply로 취소할 수 있습니다
+            let cancel = state.begin_apply();
+            let stable = wait_for_stable_displays(
+                &*state.display_backend,
+                std::time::Duration::from_secs(15),
+                &cancel,
+            );
+            if let Err(e) = stable.await {
+                log::warn!("디스플레이 구성 확인 실패: {}", e);
+            }
+            state.finish_apply(&cancel);
+
+            // 그사이 들어온 다른 적용이 있으면 대기열에서 차례를 기다립니다
+            let result = tauri::async_runtime::spawn_blocking({
+                let (app_handle, profile_id) = (app_handle.clone(), profile_id.clone());
+                move || {
+                    let state = app_handle.state::<AppState>();
+                    let request = ApplyRequest::new(&profile_id, "full", "startup");
+                    state.apply_queue.run(request, || {
+                        // 대기 단계에서 쓰던 신호를 다시 등록 (대기 중에 취소했으면 그대로 취소됨)
+                        *state.apply_cancel.lock_or_recover() = Some(cancel.clone());
+                        let result = apply_profile_with_cancel(&app_handle, &profile_id, &cancel);
+                        state.finish_apply(&cancel);
+                        result
+                    })
+                }
+            })
+            .await;
+            match result {
+                Ok(Ok(report)) if report.cancelled() => {
+                    log::info!("시작 시 프로필 적용 취소: {}", profile_id)
+                }
+                Ok(Ok(_)) => log::info!("시작 시 프로필 적용 완료: {}", profile_id),
+                Ok(Err(e)) => log::warn!("시작 시 프로필 적용 실패 ({}): {}", profile_id, e),
+                Err(e) => log::warn!("시작 시 프로필 적용 실패 ({}): {}", profile_id, e),
+            }
+        });
+    }
+
+    #[cfg(target_os = "macos")]
+    crate::tools::clear_sidecar_quarantine();
+
+    // 가져오기, 이름 바꾸기 등 다른 명령으로 프로필이 바뀌어도 단축키를 맞춥니다
+    let app_handle = app.handle().clone();
+    app.listen("profiles-changed", move |_| {
+        let state = app_handle.state::<AppState>();
+        let profiles = state.profiles.list().clone();
+        if let Err(e) = state.hotkeys.sync(&profiles) {
+            log::warn!("단축키 등록 실패: {}", e);
+        }
+    });
+
+    retain_last_events(app.handle());
+    auto_apply_on_display_change(app.handle());
+    auto_apply_on_audio_device(app.handle());
+    run_schedules(app.handle());
+    tray::setup_tray(app.handle())?;
+
+    // 외부 연동 서버 (설정에서 켠 경우)
+    // settings.json을 직접 고쳐 토큰이 비어 있으면 새로 만들어 저장합니다
+    let remote_control = {
+        let state = app.state::<AppState>();
+        let mut settings = state.settings.lock_or_recover();
+        if settings.remote_control.enabled && settings.remote_control.ensure_token() {
+            if let Err(e) = state.save_settings(&settings) {
+                log::warn!("새 원격 제어 토큰을 저장하지 못했습니다: {}", e);
+            }
+        }
+        settings.remote_control.clone()
+    };
+    if remote_control.enabled {
+        let app_handle = app.handle().clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = restart_remote_server(&app_handle, &remote_control).await {
+                log::warn!("{}", e);
+            }
+        });
+    }
+
+    // 명령줄의 --apply 요청 (UI에서 적용한 것과 같은 이벤트를 보냅니다)
+    let app_handle = app.handle().clone();
+    let listening = instance::listen(&app_data_dir.join(INSTANCE_FILE_NAME), move |request| {
+        if request.command != "apply" {
+            return Err(AppError::UnsupportedPlatform { feature: request.command.clone() });
+        }
+        let profiles = app_handle.state::<AppState>().profiles.list().clone();
+        let profile_id = resolve_profile(&profiles, &request.profile)?.id.clone();
+        let report = apply_profile_by_id(&app_handle, &profile_id, "cli")?;
+        serde_json::to_value(report).map_err(|e| AppError::Internal { detail: e.to_string() })
+    });
+    if let Err(e) = listening {
+        log::warn!("명령줄 요청을 받을 수 없습니다: {}", e);
+    }
+
+    // 창 표시
+    if let Some(window) = app.get_webview_window("main") {
+        window.show()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,317 @@
+// 적용 요청 대기열
+// 수동 적용, 트레이, MIDI, 원격 제어, 시작 시 적용이 모두 이곳을 거쳐 한 번에 하나씩 실행됩니다
+// 같은 요청이 이미 실행 중이거나 대기 중이면 새로 실행하지 않고 그 결과를 함께 받습니다
+
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+
+use crate::error::AppError;
+use crate::sync::LockExt;
+
+// 실행 중인 적용이 있을 때 새 요청을 처리하는 방법
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplyQueueMode {
+    // 대기 중인 요청 뒤에 차례로 실행
+    Wait,
+    // 아직 시작하지 않은 다른 요청은 취소하고 마지막 요청만 실행
+    #[default]
+    Replace,
+}
+
+// 대기열의 적용 요청 하나
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct ApplyRequest {
+    // 프로필 id (배치 슬롯 복원처럼 프로필이 없는 적용은 "layout-slot:1" 같은 이름)
+    pub profile_id: String,
+    // 적용 범위 ("full", "partial", "audio_only", "displays_only", "layout", "recovery")
+    pub kind: String,
+    // 요청한 곳 ("command", "tray", "midi", "remote", "startup")
+    pub source: String,
+}
+
+impl ApplyRequest {
+    pub fn new(profile_id: &str, kind: &str, source: &str) -> Self {
+        Self {
+            profile_id: profile_id.to_string(),
+            kind: kind.to_string(),
+            source: source.to_string(),
+        }
+    }
+
+    // 같은 결과를 내는 요청인지 (요청한 곳은 비교하지 않음)
+    fn same_target(&self, other: &ApplyRequest) -> bool {
+        self.profile_id == other.profile_id && self.kind == other.kind
+    }
+}
+
+// get_apply_queue와 apply-queue-changed 이벤트로 보내는 대기열 상태
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ApplyQueueState {
+    pub mode: ApplyQueueMode,
+    pub running: Option<ApplyRequest>,
+    pub pending: Vec<ApplyRequest>,
+}
+
+type SharedResult = Result<Arc<dyn Any + Send + Sync>, AppError>;
+
+// 요청 하나의 결과를 기다리는 자리 (합쳐진 요청끼리 공유)
+#[derive(Default)]
+struct Slot {
+    result: Mutex<Option<SharedResult>>,
+}
+
+struct Entry {
+    ticket: u64,
+    request: ApplyRequest,
+    slot: Arc<Slot>,
+}
+
+#[derive(Default)]
+struct Inner {
+    mode: ApplyQueueMode,
+    next_ticket: u64,
+    running: Option<Entry>,
+    pending: VecDeque<Entry>,
+}
+
+impl Inner {
+    fn state(&self) -> ApplyQueueState {
+        ApplyQueueState {
+            mode: self.mode,
+            running: self.running.as_ref().map(|entry| entry.request.clone()),
+            pending: self.pending.iter().map(|entry| entry.request.clone()).collect(),
+        }
+    }
+}
+
+type Listener = Box<dyn Fn(&ApplyQueueState) + Send + Sync>;
+
+#[derive(Default)]
+pub struct ApplyQueue {
+    inner: Mutex<Inner>,
+    changed: Condvar,
+    listener: Mutex<Option<Listener>>,
+}
+
+impl ApplyQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_mode(&self, mode: ApplyQueueMode) {
+        self.inner.lock_or_recover().mode = mode;
+        self.notify();
+    }
+
+    // 대기열이 바뀔 때마다 호출 (이벤트 전송용)
+    pub fn set_listener(&self, listener: impl Fn(&ApplyQueueState) + Send + Sync + 'static) {
+        *self.listener.lock_or_recover() = Some(Box::new(listener));
+    }
+
+    pub fn state(&self) -> ApplyQueueState {
+        self.inner.lock_or_recover().state()
+    }
+
+    fn notify(&self) {
+        let state = self.state();
+        if let Some(listener) = self.listener.lock_or_recover().as_ref() {
+            listener(&state);
+        }
+    }
+
+    // 차례가 되면 apply를 실행하고 결과를 돌려줍니다 (호출한 스레드에서 기다림)
+    // 대기 중에 다른 요청으로 교체되면 ApplySuperseded
+    pub fn run<T: Clone + Send + Sync + 'static>(
+        &self,
+        request: ApplyRequest,
+        apply: impl FnOnce() -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let (ticket, slot) = {
+            let mut inner = self.inner.lock_or_recover();
+            let existing = inner
+                .running
+                .iter()
+                .chain(inner.pending.iter())
+                .find(|entry| entry.request.same_target(&request))
+                .map(|entry| entry.slot.clone());
+            match existing {
+                Some(slot) => {
+                    log::info!("적용 요청 합침: {} ({})", request.profile_id, request.kind);
+                    let inner = self.wait_for_result(inner, &slot);
+                    drop(inner);
+                    return downcast(slot.result.lock_or_recover().clone().unwrap());
+                }
+                None => {
+                    if inner.mode == ApplyQueueMode::Replace {
+                        for replaced in inner.pending.drain(..) {
+                            log::info!(
+                                "대기 중인 적용 교체: {} → {}",
+                                replaced.request.profile_id,
+                                request.profile_id
+                            );
+                            let superseded = AppError::ApplySuperseded {
+                                profile_id: replaced.request.profile_id.clone(),
+                                replaced_by: request.profile_id.clone(),
+                            };
+                            *replaced.slot.result.lock_or_recover() = Some(Err(superseded));
+                        }
+                        self.changed.notify_all();
+                    }
+                    let ticket = inner.next_ticket;
+                    inner.next_ticket += 1;
+                    let slot = Arc::new(Slot::default());
+                    inner.pending.push_back(Entry { ticket, request, slot: slot.clone() });
+                    (ticket, slot)
+                }
+            }
+        };
+        self.notify();
+
+        // 앞선 적용이 끝나고 맨 앞 차례가 될 때까지 대기
+        {
+            let mut inner = self.inner.lock_or_recover();
+            loop {
+                if slot.result.lock_or_recover().is_some() {
+                    drop(inner);
+                    return downcast(slot.result.lock_or_recover().clone().unwrap());
+                }
+                let first = inner.pending.front().map(|entry| entry.ticket);
+                if inner.running.is_none() && first == Some(ticket) {
+                    let entry = inner.pending.pop_front();
+                    inner.running = entry;
+                    break;
+                }
+                inner = self.changed.wait(inner).unwrap_or_else(|e| e.into_inner());
+            }
+        }
+        self.notify();
+
+        let finished = Finished { queue: self, slot: &slot };
+        let result = apply();
+        let shared: SharedResult =
+            result.clone().map(|value| Arc::new(value) as Arc<dyn Any + Send + Sync>);
+        *slot.result.lock_or_recover() = Some(shared);
+        drop(finished);
+        result
+    }
+
+    fn wait_for_result<'a>(
+        &self,
+        mut inner: std::sync::MutexGuard<'a, Inner>,
+        slot: &Slot,
+    ) -> std::sync::MutexGuard<'a, Inner> {
+        while slot.result.lock_or_recover().is_none() {
+            inner = self.changed.wait(inner).unwrap_or_else(|e| e.into_inner());
+        }
+        inner
+    }
+}
+
+// 실행이 끝나면 (적용 중 패닉이 나도) 다음 요청에 차례를 넘깁니다
+struct Finished<'a> {
+    queue: &'a ApplyQueue,
+    slot: &'a Slot,
+}
+
+impl Drop for Finished<'_> {
+    fn drop(&mut self) {
+        let mut result = self.slot.result.lock().unwrap_or_else(|e| e.into_inner());
+        if result.is_none() {
+            *result = Some(Err(AppError::Internal { detail: "apply panicked".to_string() }));
+        }
+        drop(result);
+        self.queue.inner.lock().unwrap_or_else(|e| e.into_inner()).running = None;
+        self.queue.changed.notify_all();
+        self.queue.notify();
+    }
+}
+
+fn downcast<T: Clone + 'static>(result: SharedResult) -> Result<T, AppError> {
+    let value = result?;
+    value.downcast_ref::<T>().cloned().ok_or_else(|| AppError::Internal {
+        detail: "apply queue result type mismatch".to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    // 첫 요청이 실행 중인 동안 나머지 요청을 넣고, 첫 요청을 끝낸 뒤 결과를 모읍니다
+    fn run_while_busy(
+        mode: ApplyQueueMode,
+        requests: &[&'static str],
+    ) -> (Vec<Result<String, AppError>>, Vec<String>) {
+        let queue = Arc::new(ApplyQueue::new());
+        queue.set_mode(mode);
+        let executed = Arc::new(Mutex::new(Vec::new()));
+        let (release, released) = mpsc::channel::<()>();
+
+        let first = {
+            let (queue, executed) = (queue.clone(), executed.clone());
+            thread::spawn(move || {
+                queue.run(ApplyRequest::new("a", "full", "test"), || {
+                    released.recv().unwrap();
+                    executed.lock_or_recover().push("a".to_string());
+                    Ok("a".to_string())
+                })
+            })
+        };
+        while queue.state().running.is_none() {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let mut waiting = Vec::new();
+        for &profile_id in requests {
+            let (queue, executed) = (queue.clone(), executed.clone());
+            waiting.push(thread::spawn(move || {
+                queue.run(ApplyRequest::new(profile_id, "full", "test"), || {
+                    executed.lock_or_recover().push(profile_id.to_string());
+                    Ok(profile_id.to_string())
+                })
+            }));
+            // 요청 순서를 고정하기 위해 대기열에 들어갈 시간을 둡니다
+            thread::sleep(Duration::from_millis(30));
+        }
+
+        release.send(()).unwrap();
+        let mut results = vec![first.join().unwrap()];
+        results.extend(waiting.into_iter().map(|handle| handle.join().unwrap()));
+        let executed = executed.lock_or_recover().clone();
+        assert_eq!(queue.state(), ApplyQueueState { mode, running: None, pending: Vec::new() });
+        (results, executed)
+    }
+
+    #[test]
+    fn wait_mode_runs_requests_in_order() {
+        let (results, executed) = run_while_busy(ApplyQueueMode::Wait, &["b", "c"]);
+        assert_eq!(executed, ["a", "b", "c"]);
+        assert!(results.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn replace_mode_keeps_only_latest_pending_request() {
+        let (results, executed) = run_while_busy(ApplyQueueMode::Replace, &["b", "c"]);
+        assert_eq!(executed, ["a", "c"]);
+        assert!(matches!(
+            &results[1],
+            Err(AppError::ApplySuperseded { profile_id, replaced_by })
+                if profile_id == "b" && replaced_by == "c"
+        ));
+        assert_eq!(results[2].as_deref().ok(), Some("c"));
+    }
+
+    #[test]
+    fn duplicate_requests_share_one_apply() {
+        let (results, executed) = run_while_busy(ApplyQueueMode::Wait, &["a", "b", "b"]);
+        assert_eq!(executed, ["a", "b"]);
+        let values: Vec<_> = results.iter().map(|result| result.as_deref().ok()).collect();
+        assert_eq!(values, [Some("a"), Some("a"), Some("b"), Some("b")]);
+    }
+}
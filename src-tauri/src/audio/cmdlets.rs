@@ -0,0 +1,82 @@
+// AudioDeviceCmdlets(Get-AudioDevice -List | ConvertTo-Json) 출력 해석
+// Windows에서 WASAPI를 사용할 수 없을 때 쓰며, 다른 플랫폼의 테스트에서도 확인할 수 있도록 플랫폼과 무관하게 둡니다
+
+use serde::Deserialize;
+
+use super::AudioDevice;
+use crate::error::AppError;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CmdletDevice {
+    name: String,
+    #[serde(rename = "ID")]
+    id: String,
+    // "Playback" 또는 "Recording"
+    #[serde(rename = "Type")]
+    device_type: String,
+    #[serde(default)]
+    default: bool,
+}
+
+// 장치가 하나면 ConvertTo-Json이 배열 대신 객체 하나를 출력합니다
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(CmdletDevice),
+    Many(Vec<CmdletDevice>),
+}
+
+pub fn parse_audio_devices(json: &str) -> Result<Vec<AudioDevice>, AppError> {
+    // 장치가 없으면 빈 출력
+    if json.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let devices = match serde_json::from_str(json.trim()) {
+        Ok(OneOrMany::One(device)) => vec![device],
+        Ok(OneOrMany::Many(devices)) => devices,
+        Err(e) => {
+            return Err(AppError::ToolFailed {
+                tool: "AudioDeviceCmdlets".to_string(),
+                detail: format!("unable to parse Get-AudioDevice output: {}", e),
+            })
+        }
+    };
+    Ok(devices
+        .into_iter()
+        .map(|device| AudioDevice {
+            device_type: if device.device_type.eq_ignore_ascii_case("recording") { "input" } else { "output" }
+                .to_string(),
+            id: device.id,
+            name: device.name,
+            is_default: device.default,
+            ..Default::default()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_single_object_and_array_output() {
+        let single = r#"{"Name": "스피커 (Realtek(R) Audio)", "ID": "{0.0.0.00000000}.{a1}", "Type": "Playback", "Default": true}"#;
+        let devices = parse_audio_devices(single).unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!((devices[0].name.as_str(), devices[0].device_type.as_str()), ("스피커 (Realtek(R) Audio)", "output"));
+        assert!(devices[0].is_default);
+
+        let many = r#"[
+            {"Name": "Headset", "ID": "{0.0.0.00000000}.{b2}", "Type": "Playback", "Default": false},
+            {"Name": "Microphone", "ID": "{0.0.1.00000000}.{c3}", "Type": "Recording", "Default": true}
+        ]"#;
+        let devices = parse_audio_devices(many).unwrap();
+        let summary: Vec<(&str, &str, bool)> =
+            devices.iter().map(|d| (d.id.as_str(), d.device_type.as_str(), d.is_default)).collect();
+        assert_eq!(summary, vec![("{0.0.0.00000000}.{b2}", "output", false), ("{0.0.1.00000000}.{c3}", "input", true)]);
+
+        assert!(parse_audio_devices("").unwrap().is_empty());
+        assert!(matches!(parse_audio_devices("Get-AudioDevice : not recognized"), Err(AppError::ToolFailed { .. })));
+    }
+}
@@ -0,0 +1,335 @@
+// Linux: PipeWire가 실행 중이면 PipeWire CLI 도구(pipewire_cli), 아니면 PulseAudio(호환 계층 포함)의 pactl로
+// 장치 id는 sink/source(노드) 이름, 표시 이름은 description입니다
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{audio_backend_preference, AudioBackendPreference, AudioDevice, AudioSettings};
+use crate::error::AppError;
+use crate::monitor::{watch_command_output, ChangeNotifier};
+use crate::tools::{decode_output, run_command, tool_command};
+
+mod pipewire_cli;
+
+// 실제로 사용할 백엔드 (설정으로 강제 지정하지 않으면 PipeWire 실행 여부로 결정)
+fn use_pipewire() -> bool {
+    match audio_backend_preference() {
+        AudioBackendPreference::Pipewire => true,
+        AudioBackendPreference::Pulse => false,
+        AudioBackendPreference::Auto => pipewire_cli::is_running(),
+    }
+}
+
+// get_system_info에 표시할 오디오 백엔드
+pub fn backend_name() -> String {
+    if use_pipewire() {
+        "PipeWire CLI (pw-dump, pw-metadata, wpctl)".to_string()
+    } else {
+        "PulseAudio (pactl)".to_string()
+    }
+}
+
+// 적용에 필요한 외부 도구
+pub fn apply_tools() -> Vec<String> {
+    let tools: &[&str] = if use_pipewire() { &["pw-dump", "pw-metadata", "wpctl"] } else { &["pactl"] };
+    tools.iter().map(|tool| tool.to_string()).collect()
+}
+
+pub fn list_audio_devices() -> Result<Vec<AudioDevice>, AppError> {
+    if use_pipewire() {
+        pipewire_cli::list_audio_devices()
+    } else {
+        let mut devices = list_devices("output")?;
+        devices.extend(list_devices("input")?);
+        Ok(devices)
+    }
+}
+
+pub fn apply_audio_settings(audio_settings: &AudioSettings) -> Result<String, AppError> {
+    if use_pipewire() {
+        pipewire_cli::apply_audio_settings(audio_settings)
+    } else {
+        apply_pulse_settings(audio_settings)
+    }
+}
+
+pub fn set_default_audio_device(device_type: &str, device: &str) -> Result<String, AppError> {
+    if use_pipewire() {
+        pipewire_cli::set_default_audio_device(device_type, device)
+    } else {
+        set_pulse_default(device_type, device)
+    }
+}
+
+pub fn set_default_volume(device_type: &str, volume: Option<u32>, muted: Option<bool>) -> Result<String, AppError> {
+    if use_pipewire() {
+        pipewire_cli::set_default_volume(device_type, volume, muted)
+    } else {
+        set_pulse_volume(device_type, volume, muted)?;
+        Ok("pactl".to_string())
+    }
+}
+
+// 장치와 기본 장치 변경 감시 (PipeWire도 pipewire-pulse로 같은 이벤트를 보냅니다)
+pub fn watch_audio_changes(notifier: ChangeNotifier) -> Result<String, AppError> {
+    watch_command_output("pactl", &["subscribe"], is_device_event, notifier)?;
+    Ok("pactl subscribe".to_string())
+}
+
+// 예: "Event 'new' on sink #58", "Event 'change' on server #-1"
+// 재생/녹음 스트림(sink-input, source-output) 이벤트는 장치 목록과 관계없으므로 무시합니다
+fn is_device_event(line: &str) -> bool {
+    let facility = line.split(" on ").nth(1).and_then(|rest| rest.split(" #").next());
+    matches!(facility, Some("sink" | "source" | "server" | "card"))
+}
+
+// PulseAudio (pactl)
+
+// PulseAudio 볼륨 100% (PA_VOLUME_NORM)
+const PA_VOLUME_NORM: u64 = 65536;
+
+// PulseAudio 볼륨(0~65536)을 0~100으로 (반올림, 100% 초과 증폭은 100으로)
+fn volume_to_percent(volume: u64) -> u32 {
+    ((volume.min(PA_VOLUME_NORM) * 100 + PA_VOLUME_NORM / 2) / PA_VOLUME_NORM) as u32
+}
+
+// 0~100을 PulseAudio 볼륨으로 (반올림)
+fn percent_to_volume(percent: u32) -> u64 {
+    (percent.min(100) as u64 * PA_VOLUME_NORM + 50) / 100
+}
+
+#[derive(Debug, Deserialize)]
+struct PactlDevice {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    mute: bool,
+    // 채널 이름 → 볼륨
+    #[serde(default)]
+    volume: HashMap<String, PactlVolume>,
+    #[serde(default)]
+    ports: Vec<PactlPort>,
+    #[serde(default)]
+    active_port: Option<String>,
+    // source에만 있으며, sink의 모니터 source이면 해당 sink 이름
+    #[serde(default)]
+    monitor_of_sink: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PactlVolume {
+    value: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PactlPort {
+    name: String,
+}
+
+// 재생/녹음 스트림 (sink-input, source-output)
+#[derive(Debug, Deserialize)]
+struct PactlStream {
+    index: u32,
+}
+
+fn pactl(args: &[&str]) -> Result<String, AppError> {
+    let output = run_command(tool_command("pactl").args(args))
+        .map_err(|e| AppError::tool_missing("pactl", &e))?;
+    if !output.status.success() {
+        return Err(AppError::tool_failed("pactl", &output));
+    }
+    Ok(decode_output(&output.stdout).trim().to_string())
+}
+
+fn parse_pactl_json<T: serde::de::DeserializeOwned>(json: &str) -> Result<T, AppError> {
+    serde_json::from_str(json).map_err(|e| AppError::ToolFailed {
+        tool: "pactl".to_string(),
+        detail: format!("unable to parse pactl -f json output: {}", e),
+    })
+}
+
+// 채널 볼륨의 평균 (pa_cvolume_avg와 같은 방식)
+fn average_volume(volume: &HashMap<String, PactlVolume>) -> Option<u32> {
+    if volume.is_empty() {
+        return None;
+    }
+    let total: u64 = volume.values().map(|channel| channel.value).sum();
+    Some(volume_to_percent(total / volume.len() as u64))
+}
+
+fn parse_devices(
+    json: &str,
+    device_type: &str,
+    default: &str,
+) -> Result<Vec<AudioDevice>, AppError> {
+    let devices: Vec<PactlDevice> = parse_pactl_json(json)?;
+    Ok(devices
+        .into_iter()
+        // sink의 모니터 source는 입력 장치로 보여 주지 않습니다
+        .filter(|device| device.monitor_of_sink.as_deref().is_none_or(|sink| sink == "n/a"))
+        .map(|device| AudioDevice {
+            is_default: device.name == default,
+            name: if device.description.is_empty() {
+                device.name.clone()
+            } else {
+                device.description.clone()
+            },
+            device_type: device_type.to_string(),
+            volume: average_volume(&device.volume),
+            muted: Some(device.mute),
+            ports: device.ports.into_iter().map(|port| port.name).collect(),
+            active_port: device.active_port,
+            id: device.name,
+            ..Default::default()
+        })
+        .collect())
+}
+
+// 출력은 sink, 입력은 source
+struct PactlKind {
+    kind: &'static str,
+    default_alias: &'static str,
+    streams: &'static str,
+    move_stream: &'static str,
+}
+
+fn pactl_kind(device_type: &str) -> PactlKind {
+    if device_type == "input" {
+        PactlKind {
+            kind: "source",
+            default_alias: "@DEFAULT_SOURCE@",
+            streams: "source-outputs",
+            move_stream: "move-source-output",
+        }
+    } else {
+        PactlKind {
+            kind: "sink",
+            default_alias: "@DEFAULT_SINK@",
+            streams: "sink-inputs",
+            move_stream: "move-sink-input",
+        }
+    }
+}
+
+fn list_devices(device_type: &str) -> Result<Vec<AudioDevice>, AppError> {
+    let kind = pactl_kind(device_type).kind;
+    let default = pactl(&[&format!("get-default-{}", kind)])?;
+    parse_devices(&pactl(&["-f", "json", "list", &format!("{}s", kind)])?, device_type, &default)
+}
+
+fn apply_pulse_settings(audio_settings: &AudioSettings) -> Result<String, AppError> {
+    // 프로필이 바뀌면 sink/source가 새로 만들어지므로 먼저 적용합니다
+    for (card, profile) in audio_settings.device_profiles.iter().flatten() {
+        pactl(&["set-card-profile", card, profile])?;
+    }
+
+    if let Some(output_device) = &audio_settings.output_device {
+        set_pulse_default("output", output_device)?;
+    }
+    if let Some(input_device) = &audio_settings.input_device {
+        set_pulse_default("input", input_device)?;
+    }
+
+    // 볼륨과 음소거는 (바뀐) 기본 장치에 적용합니다
+    set_pulse_volume("output", Some(audio_settings.output_volume), audio_settings.output_muted)?;
+    set_pulse_volume("input", Some(audio_settings.input_volume), audio_settings.input_muted)?;
+
+    Ok("pactl".to_string())
+}
+
+// 기본 sink/source의 볼륨과 음소거
+fn set_pulse_volume(device_type: &str, volume: Option<u32>, muted: Option<bool>) -> Result<(), AppError> {
+    let PactlKind { kind, default_alias, .. } = pactl_kind(device_type);
+    if let Some(volume) = volume {
+        pactl(&[&format!("set-{}-volume", kind), default_alias, &percent_to_volume(volume).to_string()])?;
+    }
+    if let Some(muted) = muted {
+        pactl(&[&format!("set-{}-mute", kind), default_alias, if muted { "1" } else { "0" }])?;
+    }
+    Ok(())
+}
+
+// 장치 이름(description) 또는 id(sink/source 이름)로 기본 장치를 바꾸고 기존 스트림을 옮깁니다
+fn set_pulse_default(device_type: &str, device: &str) -> Result<String, AppError> {
+    let target = list_devices(device_type)?
+        .into_iter()
+        .find(|d| d.id == device || d.name == device)
+        .ok_or_else(|| AppError::DeviceNotFound {
+            device_type: device_type.to_string(),
+            pattern: device.to_string(),
+        })?;
+
+    let kind = pactl_kind(device_type);
+    pactl(&[&format!("set-default-{}", kind.kind), &target.id])?;
+
+    // 기본 장치만 바꾸면 이미 재생 중인 스트림은 이전 장치에 남으므로 함께 옮깁니다
+    let streams: Vec<PactlStream> =
+        parse_pactl_json(&pactl(&["-f", "json", "list", kind.streams])?)?;
+    for stream in streams {
+        // 이동을 허용하지 않는 스트림도 있으므로 실패해도 전환은 계속합니다
+        if let Err(e) = pactl(&[kind.move_stream, &stream.index.to_string(), &target.id]) {
+            log::warn!("스트림 {} 이동 실패: {}", stream.index, e);
+        }
+    }
+
+    Ok("pactl".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_pulse_volume_to_percent() {
+        assert_eq!(volume_to_percent(0), 0);
+        assert_eq!(volume_to_percent(65536), 100);
+        assert_eq!(volume_to_percent(32768), 50);
+        // 0.5%는 올림, 그보다 작으면 내림
+        assert_eq!(volume_to_percent(328), 1);
+        assert_eq!(volume_to_percent(327), 0);
+        assert_eq!(volume_to_percent(98304), 100);
+
+        assert_eq!(percent_to_volume(100), 65536);
+        assert_eq!(percent_to_volume(33), 21627);
+        assert_eq!(percent_to_volume(150), 65536);
+        for percent in 0..=100 {
+            assert_eq!(volume_to_percent(percent_to_volume(percent)), percent);
+        }
+    }
+
+    #[test]
+    fn filters_pactl_subscribe_events() {
+        assert!(is_device_event("Event 'new' on sink #58"));
+        assert!(is_device_event("Event 'change' on server #-1"));
+        assert!(is_device_event("Event 'remove' on card #45"));
+        assert!(!is_device_event("Event 'change' on sink-input #12"));
+        assert!(!is_device_event("Event 'new' on source-output #3"));
+    }
+
+    #[test]
+    fn parses_pactl_sources() {
+        let json = r#"[
+            {"index": 0, "name": "alsa_output.pci-0000_00_1f.3.analog-stereo.monitor",
+             "description": "Monitor of Built-in Audio", "mute": false,
+             "volume": {"front-left": {"value": 65536, "value_percent": "100%", "db": "0.00 dB"}},
+             "monitor_of_sink": "alsa_output.pci-0000_00_1f.3.analog-stereo", "ports": []},
+            {"index": 1, "name": "alsa_input.pci-0000_00_1f.3.analog-stereo",
+             "description": "Built-in Audio Analog Stereo", "mute": true,
+             "volume": {"front-left": {"value": 39322}, "front-right": {"value": 26214}},
+             "monitor_of_sink": null,
+             "ports": [{"name": "analog-input-internal-mic", "description": "Internal Microphone"},
+                       {"name": "analog-input-mic", "description": "Microphone"}],
+             "active_port": "analog-input-internal-mic"}
+        ]"#;
+        let devices =
+            parse_devices(json, "input", "alsa_input.pci-0000_00_1f.3.analog-stereo").unwrap();
+        assert_eq!(devices.len(), 1);
+        let device = &devices[0];
+        assert_eq!(device.name, "Built-in Audio Analog Stereo");
+        assert!(device.is_default);
+        assert_eq!((device.volume, device.muted), (Some(50), Some(true)));
+        assert_eq!(device.ports.len(), 2);
+        assert_eq!(device.active_port.as_deref(), Some("analog-input-internal-mic"));
+    }
+}
@@ -0,0 +1,447 @@
+// PipeWire CLI 백엔드: pw-dump로 노드와 장치(카드) 조회, 기본 장치는 WirePlumber의 default 메타데이터(pw-metadata)로 지정
+// 볼륨/음소거는 wpctl, 카드 프로필은 pw-cli로 바꿉니다
+// libpipewire에 직접 연결하지 않고 PipeWire 도구를 실행하므로 pw-dump, pw-metadata, wpctl(, pw-cli)이 설치되어 있어야 합니다
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use crate::audio::{AudioDevice, AudioSettings};
+use crate::error::AppError;
+use crate::tools::{decode_output, is_tool_available, run_command, tool_command};
+
+// 프로필을 바꾼 뒤 새 노드가 나타나기를 기다리는 횟수와 간격
+const PROFILE_SETTLE_ATTEMPTS: u32 = 10;
+const PROFILE_SETTLE_INTERVAL: Duration = Duration::from_millis(200);
+
+// PipeWire 소켓이 열려 있고 pw-dump가 있으면 pactl 대신 PipeWire 도구를 사용합니다
+pub fn is_running() -> bool {
+    let runtime_dir =
+        std::env::var_os("PIPEWIRE_RUNTIME_DIR").or_else(|| std::env::var_os("XDG_RUNTIME_DIR"));
+    let remote = std::env::var("PIPEWIRE_REMOTE").unwrap_or_else(|_| "pipewire-0".to_string());
+    runtime_dir.is_some_and(|dir| PathBuf::from(dir).join(remote).exists())
+        && is_tool_available("pw-dump")
+}
+
+// 노드의 채널 볼륨(선형)을 0~100으로 (wpctl, pactl과 같은 세제곱 눈금)
+fn linear_to_percent(volume: f64) -> u32 {
+    (volume.max(0.0).cbrt() * 100.0).round().min(100.0) as u32
+}
+
+// wpctl set-volume에 넘길 값 (세제곱 눈금의 0.0~1.0)
+fn percent_to_wpctl(percent: u32) -> String {
+    format!("{:.2}", percent.min(100) as f64 / 100.0)
+}
+
+#[derive(Debug, Deserialize)]
+struct PwObject {
+    id: u32,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    info: Option<PwInfo>,
+    // 메타데이터 객체는 info 대신 props와 metadata를 가집니다
+    #[serde(default)]
+    props: HashMap<String, Value>,
+    #[serde(default)]
+    metadata: Vec<PwMetadata>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct PwInfo {
+    props: HashMap<String, Value>,
+    params: HashMap<String, Vec<Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PwMetadata {
+    key: String,
+    #[serde(default)]
+    value: Value,
+}
+
+// 오디오 카드 (PipeWire:Interface:Device)
+#[derive(Debug)]
+struct PwCard {
+    id: u32,
+    name: String,
+    profile: Option<String>,
+    // (프로필 index, 이름, 설명)
+    profiles: Vec<(u64, String, String)>,
+}
+
+// 출력/입력 노드 (id는 wpctl에 넘길 객체 id)
+#[derive(Debug)]
+struct PwNode {
+    id: u32,
+    device: AudioDevice,
+}
+
+#[derive(Debug, Default)]
+struct PwState {
+    nodes: Vec<PwNode>,
+    cards: Vec<PwCard>,
+}
+
+impl PwState {
+    // 장치 이름(description) 또는 id(node.name)로 노드 찾기
+    fn node(&self, device_type: &str, device: &str) -> Option<&PwNode> {
+        self.nodes.iter().find(|node| {
+            node.device.device_type == device_type
+                && (node.device.id == device || node.device.name == device)
+        })
+    }
+
+    fn default_node(&self, device_type: &str) -> Option<&PwNode> {
+        self.nodes
+            .iter()
+            .find(|node| node.device.device_type == device_type && node.device.is_default)
+    }
+}
+
+fn pw_tool(tool: &str, args: &[&str]) -> Result<String, AppError> {
+    let output = run_command(tool_command(tool).args(args))
+        .map_err(|e| AppError::tool_missing(tool, &e))?;
+    if !output.status.success() {
+        return Err(AppError::tool_failed(tool, &output));
+    }
+    Ok(decode_output(&output.stdout))
+}
+
+fn dump() -> Result<PwState, AppError> {
+    parse_pw_dump(&pw_tool("pw-dump", &[])?)
+}
+
+fn prop<'a>(props: &'a HashMap<String, Value>, key: &str) -> Option<&'a str> {
+    props.get(key).and_then(Value::as_str)
+}
+
+// 메타데이터 값 {"name": "..."} (오래된 pw-dump는 JSON 문자열로 출력)
+fn metadata_name(value: &Value) -> Option<String> {
+    let value = match value {
+        Value::String(text) => serde_json::from_str(text).ok()?,
+        other => other.clone(),
+    };
+    value.get("name").and_then(Value::as_str).map(str::to_string)
+}
+
+fn parse_pw_dump(json: &str) -> Result<PwState, AppError> {
+    let objects: Vec<PwObject> = serde_json::from_str(json).map_err(|e| AppError::ToolFailed {
+        tool: "pw-dump".to_string(),
+        detail: format!("unable to parse pw-dump output: {}", e),
+    })?;
+
+    // WirePlumber가 정한 현재 기본 장치 (노드 이름)
+    let mut defaults: HashMap<&str, String> = HashMap::new();
+    for object in objects.iter().filter(|o| o.kind == "PipeWire:Interface:Metadata") {
+        if prop(&object.props, "metadata.name") != Some("default") {
+            continue;
+        }
+        for entry in &object.metadata {
+            let device_type = match entry.key.as_str() {
+                "default.audio.sink" => "output",
+                "default.audio.source" => "input",
+                _ => continue,
+            };
+            if let Some(name) = metadata_name(&entry.value) {
+                defaults.insert(device_type, name);
+            }
+        }
+    }
+
+    let mut state = PwState::default();
+    for object in objects.iter().filter(|o| o.kind == "PipeWire:Interface:Device") {
+        let Some(info) = &object.info else {
+            continue;
+        };
+        if prop(&info.props, "media.class") != Some("Audio/Device") {
+            continue;
+        }
+        let profile_fields = |profile: &Value| {
+            Some((
+                profile.get("index")?.as_u64()?,
+                profile.get("name")?.as_str()?.to_string(),
+                profile.get("description").and_then(Value::as_str).unwrap_or_default().to_string(),
+            ))
+        };
+        state.cards.push(PwCard {
+            id: object.id,
+            name: prop(&info.props, "device.name").unwrap_or_default().to_string(),
+            profile: info
+                .params
+                .get("Profile")
+                .and_then(|profiles| profiles.first())
+                .and_then(profile_fields)
+                .map(|(_, name, _)| name),
+            profiles: info
+                .params
+                .get("EnumProfile")
+                .into_iter()
+                .flatten()
+                .filter_map(profile_fields)
+                .collect(),
+        });
+    }
+
+    for object in objects.iter().filter(|o| o.kind == "PipeWire:Interface:Node") {
+        let Some(info) = &object.info else {
+            continue;
+        };
+        let Some(media_class) = prop(&info.props, "media.class") else {
+            continue;
+        };
+        let device_type = if media_class.starts_with("Audio/Sink") {
+            "output"
+        } else if media_class.starts_with("Audio/Source") {
+            "input"
+        } else {
+            continue;
+        };
+        let Some(name) = prop(&info.props, "node.name") else {
+            continue;
+        };
+        let description = prop(&info.props, "node.description")
+            .or_else(|| prop(&info.props, "node.nick"))
+            .unwrap_or(name);
+        let card = info
+            .props
+            .get("device.id")
+            .and_then(Value::as_u64)
+            .and_then(|id| state.cards.iter().find(|card| card.id as u64 == id));
+        // Props 중 channelVolumes가 있는 항목이 현재 볼륨입니다
+        let props = info
+            .params
+            .get("Props")
+            .into_iter()
+            .flatten()
+            .find(|props| props.get("channelVolumes").is_some());
+        let volume = props
+            .and_then(|props| props["channelVolumes"].as_array())
+            .filter(|channels| !channels.is_empty())
+            .map(|channels| {
+                let total: f64 = channels.iter().filter_map(Value::as_f64).sum();
+                linear_to_percent(total / channels.len() as f64)
+            });
+
+        state.nodes.push(PwNode {
+            id: object.id,
+            device: AudioDevice {
+                id: name.to_string(),
+                name: description.to_string(),
+                is_default: defaults.get(device_type).is_some_and(|default| default == name),
+                device_type: device_type.to_string(),
+                volume,
+                muted: props.and_then(|props| props.get("mute")).and_then(Value::as_bool),
+                media_class: Some(media_class.to_string()),
+                card: card.map(|card| card.name.clone()),
+                profile: card.and_then(|card| card.profile.clone()),
+                profiles: card
+                    .map(|card| card.profiles.iter().map(|(_, name, _)| name.clone()).collect())
+                    .unwrap_or_default(),
+                ..Default::default()
+            },
+        });
+    }
+    Ok(state)
+}
+
+pub fn list_audio_devices() -> Result<Vec<AudioDevice>, AppError> {
+    Ok(dump()?.nodes.into_iter().map(|node| node.device).collect())
+}
+
+// 카드 프로필 지정 (프로필 이름 또는 설명)
+fn set_card_profile(state: &PwState, card: &str, profile: &str) -> Result<(), AppError> {
+    let target = state.cards.iter().find(|c| c.name == card).ok_or_else(|| {
+        AppError::DeviceNotFound { device_type: "card".to_string(), pattern: card.to_string() }
+    })?;
+    let available =
+        || -> Vec<&str> { target.profiles.iter().map(|(_, name, _)| name.as_str()).collect() };
+    let (index, name, _) = target
+        .profiles
+        .iter()
+        .find(|(_, name, description)| name == profile || description == profile)
+        .ok_or_else(|| AppError::ToolFailed {
+            tool: "pw-cli".to_string(),
+            detail: format!(
+                "invalid profile '{}' for {} (available: {})",
+                profile,
+                card,
+                available().join(", ")
+            ),
+        })?;
+    if target.profile.as_ref() == Some(name) {
+        return Ok(());
+    }
+    let param = json!({ "index": index, "save": true }).to_string();
+    pw_tool("pw-cli", &["set-param", &target.id.to_string(), "Profile", &param])?;
+    Ok(())
+}
+
+// WirePlumber는 default.configured.* 를 보고 기본 장치를 바꾸며,
+// 대상을 따로 지정하지 않은 스트림도 새 기본 장치로 옮깁니다
+fn set_default_node(node: &PwNode) -> Result<(), AppError> {
+    let key = if node.device.device_type == "input" {
+        "default.configured.audio.source"
+    } else {
+        "default.configured.audio.sink"
+    };
+    let value = json!({ "name": node.device.id }).to_string();
+    pw_tool("pw-metadata", &["-n", "default", "0", key, &value, "Spa:String:JSON"])?;
+    Ok(())
+}
+
+pub fn set_default_audio_device(device_type: &str, device: &str) -> Result<String, AppError> {
+    let state = dump()?;
+    let node = state.node(device_type, device).ok_or_else(|| AppError::DeviceNotFound {
+        device_type: device_type.to_string(),
+        pattern: device.to_string(),
+    })?;
+    set_default_node(node)?;
+    Ok("pipewire-cli".to_string())
+}
+
+pub fn apply_audio_settings(audio_settings: &AudioSettings) -> Result<String, AppError> {
+    let requested = [
+        ("output", &audio_settings.output_device),
+        ("input", &audio_settings.input_device),
+    ];
+    let mut state = dump()?;
+
+    // 프로필을 바꾸면 노드가 다시 만들어지므로, 지정한 장치가 나타날 때까지 다시 조회합니다
+    let profiles = audio_settings.device_profiles.clone().unwrap_or_default();
+    if !profiles.is_empty() {
+        for (card, profile) in &profiles {
+            set_card_profile(&state, card, profile)?;
+        }
+        for _ in 0..PROFILE_SETTLE_ATTEMPTS {
+            state = dump()?;
+            let ready = requested.iter().all(|(device_type, device)| {
+                device.as_deref().is_none_or(|device| state.node(device_type, device).is_some())
+            });
+            if ready {
+                break;
+            }
+            thread::sleep(PROFILE_SETTLE_INTERVAL);
+        }
+    }
+
+    for ((device_type, device), (volume, muted)) in requested.iter().zip([
+        (audio_settings.output_volume, audio_settings.output_muted),
+        (audio_settings.input_volume, audio_settings.input_muted),
+    ]) {
+        // 메타데이터 변경은 비동기로 반영되므로 볼륨은 기본 장치 별칭 대신 노드 id로 지정합니다
+        let node = match device {
+            Some(device) => {
+                let node = state.node(device_type, device).ok_or_else(|| AppError::DeviceNotFound {
+                    device_type: device_type.to_string(),
+                    pattern: device.to_string(),
+                })?;
+                set_default_node(node)?;
+                node
+            }
+            None => match state.default_node(device_type) {
+                Some(node) => node,
+                None => continue,
+            },
+        };
+        set_node_volume(node.id, Some(volume), muted)?;
+    }
+
+    Ok("pipewire-cli".to_string())
+}
+
+// 기본 노드의 볼륨과 음소거 (기본 노드가 없으면 DeviceNotFound)
+pub fn set_default_volume(device_type: &str, volume: Option<u32>, muted: Option<bool>) -> Result<String, AppError> {
+    let state = dump()?;
+    let node = state.default_node(device_type).ok_or_else(|| AppError::DeviceNotFound {
+        device_type: device_type.to_string(),
+        pattern: "default".to_string(),
+    })?;
+    set_node_volume(node.id, volume, muted)?;
+    Ok("pipewire-cli".to_string())
+}
+
+fn set_node_volume(id: u32, volume: Option<u32>, muted: Option<bool>) -> Result<(), AppError> {
+    let id = id.to_string();
+    if let Some(volume) = volume {
+        pw_tool("wpctl", &["set-volume", &id, &percent_to_wpctl(volume)])?;
+    }
+    if let Some(muted) = muted {
+        pw_tool("wpctl", &["set-mute", &id, if muted { "1" } else { "0" }])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_linear_volume_to_percent() {
+        assert_eq!(linear_to_percent(1.0), 100);
+        assert_eq!(linear_to_percent(0.125), 50);
+        assert_eq!(linear_to_percent(0.0), 0);
+        assert_eq!(linear_to_percent(1.5), 100);
+        assert_eq!(percent_to_wpctl(50), "0.50");
+        assert_eq!(percent_to_wpctl(130), "1.00");
+    }
+
+    #[test]
+    fn parses_pw_dump_nodes_and_profiles() {
+        let json = r#"[
+            {"id": 40, "type": "PipeWire:Interface:Device", "info": {
+                "props": {"device.name": "alsa_card.usb-Focusrite_Scarlett_2i2", "media.class": "Audio/Device"},
+                "params": {
+                    "EnumProfile": [
+                        {"index": 0, "name": "off", "description": "Off"},
+                        {"index": 1, "name": "pro-audio", "description": "Pro Audio"},
+                        {"index": 2, "name": "output:analog-stereo+input:analog-stereo", "description": "Analog Stereo Duplex"}
+                    ],
+                    "Profile": [{"index": 1, "name": "pro-audio", "description": "Pro Audio"}]
+                }}},
+            {"id": 52, "type": "PipeWire:Interface:Node", "info": {
+                "props": {"node.name": "alsa_output.usb-Focusrite_Scarlett_2i2.pro-output-0",
+                          "node.description": "Scarlett 2i2 Pro", "media.class": "Audio/Sink", "device.id": 40},
+                "params": {"Props": [{"volume": 1.0, "mute": false, "channelVolumes": [0.125, 0.125]}, {"params": []}]}}},
+            {"id": 53, "type": "PipeWire:Interface:Node", "info": {
+                "props": {"node.name": "alsa_input.pci-0000_00_1f.3.analog-stereo",
+                          "node.description": "Built-in Audio Analog Stereo", "media.class": "Audio/Source"},
+                "params": {"Props": [{"mute": true, "channelVolumes": [1.0]}]}}},
+            {"id": 60, "type": "PipeWire:Interface:Node", "info": {
+                "props": {"node.name": "Firefox", "media.class": "Stream/Output/Audio"}, "params": {}}},
+            {"id": 34, "type": "PipeWire:Interface:Metadata", "props": {"metadata.name": "default"},
+             "metadata": [
+                {"subject": 0, "key": "default.audio.sink", "type": "Spa:String:JSON",
+                 "value": {"name": "alsa_output.usb-Focusrite_Scarlett_2i2.pro-output-0"}},
+                {"subject": 0, "key": "default.audio.source", "type": "Spa:String:JSON",
+                 "value": "{\"name\":\"alsa_input.pci-0000_00_1f.3.analog-stereo\"}"}
+             ]}
+        ]"#;
+        let state = parse_pw_dump(json).unwrap();
+        assert_eq!(state.nodes.len(), 2);
+
+        let output = &state.nodes[0].device;
+        assert_eq!((output.name.as_str(), output.is_default), ("Scarlett 2i2 Pro", true));
+        assert_eq!((output.volume, output.muted), (Some(50), Some(false)));
+        assert_eq!(output.card.as_deref(), Some("alsa_card.usb-Focusrite_Scarlett_2i2"));
+        assert_eq!(output.profile.as_deref(), Some("pro-audio"));
+        assert_eq!(output.profiles.len(), 3);
+
+        let input = state.node("input", "Built-in Audio Analog Stereo").unwrap();
+        assert_eq!(input.id, 53);
+        assert!(input.device.is_default);
+        assert_eq!(input.device.muted, Some(true));
+
+        // 설명으로도 프로필을 찾으며, 없는 프로필은 사용 가능한 목록과 함께 실패합니다
+        let card = "alsa_card.usb-Focusrite_Scarlett_2i2";
+        assert!(matches!(
+            set_card_profile(&state, card, "Surround 5.1"),
+            Err(AppError::ToolFailed { detail, .. }) if detail.contains("pro-audio")
+        ));
+        assert!(set_card_profile(&state, card, "pro-audio").is_ok());
+    }
+}
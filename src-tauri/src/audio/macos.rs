@@ -0,0 +1,610 @@
+// macOS: CoreAudio HAL로 목록 조회, 전환, 볼륨 (장치 id는 장치 UID)
+// HAL 호출이 실패하면 SwitchAudioSource(전환)와 osascript(볼륨)를 사용합니다
+
+use std::ffi::c_void;
+
+use core_foundation::base::TCFType;
+use core_foundation::string::{CFString, CFStringRef};
+
+use super::{AudioDevice, AudioSettings};
+use crate::error::AppError;
+use crate::monitor::ChangeNotifier;
+use crate::tools::{
+    decode_output, has_custom_template, run_command, template_backend, template_program, templated_command,
+    tool_command,
+};
+
+// get_system_info에 표시할 오디오 백엔드
+pub fn backend_name() -> String {
+    "CoreAudio".to_string()
+}
+
+// 적용에 필요한 외부 도구 (사용자 정의 명령 템플릿을 지정한 경우만, 기본은 CoreAudio을 직접 사용)
+pub fn apply_tools() -> Vec<String> {
+    let mut tools: Vec<String> = ["audio.output", "audio.input"]
+        .into_iter()
+        .filter(|operation| has_custom_template(operation))
+        .filter_map(template_program)
+        .collect();
+    tools.dedup();
+    tools
+}
+
+pub fn list_audio_devices() -> Result<Vec<AudioDevice>, AppError> {
+    match list_hal_devices() {
+        Ok(devices) => Ok(devices.into_iter().map(|(_, device)| device).collect()),
+        Err(e) => {
+            log::warn!("CoreAudio로 오디오 장치를 읽지 못해 SwitchAudioSource를 사용합니다: {}", e);
+            list_audio_devices_switch_audio_source()
+        }
+    }
+}
+
+// (AudioObjectID, 장치) 목록, 입출력을 모두 가진 장치는 양쪽에 나타납니다
+fn list_hal_devices() -> Result<Vec<(u32, AudioDevice)>, AppError> {
+    let objects = device_objects()?;
+    let mut devices = Vec::new();
+    for (device_type, scope, default_selector) in
+        [("output", SCOPE_OUTPUT, DEFAULT_OUTPUT), ("input", SCOPE_INPUT, DEFAULT_INPUT)]
+    {
+        // 장치가 하나도 없으면 기본 장치도 없습니다
+        let default = u32_property(SYSTEM_OBJECT, default_selector).ok();
+        let volume = read_volume(device_type);
+        let muted = read_hal_muted(device_type).ok();
+        for &object in &objects {
+            // 이 방향의 스트림이 없는 장치는 제외
+            if property_size(object, STREAMS, scope).unwrap_or(0) == 0 {
+                continue;
+            }
+            let id = string_property(object, DEVICE_UID)?;
+            let is_default = default == Some(object);
+            devices.push((
+                object,
+                AudioDevice {
+                    name: string_property(object, OBJECT_NAME).unwrap_or_else(|_| id.clone()),
+                    id,
+                    is_default,
+                    device_type: device_type.to_string(),
+                    volume: volume.filter(|_| is_default),
+                    muted: muted.filter(|_| is_default),
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+    Ok(devices)
+}
+
+fn list_audio_devices_switch_audio_source() -> Result<Vec<AudioDevice>, AppError> {
+    let mut devices = Vec::new();
+
+    // SwitchAudioSource로 종류별 장치 목록과 현재 기본 장치 가져오기
+    for (device_type, fallback_name) in [("output", "기본 출력 장치"), ("input", "기본 입력 장치")] {
+        let listed = switch_audio_source(&["-a", "-t", device_type]);
+        let current = switch_audio_source(&["-c", "-t", device_type]).ok().and_then(|lines| lines.into_iter().next());
+        let volume = read_volume(device_type);
+        match listed {
+            Ok(names) => {
+                // 이름이 같은 장치가 여러 개여도 기본 장치는 하나만 표시합니다 (처음 나온 장치)
+                let default_index = names.iter().position(|name| current.as_deref() == Some(name.as_str()));
+                for (index, name) in names.into_iter().enumerate() {
+                    let is_default = default_index == Some(index);
+                    devices.push(AudioDevice {
+                        id: name.clone(),
+                        name,
+                        is_default,
+                        device_type: device_type.to_string(),
+                        volume: volume.filter(|_| is_default),
+                        ..Default::default()
+                    });
+                }
+            }
+            Err(_) => {
+                // SwitchAudioSource가 없는 경우 기본 장치만 반환
+                devices.push(AudioDevice {
+                    id: format!("default_{}", device_type),
+                    name: fallback_name.to_string(),
+                    is_default: true,
+                    device_type: device_type.to_string(),
+                    volume,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    Ok(devices)
+}
+
+// SwitchAudioSource 실행 결과의 비어 있지 않은 줄
+fn switch_audio_source(args: &[&str]) -> Result<Vec<String>, AppError> {
+    let output = run_command(tool_command("SwitchAudioSource").args(args))
+        .map_err(|e| AppError::tool_missing("SwitchAudioSource", &e))?;
+    if !output.status.success() {
+        return Err(AppError::tool_failed("SwitchAudioSource", &output));
+    }
+    Ok(decode_output(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+// 기본 장치의 볼륨 (0~100)
+fn read_volume(device_type: &str) -> Option<u32> {
+    match read_hal_volume(device_type) {
+        Ok(volume) => Some(volume),
+        Err(e) => {
+            log::debug!("CoreAudio로 {} 볼륨을 읽지 못해 osascript를 사용합니다: {}", device_type, e);
+            read_volume_osascript(device_type)
+        }
+    }
+}
+
+// AppleScript의 volume settings
+fn read_volume_osascript(device_type: &str) -> Option<u32> {
+    let script = format!("{} volume of (get volume settings)", device_type);
+    let output = run_command(tool_command("osascript").args(["-e", &script])).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    decode_output(&output.stdout).trim().parse().ok()
+}
+
+pub fn apply_audio_settings(audio_settings: &AudioSettings) -> Result<String, AppError> {
+    let mut backend = "none".to_string();
+
+    // 출력 장치 설정
+    if let Some(output_device) = &audio_settings.output_device {
+        backend = set_default_audio_device("output", output_device)?;
+    }
+    if audio_settings.output_roles.as_ref().is_some_and(|roles| !roles.is_empty()) {
+        log::warn!("macOS에는 역할별 기본 장치가 없어 output_roles를 무시합니다");
+    }
+
+    // 입력 장치 설정 (SwitchAudioSource -t input)
+    if let Some(input_device) = &audio_settings.input_device {
+        backend = set_default_audio_device("input", input_device)?;
+    }
+
+    // 볼륨은 장치를 전환한 뒤에 설정해야 새 장치에 적용됩니다
+    set_volume("output", audio_settings.output_volume)?;
+    set_volume("input", audio_settings.input_volume)?;
+
+    Ok(backend)
+}
+
+// 기본 장치 볼륨 설정 (0~100)
+pub fn set_volume(device_type: &str, volume: u32) -> Result<(), AppError> {
+    match set_hal_volume(device_type, volume) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            log::warn!("CoreAudio로 {} 볼륨을 바꾸지 못해 osascript를 사용합니다: {}", device_type, e);
+            set_volume_osascript(device_type, volume)
+        }
+    }
+}
+
+// 기본 장치의 볼륨과 음소거 (None이면 그대로 둠)
+pub fn set_default_volume(device_type: &str, volume: Option<u32>, muted: Option<bool>) -> Result<String, AppError> {
+    if let Some(volume) = volume {
+        set_volume(device_type, volume)?;
+    }
+    if let Some(muted) = muted {
+        set_hal_muted(device_type, muted)?;
+    }
+    Ok("CoreAudio".to_string())
+}
+
+// AppleScript의 set volume
+fn set_volume_osascript(device_type: &str, volume: u32) -> Result<(), AppError> {
+    let script = format!("set volume {} volume {}", device_type, volume.min(100));
+    let output = run_command(tool_command("osascript").args(["-e", &script]))
+        .map_err(|e| AppError::tool_missing("osascript", &e))?;
+    if !output.status.success() {
+        return Err(AppError::tool_failed("osascript", &output));
+    }
+    Ok(())
+}
+
+// 장치 UID 또는 이름으로 기본 장치 변경 (이전 버전의 프로필은 이름을 저장함, 사용한 백엔드 이름 반환)
+pub fn set_default_audio_device(device_type: &str, device: &str) -> Result<String, AppError> {
+    // 명령 템플릿을 지정했으면 그 명령을 사용합니다
+    if has_custom_template(&format!("audio.{}", device_type)) {
+        return set_default_audio_device_by_name(device_type, device);
+    }
+    let devices = match list_hal_devices() {
+        Ok(devices) => devices,
+        Err(e) => {
+            log::warn!("CoreAudio를 사용할 수 없어 SwitchAudioSource로 기본 장치를 바꿉니다: {}", e);
+            return set_default_audio_device_by_name(device_type, device);
+        }
+    };
+    let of_type = || devices.iter().filter(|(_, candidate)| candidate.device_type == device_type);
+    let (object, found) = of_type()
+        .find(|(_, candidate)| candidate.id == device)
+        .or_else(|| of_type().find(|(_, candidate)| candidate.name == device))
+        .ok_or_else(|| AppError::DeviceNotFound {
+            device_type: device_type.to_string(),
+            pattern: device.to_string(),
+        })?;
+
+    let selector = if device_type == "input" { DEFAULT_INPUT } else { DEFAULT_OUTPUT };
+    if let Err(e) = set_u32_property(SYSTEM_OBJECT, selector, *object) {
+        log::warn!("CoreAudio로 기본 장치를 바꾸지 못해 SwitchAudioSource를 사용합니다: {}", e);
+        return set_default_audio_device_by_name(device_type, &found.name);
+    }
+    Ok("CoreAudio".to_string())
+}
+
+// SwitchAudioSource(또는 명령 템플릿)로 이름으로 기본 장치 변경
+fn set_default_audio_device_by_name(device_type: &str, device: &str) -> Result<String, AppError> {
+    let (mut command, program, custom) = templated_command(
+        &format!("audio.{}", device_type),
+        &[("device", &[device.to_string()])],
+    )?;
+    match run_command(&mut command) {
+        Ok(output) => {
+            if !output.status.success() {
+                return Err(AppError::tool_failed(&program, &output));
+            }
+            Ok(template_backend(&program, custom))
+        }
+        Err(e) => Err(AppError::tool_missing(&program, &e)),
+    }
+}
+
+// CoreAudio HAL (장치 목록, 속성 읽기/쓰기, 변경 리스너)
+
+#[repr(C)]
+struct AudioObjectPropertyAddress {
+    selector: u32,
+    scope: u32,
+    element: u32,
+}
+
+type PropertyListener = unsafe extern "C" fn(
+    object: u32,
+    address_count: u32,
+    addresses: *const AudioObjectPropertyAddress,
+    client_data: *mut c_void,
+) -> i32;
+
+#[link(name = "CoreAudio", kind = "framework")]
+extern "C" {
+    fn AudioObjectGetPropertyDataSize(
+        object: u32,
+        address: *const AudioObjectPropertyAddress,
+        qualifier_size: u32,
+        qualifier: *const c_void,
+        data_size: *mut u32,
+    ) -> i32;
+    fn AudioObjectGetPropertyData(
+        object: u32,
+        address: *const AudioObjectPropertyAddress,
+        qualifier_size: u32,
+        qualifier: *const c_void,
+        data_size: *mut u32,
+        data: *mut c_void,
+    ) -> i32;
+    fn AudioObjectSetPropertyData(
+        object: u32,
+        address: *const AudioObjectPropertyAddress,
+        qualifier_size: u32,
+        qualifier: *const c_void,
+        data_size: u32,
+        data: *const c_void,
+    ) -> i32;
+    fn AudioObjectHasProperty(object: u32, address: *const AudioObjectPropertyAddress) -> u8;
+    fn AudioObjectAddPropertyListener(
+        object: u32,
+        address: *const AudioObjectPropertyAddress,
+        listener: PropertyListener,
+        client_data: *mut c_void,
+    ) -> i32;
+}
+
+// kAudioObjectSystemObject
+const SYSTEM_OBJECT: u32 = 1;
+
+const fn four_cc(code: &[u8; 4]) -> u32 {
+    u32::from_be_bytes(*code)
+}
+
+// kAudioHardwarePropertyDevices, DefaultOutputDevice, DefaultInputDevice, DefaultSystemOutputDevice
+const DEVICES: u32 = four_cc(b"dev#");
+const DEFAULT_OUTPUT: u32 = four_cc(b"dOut");
+const DEFAULT_INPUT: u32 = four_cc(b"dIn ");
+const DEFAULT_SYSTEM_OUTPUT: u32 = four_cc(b"sOut");
+const WATCHED_PROPERTIES: [u32; 4] = [DEVICES, DEFAULT_OUTPUT, DEFAULT_INPUT, DEFAULT_SYSTEM_OUTPUT];
+
+// kAudioDevicePropertyDeviceUID, kAudioObjectPropertyName, kAudioDevicePropertyStreams
+const DEVICE_UID: u32 = four_cc(b"uid ");
+const OBJECT_NAME: u32 = four_cc(b"lnam");
+const STREAMS: u32 = four_cc(b"stm#");
+
+// kAudioDevicePropertyVolumeScalar (0.0~1.0)
+const VOLUME_SCALAR: u32 = four_cc(b"volm");
+
+// kAudioDevicePropertyMute (0 / 1)
+const MUTE: u32 = four_cc(b"mute");
+
+// kAudioObjectPropertyScopeGlobal / Input / Output
+const SCOPE_GLOBAL: u32 = four_cc(b"glob");
+const SCOPE_INPUT: u32 = four_cc(b"inpt");
+const SCOPE_OUTPUT: u32 = four_cc(b"outp");
+
+// element는 항상 kAudioObjectPropertyElementMain
+fn property_address(selector: u32, scope: u32) -> AudioObjectPropertyAddress {
+    AudioObjectPropertyAddress { selector, scope, element: 0 }
+}
+
+fn hal_error(call: &str, status: i32) -> AppError {
+    AppError::ToolFailed {
+        tool: "CoreAudio".to_string(),
+        detail: format!("{} returned {}", call, status),
+    }
+}
+
+fn property_size(object: u32, selector: u32, scope: u32) -> Result<u32, AppError> {
+    let address = property_address(selector, scope);
+    let mut size = 0u32;
+    let status = unsafe { AudioObjectGetPropertyDataSize(object, &address, 0, std::ptr::null(), &mut size) };
+    if status != 0 {
+        return Err(hal_error("AudioObjectGetPropertyDataSize", status));
+    }
+    Ok(size)
+}
+
+// 연결된 모든 오디오 장치의 AudioObjectID
+fn device_objects() -> Result<Vec<u32>, AppError> {
+    let size = property_size(SYSTEM_OBJECT, DEVICES, SCOPE_GLOBAL)?;
+    let mut objects = vec![0u32; size as usize / std::mem::size_of::<u32>()];
+    let mut size = std::mem::size_of_val(objects.as_slice()) as u32;
+    let address = property_address(DEVICES, SCOPE_GLOBAL);
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            SYSTEM_OBJECT,
+            &address,
+            0,
+            std::ptr::null(),
+            &mut size,
+            objects.as_mut_ptr() as *mut c_void,
+        )
+    };
+    if status != 0 {
+        return Err(hal_error("AudioObjectGetPropertyData", status));
+    }
+    // 그 사이에 장치가 분리됐으면 실제로 받은 개수만
+    objects.truncate(size as usize / std::mem::size_of::<u32>());
+    Ok(objects)
+}
+
+fn u32_property(object: u32, selector: u32) -> Result<u32, AppError> {
+    let address = property_address(selector, SCOPE_GLOBAL);
+    let mut value = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            object,
+            &address,
+            0,
+            std::ptr::null(),
+            &mut size,
+            &mut value as *mut u32 as *mut c_void,
+        )
+    };
+    if status != 0 {
+        return Err(hal_error("AudioObjectGetPropertyData", status));
+    }
+    Ok(value)
+}
+
+fn set_u32_property(object: u32, selector: u32, value: u32) -> Result<(), AppError> {
+    let address = property_address(selector, SCOPE_GLOBAL);
+    let status = unsafe {
+        AudioObjectSetPropertyData(
+            object,
+            &address,
+            0,
+            std::ptr::null(),
+            std::mem::size_of::<u32>() as u32,
+            &value as *const u32 as *const c_void,
+        )
+    };
+    if status != 0 {
+        return Err(hal_error("AudioObjectSetPropertyData", status));
+    }
+    Ok(())
+}
+
+fn f32_property(object: u32, address: &AudioObjectPropertyAddress) -> Result<f32, AppError> {
+    let mut value = 0f32;
+    let mut size = std::mem::size_of::<f32>() as u32;
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            object,
+            address,
+            0,
+            std::ptr::null(),
+            &mut size,
+            &mut value as *mut f32 as *mut c_void,
+        )
+    };
+    if status != 0 {
+        return Err(hal_error("AudioObjectGetPropertyData", status));
+    }
+    Ok(value)
+}
+
+fn set_f32_property(object: u32, address: &AudioObjectPropertyAddress, value: f32) -> Result<(), AppError> {
+    let status = unsafe {
+        AudioObjectSetPropertyData(
+            object,
+            address,
+            0,
+            std::ptr::null(),
+            std::mem::size_of::<f32>() as u32,
+            &value as *const f32 as *const c_void,
+        )
+    };
+    if status != 0 {
+        return Err(hal_error("AudioObjectSetPropertyData", status));
+    }
+    Ok(())
+}
+
+// 기본 장치의 볼륨 속성 주소
+// 주 채널(element 0)에 볼륨이 없는 장치는 채널마다 (1, 2) 볼륨이 있습니다
+fn default_volume_addresses(device_type: &str) -> Result<(u32, Vec<AudioObjectPropertyAddress>), AppError> {
+    let (object, scope) = default_device(device_type)?;
+    let address = |element| AudioObjectPropertyAddress { selector: VOLUME_SCALAR, scope, element };
+    let has_volume = |address: &AudioObjectPropertyAddress| unsafe { AudioObjectHasProperty(object, address) != 0 };
+    let main = address(0);
+    let addresses: Vec<AudioObjectPropertyAddress> = if has_volume(&main) {
+        vec![main]
+    } else {
+        [address(1), address(2)].into_iter().filter(has_volume).collect()
+    };
+    if addresses.is_empty() {
+        return Err(AppError::ToolFailed {
+            tool: "CoreAudio".to_string(),
+            detail: format!("default {} device has no volume control", device_type),
+        });
+    }
+    Ok((object, addresses))
+}
+
+// 채널이 여러 개면 평균 (0~100)
+fn read_hal_volume(device_type: &str) -> Result<u32, AppError> {
+    let (object, addresses) = default_volume_addresses(device_type)?;
+    let mut total = 0f32;
+    for address in &addresses {
+        total += f32_property(object, address)?;
+    }
+    Ok((total / addresses.len() as f32 * 100.0).round().clamp(0.0, 100.0) as u32)
+}
+
+fn set_hal_volume(device_type: &str, volume: u32) -> Result<(), AppError> {
+    let (object, addresses) = default_volume_addresses(device_type)?;
+    let scalar = volume.min(100) as f32 / 100.0;
+    for address in &addresses {
+        set_f32_property(object, address, scalar)?;
+    }
+    Ok(())
+}
+
+// 기본 장치의 AudioObjectID와 방향(scope)
+fn default_device(device_type: &str) -> Result<(u32, u32), AppError> {
+    let (selector, scope) = if device_type == "input" {
+        (DEFAULT_INPUT, SCOPE_INPUT)
+    } else {
+        (DEFAULT_OUTPUT, SCOPE_OUTPUT)
+    };
+    Ok((u32_property(SYSTEM_OBJECT, selector)?, scope))
+}
+
+// 기본 장치의 음소거 속성 주소 (음소거를 지원하지 않는 장치도 있습니다)
+fn default_mute_address(device_type: &str) -> Result<(u32, AudioObjectPropertyAddress), AppError> {
+    let (object, scope) = default_device(device_type)?;
+    let address = property_address(MUTE, scope);
+    if unsafe { AudioObjectHasProperty(object, &address) } == 0 {
+        return Err(AppError::ToolFailed {
+            tool: "CoreAudio".to_string(),
+            detail: format!("default {} device has no mute control", device_type),
+        });
+    }
+    Ok((object, address))
+}
+
+fn read_hal_muted(device_type: &str) -> Result<bool, AppError> {
+    let (object, address) = default_mute_address(device_type)?;
+    let mut value = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            object,
+            &address,
+            0,
+            std::ptr::null(),
+            &mut size,
+            &mut value as *mut u32 as *mut c_void,
+        )
+    };
+    if status != 0 {
+        return Err(hal_error("AudioObjectGetPropertyData", status));
+    }
+    Ok(value != 0)
+}
+
+fn set_hal_muted(device_type: &str, muted: bool) -> Result<(), AppError> {
+    let (object, address) = default_mute_address(device_type)?;
+    let value = muted as u32;
+    let status = unsafe {
+        AudioObjectSetPropertyData(
+            object,
+            &address,
+            0,
+            std::ptr::null(),
+            std::mem::size_of::<u32>() as u32,
+            &value as *const u32 as *const c_void,
+        )
+    };
+    if status != 0 {
+        return Err(hal_error("AudioObjectSetPropertyData", status));
+    }
+    Ok(())
+}
+
+// 문자열 속성 (받은 CFString은 호출한 쪽이 해제합니다)
+fn string_property(object: u32, selector: u32) -> Result<String, AppError> {
+    let address = property_address(selector, SCOPE_GLOBAL);
+    let mut value: CFStringRef = std::ptr::null();
+    let mut size = std::mem::size_of::<CFStringRef>() as u32;
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            object,
+            &address,
+            0,
+            std::ptr::null(),
+            &mut size,
+            &mut value as *mut CFStringRef as *mut c_void,
+        )
+    };
+    if status != 0 || value.is_null() {
+        return Err(hal_error("AudioObjectGetPropertyData", status));
+    }
+    Ok(unsafe { CFString::wrap_under_create_rule(value) }.to_string())
+}
+
+unsafe extern "C" fn audio_property_changed(
+    _object: u32,
+    _address_count: u32,
+    _addresses: *const AudioObjectPropertyAddress,
+    client_data: *mut c_void,
+) -> i32 {
+    let notifier = &*(client_data as *const ChangeNotifier);
+    notifier.changed();
+    0
+}
+
+// 콜백은 CoreAudio 스레드에서 호출되며, 앱이 끝날 때까지 등록을 유지합니다
+// 볼륨 변경은 장치마다 리스너가 필요하므로 아직 감시하지 않습니다
+pub fn watch_audio_changes(notifier: ChangeNotifier) -> Result<String, AppError> {
+    let client_data = Box::into_raw(Box::new(notifier)) as *mut c_void;
+    for selector in WATCHED_PROPERTIES {
+        let address = property_address(selector, SCOPE_GLOBAL);
+        let status = unsafe {
+            AudioObjectAddPropertyListener(SYSTEM_OBJECT, &address, audio_property_changed, client_data)
+        };
+        if status != 0 {
+            // 이미 등록한 리스너가 client_data를 쓰고 있을 수 있으므로 해제하지 않습니다
+            return Err(AppError::ToolFailed {
+                tool: "CoreAudio".to_string(),
+                detail: format!("AudioObjectAddPropertyListener returned {}", status),
+            });
+        }
+    }
+    Ok("CoreAudio property listeners".to_string())
+}
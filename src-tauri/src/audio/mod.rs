@@ -0,0 +1,316 @@
+// 오디오 장치 목록 / 전환 / 비교
+// 플랫폼별 구현은 macos, windows, linux 모듈에 있습니다
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::error::AppError;
+use crate::monitor::ChangeNotifier;
+
+pub mod cmdlets;
+
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "macos")]
+use self::macos as platform;
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "windows")]
+use self::windows as platform;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod linux;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+use self::linux as platform;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AudioDevice {
+    pub id: String,
+    pub name: String,
+    pub is_default: bool,
+    pub device_type: String,
+    // 현재 볼륨(0~100)과 음소거 상태 (읽을 수 없는 플랫폼에서는 None)
+    #[serde(default)]
+    pub volume: Option<u32>,
+    #[serde(default)]
+    pub muted: Option<bool>,
+    // 장치의 포트 (예: 스피커/헤드폰 단자, Linux 전용)
+    #[serde(default)]
+    pub ports: Vec<String>,
+    #[serde(default)]
+    pub active_port: Option<String>,
+    // PipeWire media.class (예: "Audio/Sink") 와 장치가 속한 카드 이름, 카드의 현재 프로필
+    #[serde(default)]
+    pub media_class: Option<String>,
+    #[serde(default)]
+    pub card: Option<String>,
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub profiles: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AudioSettings {
+    pub output_device: Option<String>,
+    pub input_device: Option<String>,
+    pub output_volume: u32,
+    // 입력 장치를 저장하지 않던 프로필은 0
+    #[serde(default)]
+    pub input_volume: u32,
+    // 역할별 기본 출력 장치 (역할 → 장치 id, Windows 전용)
+    // 모든 역할에 같은 장치를 쓰면 output_device만 지정하면 됩니다
+    #[serde(default)]
+    pub output_roles: Option<HashMap<String, String>>,
+    // 음소거 여부 (None이면 건드리지 않음)
+    #[serde(default)]
+    pub output_muted: Option<bool>,
+    #[serde(default)]
+    pub input_muted: Option<bool>,
+    // 카드 이름 → 프로필 이름 (예: USB 인터페이스를 Pro Audio에서 스테레오로, Linux 전용)
+    // 기본 장치를 바꾸기 전에 적용합니다
+    #[serde(default)]
+    pub device_profiles: Option<HashMap<String, String>>,
+}
+
+// Linux 오디오 백엔드 선택 (Auto면 PipeWire가 실행 중일 때 PipeWire를 직접 사용)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioBackendPreference {
+    #[default]
+    Auto,
+    Pipewire,
+    Pulse,
+}
+
+static AUDIO_BACKEND_PREFERENCE: RwLock<AudioBackendPreference> =
+    RwLock::new(AudioBackendPreference::Auto);
+
+pub fn set_audio_backend_preference(preference: AudioBackendPreference) {
+    *AUDIO_BACKEND_PREFERENCE.write().unwrap_or_else(|e| e.into_inner()) = preference;
+}
+
+#[cfg_attr(any(target_os = "macos", target_os = "windows"), allow(dead_code))]
+pub fn audio_backend_preference() -> AudioBackendPreference {
+    *AUDIO_BACKEND_PREFERENCE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+// 오디오 장치 조회/전환 백엔드 (실제 OS 또는 테스트용 mock)
+pub trait AudioBackend: Send + Sync {
+    // 연결된 입출력 장치 목록
+    fn enumerate(&self) -> Result<Vec<AudioDevice>, AppError>;
+    // 패턴을 해석한 오디오 설정 적용 (사용한 백엔드 이름 반환)
+    fn apply(&self, audio_settings: &AudioSettings) -> Result<String, AppError>;
+    // 기본 오디오 장치 변경 (프로필 없이 장치만 전환, 사용한 백엔드 이름 반환)
+    fn set_default_device(&self, device_type: &str, device: &str) -> Result<String, AppError>;
+    // 기본 장치의 볼륨(0~100)과 음소거 변경 (None이면 그대로 둠, 사용한 백엔드 이름 반환)
+    fn set_volume(&self, device_type: &str, volume: Option<u32>, muted: Option<bool>) -> Result<String, AppError>;
+    // 적용에 필요한 외부 도구 (적용하기 전에 설치 여부를 확인, 기본은 없음)
+    fn required_tools(&self) -> Vec<String> {
+        Vec::new()
+    }
+    // get_system_info에 표시할 백엔드 이름
+    fn name(&self) -> String;
+    // 장치 추가/제거나 기본 장치 변경을 notifier로 알리는 감시 시작 (알린 방법 반환)
+    // 지원하지 않으면 오류를 반환하고, 모니터가 주기적으로 다시 읽습니다
+    fn watch(&self, notifier: ChangeNotifier) -> Result<String, AppError> {
+        drop(notifier);
+        Err(AppError::UnsupportedPlatform { feature: "audio change notifications".to_string() })
+    }
+}
+
+// 현재 OS의 오디오 백엔드
+pub struct SystemAudioBackend;
+
+impl AudioBackend for SystemAudioBackend {
+    fn enumerate(&self) -> Result<Vec<AudioDevice>, AppError> {
+        platform::list_audio_devices()
+    }
+
+    fn apply(&self, audio_settings: &AudioSettings) -> Result<String, AppError> {
+        platform::apply_audio_settings(audio_settings)
+    }
+
+    fn set_default_device(&self, device_type: &str, device: &str) -> Result<String, AppError> {
+        platform::set_default_audio_device(device_type, device)
+    }
+
+    fn set_volume(&self, device_type: &str, volume: Option<u32>, muted: Option<bool>) -> Result<String, AppError> {
+        platform::set_default_volume(device_type, volume, muted)
+    }
+
+    fn required_tools(&self) -> Vec<String> {
+        platform::apply_tools()
+    }
+
+    fn name(&self) -> String {
+        platform::backend_name()
+    }
+
+    fn watch(&self, notifier: ChangeNotifier) -> Result<String, AppError> {
+        platform::watch_audio_changes(notifier)
+    }
+}
+
+// 오디오 설정 적용 (패턴은 현재 장치 목록으로 해석, 사용한 백엔드 이름 반환)
+pub fn apply_audio_settings(
+    backend: &dyn AudioBackend,
+    audio_settings: &AudioSettings,
+) -> Result<String, AppError> {
+    backend.apply(&resolve_audio_patterns(backend, audio_settings)?)
+}
+
+// "re:" 접두사가 붙은 장치 이름은 정규식으로 매칭합니다 (예: "re:USB.*Headset")
+pub const DEVICE_PATTERN_PREFIX: &str = "re:";
+
+// "re:" 패턴이면 정규식으로 변환 (일반 장치 이름이면 None)
+pub fn device_pattern(requested: &str) -> Result<Option<Regex>, AppError> {
+    let Some(pattern) = requested.strip_prefix(DEVICE_PATTERN_PREFIX) else {
+        return Ok(None);
+    };
+    Regex::new(pattern)
+        .map(Some)
+        .map_err(|e| AppError::InvalidDevicePattern {
+            pattern: pattern.to_string(),
+            detail: e.to_string(),
+        })
+}
+
+// 장치 이름 또는 패턴을 실제 장치 이름으로 변환
+pub fn resolve_audio_device(
+    requested: &str,
+    device_type: &str,
+    devices: &[AudioDevice],
+) -> Result<String, AppError> {
+    let Some(regex) = device_pattern(requested)? else {
+        return Ok(requested.to_string());
+    };
+
+    devices
+        .iter()
+        .filter(|device| device.device_type == device_type)
+        .find(|device| regex.is_match(&device.name))
+        .map(|device| device.name.clone())
+        .ok_or_else(|| AppError::DeviceNotFound {
+            device_type: device_type.to_string(),
+            pattern: requested.to_string(),
+        })
+}
+
+fn is_device_pattern(device: &Option<String>) -> bool {
+    device.as_deref().is_some_and(|d| d.starts_with(DEVICE_PATTERN_PREFIX))
+}
+
+// 패턴으로 지정된 입출력 장치를 현재 연결된 장치 이름으로 바꾼 설정 반환
+pub fn resolve_audio_patterns(
+    backend: &dyn AudioBackend,
+    audio_settings: &AudioSettings,
+) -> Result<AudioSettings, AppError> {
+    if !is_device_pattern(&audio_settings.output_device) && !is_device_pattern(&audio_settings.input_device) {
+        return Ok(audio_settings.clone());
+    }
+    resolve_audio_settings(audio_settings, &backend.enumerate()?)
+}
+
+// 주어진 장치 목록으로 패턴 해석 (장치 목록을 조회하지 않음)
+pub fn resolve_audio_settings(
+    audio_settings: &AudioSettings,
+    devices: &[AudioDevice],
+) -> Result<AudioSettings, AppError> {
+    let mut resolved = audio_settings.clone();
+    if let Some(output_device) = &audio_settings.output_device {
+        resolved.output_device = Some(resolve_audio_device(output_device, "output", devices)?);
+    }
+    if let Some(input_device) = &audio_settings.input_device {
+        resolved.input_device = Some(resolve_audio_device(input_device, "input", devices)?);
+    }
+    Ok(resolved)
+}
+
+// Windows 기본 장치 역할 (ERole 값)
+pub const AUDIO_ROLES: &[(&str, u32)] = &[("console", 0), ("multimedia", 1), ("communications", 2)];
+
+pub fn audio_role(name: &str) -> Result<u32, AppError> {
+    AUDIO_ROLES
+        .iter()
+        .find(|(role, _)| role.eq_ignore_ascii_case(name))
+        .map(|(_, value)| *value)
+        .ok_or_else(|| AppError::UnknownAudioRole { role: name.to_string() })
+}
+
+// 기본 장치 볼륨 설정 (0~100, 사용한 백엔드 이름 반환)
+pub fn set_device_volume(backend: &dyn AudioBackend, device_type: &str, volume: u32) -> Result<String, AppError> {
+    log::info!("볼륨 설정: {} {}", device_type, volume);
+    backend.set_volume(device_type, Some(volume.min(100)), None)
+}
+
+// 기본 장치의 현재 볼륨과 음소거 (volume-changed 이벤트의 내용)
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct VolumeState {
+    pub device_type: String,
+    pub device: Option<String>,
+    pub volume: Option<u32>,
+    pub muted: Option<bool>,
+}
+
+pub fn default_volume_state(backend: &dyn AudioBackend, device_type: &str) -> Result<VolumeState, AppError> {
+    let devices = backend.enumerate()?;
+    let default = devices.iter().find(|device| device.device_type == device_type && device.is_default);
+    Ok(VolumeState {
+        device_type: device_type.to_string(),
+        device: default.map(|device| device.name.clone()),
+        volume: default.and_then(|device| device.volume),
+        muted: default.and_then(|device| device.muted),
+    })
+}
+
+// 기본 장치의 음소거 전환 (음소거 상태를 읽을 수 없으면 음소거합니다, 사용한 백엔드 이름 반환)
+pub fn toggle_device_mute(backend: &dyn AudioBackend, device_type: &str) -> Result<String, AppError> {
+    let muted = default_volume_state(backend, device_type)?.muted.unwrap_or(false);
+    log::info!("음소거 전환: {} ({} → {})", device_type, muted, !muted);
+    backend.set_volume(device_type, None, Some(!muted))
+}
+
+// 현재 기본 장치로 오디오 설정 구성 (볼륨을 읽을 수 없는 플랫폼에서는 0)
+pub fn capture_audio_settings(devices: &[AudioDevice]) -> AudioSettings {
+    let default_of = |device_type: &str| {
+        devices
+            .iter()
+            .find(|device| device.device_type == device_type && device.is_default)
+    };
+    let (output, input) = (default_of("output"), default_of("input"));
+
+    AudioSettings {
+        output_device: output.map(|device| device.id.clone()),
+        input_device: input.map(|device| device.id.clone()),
+        output_volume: output.and_then(|device| device.volume).unwrap_or(0),
+        input_volume: input.and_then(|device| device.volume).unwrap_or(0),
+        output_roles: None,
+        output_muted: output.and_then(|device| device.muted),
+        input_muted: input.and_then(|device| device.muted),
+        device_profiles: None,
+    }
+}
+
+// 저장된 오디오 설정과 현재 설정의 차이 (지정된 장치만 비교)
+pub fn diff_audio_settings(expected: &AudioSettings, actual: &AudioSettings) -> Vec<String> {
+    let mut drift = Vec::new();
+
+    if expected.output_device.is_some() && expected.output_device != actual.output_device {
+        drift.push(format!(
+            "출력 장치: {} → {}",
+            expected.output_device.as_deref().unwrap_or("-"),
+            actual.output_device.as_deref().unwrap_or("-")
+        ));
+    }
+    if expected.input_device.is_some() && expected.input_device != actual.input_device {
+        drift.push(format!(
+            "입력 장치: {} → {}",
+            expected.input_device.as_deref().unwrap_or("-"),
+            actual.input_device.as_deref().unwrap_or("-")
+        ));
+    }
+
+    drift
+}
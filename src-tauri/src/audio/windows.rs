@@ -0,0 +1,394 @@
+// Windows: WASAPI(IMMDeviceEnumerator)로 목록 조회, IPolicyConfig로 기본 장치 지정
+// COM을 사용할 수 없으면 PowerShell(AudioDeviceCmdlets)과 nircmd로 대신합니다
+
+use std::ffi::c_void;
+use windows::core::{IUnknown, IUnknown_Vtbl, BOOL, GUID, HRESULT, PCWSTR};
+use windows::Win32::Devices::FunctionDiscovery::PKEY_Device_FriendlyName;
+use windows::Win32::Foundation::{E_FAIL, PROPERTYKEY};
+use windows::Win32::Media::Audio::Endpoints::IAudioEndpointVolume;
+use windows::Win32::Media::Audio::{
+    eCapture, eConsole, eRender, EDataFlow, ERole, IMMDevice, IMMDeviceEnumerator, IMMNotificationClient,
+    IMMNotificationClient_Impl, MMDeviceEnumerator, DEVICE_STATE, DEVICE_STATE_ACTIVE,
+};
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoTaskMemFree, CoUninitialize, CLSCTX_ALL, COINIT_APARTMENTTHREADED,
+    COINIT_MULTITHREADED, STGM_READ,
+};
+
+use super::cmdlets::parse_audio_devices;
+use super::{audio_role, AudioDevice, AudioSettings, AUDIO_ROLES};
+use crate::error::AppError;
+use crate::monitor::ChangeNotifier;
+use crate::shell::powershell;
+use crate::tools::{has_custom_template, run_command, template_program, templated_command, tool_missing_message};
+
+// get_system_info에 표시할 오디오 백엔드
+pub fn backend_name() -> String {
+    "WASAPI + IPolicyConfig".to_string()
+}
+
+// 적용에 필요한 외부 도구 (사용자 정의 명령 템플릿을 지정한 경우만, 기본은 IPolicyConfig을 직접 사용)
+pub fn apply_tools() -> Vec<String> {
+    let mut tools: Vec<String> = ["audio.output", "audio.input"]
+        .into_iter()
+        .filter(|operation| has_custom_template(operation))
+        .filter_map(template_program)
+        .collect();
+    tools.dedup();
+    tools
+}
+
+// WASAPI로 활성 재생/녹음 엔드포인트 목록 (id는 재부팅해도 같은 엔드포인트 id)
+// COM을 사용할 수 없으면 PowerShell로 대신합니다
+pub fn list_audio_devices() -> Result<Vec<AudioDevice>, AppError> {
+    match list_endpoints() {
+        Ok(devices) => Ok(devices),
+        Err(e) => {
+            log::warn!("WASAPI로 오디오 장치를 읽지 못해 PowerShell을 사용합니다 (HRESULT 0x{:08X})", e.code().0 as u32);
+            list_audio_devices_powershell()
+        }
+    }
+}
+
+fn list_endpoints() -> windows::core::Result<Vec<AudioDevice>> {
+    let mut devices = Vec::new();
+
+    unsafe {
+        let initialized = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let result = (|| -> windows::core::Result<()> {
+            let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+            for (flow, device_type) in [(eRender, "output"), (eCapture, "input")] {
+                // 기본 장치는 eConsole 역할 기준 (장치가 하나도 없으면 오류이므로 무시)
+                let default_id = enumerator
+                    .GetDefaultAudioEndpoint(flow, eConsole)
+                    .and_then(|device| endpoint_id(&device))
+                    .ok();
+                let collection = enumerator.EnumAudioEndpoints(flow, DEVICE_STATE_ACTIVE)?;
+                for index in 0..collection.GetCount()? {
+                    let device = collection.Item(index)?;
+                    let id = endpoint_id(&device)?;
+                    let volume = device.Activate::<IAudioEndpointVolume>(CLSCTX_ALL, None).ok();
+                    devices.push(AudioDevice {
+                        name: friendly_name(&device).unwrap_or_else(|| id.clone()),
+                        is_default: default_id.as_deref() == Some(id.as_str()),
+                        device_type: device_type.to_string(),
+                        volume: volume
+                            .as_ref()
+                            .and_then(|volume| volume.GetMasterVolumeLevelScalar().ok())
+                            .map(scalar_to_percent),
+                        muted: volume.as_ref().and_then(|volume| volume.GetMute().ok()).map(|muted| muted.as_bool()),
+                        id,
+                        ..Default::default()
+                    });
+                }
+            }
+            Ok(())
+        })();
+        // 열거자와 장치는 위에서 이미 해제되었으므로 여기서 COM을 정리해도 됩니다
+        if initialized.is_ok() {
+            CoUninitialize();
+        }
+        result?;
+    }
+
+    Ok(devices)
+}
+
+// IAudioEndpointVolume의 0.0~1.0 ↔ 0~100
+fn scalar_to_percent(level: f32) -> u32 {
+    (level.clamp(0.0, 1.0) * 100.0).round() as u32
+}
+
+fn percent_to_scalar(percent: u32) -> f32 {
+    percent.min(100) as f32 / 100.0
+}
+
+// 기본 장치(eConsole)의 볼륨과 음소거 설정 (입력은 녹음 엔드포인트에 적용, None이면 그대로 둠)
+fn set_default_endpoint_volume(device_type: &str, volume: Option<u32>, muted: Option<bool>) -> Result<(), AppError> {
+    let flow = if device_type == "input" { eCapture } else { eRender };
+    unsafe {
+        let initialized = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let result = (|| -> windows::core::Result<()> {
+            let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+            let device = enumerator.GetDefaultAudioEndpoint(flow, eConsole)?;
+            let endpoint_volume: IAudioEndpointVolume = device.Activate(CLSCTX_ALL, None)?;
+            if let Some(volume) = volume {
+                endpoint_volume.SetMasterVolumeLevelScalar(percent_to_scalar(volume), std::ptr::null())?;
+            }
+            if let Some(muted) = muted {
+                endpoint_volume.SetMute(BOOL::from(muted), std::ptr::null())?;
+            }
+            Ok(())
+        })();
+        if initialized.is_ok() {
+            CoUninitialize();
+        }
+        result.map_err(|e| AppError::ToolFailed {
+            tool: "IAudioEndpointVolume".to_string(),
+            detail: format!("{} volume failed (HRESULT 0x{:08X})", device_type, e.code().0 as u32),
+        })
+    }
+}
+
+// 엔드포인트 id (예: {0.0.0.00000000}.{b3f8fa53-...})
+unsafe fn endpoint_id(device: &IMMDevice) -> windows::core::Result<String> {
+    let id = device.GetId()?;
+    let value = id.to_string();
+    CoTaskMemFree(Some(id.0 as *const c_void));
+    value.map_err(|_| windows::core::Error::from(E_FAIL))
+}
+
+// 제어판에 표시되는 이름 (예: "스피커 (Realtek(R) Audio)")
+unsafe fn friendly_name(device: &IMMDevice) -> Option<String> {
+    let store = device.OpenPropertyStore(STGM_READ).ok()?;
+    let value = store.GetValue(&PKEY_Device_FriendlyName).ok()?;
+    Some(value.to_string()).filter(|name| !name.is_empty())
+}
+
+// PowerShell(AudioDeviceCmdlets)로 목록 조회 (WASAPI를 사용할 수 없을 때)
+// 모듈이 없으면 설치 방법을 담은 오류를 반환합니다
+fn list_audio_devices_powershell() -> Result<Vec<AudioDevice>, AppError> {
+    let output = powershell()
+        .run("Get-AudioDevice -List | Select-Object Name, ID, Type, Default | ConvertTo-Json")
+        .map_err(|e| match e {
+            // 스크립트 오류 (Get-AudioDevice를 찾을 수 없음)
+            AppError::ToolFailed { detail, .. } => AppError::ToolMissing {
+                tool: "AudioDeviceCmdlets".to_string(),
+                install_hint: "Install-Module -Name AudioDeviceCmdlets -Scope CurrentUser".to_string(),
+                detail,
+            },
+            e => e,
+        })?;
+    parse_audio_devices(&output)
+}
+
+// 문서화되지 않은 IPolicyConfig COM 인터페이스 (Windows 7 이후 제어판과 같은 방식)
+// SetDefaultEndpoint 앞의 메서드는 사용하지 않지만 vtable 순서를 맞추기 위해 선언합니다
+#[allow(non_snake_case)]
+#[windows_core::interface("f8679f50-850a-41cf-9c72-430f290290c8")]
+unsafe trait IPolicyConfig: IUnknown {
+    fn GetMixFormat(&self, device_id: PCWSTR, format: *mut *mut c_void) -> HRESULT;
+    fn GetDeviceFormat(&self, device_id: PCWSTR, default: BOOL, format: *mut *mut c_void) -> HRESULT;
+    fn ResetDeviceFormat(&self, device_id: PCWSTR) -> HRESULT;
+    fn SetDeviceFormat(&self, device_id: PCWSTR, endpoint: *const c_void, mix: *const c_void)
+        -> HRESULT;
+    fn GetProcessingPeriod(&self, device_id: PCWSTR, default: BOOL, period: *mut i64, min: *mut i64)
+        -> HRESULT;
+    fn SetProcessingPeriod(&self, device_id: PCWSTR, period: *const i64) -> HRESULT;
+    fn GetShareMode(&self, device_id: PCWSTR, mode: *mut c_void) -> HRESULT;
+    fn SetShareMode(&self, device_id: PCWSTR, mode: *const c_void) -> HRESULT;
+    fn GetPropertyValue(&self, device_id: PCWSTR, key: *const c_void, value: *mut c_void) -> HRESULT;
+    fn SetPropertyValue(&self, device_id: PCWSTR, key: *const c_void, value: *const c_void)
+        -> HRESULT;
+    fn SetDefaultEndpoint(&self, device_id: PCWSTR, role: ERole) -> HRESULT;
+}
+
+const CLSID_POLICY_CONFIG_CLIENT: GUID = GUID::from_u128(0x870af99c_171d_4f9e_af0d_e63df40c2bc9);
+
+// 장치 id(엔드포인트 id)를 역할의 기본 장치로 지정
+fn set_default_endpoint(device_id: &str, role: u32) -> Result<(), AppError> {
+    let failed = |step: &str, error: windows::core::Error| AppError::ToolFailed {
+        tool: "IPolicyConfig".to_string(),
+        detail: format!("{} failed (HRESULT 0x{:08X})", step, error.code().0 as u32),
+    };
+    let wide: Vec<u16> = device_id.encode_utf16().chain(std::iter::once(0)).collect();
+
+    unsafe {
+        let initialized = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+        let policy: windows::core::Result<IPolicyConfig> =
+            CoCreateInstance(&CLSID_POLICY_CONFIG_CLIENT, None, CLSCTX_ALL);
+        let result = policy
+            .map_err(|e| failed("CoCreateInstance", e))
+            .and_then(|policy| {
+                policy
+                    .SetDefaultEndpoint(PCWSTR(wide.as_ptr()), ERole(role as i32))
+                    .ok()
+                    .map_err(|e| failed("SetDefaultEndpoint", e))
+            });
+        // 인터페이스는 위에서 이미 해제되었으므로 여기서 COM을 정리해도 됩니다
+        if initialized.is_ok() {
+            CoUninitialize();
+        }
+        result
+    }
+}
+
+pub fn apply_audio_settings(audio_settings: &AudioSettings) -> Result<String, AppError> {
+    let mut backend = "none".to_string();
+
+    // 엔드포인트 id로 세 역할 모두의 기본 장치를 지정 (COM을 사용할 수 없으면 nircmd 또는 PowerShell)
+    if let Some(output_device) = &audio_settings.output_device {
+        backend = set_default_audio_device("output", output_device)?;
+    }
+    if let Some(input_device) = &audio_settings.input_device {
+        backend = set_default_audio_device("input", input_device)?;
+    }
+
+    // 역할별 기본 장치는 output_device 다음에 적용해 해당 역할만 덮어씁니다
+    if let Some(output_roles) = &audio_settings.output_roles {
+        for (role, device_id) in output_roles {
+            set_default_endpoint(device_id, audio_role(role)?)?;
+            backend = "policy-config".to_string();
+        }
+    }
+
+    // 볼륨과 음소거는 (바뀐) 기본 장치에 적용합니다 (볼륨 0은 소리 없음)
+    set_default_endpoint_volume("output", Some(audio_settings.output_volume), audio_settings.output_muted)?;
+    set_default_endpoint_volume("input", Some(audio_settings.input_volume), audio_settings.input_muted)?;
+
+    Ok(backend)
+}
+
+pub fn set_default_volume(device_type: &str, volume: Option<u32>, muted: Option<bool>) -> Result<String, AppError> {
+    set_default_endpoint_volume(device_type, volume, muted)?;
+    Ok("IAudioEndpointVolume".to_string())
+}
+
+// 기본 장치 변경 (사용한 백엔드 이름 반환)
+// 장치는 엔드포인트 id 또는 이름(패턴으로 찾은 장치)이며, 연결되어 있지 않으면 오류를 반환합니다
+pub fn set_default_audio_device(device_type: &str, device: &str) -> Result<String, AppError> {
+    // 명령 템플릿을 지정했으면 그 명령을 사용합니다
+    if has_custom_template(&format!("audio.{}", device_type)) {
+        return set_default_audio_device_by_name(device_type, device);
+    }
+    let endpoints = match list_endpoints() {
+        Ok(endpoints) => endpoints,
+        Err(e) => {
+            log::warn!("WASAPI를 사용할 수 없어 nircmd로 기본 장치를 바꿉니다 (HRESULT 0x{:08X})", e.code().0 as u32);
+            return set_default_audio_device_by_name(device_type, device);
+        }
+    };
+    let endpoint = endpoints
+        .iter()
+        .filter(|endpoint| endpoint.device_type == device_type)
+        .find(|endpoint| endpoint.id == device)
+        .or_else(|| endpoints.iter().find(|endpoint| endpoint.device_type == device_type && endpoint.name == device))
+        .ok_or_else(|| AppError::DeviceNotFound {
+            device_type: device_type.to_string(),
+            pattern: device.to_string(),
+        })?;
+
+    for (_, role) in AUDIO_ROLES {
+        match set_default_endpoint(&endpoint.id, *role) {
+            Ok(()) => {}
+            // IPolicyConfig를 만들 수 없는 경우에만 nircmd로 대신합니다
+            Err(AppError::ToolFailed { detail, .. }) if detail.starts_with("CoCreateInstance") => {
+                log::warn!("IPolicyConfig를 사용할 수 없어 nircmd로 기본 장치를 바꿉니다: {}", detail);
+                return set_default_audio_device_by_name(device_type, &endpoint.name);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok("policy-config".to_string())
+}
+
+// nircmd, 실패 시 PowerShell로 이름으로 기본 장치 변경 (사용한 백엔드 이름 반환)
+fn set_default_audio_device_by_name(device_type: &str, device: &str) -> Result<String, AppError> {
+    let mut backend = "none".to_string();
+
+    // nircmd를 사용한 오디오 장치 변경 시도
+    let (mut command, program, custom) = templated_command(
+        &format!("audio.{}", device_type),
+        &[("device", &[device.to_string()])],
+    )?;
+    match run_command(&mut command) {
+        Ok(output) => {
+            if output.status.success() {
+                backend = if custom { format!("{} (custom)", program) } else { program.clone() };
+            } else {
+                // nircmd가 실패하면 PowerShell 시도
+                match powershell().run(&format!("Set-AudioDevice -Name '{}'", device)) {
+                    Ok(_) => {
+                        backend = "powershell".to_string();
+                    }
+                    Err(AppError::ToolFailed { detail, .. }) => {
+                        log::warn!("Windows 오디오 설정 변경이 부분적으로 실패했습니다. nircmd 또는 AudioDeviceCmdlets 모듈이 필요할 수 있습니다. ({})", detail);
+                    }
+                    Err(_) => {
+                        log::warn!("Windows 오디오 설정 변경을 위해 nircmd 또는 AudioDeviceCmdlets PowerShell 모듈이 필요합니다.");
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            log::warn!("{} Windows 오디오 설정 변경이 제한됩니다.", tool_missing_message(&program, &e));
+        }
+    }
+
+    Ok(backend)
+}
+
+// 장치 추가/제거, 상태, 기본 장치 변경을 받는 IMMNotificationClient
+#[windows_core::implement(IMMNotificationClient)]
+struct EndpointNotifier {
+    notifier: ChangeNotifier,
+}
+
+#[allow(non_snake_case)]
+impl IMMNotificationClient_Impl for EndpointNotifier_Impl {
+    fn OnDeviceStateChanged(&self, _device_id: &PCWSTR, _state: DEVICE_STATE) -> windows::core::Result<()> {
+        self.notifier.changed();
+        Ok(())
+    }
+
+    fn OnDeviceAdded(&self, _device_id: &PCWSTR) -> windows::core::Result<()> {
+        self.notifier.changed();
+        Ok(())
+    }
+
+    fn OnDeviceRemoved(&self, _device_id: &PCWSTR) -> windows::core::Result<()> {
+        self.notifier.changed();
+        Ok(())
+    }
+
+    fn OnDefaultDeviceChanged(
+        &self,
+        _flow: EDataFlow,
+        _role: ERole,
+        _device_id: &PCWSTR,
+    ) -> windows::core::Result<()> {
+        self.notifier.changed();
+        Ok(())
+    }
+
+    // 이름 변경 외에도 자주 오는 알림이라 무시합니다 (목록 비교로 걸러지지만 다시 읽는 비용이 큼)
+    fn OnPropertyValueChanged(&self, _device_id: &PCWSTR, _key: &PROPERTYKEY) -> windows::core::Result<()> {
+        Ok(())
+    }
+}
+
+// 콜백은 COM(MTA) 스레드에서 호출됩니다
+// 등록한 열거자와 콜백은 앱이 끝날 때까지 유지해야 하므로 전용 스레드가 들고 있습니다
+pub fn watch_audio_changes(notifier: ChangeNotifier) -> Result<String, AppError> {
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let registered = (|| -> windows::core::Result<(IMMDeviceEnumerator, IMMNotificationClient)> {
+            let enumerator: IMMDeviceEnumerator =
+                CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+            let client: IMMNotificationClient = EndpointNotifier { notifier }.into();
+            enumerator.RegisterEndpointNotificationCallback(&client)?;
+            Ok((enumerator, client))
+        })();
+        match registered {
+            Ok(_registration) => {
+                let _ = ready_tx.send(Ok(()));
+                loop {
+                    std::thread::park();
+                }
+            }
+            Err(e) => {
+                let _ = ready_tx.send(Err(AppError::ToolFailed {
+                    tool: "IMMNotificationClient".to_string(),
+                    detail: format!(
+                        "RegisterEndpointNotificationCallback failed (HRESULT 0x{:08X})",
+                        e.code().0 as u32
+                    ),
+                }));
+                CoUninitialize();
+            }
+        }
+    });
+    ready_rx.recv().map_err(|e| AppError::Internal { detail: e.to_string() })??;
+    Ok("IMMNotificationClient".to_string())
+}
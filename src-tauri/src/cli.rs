@@ -0,0 +1,245 @@
+// 명령줄 모드 (창을 띄우지 않고 결과만 출력)
+//   --list-profiles [--plain]  프로필 목록 (JSON, --plain이면 사람이 읽는 표 형식)
+//   --apply <이름 또는 id>      프로필 적용 (실행 중인 앱이 있으면 그 앱에 넘깁니다)
+
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::audio::{set_audio_backend_preference, AudioBackend, AudioBackendPreference, SystemAudioBackend};
+use crate::display::layout::DEFAULT_DESKTOP_LIMIT;
+use crate::display::registry::StableDisplayBackend;
+use crate::display::{DisplayBackend, SystemDisplayBackend};
+use crate::error::AppError;
+use crate::hooks::{run_hook, DEFAULT_HOOK_TIMEOUT_SECS};
+use crate::instance::{forward, INSTANCE_FILE_NAME};
+use crate::locale::{detect_system_locale, set_current_locale, Locale};
+use crate::mock::MockBackend;
+use crate::profile_store::ProfileStore;
+use crate::profiles::{
+    apply_profile_with_events, require_connected_hardware, resolve_profile, validate_profile,
+    validate_profile_against_system, ApplyReport, CancellationToken, Profile,
+};
+use crate::tools::{set_command_timeout, DEFAULT_COMMAND_TIMEOUT_SECS};
+
+#[derive(Debug, PartialEq)]
+enum CliCommand {
+    ListProfiles { plain: bool },
+    Apply { profile: String },
+}
+
+// 창 없이 적용할 때 쓰는 설정 (settings.json에서 필요한 값만 읽습니다)
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct HeadlessSettings {
+    locale: Option<String>,
+    desktop_limit: i32,
+    command_timeout_secs: u64,
+    hook_timeout_secs: u64,
+    skip_missing_audio_devices: bool,
+    audio_backend: AudioBackendPreference,
+}
+
+impl Default for HeadlessSettings {
+    fn default() -> Self {
+        Self {
+            locale: None,
+            desktop_limit: DEFAULT_DESKTOP_LIMIT,
+            command_timeout_secs: DEFAULT_COMMAND_TIMEOUT_SECS,
+            hook_timeout_secs: DEFAULT_HOOK_TIMEOUT_SECS,
+            skip_missing_audio_devices: false,
+            audio_backend: AudioBackendPreference::Auto,
+        }
+    }
+}
+
+// 처리할 옵션이 없으면 None (앱을 그대로 실행)
+fn parse_args(args: &[String]) -> Option<Result<CliCommand, String>> {
+    if let Some(index) = args.iter().position(|arg| arg == "--apply" || arg.starts_with("--apply=")) {
+        let value = match args[index].strip_prefix("--apply=") {
+            Some(value) => Some(value),
+            None => args.get(index + 1).map(String::as_str).filter(|value| !value.starts_with("--")),
+        };
+        return Some(match value.map(str::trim).filter(|value| !value.is_empty()) {
+            Some(profile) => Ok(CliCommand::Apply { profile: profile.to_string() }),
+            None => Err("--apply requires a profile name or id".to_string()),
+        });
+    }
+    // 이전 버전의 --json은 기본값과 같으므로 그대로 받습니다
+    if args.iter().any(|arg| arg == "--list-profiles") {
+        return Some(Ok(CliCommand::ListProfiles { plain: args.iter().any(|arg| arg == "--plain") }));
+    }
+    None
+}
+
+// 명령줄 옵션 처리
+// 창을 띄우기 전에 처리하며, 처리한 경우 종료 코드를 반환합니다 (0 성공, 1 실패, 2 잘못된 옵션)
+pub fn handle_args(identifier: &str) -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = parse_args(&args)?;
+
+    // 릴리스 빌드의 Windows 앱은 콘솔이 없으므로 실행한 터미널에 연결합니다
+    #[cfg(target_os = "windows")]
+    unsafe {
+        use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+        // 터미널에서 실행하지 않았으면 실패하며, 그대로 진행합니다
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+
+    let command = match command {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}", message);
+            return Some(2);
+        }
+    };
+
+    // Tauri의 app_data_dir과 같은 위치 ({데이터 디렉터리}/{identifier})
+    let Some(data_dir) = dirs::data_dir() else {
+        eprintln!("Failed to get app data directory");
+        return Some(1);
+    };
+    let data_dir = data_dir.join(identifier);
+
+    Some(match command {
+        CliCommand::ListProfiles { plain } => list_profiles(&data_dir, plain),
+        CliCommand::Apply { profile } => apply(&data_dir, &profile),
+    })
+}
+
+// 실행 중인 앱이 저장하는 도중의 파일을 읽지 않도록 잠근 뒤 읽습니다
+fn load_profiles(data_dir: &Path) -> Result<Vec<Profile>, AppError> {
+    ProfileStore::new(data_dir.join("profiles.json")).reload()
+}
+
+fn list_profiles(data_dir: &Path, plain: bool) -> i32 {
+    let profiles = match load_profiles(data_dir) {
+        Ok(profiles) => profiles,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    if plain {
+        for profile in &profiles {
+            println!("{}\t{}\t{} display(s)", profile.id, profile.name, profile.displays.len());
+        }
+    } else {
+        let listing: Vec<serde_json::Value> = profiles
+            .iter()
+            .map(|profile| {
+                serde_json::json!({
+                    "id": profile.id,
+                    "name": profile.name,
+                    "created_at": profile.created_at,
+                    "display_count": profile.displays.len(),
+                    "tags": profile.tags,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::Value::Array(listing));
+    }
+    0
+}
+
+fn apply(data_dir: &Path, query: &str) -> i32 {
+    // 실행 중인 앱이 적용하면 UI에서 적용한 것과 같은 이벤트가 나갑니다
+    let result = match forward(&data_dir.join(INSTANCE_FILE_NAME), "apply", query) {
+        Some(Ok(response)) if response["ok"] == true => Ok(response["result"].clone()),
+        Some(Ok(response)) => Err(response["error"]["message"].as_str().unwrap_or("apply failed").to_string()),
+        Some(Err(e)) => Err(format!("Failed to reach the running app: {}", e)),
+        None => apply_headless(data_dir, query)
+            .and_then(|report| serde_json::to_value(report).map_err(|e| AppError::Internal { detail: e.to_string() }))
+            .map_err(|e| e.to_string()),
+    };
+    match result {
+        Ok(report) => {
+            println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+            0
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            1
+        }
+    }
+}
+
+// 실행 중인 앱이 없을 때 같은 프로필 파일과 설정으로 직접 적용합니다
+// 적용 기록, 되돌리기, 정상 구성과 활성 프로필 저장은 앱에서만 합니다
+fn apply_headless(data_dir: &Path, query: &str) -> Result<ApplyReport, AppError> {
+    let settings: HeadlessSettings = std::fs::read(data_dir.join("settings.json"))
+        .ok()
+        .and_then(|content| serde_json::from_slice(&content).ok())
+        .unwrap_or_default();
+    set_current_locale(settings.locale.as_deref().and_then(Locale::parse).unwrap_or_else(detect_system_locale));
+    set_command_timeout(settings.command_timeout_secs);
+    set_audio_backend_preference(settings.audio_backend);
+
+    let profiles = load_profiles(data_dir)?;
+    let profile = resolve_profile(&profiles, query)?.clone();
+    validate_profile(&profile, settings.desktop_limit)?;
+
+    let (displays, audio): (Arc<dyn DisplayBackend>, Arc<dyn AudioBackend>) = match MockBackend::from_env() {
+        Some(mock) => {
+            let mock = mock?;
+            (Arc::new(mock.clone()), Arc::new(mock))
+        }
+        None => (
+            Arc::new(StableDisplayBackend::load(SystemDisplayBackend, data_dir.join("display-registry.json"))),
+            Arc::new(SystemAudioBackend),
+        ),
+    };
+
+    // 앱과 같이 연결되지 않은 디스플레이나 장치가 있으면 적용하지 않습니다
+    let (profile, skipped) = match validate_profile_against_system(&profile, &*displays, &*audio) {
+        Ok(report) => require_connected_hardware(&profile, &report, settings.skip_missing_audio_devices)?,
+        Err(_) => (profile, Vec::new()),
+    };
+
+    let hook_timeout = Duration::from_secs(settings.hook_timeout_secs);
+    let hook = |stage: &str, command: &Option<String>| {
+        let command = command.as_deref().map(str::trim).filter(|command| !command.is_empty())?;
+        run_hook(&profile.id, stage, command, hook_timeout).warning()
+    };
+    let pre_warning = hook("pre", &profile.pre_apply_command);
+    let mut report = apply_profile_with_events(&profile, &*displays, &*audio, &CancellationToken::new(), &|_| {})?;
+    report.warnings.extend(skipped);
+    report.warnings.extend(pre_warning);
+    // 프로세스가 곧 끝나므로 적용 후 명령도 기다립니다
+    report.warnings.extend(hook("post", &profile.post_apply_command));
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_apply_and_list_options() {
+        assert_eq!(parse_args(&args(&[])), None);
+        assert_eq!(parse_args(&args(&["--minimized"])), None);
+        assert_eq!(
+            parse_args(&args(&["--apply", "Desk Setup"])),
+            Some(Ok(CliCommand::Apply { profile: "Desk Setup".to_string() }))
+        );
+        assert_eq!(
+            parse_args(&args(&["--apply=desk"])),
+            Some(Ok(CliCommand::Apply { profile: "desk".to_string() }))
+        );
+        assert!(matches!(parse_args(&args(&["--apply", "--json"])), Some(Err(_))));
+        assert!(matches!(parse_args(&args(&["--apply"])), Some(Err(_))));
+        // 옵션 없이도 JSON (--json은 이전 버전과 같이 받음)
+        for listing in [&["--list-profiles"][..], &["--list-profiles", "--json"]] {
+            assert_eq!(parse_args(&args(listing)), Some(Ok(CliCommand::ListProfiles { plain: false })));
+        }
+        assert_eq!(
+            parse_args(&args(&["--list-profiles", "--plain"])),
+            Some(Ok(CliCommand::ListProfiles { plain: true }))
+        );
+    }
+}
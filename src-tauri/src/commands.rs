@@ -0,0 +1,1924 @@
+// 프론트엔드에서 호출하는 Tauri 명령
+// 명령은 상태 조회와 이벤트 전달만 맡고, 실제 처리는 각 모듈의 함수에 맡깁니다
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{Emitter, Listener, Manager};
+
+use crate::apply_queue::{ApplyQueueMode, ApplyQueueState, ApplyRequest};
+use crate::audio::{
+    apply_audio_settings, capture_audio_settings, default_volume_state, diff_audio_settings, set_audio_backend_preference,
+    set_device_volume, toggle_device_mute, AudioBackendPreference, AudioDevice, AudioSettings, VolumeState,
+};
+use crate::display::layout::{
+    arrange_displays, common_resolution, extend_layout, layout_preview, mirror_layout, LayoutPreview,
+};
+use crate::display::{diff_displays, DesktopMode, DisplayInfo, DisplayMode};
+use crate::error::AppError;
+use crate::hooks::run_hook;
+use crate::hotkeys::Hotkey;
+use crate::last_good::LAST_KNOWN_GOOD_ID;
+use crate::locale::{current_locale, set_current_locale, Locale};
+use crate::profiles::{
+    apply_profile_partial_settings, apply_profile_with_events, display_topology, match_profile_for_displays,
+    merge_imported_profiles, profile_to_script, read_profile_export, replace_with_imported_profiles,
+    require_connected_hardware, suggest_profile_name, validate_profile as check_profile_settings,
+    validate_profile_against_system, validate_profile_name, verify_profile, write_profile_export, ApplyEvent,
+    ApplyReport, CancellationToken, Profile, ProfilesRecovery, ScriptKind, StepStatus, ValidationReport,
+    STARTER_PROFILE_ID,
+};
+use crate::schedules::{due_schedule, parse_schedule_time, Schedule};
+use crate::state::{
+    ActiveProfileRecord, ActiveProfileStatus, AppState, ApplyJournal, RemoteControlSettings, RevertPoint,
+    REVERT_PROFILE_ID,
+};
+use crate::privileges::{check_permissions, refresh_permission_status, PermissionStatus};
+use crate::sync::LockExt;
+use crate::system::{focus_state, machine_name, system_info, SystemInfo};
+use crate::tools::{
+    collect_dependency_report, dependency_updates, install_dependency_blocking, parse_command_template,
+    set_command_templates_override, set_command_timeout, with_command_timeout, DependencyReport, DependencyUpdate,
+    InstallResult, RetryPolicy, DEFAULT_COMMAND_TEMPLATES,
+};
+use crate::{midi, mock, tools, ws};
+
+// 현재 사용 중인 명령 템플릿 (기본값 포함)
+#[tauri::command]
+pub async fn get_command_templates(
+    state: tauri::State<'_, AppState>,
+) -> Result<HashMap<String, String>, AppError> {
+    log::info!("명령 호출: get_command_templates");
+    let mut templates: HashMap<String, String> = DEFAULT_COMMAND_TEMPLATES
+        .iter()
+        .map(|(operation, template)| (operation.to_string(), template.to_string()))
+        .collect();
+    templates.extend(state.settings.lock_or_recover().command_templates.clone());
+    Ok(templates)
+}
+
+// 사용자 정의 명령 템플릿 저장 (비어 있으면 모두 기본값으로 되돌림)
+#[tauri::command]
+pub async fn set_command_templates(
+    state: tauri::State<'_, AppState>,
+    templates: HashMap<String, String>,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: set_command_templates ({:?})", templates);
+    for (operation, template) in &templates {
+        parse_command_template(operation, template)?;
+    }
+
+    let mut settings = state.settings.lock_or_recover();
+    settings.command_templates = templates.clone();
+    state.save_settings(&settings)?;
+
+    set_command_templates_override(templates);
+    Ok(())
+}
+
+// 외부 명령 제한 시간 변경 (초)
+#[tauri::command]
+pub async fn set_command_timeout_secs(state: tauri::State<'_, AppState>, secs: u64) -> Result<(), AppError> {
+    log::info!("명령 호출: set_command_timeout_secs ({})", secs);
+    let mut settings = state.settings.lock_or_recover();
+    settings.command_timeout_secs = secs.max(1);
+    state.save_settings(&settings)?;
+
+    set_command_timeout(settings.command_timeout_secs);
+    Ok(())
+}
+
+// 프로필의 적용 전후 명령 제한 시간 변경 (초)
+#[tauri::command]
+pub async fn set_hook_timeout_secs(state: tauri::State<'_, AppState>, secs: u64) -> Result<(), AppError> {
+    log::info!("명령 호출: set_hook_timeout_secs ({})", secs);
+    let mut settings = state.settings.lock_or_recover();
+    settings.hook_timeout_secs = secs.max(1);
+    state.save_settings(&settings)
+}
+
+// 연결되지 않은 오디오 장치를 건너뛰고 적용할지 변경
+#[tauri::command]
+pub async fn set_skip_missing_audio_devices(state: tauri::State<'_, AppState>, enabled: bool) -> Result<(), AppError> {
+    log::info!("명령 호출: set_skip_missing_audio_devices ({})", enabled);
+    let mut settings = state.settings.lock_or_recover();
+    settings.skip_missing_audio_devices = enabled;
+    state.save_settings(&settings)
+}
+
+// 일시적인 외부 도구 실패의 재시도 정책 변경
+#[tauri::command]
+pub async fn set_retry_policy(
+    state: tauri::State<'_, AppState>,
+    policy: RetryPolicy,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: set_retry_policy ({:?})", policy);
+    let mut settings = state.settings.lock_or_recover();
+    settings.retry_policy = policy.clone();
+    state.save_settings(&settings)?;
+
+    tools::set_retry_policy(policy);
+    Ok(())
+}
+
+// Linux 오디오 백엔드 강제 지정 (auto, pipewire, pulse)
+#[tauri::command]
+pub async fn set_audio_backend(
+    state: tauri::State<'_, AppState>,
+    backend: AudioBackendPreference,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: set_audio_backend ({:?})", backend);
+    let mut settings = state.settings.lock_or_recover();
+    settings.audio_backend = backend;
+    state.save_settings(&settings)?;
+
+    set_audio_backend_preference(backend);
+    Ok(())
+}
+
+// 변경 알림을 받을 수 없을 때 장치 목록을 다시 읽는 주기 저장 (초)
+#[tauri::command]
+pub async fn set_monitor_poll_interval(
+    state: tauri::State<'_, AppState>,
+    secs: u64,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: set_monitor_poll_interval ({})", secs);
+    let mut settings = state.settings.lock_or_recover();
+    settings.monitor_poll_interval_secs = secs.max(1);
+    state.save_settings(&settings)?;
+
+    state.device_monitor.set_poll_interval(settings.monitor_poll_interval_secs);
+    Ok(())
+}
+
+// 디스플레이 정보 가져오기
+#[tauri::command]
+pub async fn get_displays(
+    state: tauri::State<'_, AppState>,
+    timeout_secs: Option<u64>,
+) -> Result<Vec<DisplayInfo>, AppError> {
+    log::info!("명령 호출: get_displays");
+    let displays = with_command_timeout(timeout_secs, || state.display_backend.enumerate())?;
+    *state.last_displays.lock_or_recover() = Some(displays.clone());
+    Ok(displays)
+}
+
+// 디스플레이가 지원하는 모드 (해상도가 큰 순서, 현재 모드 표시)
+#[tauri::command]
+pub async fn get_display_modes(
+    state: tauri::State<'_, AppState>,
+    display_id: u32,
+) -> Result<Vec<DisplayMode>, AppError> {
+    log::info!("명령 호출: get_display_modes (display_id={})", display_id);
+    state.display_backend.modes(display_id)
+}
+
+// 오디오 장치 정보 가져오기
+#[tauri::command]
+pub async fn get_audio_devices(
+    state: tauri::State<'_, AppState>,
+    timeout_secs: Option<u64>,
+) -> Result<Vec<AudioDevice>, AppError> {
+    log::info!("명령 호출: get_audio_devices");
+    let devices = with_command_timeout(timeout_secs, || state.audio_backend.enumerate())?;
+    *state.last_audio_devices.lock_or_recover() = Some(devices.clone());
+    Ok(devices)
+}
+
+// 현재 기본 입출력 장치와 볼륨 (프로필 작성 화면의 초기값)
+#[tauri::command]
+pub async fn get_audio_settings(state: tauri::State<'_, AppState>) -> Result<AudioSettings, AppError> {
+    log::info!("명령 호출: get_audio_settings");
+    let devices = state.audio_backend.enumerate()?;
+    *state.last_audio_devices.lock_or_recover() = Some(devices.clone());
+    Ok(capture_audio_settings(&devices))
+}
+
+// 마지막으로 조회하거나 이벤트로 받은 상태 (늦게 열린 창의 초기화용)
+#[derive(Debug, Serialize, Clone)]
+pub struct LastEvents {
+    pub displays: Option<Vec<DisplayInfo>>,
+    pub audio_devices: Option<Vec<AudioDevice>>,
+    pub active_profile: Option<String>,
+}
+
+// 새로 조회하지 않고 보관 중인 상태 반환
+#[tauri::command]
+pub async fn get_last_events(state: tauri::State<'_, AppState>) -> Result<LastEvents, AppError> {
+    log::info!("명령 호출: get_last_events");
+    Ok(LastEvents {
+        displays: state.last_displays.lock_or_recover().clone(),
+        audio_devices: state.last_audio_devices.lock_or_recover().clone(),
+        active_profile: state.active_profile.lock_or_recover().clone(),
+    })
+}
+
+// displays-changed / audio-devices-changed 이벤트에 목록이 담겨 있으면 보관
+pub fn retain_last_events(app: &tauri::AppHandle) {
+    let app_handle = app.clone();
+    app.listen("displays-changed", move |event| {
+        if let Ok(displays) = serde_json::from_str::<Vec<DisplayInfo>>(event.payload()) {
+            *app_handle.state::<AppState>().last_displays.lock_or_recover() = Some(displays);
+        }
+    });
+    let app_handle = app.clone();
+    app.listen("audio-devices-changed", move |event| {
+        if let Ok(devices) = serde_json::from_str::<Vec<AudioDevice>>(event.payload()) {
+            *app_handle.state::<AppState>().last_audio_devices.lock_or_recover() = Some(devices);
+        }
+    });
+}
+
+// 도킹하면 디스플레이가 하나씩 연결되며 알림이 여러 번 오므로, 마지막 알림 뒤 이 시간 동안 조용해지면 적용합니다
+const AUTO_APPLY_DEBOUNCE: Duration = Duration::from_secs(3);
+
+// 디스플레이가 연결 / 분리되면 구성이 맞는 프로필을 자동 적용 (설정에서 켠 경우)
+// 배치만 바뀐 경우(자동 적용한 프로필의 결과 포함)는 연결된 디스플레이가 같으므로 무시합니다
+pub fn auto_apply_on_display_change(app: &tauri::AppHandle) {
+    let state = app.state::<AppState>();
+    let initial = state.display_backend.enumerate().map(|displays| display_topology(&displays)).ok();
+    let last_topology = Arc::new(Mutex::new(initial));
+    let generation = Arc::new(AtomicU64::new(0));
+
+    let app_handle = app.clone();
+    app.listen("displays-changed", move |event| {
+        let Ok(displays) = serde_json::from_str::<Vec<DisplayInfo>>(event.payload()) else {
+            return;
+        };
+        let current = generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let (app_handle, generation, last_topology) = (app_handle.clone(), generation.clone(), last_topology.clone());
+        std::thread::spawn(move || {
+            std::thread::sleep(AUTO_APPLY_DEBOUNCE);
+            // 그사이 새 알림이 왔으면 그 알림이 처리합니다
+            if generation.load(Ordering::SeqCst) != current {
+                return;
+            }
+            let topology = display_topology(&displays);
+            if last_topology.lock_or_recover().replace(topology.clone()) == Some(topology) {
+                return;
+            }
+
+            let state = app_handle.state::<AppState>();
+            let (enabled, first_match) = {
+                let settings = state.settings.lock_or_recover();
+                (settings.auto_apply_enabled, settings.auto_apply_first_match)
+            };
+            if !enabled || state.pending_recovery.lock_or_recover().is_some() {
+                return;
+            }
+            let profile_id = match match_profile_for_displays(&state.profiles.list(), &displays, first_match) {
+                Some(profile) => profile.id.clone(),
+                None => {
+                    log::info!("연결된 디스플레이와 맞는 프로필이 없어 자동 적용하지 않습니다");
+                    return;
+                }
+            };
+            log::info!("디스플레이 연결이 바뀌어 프로필을 자동 적용합니다: {}", profile_id);
+            if let Err(e) = apply_profile_by_id(&app_handle, &profile_id, "auto") {
+                log::warn!("자동 적용 실패 ({}): {}", profile_id, e);
+            }
+        });
+    });
+}
+
+// 프로필의 auto_apply_on_device 장치가 새로 연결되면 그 프로필의 오디오 설정만 적용
+// 장치가 분리된 경우는 아무것도 되돌리지 않습니다
+pub fn auto_apply_on_audio_device(app: &tauri::AppHandle) {
+    let device_ids = |devices: &[AudioDevice]| devices.iter().map(|device| device.id.clone()).collect::<Vec<_>>();
+    let state = app.state::<AppState>();
+    let initial = state.audio_backend.enumerate().map(|devices| device_ids(&devices)).ok();
+    let known_devices = Arc::new(Mutex::new(initial));
+
+    let app_handle = app.clone();
+    app.listen("audio-devices-changed", move |event| {
+        let Ok(devices) = serde_json::from_str::<Vec<AudioDevice>>(event.payload()) else {
+            return;
+        };
+        let current = device_ids(&devices);
+        // 처음 받은 목록은 비교할 대상이 없으므로 기준으로만 씁니다
+        let Some(previous) = known_devices.lock_or_recover().replace(current.clone()) else {
+            return;
+        };
+        let appeared: Vec<&String> = current.iter().filter(|id| !previous.contains(id)).collect();
+        if appeared.is_empty() {
+            return;
+        }
+
+        // 장치를 바꾸는 동안 프로필 잠금을 잡지 않도록 id만 골라 둡니다
+        let state = app_handle.state::<AppState>();
+        if state.pending_recovery.lock_or_recover().is_some() {
+            return;
+        }
+        let matched: Vec<(String, String)> = state
+            .profiles
+            .list()
+            .iter()
+            .filter(|profile| profile.audio_settings.is_some())
+            .filter_map(|profile| {
+                let device = profile.auto_apply_on_device.as_ref().filter(|id| appeared.contains(id))?;
+                Some((profile.id.clone(), device.clone()))
+            })
+            .collect();
+        let Some((profile_id, device_id)) = matched.first().cloned() else {
+            return;
+        };
+        if matched.len() > 1 {
+            log::warn!("같은 장치로 자동 적용하는 프로필이 여러 개라 {}만 적용합니다: {:?}", profile_id, matched);
+        }
+        let app_handle = app_handle.clone();
+        std::thread::spawn(move || {
+            log::info!("오디오 장치가 연결되어 프로필의 오디오 설정을 자동 적용합니다: {} ({})", profile_id, device_id);
+            if let Err(e) = apply_profile_sections(&app_handle, &profile_id, false, true, "device") {
+                log::warn!("자동 적용 실패 ({}): {}", profile_id, e);
+            }
+        });
+    });
+}
+
+// 시각별 규칙을 확인하는 간격
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+// 시각별 규칙의 프로필 적용 (지난 확인 뒤 정해진 시각이 지나간 규칙 중 가장 늦은 것 하나)
+// apply_profile과 같은 경로로 적용하며, 실패하면 다시 시도하지 않고 schedule-apply-failed로 알립니다
+// 시작하기 전의 시각은 보지 않으므로 앱을 켰을 때 지난 규칙을 적용하지 않습니다
+pub fn run_schedules(app: &tauri::AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut last_check = chrono::Local::now().naive_local();
+        loop {
+            std::thread::sleep(SCHEDULE_CHECK_INTERVAL);
+            // 복구 확인 창이 떠 있는 동안에는 기준을 옮기지 않아, 그사이 된 규칙을 창이 닫힌 뒤에 적용합니다
+            let state = app.state::<AppState>();
+            if state.pending_recovery.lock_or_recover().is_some() {
+                continue;
+            }
+
+            let now = chrono::Local::now().naive_local();
+            // 시계가 뒤로 가면 (서머타임 종료, 시각 동기화) 같은 규칙을 다시 적용하지 않도록 기준만 옮깁니다
+            let since = std::mem::replace(&mut last_check, now);
+            if now <= since {
+                continue;
+            }
+            let Some(schedule) = due_schedule(&state.schedules.lock_or_recover(), since, now).cloned() else {
+                continue;
+            };
+            log::info!("시각별 규칙으로 프로필을 적용합니다: {} ({})", schedule.profile_id, schedule.time);
+            if let Err(e) = apply_profile_by_id(&app, &schedule.profile_id, "schedule") {
+                log::warn!("시각별 적용 실패 ({}): {}", schedule.profile_id, e);
+                let _ = app.emit(
+                    "schedule-apply-failed",
+                    serde_json::json!({ "schedule_id": schedule.id, "profile_id": schedule.profile_id, "error": e }),
+                );
+            }
+        }
+    });
+}
+
+// 프로필 저장
+#[tauri::command]
+pub async fn save_profile(app: tauri::AppHandle, profile: Profile) -> Result<(), AppError> {
+    log::info!("명령 호출: save_profile (id={}, name={})", profile.id, profile.name);
+    run_blocking(app, move |app| {
+        let state = app.state::<AppState>();
+        check_profile_settings(&profile, state.settings.lock_or_recover().desktop_limit)?;
+        // 파일에 저장 (다른 프로세스가 그사이 저장한 프로필도 유지)
+        // 이름이나 단축키가 다른 프로필과 겹치거나 OS가 단축키 등록을 거부하면 저장하지 않습니다
+        state.profiles.update(|profiles| {
+            let mut profile = profile.clone();
+            profile.name = validate_profile_name(&profile.name, &profile.id, profiles)?;
+            if let Some(pos) = profiles.iter().position(|p| p.id == profile.id) {
+                profiles[pos] = profile;
+            } else {
+                profiles.push(profile);
+            }
+            state.hotkeys.sync(profiles)
+        })?;
+
+        let _ = app.emit("profiles-changed", ());
+        Ok(())
+    })
+    .await
+}
+
+// 프로필 이름만 바꾸기 (비어 있거나 다른 프로필과 같은 이름이면 오류)
+#[tauri::command]
+pub async fn rename_profile(app: tauri::AppHandle, profile_id: String, new_name: String) -> Result<Profile, AppError> {
+    log::info!("명령 호출: rename_profile (id={}, name={})", profile_id, new_name);
+    run_blocking(app, move |app| {
+        let renamed = app.state::<AppState>().profiles.update(|profiles| {
+            let name = validate_profile_name(&new_name, &profile_id, profiles)?;
+            let profile = profiles
+                .iter_mut()
+                .find(|p| p.id == profile_id)
+                .ok_or_else(|| AppError::ProfileNotFound { profile_id: profile_id.clone() })?;
+            profile.name = name;
+            Ok(profile.clone())
+        })?;
+
+        let _ = app.emit("profiles-changed", ());
+        Ok(renamed)
+    })
+    .await
+}
+
+// 프로필 복제 (새 id와 생성 시각)
+// 이름을 주지 않으면 "이름 (copy)", 그 이름도 있으면 "이름 (copy) (2)", ...
+// 단축키와 자동 적용 장치는 원본과 겹치므로 복사하지 않습니다
+#[tauri::command]
+pub async fn duplicate_profile(
+    app: tauri::AppHandle,
+    profile_id: String,
+    new_name: Option<String>,
+) -> Result<Profile, AppError> {
+    log::info!("명령 호출: duplicate_profile (id={}, name={:?})", profile_id, new_name);
+    run_blocking(app, move |app| {
+        let copy = app.state::<AppState>().profiles.update(|profiles| {
+            let original = profiles
+                .iter()
+                .find(|p| p.id == profile_id)
+                .ok_or_else(|| AppError::ProfileNotFound { profile_id: profile_id.clone() })?;
+            let id = uuid::Uuid::new_v4().to_string();
+            let name = match &new_name {
+                Some(name) => validate_profile_name(name, &id, profiles)?,
+                None => suggest_profile_name(&format!("{} (copy)", original.name.trim()), profiles),
+            };
+            let copy = Profile {
+                id,
+                name,
+                created_at: chrono::Local::now().to_rfc3339(),
+                hotkey: None,
+                auto_apply_on_device: None,
+                ..original.clone()
+            };
+            profiles.push(copy.clone());
+            Ok(copy)
+        })?;
+
+        let _ = app.emit("profiles-changed", ());
+        Ok(copy)
+    })
+    .await
+}
+
+// 저장하기 전에 단축키 형식 확인 (정규화한 형식 반환, 예: "CmdOrCtrl+Shift+1" → "Ctrl+Shift+1")
+#[tauri::command]
+pub async fn validate_hotkey(accel: String) -> Result<String, AppError> {
+    log::info!("명령 호출: validate_hotkey ({})", accel);
+    Ok(Hotkey::parse(&accel)?.canonical())
+}
+
+// 현재 디스플레이 배치와 기본 오디오 장치(볼륨 포함)로 만든 새 프로필 (적용할 때 읽는 백엔드와 같은 값)
+fn current_state_profile(state: &AppState, name: &str) -> Result<Profile, AppError> {
+    let displays = state.display_backend.enumerate()?;
+    let devices = state.audio_backend.enumerate()?;
+    // 이름을 비워 두면 컴퓨터 이름을 사용합니다
+    let name = match name.trim() {
+        "" => machine_name(),
+        name => name.to_string(),
+    };
+    Ok(Profile {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        displays,
+        audio_settings: Some(capture_audio_settings(&devices)),
+        created_at: chrono::Local::now().to_rfc3339(),
+        tags: Vec::new(),
+        color: None,
+        hotkey: None,
+        pre_apply_command: None,
+        post_apply_command: None,
+        auto_apply_on_device: None,
+        output_follows_display: false,
+    })
+}
+
+// 현재 구성으로 만든 프로필을 저장하지 않고 반환 (이름을 확인받은 뒤 save_profile로 저장)
+#[tauri::command]
+pub async fn capture_current_profile(
+    state: tauri::State<'_, AppState>,
+    name: String,
+) -> Result<Profile, AppError> {
+    log::info!("명령 호출: capture_current_profile (name={})", name);
+    current_state_profile(&state, &name)
+}
+
+// 현재 구성으로 새 프로필을 만들어 저장
+// 같은 이름의 프로필이 있으면 덮어쓰지 않고 번호를 붙입니다 (예: "Desk (2)")
+#[tauri::command]
+pub async fn capture_current_state(app: tauri::AppHandle, name: String) -> Result<Profile, AppError> {
+    log::info!("명령 호출: capture_current_state (name={})", name);
+    run_blocking(app, move |app| save_current_state(app, &app.state::<AppState>(), &name)).await
+}
+
+// capture_current_state와 트레이의 "현재 구성 저장"이 같이 사용
+pub fn save_current_state(app: &tauri::AppHandle, state: &AppState, name: &str) -> Result<Profile, AppError> {
+    let mut profile = current_state_profile(state, name)?;
+    check_profile_settings(&profile, state.settings.lock_or_recover().desktop_limit)?;
+
+    // 이름은 잠금을 잡은 뒤 파일의 최신 목록으로 정합니다
+    let profile = state.profiles.update(|profiles| {
+        profile.name = suggest_profile_name(&profile.name, profiles);
+        profiles.push(profile.clone());
+        Ok(profile)
+    })?;
+
+    let _ = app.emit("profiles-changed", ());
+    Ok(profile)
+}
+
+// 프로필 목록 가져오기 (메모리의 목록, 파일은 읽지 않음)
+// 앱의 명령은 모두 저장소를 거쳐 쓰므로 메모리의 목록이 최신이며, 파일을 직접 고쳤으면 refresh_profiles로 다시 읽습니다
+#[tauri::command]
+pub async fn get_profiles(state: tauri::State<'_, AppState>) -> Result<Vec<Profile>, AppError> {
+    log::info!("명령 호출: get_profiles");
+    Ok(state.profiles.list())
+}
+
+// profiles.json이 앱 밖에서 바뀌었으면 다시 읽음 (바뀌었으면 profiles-changed 이벤트)
+#[tauri::command]
+pub async fn refresh_profiles(app: tauri::AppHandle) -> Result<Vec<Profile>, AppError> {
+    log::info!("명령 호출: refresh_profiles");
+    run_blocking(app, |app| {
+        let state = app.state::<AppState>();
+        let before = state.profiles.list();
+        let profiles = state.profiles.reload()?;
+        if serde_json::to_value(&profiles).ok() != serde_json::to_value(&before).ok() {
+            let _ = app.emit("profiles-changed", ());
+        }
+        Ok(profiles)
+    })
+    .await
+}
+
+// 프로필 삭제
+#[tauri::command]
+pub async fn delete_profile(app: tauri::AppHandle, profile_id: String) -> Result<(), AppError> {
+    log::info!("명령 호출: delete_profile (id={})", profile_id);
+    run_blocking(app, move |app| {
+        let state = app.state::<AppState>();
+        state.profiles.update(|profiles| {
+            profiles.retain(|p| p.id != profile_id);
+            // 삭제한 프로필의 단축키 해제
+            if let Err(e) = state.hotkeys.sync(profiles) {
+                log::warn!("단축키 등록 실패: {}", e);
+            }
+            Ok(())
+        })?;
+
+        let was_active = state.active_profile.lock_or_recover().as_deref() == Some(profile_id.as_str());
+        if was_active {
+            state.set_active_profile(None);
+            let _ = app.emit("active-profile-changed", None::<String>);
+        }
+
+        // 삭제한 프로필을 적용하는 시각별 규칙도 삭제
+        if state.schedules.lock_or_recover().iter().any(|schedule| schedule.profile_id == profile_id) {
+            state.update_schedules(|schedules| {
+                schedules.retain(|schedule| schedule.profile_id != profile_id);
+                Ok(())
+            })?;
+            let _ = app.emit("schedules-changed", ());
+        }
+
+        let _ = app.emit("profiles-changed", ());
+        Ok(())
+    })
+    .await
+}
+
+// 시각별 프로필 적용 규칙 목록
+#[tauri::command]
+pub async fn get_schedules(state: tauri::State<'_, AppState>) -> Result<Vec<Schedule>, AppError> {
+    log::info!("명령 호출: get_schedules");
+    Ok(state.schedules.lock_or_recover().clone())
+}
+
+// 시각별 규칙 저장 (같은 id가 있으면 바꾸고, id가 비어 있으면 새로 만듦)
+#[tauri::command]
+pub async fn save_schedule(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    mut schedule: Schedule,
+) -> Result<Schedule, AppError> {
+    log::info!("명령 호출: save_schedule (id={}, profile={}, time={})", schedule.id, schedule.profile_id, schedule.time);
+    // 시각은 "9:05"처럼 입력해도 "09:05"로 저장하며, 저장된 프로필만 가리킬 수 있습니다
+    schedule.time = parse_schedule_time(&schedule.time)?.format("%H:%M").to_string();
+    state.profiles.get(&schedule.profile_id)?;
+    if schedule.id.is_empty() {
+        schedule.id = uuid::Uuid::new_v4().to_string();
+    }
+    state.update_schedules(|schedules| {
+        match schedules.iter().position(|s| s.id == schedule.id) {
+            Some(pos) => schedules[pos] = schedule.clone(),
+            None => schedules.push(schedule.clone()),
+        }
+        Ok(())
+    })?;
+    let _ = app.emit("schedules-changed", ());
+    Ok(schedule)
+}
+
+// 시각별 규칙 삭제
+#[tauri::command]
+pub async fn delete_schedule(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    schedule_id: String,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: delete_schedule (id={})", schedule_id);
+    state.update_schedules(|schedules| {
+        schedules.retain(|schedule| schedule.id != schedule_id);
+        Ok(())
+    })?;
+    let _ = app.emit("schedules-changed", ());
+    Ok(())
+}
+
+// 외부 도구를 기다리는 작업은 블로킹 스레드에서 실행 (비동기 런타임이 멈추지 않도록)
+async fn run_blocking<T: Send + 'static>(
+    app: tauri::AppHandle,
+    task: impl FnOnce(&tauri::AppHandle) -> Result<T, AppError> + Send + 'static,
+) -> Result<T, AppError> {
+    tauri::async_runtime::spawn_blocking(move || task(&app))
+        .await
+        .map_err(|e| AppError::Internal { detail: format!("작업 실행 실패: {}", e) })?
+}
+
+// 프로필 적용 (timeout_secs를 주면 이번 적용의 외부 명령에만 그 제한 시간을 씁니다)
+#[tauri::command]
+pub async fn apply_profile(
+    app: tauri::AppHandle,
+    profile_id: String,
+    timeout_secs: Option<u64>,
+) -> Result<ApplyReport, AppError> {
+    log::info!("명령 호출: apply_profile (id={})", profile_id);
+    run_blocking(app, move |app| {
+        with_command_timeout(timeout_secs, || apply_profile_by_id(app, &profile_id, "command"))
+    })
+    .await
+}
+
+// 프로필을 적용하면 무엇이 바뀌는지 확인 (적용과 같은 방법으로 조회하고 아무것도 바꾸지 않음)
+#[tauri::command]
+pub async fn validate_profile(app: tauri::AppHandle, profile_id: String) -> Result<ValidationReport, AppError> {
+    log::info!("명령 호출: validate_profile (id={})", profile_id);
+    run_blocking(app, move |app| {
+        let state = app.state::<AppState>();
+        let profile = state.profiles.get(&profile_id)?;
+        check_profile_settings(&profile, state.settings.lock_or_recover().desktop_limit)?;
+        validate_profile_against_system(&profile, &*state.display_backend, &*state.audio_backend)
+    })
+    .await
+}
+
+// 중단된 적용 처리 방법
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryAction {
+    // 적용 전 구성으로 되돌리기
+    Restore,
+    // 기록만 지우기
+    Discard,
+    // 같은 프로필 다시 적용
+    Retry,
+}
+
+// 손상된 프로필 파일을 백업으로 되돌린 기록 (없으면 None)
+#[tauri::command]
+pub async fn get_profiles_recovery(
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<ProfilesRecovery>, AppError> {
+    log::info!("명령 호출: get_profiles_recovery");
+    Ok(state.profiles.recovery())
+}
+
+// 이전 실행에서 적용 도중 종료된 기록
+#[tauri::command]
+pub async fn get_pending_recovery(
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<ApplyJournal>, AppError> {
+    log::info!("명령 호출: get_pending_recovery");
+    Ok(state.pending_recovery.lock_or_recover().clone())
+}
+
+#[tauri::command]
+pub async fn resolve_pending_recovery(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    action: RecoveryAction,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: resolve_pending_recovery ({:?})", action);
+    let journal = state
+        .pending_recovery
+        .lock_or_recover()
+        .clone()
+        .ok_or(AppError::NoPendingRecovery)?;
+
+    match action {
+        RecoveryAction::Restore => {
+            let snapshot = journal.snapshot.clone().ok_or(AppError::NoRecoverySnapshot)?;
+            let profile_id = journal.profile_id.clone();
+            run_blocking(app, move |app| {
+                let state = app.state::<AppState>();
+                let request = ApplyRequest::new(&profile_id, "recovery", "command");
+                state.apply_queue.run(request, || {
+                    // 디스플레이와 오디오는 따로 적용되었을 수 있으므로 한쪽이 실패해도 다른 쪽은 되돌립니다
+                    state.with_apply_journal(&profile_id, &["display", "audio"], || {
+                        let display = state.display_backend.apply(&snapshot.displays);
+                        let audio =
+                            apply_audio_settings(&*state.audio_backend, &snapshot.audio_settings);
+                        display.and(audio)
+                    })
+                })
+            })
+            .await?;
+        }
+        RecoveryAction::Discard => state.clear_apply_journal(),
+        RecoveryAction::Retry if journal.profile_id == LAST_KNOWN_GOOD_ID => {
+            run_blocking(app, |app| restore_last_known_good_by_source(app, "command")).await?;
+        }
+        RecoveryAction::Retry => {
+            let profile_id = journal.profile_id.clone();
+            run_blocking(app, move |app| apply_profile_by_id(app, &profile_id, "command")).await?;
+        }
+    }
+
+    *state.pending_recovery.lock_or_recover() = None;
+    Ok(())
+}
+
+// 마지막 적용 전 구성 (없으면 None)
+#[tauri::command]
+pub async fn get_revert_point(state: tauri::State<'_, AppState>) -> Result<Option<RevertPoint>, AppError> {
+    log::info!("명령 호출: get_revert_point");
+    Ok(state.revert_point.lock_or_recover().clone())
+}
+
+// 마지막 적용 직전의 구성으로 되돌리기 (적용이 중간에 실패했어도 적용 전 배치로)
+// 한 번 되돌리면 지점을 지우므로 다시 되돌릴 수 없습니다
+#[tauri::command]
+pub async fn revert_last_apply(app: tauri::AppHandle) -> Result<(), AppError> {
+    log::info!("명령 호출: revert_last_apply");
+    run_blocking(app, |app| {
+        let state = app.state::<AppState>();
+        let point = state.revert_point.lock_or_recover().clone().ok_or(AppError::NoRevertSnapshot)?;
+        let request = ApplyRequest::new(REVERT_PROFILE_ID, "revert", "command");
+        state.apply_queue.run(request, || {
+            state.with_apply_journal(REVERT_PROFILE_ID, &["display", "audio"], || {
+                let display = state.display_backend.apply(&point.snapshot.displays);
+                let audio = apply_audio_settings(&*state.audio_backend, &point.snapshot.audio_settings);
+                display.and(audio)
+            })
+        })?;
+
+        *state.revert_point.lock_or_recover() = None;
+        state.set_active_profile(point.previous_profile.clone());
+        let _ = app.emit("active-profile-changed", &point.previous_profile);
+        log::info!("적용 되돌림: {} → {:?}", point.profile_id, point.previous_profile);
+        Ok(())
+    })
+    .await
+}
+
+// revert_last_apply의 다른 이름 (오류도 같은 AppError)
+#[tauri::command]
+pub async fn undo_last_apply(app: tauri::AppHandle) -> Result<(), AppError> {
+    revert_last_apply(app).await
+}
+
+// 프로필의 오디오 설정만 적용 (디스플레이 배치는 그대로)
+// 일부만 적용하는 명령은 활성 프로필을 바꾸지 않고, 로그에 부분 적용으로 남깁니다
+#[tauri::command]
+pub async fn apply_profile_audio_only(
+    app: tauri::AppHandle,
+    profile_id: String,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: apply_profile_audio_only (id={})", profile_id);
+    run_blocking(app, move |app| {
+        let state = app.state::<AppState>();
+        let profile = state.profiles.get(&profile_id)?;
+        let audio_settings = profile.audio_settings.ok_or_else(|| AppError::ProfileSectionMissing {
+            profile_id: profile_id.clone(),
+            section: "audio".to_string(),
+        })?;
+        let request = ApplyRequest::new(&profile_id, "audio_only", "command");
+        let backend = state.apply_queue.run(request, || {
+            state.with_apply_journal(&profile_id, &["audio"], || {
+                apply_audio_settings(&*state.audio_backend, &audio_settings)
+            })
+        })?;
+        log::info!("프로필 부분 적용 (오디오만): {} ({})", profile_id, backend);
+        Ok(())
+    })
+    .await
+}
+
+// 프로필의 디스플레이 배치만 적용 (오디오 장치는 그대로)
+#[tauri::command]
+pub async fn apply_profile_displays_only(
+    app: tauri::AppHandle,
+    profile_id: String,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: apply_profile_displays_only (id={})", profile_id);
+    run_blocking(app, move |app| {
+        let state = app.state::<AppState>();
+        let profile = state.profiles.get(&profile_id)?;
+        if profile.displays.is_empty() {
+            return Err(AppError::ProfileSectionMissing { profile_id, section: "display".to_string() });
+        }
+        check_profile_settings(&profile, state.settings.lock_or_recover().desktop_limit)?;
+        let request = ApplyRequest::new(&profile_id, "displays_only", "command");
+        let backend = state.apply_queue.run(request, || {
+            state.with_apply_journal(&profile_id, &["display"], || {
+                state.display_backend.apply(&profile.displays)
+            })
+        })?;
+        log::info!("프로필 부분 적용 (디스플레이만): {} ({})", profile_id, backend);
+        Ok(())
+    })
+    .await
+}
+
+// 프로필 중 지금 연결된 디스플레이만 적용 (연결되지 않은 디스플레이와 프로필에 없는 디스플레이는 그대로)
+// apply_displays / apply_audio가 false면 그 부분은 프로필에 있어도 적용하지 않습니다 (기본값 true)
+#[tauri::command]
+pub async fn apply_profile_partial(
+    app: tauri::AppHandle,
+    profile_id: String,
+    apply_displays: Option<bool>,
+    apply_audio: Option<bool>,
+) -> Result<ApplyReport, AppError> {
+    log::info!(
+        "명령 호출: apply_profile_partial (id={}, displays={:?}, audio={:?})",
+        profile_id,
+        apply_displays,
+        apply_audio
+    );
+    run_blocking(app, move |app| {
+        apply_profile_sections(app, &profile_id, apply_displays.unwrap_or(true), apply_audio.unwrap_or(true), "command")
+    })
+    .await
+}
+
+// 프로필의 디스플레이 / 오디오 중 고른 부분만 적용 (연결된 디스플레이만)
+pub fn apply_profile_sections(
+    app: &tauri::AppHandle,
+    profile_id: &str,
+    apply_displays: bool,
+    apply_audio: bool,
+    source: &str,
+) -> Result<ApplyReport, AppError> {
+    let state = app.state::<AppState>();
+    let mut profile = state.profiles.get(profile_id)?;
+    if !apply_displays {
+        profile.displays.clear();
+    }
+    if !apply_audio {
+        profile.audio_settings = None;
+    }
+    check_profile_settings(&profile, state.settings.lock_or_recover().desktop_limit)?;
+    let intents: Vec<&str> = [
+        (!profile.displays.is_empty()).then_some("display"),
+        profile.audio_settings.is_some().then_some("audio"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let request = ApplyRequest::new(profile_id, "partial", source);
+    let report = state.apply_queue.run(request, || {
+        let cancel = state.begin_apply();
+        let result = state.with_apply_journal(profile_id, &intents, || {
+            apply_profile_partial_settings(
+                &profile,
+                &*state.display_backend,
+                &*state.audio_backend,
+                &cancel,
+            )
+        });
+        state.finish_apply(&cancel);
+        result
+    })?;
+    if let Some(error) = report.error() {
+        emit_apply_event(app, ApplyEvent::Failed { profile_id, error, report: Some(&report) });
+        return Err(error.clone());
+    }
+    if report.cancelled() {
+        emit_apply_event(app, ApplyEvent::Cancelled(&report));
+    }
+    log::info!("프로필 부분 적용 (연결된 디스플레이만): {} ({:?})", profile_id, report.backend_used);
+    Ok(report)
+}
+
+// 현재 디스플레이 배치를 메모리 슬롯에 보관 (프로필과 별개, 앱을 끄면 사라짐)
+#[tauri::command]
+pub async fn stash_layout(state: tauri::State<'_, AppState>, slot: u8) -> Result<(), AppError> {
+    log::info!("명령 호출: stash_layout (slot={})", slot);
+    let index = AppState::layout_slot(slot)?;
+    let displays = state.display_backend.enumerate()?;
+    state.layout_slots.lock_or_recover()[index] = Some(displays);
+    Ok(())
+}
+
+// 슬롯에 보관한 디스플레이 배치 적용
+#[tauri::command]
+pub async fn restore_layout(app: tauri::AppHandle, slot: u8) -> Result<(), AppError> {
+    log::info!("명령 호출: restore_layout (slot={})", slot);
+    let index = AppState::layout_slot(slot)?;
+    let displays = app.state::<AppState>().layout_slots.lock_or_recover()[index]
+        .clone()
+        .ok_or_else(|| AppError::EmptyLayoutSlot { slot: slot.to_string() })?;
+    let backend = run_blocking(app, move |app| {
+        let state = app.state::<AppState>();
+        let request = ApplyRequest::new(&format!("layout-slot:{}", slot), "layout", "command");
+        state.apply_queue.run(request, || state.display_backend.apply(&displays))
+    })
+    .await?;
+    log::info!("배치 슬롯 {} 복원 ({})", slot, backend);
+    Ok(())
+}
+
+// 저장된 프로필을 찾아 적용 (명령 외에 원격 제어, 트레이, MIDI에서도 사용)
+// 다른 적용이 진행 중이면 대기열에서 차례를 기다리며, source는 대기열에 표시할 요청한 곳입니다
+// 적용하는 동안에는 cancel_apply로 남은 단계를 취소할 수 있습니다
+pub fn apply_profile_by_id(
+    app: &tauri::AppHandle,
+    profile_id: &str,
+    source: &str,
+) -> Result<ApplyReport, AppError> {
+    let state = app.state::<AppState>();
+    state.apply_queue.run(ApplyRequest::new(profile_id, "full", source), || {
+        let cancel = state.begin_apply();
+        let result = apply_profile_with_cancel(app, profile_id, &cancel);
+        state.finish_apply(&cancel);
+        result
+    })
+}
+
+// 적용 대기열 (실행 중인 요청과 차례를 기다리는 요청)
+#[tauri::command]
+pub async fn get_apply_queue(state: tauri::State<'_, AppState>) -> Result<ApplyQueueState, AppError> {
+    log::info!("명령 호출: get_apply_queue");
+    Ok(state.apply_queue.state())
+}
+
+// 적용 중에 다른 프로필 요청이 들어왔을 때의 처리 방법 저장
+#[tauri::command]
+pub async fn set_apply_queue_mode(
+    state: tauri::State<'_, AppState>,
+    mode: ApplyQueueMode,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: set_apply_queue_mode ({:?})", mode);
+    let mut settings = state.settings.lock_or_recover();
+    settings.apply_queue_mode = mode;
+    state.save_settings(&settings)?;
+
+    state.apply_queue.set_mode(mode);
+    Ok(())
+}
+
+// 취소 신호를 받아 프로필 적용 (시작 시 적용은 대기 단계부터 같은 신호를 사용)
+// 진행 상황은 apply-started / profile-applied / profile-apply-cancelled / apply-failed 이벤트로 알립니다
+pub fn apply_profile_with_cancel(
+    app: &tauri::AppHandle,
+    profile_id: &str,
+    cancel: &CancellationToken,
+) -> Result<ApplyReport, AppError> {
+    let state = app.state::<AppState>();
+    let profile = state.profiles.get(profile_id)?;
+    check_profile_settings(&profile, state.settings.lock_or_recover().desktop_limit)?;
+    let hook_timeout = Duration::from_secs(state.settings.lock_or_recover().hook_timeout_secs);
+    let (profile, skipped) = check_connected_hardware(&state, &profile)?;
+
+    // 적용 전 명령은 끝날 때까지 기다리며, 실패해도 적용은 계속합니다
+    let pre_hook = profile_hook(&profile.pre_apply_command).map(|command| {
+        let result = run_hook(&profile.id, "pre", command, hook_timeout);
+        let _ = app.emit("apply-hook-finished", &result);
+        result
+    });
+
+    let report = state.with_apply_journal(&profile.id, &["display", "audio"], || {
+        apply_profile_with_events(&profile, &*state.display_backend, &*state.audio_backend, cancel, &|event| {
+            emit_apply_event(app, event)
+        })
+    });
+    // 실패해도 적용 전 구성으로 되돌릴 수 있습니다 (UI의 "되돌리기" 알림)
+    if state.revert_point.lock_or_recover().is_some() {
+        let _ = app.emit("revert-available", serde_json::json!({ "profile_id": profile.id, "revert_available": true }));
+    }
+    let mut report = report?;
+    report.warnings.extend(skipped);
+    report.warnings.extend(pre_hook.and_then(|result| result.warning()));
+
+    // 일부만 적용되었으므로 활성 프로필은 바꾸지 않습니다
+    if report.cancelled() {
+        log::info!("프로필 적용 취소: {} ({:?})", profile.id, report.steps);
+        return Ok(report);
+    }
+
+    state.set_active_profile(Some(profile.id.clone()));
+    let _ = app.emit("active-profile-changed", Some(&profile.id));
+    record_last_known_good(app, &profile);
+    // 프로필의 오디오 설정은 입출력 볼륨을 모두 바꿉니다
+    if report.steps.get("audio") == Some(&StepStatus::Applied) {
+        emit_volume_changed(app, "output");
+        emit_volume_changed(app, "input");
+    }
+
+    // 적용 후 명령은 기다리지 않고 백그라운드에서 실행해 apply-hook-finished 이벤트로 알립니다
+    if let Some(command) = profile_hook(&profile.post_apply_command) {
+        let (app, profile_id, command) = (app.clone(), profile.id.clone(), command.to_string());
+        std::thread::spawn(move || {
+            let result = run_hook(&profile_id, "post", &command, hook_timeout);
+            let _ = app.emit("apply-hook-finished", &result);
+        });
+    }
+    Ok(report)
+}
+
+// 적용하기 전에 프로필의 디스플레이와 오디오 장치가 연결되어 있는지 확인
+// 현재 구성을 읽지 못하면 확인하지 않고 적용 단계에서 오류를 알립니다
+fn check_connected_hardware(state: &AppState, profile: &Profile) -> Result<(Profile, Vec<String>), AppError> {
+    let report = match validate_profile_against_system(profile, &*state.display_backend, &*state.audio_backend) {
+        Ok(report) => report,
+        Err(e) => {
+            log::warn!("적용 전 확인을 건너뜁니다: {}", e);
+            return Ok((profile.clone(), Vec::new()));
+        }
+    };
+    require_connected_hardware(profile, &report, state.settings.lock_or_recover().skip_missing_audio_devices)
+}
+
+// 비어 있지 않은 적용 전후 명령
+fn profile_hook(command: &Option<String>) -> Option<&str> {
+    command.as_deref().map(str::trim).filter(|command| !command.is_empty())
+}
+
+// 확인에 통과하면 정상 구성으로 저장하고 last-known-good-changed 이벤트로 알림
+fn record_last_known_good(app: &tauri::AppHandle, profile: &Profile) {
+    if app.state::<AppState>().record_last_known_good(profile) {
+        let _ = app.emit("last-known-good-changed", ());
+    }
+}
+
+// 마지막으로 정상 동작이 확인된 구성으로 복구
+// 모니터 id가 바뀌었으면 이름으로 찾아 적용하고, 찾지 못한 모니터는 건너뛰고 경고로 알립니다
+#[tauri::command]
+pub async fn restore_last_known_good(app: tauri::AppHandle) -> Result<ApplyReport, AppError> {
+    log::info!("명령 호출: restore_last_known_good");
+    run_blocking(app, |app| restore_last_known_good_by_source(app, "command")).await
+}
+
+// 명령과 트레이의 디스플레이 복구 (다른 적용처럼 대기열과 적용 기록을 거칩니다)
+pub fn restore_last_known_good_by_source(app: &tauri::AppHandle, source: &str) -> Result<ApplyReport, AppError> {
+    let state = app.state::<AppState>();
+    let saved = state.read_last_known_good()?.ok_or(AppError::NoLastKnownGood)?;
+    let request = ApplyRequest::new(LAST_KNOWN_GOOD_ID, "recovery", source);
+    let report = state.apply_queue.run(request, || {
+        let profile = saved.to_profile(&state.display_backend.enumerate()?, &state.audio_backend.enumerate()?);
+        emit_apply_event(app, ApplyEvent::Started { profile_id: &profile.id });
+        let cancel = state.begin_apply();
+        let result = state.with_apply_journal(&profile.id, &["display", "audio"], || {
+            apply_profile_partial_settings(&profile, &*state.display_backend, &*state.audio_backend, &cancel)
+        });
+        state.finish_apply(&cancel);
+        result
+    })?;
+
+    if let Some(error) = report.error() {
+        emit_apply_event(app, ApplyEvent::Failed { profile_id: LAST_KNOWN_GOOD_ID, error, report: Some(&report) });
+        return Err(error.clone());
+    }
+    if report.cancelled() {
+        emit_apply_event(app, ApplyEvent::Cancelled(&report));
+    } else {
+        emit_apply_event(app, ApplyEvent::Applied(&report));
+    }
+    log::info!("정상 구성으로 복구 (저장된 프로필 {}): {:?}", saved.profile_id, report.backend_used);
+    Ok(report)
+}
+
+fn emit_apply_event(app: &tauri::AppHandle, event: ApplyEvent) {
+    let _ = app.emit(event.name(), event.payload());
+}
+
+// 진행 중인 적용 취소 (진행 중인 적용이 있었으면 true)
+// 실행 중인 단계는 끝까지 진행하고 남은 단계만 건너뜁니다
+#[tauri::command]
+pub async fn cancel_apply(state: tauri::State<'_, AppState>) -> Result<bool, AppError> {
+    log::info!("명령 호출: cancel_apply");
+    match state.apply_cancel.lock_or_recover().as_ref() {
+        Some(token) => {
+            token.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+// 시작 시 적용할 프로필 가져오기
+#[tauri::command]
+pub async fn get_apply_on_startup(state: tauri::State<'_, AppState>) -> Result<Option<String>, AppError> {
+    Ok(state.settings.lock_or_recover().apply_on_startup.clone())
+}
+
+// 시작 시 적용할 프로필 지정 (None이면 해제)
+#[tauri::command]
+pub async fn set_apply_on_startup(
+    state: tauri::State<'_, AppState>,
+    profile_id: Option<String>,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: set_apply_on_startup (id={:?})", profile_id);
+    if let Some(profile_id) = &profile_id {
+        if !state.profiles.list().iter().any(|p| &p.id == profile_id) {
+            return Err(AppError::ProfileNotFound {
+                profile_id: profile_id.clone(),
+            });
+        }
+    }
+
+    let mut settings = state.settings.lock_or_recover();
+    settings.apply_on_startup = profile_id;
+    state.save_settings(&settings)?;
+    Ok(())
+}
+
+// 디스플레이 연결 / 분리 시 자동 적용 여부
+#[tauri::command]
+pub async fn get_auto_apply_enabled(state: tauri::State<'_, AppState>) -> Result<bool, AppError> {
+    Ok(state.settings.lock_or_recover().auto_apply_enabled)
+}
+
+#[tauri::command]
+pub async fn set_auto_apply_enabled(state: tauri::State<'_, AppState>, enabled: bool) -> Result<(), AppError> {
+    log::info!("명령 호출: set_auto_apply_enabled ({})", enabled);
+    let mut settings = state.settings.lock_or_recover();
+    settings.auto_apply_enabled = enabled;
+    state.save_settings(&settings)
+}
+
+// set_auto_apply_enabled와 같고, first_match를 주면 맞는 프로필이 여러 개일 때 처음 것을 적용할지도 바꿉니다
+#[tauri::command]
+pub async fn set_auto_apply(
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+    first_match: Option<bool>,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: set_auto_apply ({}, first_match={:?})", enabled, first_match);
+    let mut settings = state.settings.lock_or_recover();
+    settings.auto_apply_enabled = enabled;
+    if let Some(first_match) = first_match {
+        settings.auto_apply_first_match = first_match;
+    }
+    state.save_settings(&settings)
+}
+
+// 창을 닫을 때 트레이로 숨길지 여부 (끄면 창을 닫을 때 앱을 종료합니다)
+#[tauri::command]
+pub async fn get_close_to_tray(state: tauri::State<'_, AppState>) -> Result<bool, AppError> {
+    Ok(state.settings.lock_or_recover().close_to_tray)
+}
+
+#[tauri::command]
+pub async fn set_close_to_tray(state: tauri::State<'_, AppState>, enabled: bool) -> Result<(), AppError> {
+    log::info!("명령 호출: set_close_to_tray ({})", enabled);
+    let mut settings = state.settings.lock_or_recover();
+    settings.close_to_tray = enabled;
+    state.save_settings(&settings)
+}
+
+// EDID 일련번호로 특정 디스플레이가 연결되어 있는지 확인 (도킹 자동화용)
+#[tauri::command]
+pub async fn is_display_connected(
+    state: tauri::State<'_, AppState>,
+    serial: String,
+) -> Result<bool, AppError> {
+    log::info!("명령 호출: is_display_connected (serial={})", serial);
+    let serial = serial.trim();
+    Ok(state
+        .display_backend
+        .connected_serials()?
+        .iter()
+        .any(|connected| connected.trim().eq_ignore_ascii_case(serial)))
+}
+
+// 겹치거나 떨어진 디스플레이를 서로 맞닿도록 재배치
+#[tauri::command]
+pub async fn auto_arrange(displays: Vec<DisplayInfo>) -> Result<Vec<DisplayInfo>, AppError> {
+    log::info!("명령 호출: auto_arrange ({}개)", displays.len());
+    Ok(arrange_displays(displays))
+}
+
+// 연결된 디스플레이 전체를 미러링(mode="mirror") 또는 확장(mode="extend")으로 전환하고 바뀐 목록 반환
+// OS가 직접 전환하지 못하면 미러링은 공통 해상도로 주 디스플레이 위치에 겹치고, 확장은 나란히 배치합니다
+#[tauri::command]
+pub async fn set_display_mode(app: tauri::AppHandle, mode: String) -> Result<Vec<DisplayInfo>, AppError> {
+    log::info!("명령 호출: set_display_mode ({})", mode);
+    let mode = DesktopMode::parse(&mode)?;
+    run_blocking(app, move |app| {
+        let state = app.state::<AppState>();
+        let request = ApplyRequest::new(&format!("display-mode:{:?}", mode).to_lowercase(), "layout", "command");
+        let backend = state.apply_queue.run(request, || {
+            if let Some(backend) = state.display_backend.set_desktop_mode(mode)? {
+                return Ok(backend);
+            }
+            let displays = state.display_backend.enumerate()?;
+            let displays = match mode {
+                DesktopMode::Mirror => {
+                    let modes: Result<Vec<_>, _> = displays
+                        .iter()
+                        .filter(|display| display.enabled)
+                        .map(|display| state.display_backend.modes(display.id))
+                        .collect();
+                    mirror_layout(displays, modes.ok().and_then(|modes| common_resolution(&modes)))
+                }
+                DesktopMode::Extend => extend_layout(displays),
+            };
+            state.display_backend.apply(&displays)
+        })?;
+        log::info!("디스플레이 모드 전환: {:?} ({})", mode, backend);
+        let displays = state.display_backend.enumerate()?;
+        *state.last_displays.lock_or_recover() = Some(displays.clone());
+        Ok(displays)
+    })
+    .await
+}
+
+// 현재 구성 또는 저장된 프로필의 배치 미리보기
+#[tauri::command]
+pub async fn get_layout_preview(
+    state: tauri::State<'_, AppState>,
+    profile_id: Option<String>,
+) -> Result<LayoutPreview, AppError> {
+    log::info!("명령 호출: get_layout_preview (id={:?})", profile_id);
+    let displays = match profile_id {
+        Some(profile_id) => state
+            .profiles
+            .list()
+            .iter()
+            .find(|p| p.id == profile_id)
+            .map(|p| p.displays.clone())
+            .ok_or(AppError::ProfileNotFound { profile_id })?,
+        None => {
+            let displays = state.display_backend.enumerate()?;
+            *state.last_displays.lock_or_recover() = Some(displays.clone());
+            displays
+        }
+    };
+
+    Ok(layout_preview(&displays))
+}
+
+#[tauri::command]
+pub async fn set_default_audio_device(
+    app: tauri::AppHandle,
+    device_id: String,
+    device_type: String,
+) -> Result<String, AppError> {
+    log::info!("명령 호출: set_default_audio_device (id={}, type={})", device_id, device_type);
+    let state = app.state::<AppState>();
+    let devices = state.audio_backend.enumerate()?;
+    let name = devices
+        .iter()
+        .find(|device| device.id == device_id && device.device_type == device_type)
+        .map(|device| device.name.clone())
+        .ok_or_else(|| AppError::DeviceNotFound {
+            device_type: device_type.clone(),
+            pattern: device_id.clone(),
+        })?;
+
+    let backend = state.audio_backend.set_default_device(&device_type, &name)?;
+    let _ = app.emit("audio-devices-changed", ());
+    Ok(backend)
+}
+
+// 기본 장치의 볼륨 변경 (MIDI 페이더, 원격 제어), 바뀐 상태를 volume-changed 이벤트로 알립니다
+pub fn set_volume_by_source(
+    app: &tauri::AppHandle,
+    device_type: &str,
+    volume: u32,
+    source: &str,
+) -> Result<VolumeState, AppError> {
+    log::info!("볼륨 변경 요청 ({}): {} {}", source, device_type, volume);
+    set_device_volume(&*app.state::<AppState>().audio_backend, device_type, volume)?;
+    Ok(emit_volume_changed(app, device_type))
+}
+
+// 기본 장치의 음소거 전환 (원격 제어)
+pub fn toggle_mute_by_source(app: &tauri::AppHandle, device_type: &str, source: &str) -> Result<VolumeState, AppError> {
+    log::info!("음소거 전환 요청 ({}): {}", source, device_type);
+    toggle_device_mute(&*app.state::<AppState>().audio_backend, device_type)?;
+    Ok(emit_volume_changed(app, device_type))
+}
+
+// 기본 장치의 현재 볼륨과 음소거를 volume-changed 이벤트로 알림
+// 바꾼 뒤에 다시 읽지 못하면 장치와 값을 비운 채로 알립니다
+fn emit_volume_changed(app: &tauri::AppHandle, device_type: &str) -> VolumeState {
+    let volume = default_volume_state(&*app.state::<AppState>().audio_backend, device_type).unwrap_or_else(|e| {
+        log::warn!("바뀐 {} 볼륨을 읽지 못했습니다: {}", device_type, e);
+        VolumeState { device_type: device_type.to_string(), device: None, volume: None, muted: None }
+    });
+    let _ = app.emit("volume-changed", &volume);
+    volume
+}
+
+// 장치 별명 지정 (None이면 별명 제거)
+#[tauri::command]
+pub async fn set_device_nickname(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    device_id: String,
+    nickname: Option<String>,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: set_device_nickname (id={}, nickname={:?})", device_id, nickname);
+    let mut settings = state.settings.lock_or_recover();
+    match nickname.filter(|nickname| !nickname.trim().is_empty()) {
+        Some(nickname) => {
+            settings.device_nicknames.insert(device_id, nickname);
+        }
+        None => {
+            settings.device_nicknames.remove(&device_id);
+        }
+    }
+    state.save_settings(&settings)?;
+
+    let _ = app.emit("audio-devices-changed", ());
+    Ok(())
+}
+
+// 트레이 등 빠른 전환 목록에서 장치 숨기기
+#[tauri::command]
+pub async fn set_device_hidden(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    device_id: String,
+    hidden: bool,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: set_device_hidden (id={}, hidden={})", device_id, hidden);
+    let mut settings = state.settings.lock_or_recover();
+    settings.hidden_devices.retain(|id| *id != device_id);
+    if hidden {
+        settings.hidden_devices.push(device_id);
+    }
+    state.save_settings(&settings)?;
+
+    let _ = app.emit("audio-devices-changed", ());
+    Ok(())
+}
+
+// 마지막으로 적용한 프로필과 적용 시각 (재시작 후에도 유지)
+// matches_current는 지금 연결된 디스플레이 구성과 비교한 결과입니다
+#[tauri::command]
+pub async fn get_active_profile(state: tauri::State<'_, AppState>) -> Result<Option<ActiveProfileStatus>, AppError> {
+    log::info!("명령 호출: get_active_profile");
+    let Some(profile_id) = state.active_profile.lock_or_recover().clone() else {
+        return Ok(None);
+    };
+    // 파일 저장에 실패했으면 적용 시각을 알 수 없습니다
+    let record = state
+        .read_active_profile()
+        .filter(|record| record.profile_id == profile_id)
+        .unwrap_or(ActiveProfileRecord { profile_id, applied_at: String::new() });
+    let Ok(profile) = state.profiles.get(&record.profile_id) else {
+        return Ok(None);
+    };
+    // 디스플레이가 없는 (오디오만 바꾸는) 프로필은 배치와 관계없이 일치로 봅니다
+    let displays = state.display_backend.enumerate()?;
+    let added = displays
+        .iter()
+        .any(|display| display.enabled && !profile.displays.iter().any(|saved| saved.id == display.id));
+    let matches_current =
+        profile.displays.is_empty() || (!added && diff_displays(&profile.displays, &displays).is_empty());
+    Ok(Some(ActiveProfileStatus { record, matches_current }))
+}
+
+// 현재 디스플레이/오디오 구성이 마지막으로 적용한 프로필과 같은지 확인
+// 적용한 프로필이 없거나 그 뒤에 직접 바꾼 설정이 있으면 false (일치하면 정상 구성으로 저장)
+#[tauri::command]
+pub async fn current_matches_active(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, AppError> {
+    log::info!("명령 호출: current_matches_active");
+    let Some(profile_id) = state.active_profile.lock_or_recover().clone() else {
+        return Ok(false);
+    };
+    let Some(profile) = state
+        .profiles
+        .list()
+        .iter()
+        .find(|p| p.id == profile_id)
+        .cloned()
+    else {
+        return Ok(false);
+    };
+
+    // 패턴으로 지정된 장치가 지금 연결되어 있지 않으면 일치하지 않는 것으로 봅니다
+    let drift = match verify_profile(&profile, &*state.display_backend, &*state.audio_backend) {
+        Ok(drift) => drift,
+        Err(AppError::DeviceNotFound { .. }) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    if drift.is_empty() {
+        record_last_known_good(&app, &profile);
+    } else {
+        log::info!("현재 구성이 적용한 프로필과 다릅니다 (id={}): {:?}", profile_id, drift);
+    }
+
+    Ok(drift.is_empty())
+}
+
+// 자체 점검 결과
+#[derive(Debug, Serialize, Clone)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    // 적용 전후 상태의 차이
+    pub drift: Vec<String>,
+    // 적용 중 발생한 오류
+    pub error: Option<AppError>,
+    // 차이가 있어 원래 구성을 다시 적용했는지 여부
+    pub restored: bool,
+    pub backend_used: HashMap<String, String>,
+}
+
+// 현재 구성을 캡처 → 다시 적용 → 결과 비교
+// 이론상 아무것도 바뀌지 않아야 하며, 차이가 생기면 원래 구성을 복원합니다
+#[tauri::command]
+pub async fn self_test(state: tauri::State<'_, AppState>) -> Result<SelfTestReport, AppError> {
+    log::info!("명령 호출: self_test");
+    let (displays, audio) = (&*state.display_backend, &*state.audio_backend);
+    let original_displays = displays.enumerate()?;
+    let original_audio = capture_audio_settings(&audio.enumerate()?);
+
+    let mut backend_used = HashMap::new();
+    let mut error = None;
+    match displays.apply(&original_displays) {
+        Ok(backend) => {
+            backend_used.insert("display".to_string(), backend);
+        }
+        Err(e) => error = Some(e),
+    }
+    if error.is_none() {
+        match apply_audio_settings(audio, &original_audio) {
+            Ok(backend) => {
+                backend_used.insert("audio".to_string(), backend);
+            }
+            Err(e) => error = Some(e),
+        }
+    }
+
+    // 시스템이 새 구성을 반영할 시간을 줍니다
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    let mut drift = diff_displays(&original_displays, &displays.enumerate()?);
+    drift.extend(diff_audio_settings(
+        &original_audio,
+        &capture_audio_settings(&audio.enumerate()?),
+    ));
+
+    let mut restored = false;
+    if error.is_some() || !drift.is_empty() {
+        log::warn!("자체 점검에서 차이가 발견되어 원래 구성을 복원합니다: {:?}", drift);
+        let display_restored = displays.apply(&original_displays).is_ok();
+        let audio_restored = apply_audio_settings(audio, &original_audio).is_ok();
+        restored = display_restored && audio_restored;
+    }
+
+    Ok(SelfTestReport {
+        passed: error.is_none() && drift.is_empty(),
+        drift,
+        error,
+        restored,
+        backend_used,
+    })
+}
+
+// 지금 연결된 장치 구성을 fixture 파일로 기록 (tests/fixtures에 넣으면 통합 테스트에서 사용)
+// 모니터 일련번호가 포함되므로 공유하기 전에 확인하세요
+#[tauri::command]
+pub async fn record_fixture(state: tauri::State<'_, AppState>, path: String) -> Result<(), AppError> {
+    log::info!("명령 호출: record_fixture (path={})", path);
+    let fixture = mock::record_fixture(&*state.display_backend, &*state.audio_backend)?;
+    let content = serde_json::to_string_pretty(&fixture)
+        .map_err(|e| AppError::Internal { detail: format!("Failed to serialize fixture: {}", e) })?;
+    fs::write(&path, content).map_err(|e| AppError::io(std::path::Path::new(&path), &e))
+}
+
+// 첫 실행 설정 결과
+#[derive(Debug, Serialize, Clone)]
+pub struct OnboardingReport {
+    pub dependencies: DependencyReport,
+    pub starter_profile_id: String,
+    // 이번 실행에서 시작 프로필을 새로 만들었는지 여부
+    pub starter_profile_created: bool,
+    // 설정된 항목
+    pub configured: Vec<String>,
+    // 사용자가 직접 처리해야 하는 항목
+    pub user_actions: Vec<String>,
+}
+
+// 첫 실행 설정: 의존성 확인 → 권한 확인 → 현재 구성으로 시작 프로필 생성 → 트레이 사용
+// 여러 번 실행해도 같은 결과가 되도록 이미 있는 시작 프로필은 덮어쓰지 않습니다
+#[tauri::command]
+pub async fn run_onboarding(state: tauri::State<'_, AppState>) -> Result<OnboardingReport, AppError> {
+    log::info!("명령 호출: run_onboarding");
+    let mut configured = Vec::new();
+    let mut user_actions = Vec::new();
+
+    let dependencies = collect_dependency_report();
+    for dependency in &dependencies.dependencies {
+        if !dependency.available {
+            user_actions.push(format!(
+                "{} 설치 필요 ({}): {}",
+                dependency.name, dependency.used_for, dependency.install_hint
+            ));
+        } else if dependency.version_supported == Some(false) {
+            user_actions.push(format!(
+                "{} 업데이트 필요 (최소 {}): {}",
+                dependency.name,
+                dependency.min_version.as_deref().unwrap_or("-"),
+                dependency.install_hint
+            ));
+        }
+    }
+
+    user_actions.extend(check_permissions());
+
+    let exists = state
+        .profiles
+        .list()
+        .iter()
+        .any(|p| p.id == STARTER_PROFILE_ID);
+    let starter_profile_created = !exists;
+    if exists {
+        configured.push("시작 프로필이 이미 있습니다".to_string());
+    } else {
+        // 같은 이름의 프로필이 이미 있으면 번호를 붙입니다
+        let name = suggest_profile_name(&machine_name(), &state.profiles.list());
+        let profile = Profile {
+            id: STARTER_PROFILE_ID.to_string(),
+            name,
+            displays: state.display_backend.enumerate()?,
+            audio_settings: Some(capture_audio_settings(&state.audio_backend.enumerate()?)),
+            created_at: chrono::Local::now().to_rfc3339(),
+            tags: Vec::new(),
+            color: None,
+            hotkey: None,
+            pre_apply_command: None,
+            post_apply_command: None,
+            auto_apply_on_device: None,
+            output_follows_display: false,
+        };
+        configured.push(format!("현재 구성으로 \"{}\" 프로필을 만들었습니다", profile.name));
+
+        state.profiles.update(|profiles| {
+            profiles.push(profile);
+            Ok(())
+        })?;
+    }
+
+    let mut settings = state.settings.lock_or_recover();
+    if !settings.tray_enabled {
+        settings.tray_enabled = true;
+        state.save_settings(&settings)?;
+    }
+    configured.push("트레이 아이콘을 사용합니다".to_string());
+
+    Ok(OnboardingReport {
+        dependencies,
+        starter_profile_id: STARTER_PROFILE_ID.to_string(),
+        starter_profile_created,
+        configured,
+        user_actions,
+    })
+}
+
+// 선택한(없으면 모든) 프로필을 다른 컴퓨터로 옮길 수 있는 파일로 내보내기
+#[tauri::command]
+pub async fn export_profiles(
+    app: tauri::AppHandle,
+    path: String,
+    profile_ids: Option<Vec<String>>,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: export_profiles (path={}, ids={:?})", path, profile_ids);
+    run_blocking(app, move |app| {
+        let profiles = app.state::<AppState>().profiles.reload()?;
+        let selected = match profile_ids {
+            Some(ids) => ids
+                .iter()
+                .map(|id| {
+                    profiles
+                        .iter()
+                        .find(|p| &p.id == id)
+                        .cloned()
+                        .ok_or_else(|| AppError::ProfileNotFound { profile_id: id.clone() })
+                })
+                .collect::<Result<Vec<Profile>, AppError>>()?,
+            None => profiles,
+        };
+        write_profile_export(std::path::Path::new(&path), &selected)
+    })
+    .await
+}
+
+// 내보낸 파일의 프로필을 가져와 저장하고 가져온 프로필 이름 반환
+// overwrite가 아니면 id가 겹치는 프로필은 새 id와 " (imported)" 이름으로 추가합니다
+// replace면 기존 프로필을 모두 지우고 가져온 프로필로 바꿉니다 (새 컴퓨터로 옮길 때)
+#[tauri::command]
+pub async fn import_profiles(
+    app: tauri::AppHandle,
+    path: String,
+    overwrite: bool,
+    replace: Option<bool>,
+) -> Result<Vec<String>, AppError> {
+    log::info!("명령 호출: import_profiles (path={}, overwrite={}, replace={:?})", path, overwrite, replace);
+    run_blocking(app, move |app| {
+        let state = app.state::<AppState>();
+        let imported = read_profile_export(std::path::Path::new(&path))?;
+        let desktop_limit = state.settings.lock_or_recover().desktop_limit;
+        for profile in &imported {
+            check_profile_settings(profile, desktop_limit)?;
+        }
+
+        let names = state.profiles.update(|profiles| {
+            Ok(if replace.unwrap_or(false) {
+                replace_with_imported_profiles(profiles, imported)
+            } else {
+                merge_imported_profiles(profiles, imported, overwrite)
+            })
+        })?;
+        let _ = app.emit("profiles-changed", ());
+        Ok(names)
+    })
+    .await
+}
+
+// 프로필을 독립 실행 스크립트로 내보내기 (macOS: .sh, Windows: .ps1)
+// 스크립트는 OS 도구가 직접 실행하므로 디스플레이 id를 지금 연결된 디스플레이의 OS id로 바꿔 씁니다
+#[tauri::command]
+pub async fn export_profile_as_script(
+    state: tauri::State<'_, AppState>,
+    profile_id: String,
+    path: String,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: export_profile_as_script (id={}, path={})", profile_id, path);
+    let mut profile = state
+        .profiles
+        .list()
+        .iter()
+        .find(|p| p.id == profile_id)
+        .cloned()
+        .ok_or_else(|| AppError::ProfileNotFound {
+            profile_id: profile_id.clone(),
+        })?;
+    profile.displays = state.display_backend.native_displays(&profile.displays)?;
+
+    let kind = if cfg!(target_os = "windows") {
+        ScriptKind::PowerShell
+    } else if cfg!(target_os = "macos") {
+        ScriptKind::Shell
+    } else {
+        return Err(AppError::UnsupportedPlatform {
+            feature: "export_profile_as_script".to_string(),
+        });
+    };
+
+    let script = profile_to_script(&profile, kind, &chrono::Local::now().to_rfc3339());
+    fs::write(&path, script).map_err(|e| AppError::io(std::path::Path::new(&path), &e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .map_err(|e| AppError::io(std::path::Path::new(&path), &e))?;
+    }
+
+    Ok(())
+}
+
+// 실행 중인 WebSocket 브리지를 멈추고 설정에 따라 다시 시작
+pub async fn restart_remote_server(
+    app: &tauri::AppHandle,
+    settings: &RemoteControlSettings,
+) -> Result<(), AppError> {
+    let state = app.state::<AppState>();
+    if let Some(shutdown) = state.remote_server.lock_or_recover().take() {
+        let _ = shutdown.send(());
+    }
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    let Some(token) = settings.token.clone().filter(|token| !token.trim().is_empty()) else {
+        return Err(AppError::RemoteServerFailed {
+            port: settings.port.to_string(),
+            detail: "no access token".to_string(),
+        });
+    };
+    let access = ws::Access { token, allowed_origins: settings.allowed_origins.clone() };
+    let shutdown = ws::start(app.clone(), &state.event_bus, settings.port, access)
+        .await
+        .map_err(|e| AppError::RemoteServerFailed {
+            port: settings.port.to_string(),
+            detail: e.to_string(),
+        })?;
+    *state.remote_server.lock_or_recover() = Some(shutdown);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_remote_control(
+    state: tauri::State<'_, AppState>,
+) -> Result<RemoteControlSettings, AppError> {
+    log::info!("명령 호출: get_remote_control");
+    Ok(state.settings.lock_or_recover().remote_control.clone())
+}
+
+// 외부 연동 서버 켜기/끄기 (토큰이 없거나 비어 있으면 생성, allowed_origins를 주면 허용할 Origin을 바꿈)
+#[tauri::command]
+pub async fn set_remote_control(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+    port: Option<u16>,
+    allowed_origins: Option<Vec<String>>,
+) -> Result<RemoteControlSettings, AppError> {
+    log::info!(
+        "명령 호출: set_remote_control (enabled={}, port={:?}, allowed_origins={:?})",
+        enabled,
+        port,
+        allowed_origins
+    );
+    let remote_control = {
+        let mut settings = state.settings.lock_or_recover();
+        let remote_control = &mut settings.remote_control;
+        remote_control.enabled = enabled;
+        if let Some(port) = port {
+            remote_control.port = port;
+        }
+        if let Some(allowed_origins) = allowed_origins {
+            remote_control.allowed_origins = allowed_origins;
+        }
+        remote_control.ensure_token();
+        let remote_control = remote_control.clone();
+        state.save_settings(&settings)?;
+        remote_control
+    };
+
+    restart_remote_server(&app, &remote_control).await?;
+    Ok(remote_control)
+}
+
+#[tauri::command]
+pub async fn list_midi_inputs() -> Result<Vec<String>, AppError> {
+    log::info!("명령 호출: list_midi_inputs");
+    midi::list_inputs()
+}
+
+#[tauri::command]
+pub async fn get_midi_mappings(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<midi::MidiMapping>, AppError> {
+    log::info!("명령 호출: get_midi_mappings");
+    Ok(state.settings.lock_or_recover().midi_mappings.clone())
+}
+
+// MIDI 매핑 저장 (리스너에 바로 반영)
+#[tauri::command]
+pub async fn set_midi_mappings(
+    state: tauri::State<'_, AppState>,
+    mappings: Vec<midi::MidiMapping>,
+) -> Result<(), AppError> {
+    log::info!("명령 호출: set_midi_mappings ({}개)", mappings.len());
+    let mut settings = state.settings.lock_or_recover();
+    settings.midi_mappings = mappings.clone();
+    state.save_settings(&settings)?;
+
+    state.midi.set_mappings(mappings);
+    Ok(())
+}
+
+// 최근 로그 가져오기
+#[tauri::command]
+pub async fn get_recent_logs(
+    state: tauri::State<'_, AppState>,
+    lines: u32,
+) -> Result<Vec<String>, AppError> {
+    if !state.log_file.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&state.log_file).map_err(|e| AppError::io(&state.log_file, &e))?;
+    let all_lines: Vec<&str> = content.lines().collect();
+    let start = all_lines.len().saturating_sub(lines as usize);
+    Ok(all_lines[start..].iter().map(|line| line.to_string()).collect())
+}
+
+// 로그 파일 경로 가져오기
+#[tauri::command]
+pub async fn get_log_file_path(state: tauri::State<'_, AppState>) -> Result<String, AppError> {
+    Ok(state.log_file.to_string_lossy().to_string())
+}
+
+// 로그 레벨 변경 (settings.json에 저장)
+#[tauri::command]
+pub async fn set_log_level(state: tauri::State<'_, AppState>, level: String) -> Result<(), AppError> {
+    let filter: log::LevelFilter = level
+        .parse()
+        .map_err(|_| AppError::InvalidLogLevel { level: level.clone() })?;
+
+    let mut settings = state.settings.lock_or_recover();
+    settings.log_level = filter.to_string().to_lowercase();
+    state.save_settings(&settings)?;
+
+    log::set_max_level(filter);
+    log::info!("로그 레벨 변경: {}", filter);
+    Ok(())
+}
+
+// 메시지 언어 가져오기
+#[tauri::command]
+pub async fn get_locale() -> Result<String, AppError> {
+    Ok(current_locale().as_str().to_string())
+}
+
+// 메시지 언어 변경 (settings.json에 저장)
+#[tauri::command]
+pub async fn set_locale(state: tauri::State<'_, AppState>, locale: String) -> Result<(), AppError> {
+    log::info!("명령 호출: set_locale (locale={})", locale);
+    let parsed = Locale::parse(&locale).ok_or_else(|| AppError::UnsupportedLocale {
+        locale: locale.clone(),
+    })?;
+
+    let mut settings = state.settings.lock_or_recover();
+    settings.locale = Some(parsed.as_str().to_string());
+    state.save_settings(&settings)?;
+
+    set_current_locale(parsed);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_dependency_updates() -> Result<Vec<DependencyUpdate>, AppError> {
+    log::info!("명령 호출: get_dependency_updates");
+    Ok(dependency_updates())
+}
+
+// 외부 도구 상태 점검 (시작할 때 설정 안내 배너에 사용)
+#[tauri::command]
+pub async fn check_dependencies() -> Result<DependencyReport, AppError> {
+    log::info!("명령 호출: check_dependencies");
+    tauri::async_runtime::spawn_blocking(collect_dependency_report)
+        .await
+        .map_err(|e| AppError::Internal { detail: format!("의존성 점검 실패: {}", e) })
+}
+
+// 패키지 관리자로 외부 도구 설치 (진행 상황은 dependency-install-progress 이벤트로 전달)
+#[tauri::command]
+pub async fn install_dependency(app: tauri::AppHandle, name: String) -> Result<InstallResult, AppError> {
+    log::info!("명령 호출: install_dependency (name={})", name);
+    tauri::async_runtime::spawn_blocking(move || {
+        install_dependency_blocking(&name, &|progress| {
+            let _ = app.emit("dependency-install-progress", progress);
+        })
+    })
+        .await
+        .map_err(|e| AppError::Internal { detail: format!("설치 작업 실패: {}", e) })?
+}
+
+// 방해 금지(집중 모드) 상태 가져오기
+// 플랫폼에서 상태를 조회할 수 없으면 None을 반환합니다
+#[tauri::command]
+pub async fn get_focus_state() -> Result<Option<bool>, AppError> {
+    log::info!("명령 호출: get_focus_state");
+    Ok(focus_state())
+}
+
+// 운영체제와 사용 중인 디스플레이 백엔드
+#[tauri::command]
+pub async fn get_system_info(state: tauri::State<'_, AppState>) -> Result<SystemInfo, AppError> {
+    log::info!("명령 호출: get_system_info");
+    Ok(system_info(
+        state.display_backend.name(),
+        state.audio_backend.name(),
+        state.device_monitor.strategies(),
+    ))
+}
+
+// macOS 개인정보 보호 / 관리자 권한 상태 (첫 실행 설정에서 안내용)
+// 사용자가 설정에서 권한을 바꾼 뒤 부르므로 보관한 값 대신 다시 확인합니다
+#[tauri::command]
+pub async fn check_macos_permissions() -> Result<PermissionStatus, AppError> {
+    log::info!("명령 호출: check_macos_permissions");
+    Ok(refresh_permission_status())
+}
+
+// 권한을 켤 수 있는 시스템 설정 항목 열기
+// kind: "accessibility", "screen_recording", "full_disk_access" (PermissionRequired 오류의 kind)
+#[tauri::command]
+pub async fn open_permission_settings(kind: String) -> Result<(), AppError> {
+    log::info!("명령 호출: open_permission_settings (kind={})", kind);
+    crate::privileges::open_permission_settings(&kind)
+}
@@ -0,0 +1,158 @@
+// 모니터 밝기 (DisplayInfo.brightness, 0–100)
+// DDC/CI는 응답하지 않는 모니터에서 몇 초씩 멈출 수 있으므로 호출은 별도 스레드에서 제한 시간까지만 기다리고,
+// 제한 시간을 넘긴 모니터는 한동안 다시 묻지 않습니다 (멈춘 호출이 목록 조회와 적용을 계속 늦추지 않도록)
+
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::sync::LockExt;
+
+// 모니터 하나의 밝기를 읽거나 바꿀 때 기다리는 시간
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+pub const BRIGHTNESS_TIMEOUT: Duration = Duration::from_secs(2);
+// 제한 시간을 넘긴 모니터를 다시 묻기까지의 시간
+const UNRESPONSIVE_BACKOFF: Duration = Duration::from_secs(300);
+
+// (모니터, 제한 시간을 넘긴 시각)
+static UNRESPONSIVE: Mutex<Vec<(String, Instant)>> = Mutex::new(Vec::new());
+
+// task를 별도 스레드에서 실행하고 제한 시간까지 기다린 결과
+// 제한 시간을 넘겼거나 최근에 넘긴 모니터면 None (넘긴 task는 끝날 때까지 그 스레드에서 계속 실행됩니다)
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+pub fn with_timeout<T: Send + 'static>(
+    monitor: &str,
+    timeout: Duration,
+    task: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    {
+        let mut unresponsive = UNRESPONSIVE.lock_or_recover();
+        unresponsive.retain(|(_, since)| since.elapsed() < UNRESPONSIVE_BACKOFF);
+        if unresponsive.iter().any(|(name, _)| name == monitor) {
+            return None;
+        }
+    }
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(task());
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => Some(result),
+        Err(RecvTimeoutError::Timeout) => {
+            log::warn!("{}: 밝기 호출이 {}초 안에 끝나지 않아 한동안 묻지 않습니다", monitor, timeout.as_secs());
+            UNRESPONSIVE.lock_or_recover().push((monitor.to_string(), Instant::now()));
+            None
+        }
+        // task가 패닉함
+        Err(RecvTimeoutError::Disconnected) => None,
+    }
+}
+
+// 모니터가 알려 준 범위(min..=max)의 값 → 0–100 (범위가 잘못되었으면 None)
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+pub fn to_percent(value: u32, min: u32, max: u32) -> Option<u32> {
+    if max <= min {
+        return None;
+    }
+    let value = value.clamp(min, max) - min;
+    Some(((value as f64 * 100.0) / (max - min) as f64).round() as u32)
+}
+
+// 0–100 → 모니터의 범위(min..=max)의 값
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+pub fn from_percent(percent: u32, min: u32, max: u32) -> u32 {
+    if max <= min {
+        return min;
+    }
+    min + ((percent.min(100) as f64 * (max - min) as f64) / 100.0).round() as u32
+}
+
+// DDC/CI (VESA MCCS) 메시지 (macOS 외장 모니터, IOKit I2C로 보냄)
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub mod ddc {
+    // 밝기 기능 코드
+    pub const VCP_BRIGHTNESS: u8 = 0x10;
+    // 모니터의 I2C 주소 (7비트, 쓰기 주소는 0x6E)
+    pub const DDC_DISPLAY_ADDRESS: u8 = 0x37;
+    // 호스트 주소 (I2C로 보내는 메시지의 첫 바이트)
+    pub const DDC_HOST_ADDRESS: u8 = 0x51;
+
+    // 호스트 주소 뒤에 보내는 DDC/CI 메시지 (길이, 내용, 검사합)
+    fn ddc_message(payload: &[u8]) -> Vec<u8> {
+        let mut message = vec![0x80 | payload.len() as u8];
+        message.extend_from_slice(payload);
+        let checksum = message.iter().fold((DDC_DISPLAY_ADDRESS << 1) ^ DDC_HOST_ADDRESS, |sum, byte| sum ^ byte);
+        message.push(checksum);
+        message
+    }
+
+    // Get VCP Feature 요청
+    pub fn vcp_get_request(code: u8) -> Vec<u8> {
+        ddc_message(&[0x01, code])
+    }
+
+    // Set VCP Feature 요청
+    pub fn vcp_set_request(code: u8, value: u16) -> Vec<u8> {
+        let [high, low] = value.to_be_bytes();
+        ddc_message(&[0x03, code, high, low])
+    }
+
+    // Get VCP Feature 응답의 (현재 값, 최댓값)
+    // 응답 앞에 주소와 길이 바이트가 붙어 있는지와 관계없이 응답 코드(0x02)부터 찾습니다
+    pub fn parse_vcp_reply(reply: &[u8], code: u8) -> Option<(u16, u16)> {
+        let start = reply.windows(3).position(|bytes| bytes[0] == 0x02 && bytes[2] == code)?;
+        // [0x02, 결과 (0이면 지원), 기능 코드, 종류, 최댓값 (2바이트), 현재 값 (2바이트)]
+        let reply = reply.get(start..start + 8)?;
+        if reply[1] != 0 {
+            return None;
+        }
+        let max = u16::from_be_bytes([reply[4], reply[5]]);
+        (max > 0).then(|| (u16::from_be_bytes([reply[6], reply[7]]), max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ddc::*;
+    use super::*;
+
+    #[test]
+    fn converts_between_monitor_ranges_and_percent() {
+        assert_eq!(to_percent(50, 0, 100), Some(50));
+        assert_eq!(to_percent(128, 0, 255), Some(50));
+        assert_eq!(to_percent(300, 0, 255), Some(100));
+        assert_eq!(to_percent(10, 10, 10), None);
+        assert_eq!(from_percent(50, 0, 255), 128);
+        assert_eq!(from_percent(150, 20, 80), 80);
+        assert_eq!(from_percent(0, 20, 80), 20);
+    }
+
+    #[test]
+    fn builds_and_reads_ddc_brightness_messages() {
+        // ddcutil 문서의 예 (6E 51 82 01 10 AC)
+        assert_eq!(vcp_get_request(VCP_BRIGHTNESS), [0x82, 0x01, 0x10, 0xAC]);
+        assert_eq!(vcp_set_request(VCP_BRIGHTNESS, 50), [0x84, 0x03, 0x10, 0x00, 0x32, 0x9A]);
+
+        // 모니터 주소와 길이가 붙은 응답 (최댓값 100, 현재 값 70)
+        let reply = [0x6E, 0x88, 0x02, 0x00, 0x10, 0x00, 0x00, 0x64, 0x00, 0x46, 0x00];
+        assert_eq!(parse_vcp_reply(&reply, VCP_BRIGHTNESS), Some((70, 100)));
+        assert_eq!(parse_vcp_reply(&reply[2..10], VCP_BRIGHTNESS), Some((70, 100)));
+        // 지원하지 않는 기능, 잘린 응답
+        assert_eq!(parse_vcp_reply(&[0x6E, 0x88, 0x02, 0x01, 0x10, 0, 0, 0, 0, 0], VCP_BRIGHTNESS), None);
+        assert_eq!(parse_vcp_reply(&reply[..6], VCP_BRIGHTNESS), None);
+    }
+
+    #[test]
+    fn stops_asking_a_monitor_that_timed_out() {
+        assert_eq!(with_timeout("test-fast", BRIGHTNESS_TIMEOUT, || 70), Some(70));
+        let slow = with_timeout("test-slow", Duration::from_millis(10), || {
+            std::thread::sleep(Duration::from_millis(200));
+            70
+        });
+        assert_eq!(slow, None);
+        // 제한 시간을 넘긴 모니터는 바로 None
+        let started = Instant::now();
+        assert_eq!(with_timeout("test-slow", BRIGHTNESS_TIMEOUT, || 70), None);
+        assert!(started.elapsed() < Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,85 @@
+// EDID 해석 (모니터 이름, 일련번호)
+// Linux는 DRM에서 읽은 EDID로 목록과 일련번호를 만들고, fixture에도 원본을 16진수로 저장합니다
+
+// EDID 서술자 블록 4개 중 문자열 서술자(tag)의 내용
+fn descriptor_texts(edid: &[u8], tag: u8) -> Vec<String> {
+    if edid.len() < 128 {
+        return Vec::new();
+    }
+
+    [54, 72, 90, 108]
+        .iter()
+        .map(|&offset| &edid[offset..offset + 18])
+        .filter(|descriptor| descriptor[..3] == [0, 0, 0] && descriptor[3] == tag)
+        .map(|descriptor| {
+            descriptor[5..]
+                .iter()
+                .take_while(|&&byte| byte != 0x0A)
+                .map(|&byte| byte as char)
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+// EDID의 모니터 이름 (서술자 0xFC)
+pub fn name(edid: &[u8]) -> Option<String> {
+    descriptor_texts(edid, 0xFC).into_iter().next()
+}
+
+// EDID에서 일련번호 추출 (문자열 서술자 0xFF, 숫자 일련번호 12~15바이트)
+pub fn serials(edid: &[u8]) -> Vec<String> {
+    let mut serials = descriptor_texts(edid, 0xFF);
+    if edid.len() < 128 {
+        return serials;
+    }
+
+    let numeric = u32::from_le_bytes([edid[12], edid[13], edid[14], edid[15]]);
+    if numeric != 0 {
+        serials.push(numeric.to_string());
+    }
+
+    serials
+}
+
+// 모니터 식별값: 제조사 + 제품 코드, 일련번호가 있으면 함께 (예: "GSM5B09-405NTABC1234")
+// 일련번호가 없는 같은 모델은 식별값이 같으므로 연결 경로로 구분해야 합니다
+pub fn fingerprint(edid: &[u8]) -> Option<String> {
+    if edid.len() < 128 {
+        return None;
+    }
+    let serials = serials(edid);
+    fingerprint_from_ids(
+        u16::from_be_bytes([edid[8], edid[9]]),
+        u16::from_le_bytes([edid[10], edid[11]]),
+        serials.first().map(String::as_str),
+    )
+}
+
+// EDID 값을 따로 받는 OS용 (macOS의 CGDisplayVendorNumber / CGDisplayModelNumber)
+// 제조사 코드는 5비트씩 세 글자 (1 = 'A'), 0이면 알 수 없음
+pub fn fingerprint_from_ids(manufacturer: u16, product: u16, serial: Option<&str>) -> Option<String> {
+    if manufacturer == 0 {
+        return None;
+    }
+    let letter = |shift: u16| (b'@' + ((manufacturer >> shift) & 0x1F) as u8) as char;
+    let mut fingerprint = format!("{}{}{}{:04X}", letter(10), letter(5), letter(0), product);
+    if let Some(serial) = serial {
+        fingerprint.push('-');
+        fingerprint.push_str(serial);
+    }
+    Some(fingerprint)
+}
+
+// 16진수 문자열 ↔ 바이트 (xrandr --verbose 출력, fixture)
+pub fn decode_hex(text: &str) -> Vec<u8> {
+    (0..text.len() / 2)
+        .filter_map(|i| u8::from_str_radix(&text[i * 2..i * 2 + 2], 16).ok())
+        .collect()
+}
+
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
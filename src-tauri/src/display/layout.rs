@@ -0,0 +1,581 @@
+// 디스플레이 배치 계산 (자동 정렬, 편집기 미리보기)
+
+use serde::Serialize;
+
+use super::{logical_size, DisplayInfo, DisplayMode};
+use crate::error::AppError;
+
+// 배치 계산용 크기 (좌표계가 포인트 단위인 macOS에 맞춰 논리 크기 우선)
+pub fn layout_size(display: &DisplayInfo) -> (i32, i32) {
+    if display.logical_width > 0 && display.logical_height > 0 {
+        (display.logical_width as i32, display.logical_height as i32)
+    } else {
+        (display.width as i32, display.height as i32)
+    }
+}
+
+// 가상 데스크톱 좌표 범위 기본값 (±limit)
+pub const DEFAULT_DESKTOP_LIMIT: i32 = 65535;
+
+// stash_layout / restore_layout 슬롯 수
+pub const LAYOUT_SLOTS: usize = 4;
+
+// 화면 전체가 ±limit 범위 밖에 있는 디스플레이 거부 (손상된 프로필 등)
+// 일부라도 범위 안에 걸치면 허용합니다
+pub fn check_display_bounds(displays: &[DisplayInfo], limit: i32) -> Result<(), AppError> {
+    let limit = i64::from(limit.max(0));
+    for display in displays.iter().filter(|display| display.enabled) {
+        let (width, height) = layout_size(display);
+        let (x, y) = (i64::from(display.x), i64::from(display.y));
+        let outside = x + i64::from(width) <= -limit
+            || x >= limit
+            || y + i64::from(height) <= -limit
+            || y >= limit;
+        if outside {
+            return Err(AppError::DisplayOutOfBounds {
+                display_id: display.id.to_string(),
+                name: display.name.clone(),
+                origin: format!("{},{}", display.x, display.y),
+                limit: limit.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LayoutRect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl LayoutRect {
+    fn of(display: &DisplayInfo) -> Self {
+        let (width, height) = layout_size(display);
+        LayoutRect { x: display.x, y: display.y, width: width.max(1), height: height.max(1) }
+    }
+
+    fn center(&self) -> (f64, f64) {
+        (
+            self.x as f64 + self.width as f64 / 2.0,
+            self.y as f64 + self.height as f64 / 2.0,
+        )
+    }
+
+    fn overlaps(&self, other: &LayoutRect) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+}
+
+// 겹치거나 떨어진 디스플레이를 서로 맞닿도록 재배치
+// 주 디스플레이에서 가까운 순서로, 이미 배치된 가장 가까운 디스플레이의
+// 상하좌우 중 원래 방향에 붙이고, 주 디스플레이가 (0, 0)이 되도록 옮깁니다
+pub fn arrange_displays(mut displays: Vec<DisplayInfo>) -> Vec<DisplayInfo> {
+    // 미러링 중인 디스플레이는 원본과 같은 위치이므로, 꺼진 디스플레이는 화면이 없으므로 배치에서 제외
+    let is_mirrored = |display: &DisplayInfo, all: &[DisplayInfo]| {
+        display
+            .mirror_of
+            .is_some_and(|source| source != display.id && all.iter().any(|d| d.id == source))
+    };
+    let mut order: Vec<usize> = (0..displays.len())
+        .filter(|&i| displays[i].enabled && !is_mirrored(&displays[i], &displays))
+        .collect();
+    if order.is_empty() {
+        return displays;
+    }
+
+    let anchor = order
+        .iter()
+        .copied()
+        .find(|&i| displays[i].is_primary)
+        .unwrap_or(order[0]);
+    let anchor_center = LayoutRect::of(&displays[anchor]).center();
+    let distance = |rect: LayoutRect| {
+        let (cx, cy) = rect.center();
+        (cx - anchor_center.0).powi(2) + (cy - anchor_center.1).powi(2)
+    };
+    order.sort_by(|&a, &b| {
+        let key = |i: usize| (i != anchor, distance(LayoutRect::of(&displays[i])));
+        key(a).partial_cmp(&key(b)).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut placed: Vec<LayoutRect> = vec![LayoutRect::of(&displays[anchor])];
+    for &i in order.iter().skip(1) {
+        let mut rect = LayoutRect::of(&displays[i]);
+        let (cx, cy) = rect.center();
+
+        // 이미 배치된 것 중 중심이 가장 가까운 디스플레이 기준
+        let neighbor = *placed
+            .iter()
+            .min_by(|a, b| {
+                let d = |r: &LayoutRect| {
+                    let (x, y) = r.center();
+                    (x - cx).powi(2) + (y - cy).powi(2)
+                };
+                d(a).partial_cmp(&d(b)).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap_or(&placed[0]);
+        let (nx, ny) = neighbor.center();
+        // 크기로 정규화한 거리로 좌우/상하 중 어느 쪽에 붙일지 결정
+        let dx = (cx - nx) / (rect.width + neighbor.width).max(1) as f64;
+        let dy = (cy - ny) / (rect.height + neighbor.height).max(1) as f64;
+        let horizontal = dx.abs() >= dy.abs();
+
+        if horizontal {
+            rect.x = if dx >= 0.0 { neighbor.x + neighbor.width } else { neighbor.x - rect.width };
+            rect.y = rect.y.clamp(neighbor.y - rect.height + 1, neighbor.y + neighbor.height - 1);
+        } else {
+            rect.y = if dy >= 0.0 { neighbor.y + neighbor.height } else { neighbor.y - rect.height };
+            rect.x = rect.x.clamp(neighbor.x - rect.width + 1, neighbor.x + neighbor.width - 1);
+        }
+
+        // 다른 디스플레이와 겹치면 같은 방향으로 밀어냅니다
+        while let Some(other) = placed.iter().find(|other| rect.overlaps(other)) {
+            match (horizontal, dx >= 0.0, dy >= 0.0) {
+                (true, true, _) => rect.x = other.x + other.width,
+                (true, false, _) => rect.x = other.x - rect.width,
+                (false, _, true) => rect.y = other.y + other.height,
+                (false, _, false) => rect.y = other.y - rect.height,
+            }
+        }
+
+        displays[i].x = rect.x;
+        displays[i].y = rect.y;
+        placed.push(rect);
+    }
+
+    // 주 디스플레이를 원점으로
+    let (offset_x, offset_y) = (displays[anchor].x, displays[anchor].y);
+    for display in displays.iter_mut() {
+        display.x -= offset_x;
+        display.y -= offset_y;
+    }
+
+    // 미러링 디스플레이는 원본 위치로
+    for i in 0..displays.len() {
+        if !is_mirrored(&displays[i], &displays) {
+            continue;
+        }
+        let source_id = displays[i].mirror_of;
+        if let Some(source) = displays.iter().find(|d| Some(d.id) == source_id) {
+            let (x, y) = (source.x, source.y);
+            displays[i].x = x;
+            displays[i].y = y;
+        }
+    }
+
+    displays
+}
+
+// 모든 디스플레이가 지원하는 해상도 중 가장 큰 것 (디스플레이별 모드 목록)
+pub fn common_resolution(modes: &[Vec<DisplayMode>]) -> Option<(u32, u32)> {
+    let (first, rest) = modes.split_first()?;
+    first
+        .iter()
+        .map(|mode| (mode.width, mode.height))
+        .filter(|size| rest.iter().all(|modes| modes.iter().any(|mode| (mode.width, mode.height) == *size)))
+        .max_by_key(|(width, height)| u64::from(*width) * u64::from(*height))
+}
+
+// 켜진 디스플레이를 모두 주 디스플레이(없으면 첫 디스플레이)의 미러링으로
+// resolution이 있으면 모두 그 해상도로 맞추고, 해상도가 바뀐 디스플레이는 주사율을 지정하지 않습니다
+pub fn mirror_layout(mut displays: Vec<DisplayInfo>, resolution: Option<(u32, u32)>) -> Vec<DisplayInfo> {
+    let enabled = || displays.iter().filter(|display| display.enabled);
+    let Some(source) = enabled().find(|display| display.is_primary).or_else(|| enabled().next()) else {
+        return displays;
+    };
+    let (source_id, x, y) = (source.id, source.x, source.y);
+    for display in displays.iter_mut().filter(|display| display.enabled) {
+        if let Some((width, height)) = resolution.filter(|size| *size != (display.width, display.height)) {
+            display.width = width;
+            display.height = height;
+            display.logical_width = logical_size(width, display.scale_factor);
+            display.logical_height = logical_size(height, display.scale_factor);
+            display.refresh_rate = 0;
+        }
+        display.x = x;
+        display.y = y;
+        display.mirror_of = (display.id != source_id).then_some(source_id);
+    }
+    displays
+}
+
+// 미러링을 풀고 같은 위치에 겹친 디스플레이를 오른쪽에 나란히 놓은 뒤 맞닿도록 정렬
+pub fn extend_layout(mut displays: Vec<DisplayInfo>) -> Vec<DisplayInfo> {
+    let mut placed: Vec<LayoutRect> = Vec::new();
+    for display in displays.iter_mut().filter(|display| display.enabled) {
+        display.mirror_of = None;
+        let rect = LayoutRect::of(display);
+        if placed.iter().any(|other| (other.x, other.y) == (rect.x, rect.y)) {
+            display.x = placed.iter().map(|other| other.x + other.width).max().unwrap_or(0);
+        }
+        placed.push(LayoutRect::of(display));
+    }
+    arrange_displays(displays)
+}
+
+// 배치 편집기용 미리보기 (0~1 캔버스 좌표)
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct LayoutPreview {
+    pub displays: Vec<PreviewRect>,
+    // 서로 맞닿은 모서리
+    pub guides: Vec<SnapGuide>,
+    // 전체 배치가 차지하는 크기 (긴 쪽이 1)
+    pub content_width: f64,
+    pub content_height: f64,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PreviewRect {
+    pub id: u32,
+    pub label: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub is_primary: bool,
+    // 꺼 둔 디스플레이 (마지막 위치에 그리며 맞닿은 모서리와 떨어짐은 켜진 디스플레이끼리만 봅니다)
+    pub enabled: bool,
+    pub mirror_of: Option<u32>,
+    // 다른 디스플레이와 맞닿은 모서리가 없음
+    pub detached: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SnapGuide {
+    // "vertical"이면 position은 x, start/end는 y 범위
+    pub orientation: String,
+    pub position: f64,
+    pub start: f64,
+    pub end: f64,
+    pub display_ids: [u32; 2],
+}
+
+// 전체 배치의 왼쪽 위를 (0, 0)으로 옮기고 긴 쪽이 1이 되도록 같은 비율로 축소
+// 크기는 layout_size(논리 크기 우선)를 사용하므로 배율이 다른 디스플레이도 OS 좌표계와 맞습니다
+pub fn layout_preview(displays: &[DisplayInfo]) -> LayoutPreview {
+    let rects: Vec<LayoutRect> = displays.iter().map(LayoutRect::of).collect();
+    if rects.is_empty() {
+        return LayoutPreview {
+            displays: Vec::new(),
+            guides: Vec::new(),
+            content_width: 0.0,
+            content_height: 0.0,
+        };
+    }
+
+    let left = rects.iter().map(|r| r.x).min().unwrap_or(0);
+    let top = rects.iter().map(|r| r.y).min().unwrap_or(0);
+    let right = rects.iter().map(|r| r.x + r.width).max().unwrap_or(0);
+    let bottom = rects.iter().map(|r| r.y + r.height).max().unwrap_or(0);
+    let scale = 1.0 / (right - left).max(bottom - top).max(1) as f64;
+    let to_x = |x: i32| (x - left) as f64 * scale;
+    let to_y = |y: i32| (y - top) as f64 * scale;
+
+    let mut guides = Vec::new();
+    let mut attached = vec![false; rects.len()];
+    let enabled_count = displays.iter().filter(|display| display.enabled).count();
+    for i in 0..rects.len() {
+        for j in (i + 1)..rects.len() {
+            if !displays[i].enabled || !displays[j].enabled {
+                continue;
+            }
+            let (a, b) = (&rects[i], &rects[j]);
+            let ids = [displays[i].id, displays[j].id];
+
+            // 좌우로 맞닿은 모서리
+            let shared_x = if a.x + a.width == b.x {
+                Some(b.x)
+            } else if b.x + b.width == a.x {
+                Some(a.x)
+            } else {
+                None
+            };
+            let (start_y, end_y) = (a.y.max(b.y), (a.y + a.height).min(b.y + b.height));
+            if let Some(x) = shared_x.filter(|_| start_y < end_y) {
+                guides.push(SnapGuide {
+                    orientation: "vertical".to_string(),
+                    position: to_x(x),
+                    start: to_y(start_y),
+                    end: to_y(end_y),
+                    display_ids: ids,
+                });
+                attached[i] = true;
+                attached[j] = true;
+            }
+
+            // 위아래로 맞닿은 모서리
+            let shared_y = if a.y + a.height == b.y {
+                Some(b.y)
+            } else if b.y + b.height == a.y {
+                Some(a.y)
+            } else {
+                None
+            };
+            let (start_x, end_x) = (a.x.max(b.x), (a.x + a.width).min(b.x + b.width));
+            if let Some(y) = shared_y.filter(|_| start_x < end_x) {
+                guides.push(SnapGuide {
+                    orientation: "horizontal".to_string(),
+                    position: to_y(y),
+                    start: to_x(start_x),
+                    end: to_x(end_x),
+                    display_ids: ids,
+                });
+                attached[i] = true;
+                attached[j] = true;
+            }
+
+            // 미러링 중이거나 겹친 디스플레이는 떨어진 것으로 보지 않습니다
+            if a.overlaps(b) {
+                attached[i] = true;
+                attached[j] = true;
+            }
+        }
+    }
+
+    LayoutPreview {
+        displays: displays
+            .iter()
+            .zip(&rects)
+            .enumerate()
+            .map(|(i, (display, rect))| PreviewRect {
+                id: display.id,
+                label: display.name.clone(),
+                x: to_x(rect.x),
+                y: to_y(rect.y),
+                width: rect.width as f64 * scale,
+                height: rect.height as f64 * scale,
+                is_primary: display.is_primary,
+                enabled: display.enabled,
+                mirror_of: display.mirror_of,
+                detached: display.enabled && enabled_count > 1 && !attached[i],
+            })
+            .collect(),
+        guides,
+        content_width: (right - left) as f64 * scale,
+        content_height: (bottom - top) as f64 * scale,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(id: u32, x: i32, y: i32, width: u32, height: u32) -> DisplayInfo {
+        DisplayInfo {
+            id,
+            name: format!("Display {}", id),
+            width,
+            height,
+            logical_width: width,
+            logical_height: height,
+            refresh_rate: 0,
+            x,
+            y,
+            scale_factor: 1.0,
+            is_primary: id == 1,
+            rotation: 0,
+            mirror_of: None,
+            persistent_id: String::new(),
+            stable_id: None,
+            enabled: true,
+            brightness: None,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn layout_preview_empty() {
+        let preview = layout_preview(&[]);
+        assert!(preview.displays.is_empty());
+        assert!(preview.guides.is_empty());
+        assert_close(preview.content_width, 0.0);
+    }
+
+    #[test]
+    fn layout_preview_single_display() {
+        let preview = layout_preview(&[display(1, 100, 200, 1920, 1080)]);
+        let rect = &preview.displays[0];
+        assert_close(rect.x, 0.0);
+        assert_close(rect.y, 0.0);
+        assert_close(rect.width, 1.0);
+        assert_close(rect.height, 1080.0 / 1920.0);
+        assert!(rect.is_primary);
+        assert!(!rect.detached);
+        assert!(preview.guides.is_empty());
+    }
+
+    #[test]
+    fn layout_preview_portrait_uses_height_as_long_side() {
+        let preview = layout_preview(&[display(1, 0, 0, 1080, 1920)]);
+        assert_close(preview.content_width, 1080.0 / 1920.0);
+        assert_close(preview.content_height, 1.0);
+        assert_close(preview.displays[0].height, 1.0);
+    }
+
+    #[test]
+    fn layout_preview_three_displays_with_negative_origins() {
+        // 왼쪽 QHD, 가운데 주 디스플레이, 오른쪽 세로 모니터
+        let displays = [
+            display(1, 0, 0, 1920, 1080),
+            display(2, -2560, -360, 2560, 1440),
+            display(3, 1920, -420, 1080, 1920),
+        ];
+        let preview = layout_preview(&displays);
+        let width = 1920.0 + 2560.0 + 1080.0;
+        let scale = 1.0 / width;
+
+        let left = &preview.displays[1];
+        assert_close(left.x, 0.0);
+        assert_close(left.y, 60.0 * scale);
+        let primary = &preview.displays[0];
+        assert_close(primary.x, 2560.0 * scale);
+        assert_close(primary.y, 420.0 * scale);
+        let portrait = &preview.displays[2];
+        assert_close(portrait.y, 0.0);
+        assert_close(portrait.x + portrait.width, 1.0);
+        assert_close(preview.content_width, 1.0);
+        assert_close(preview.content_height, 1920.0 * scale);
+
+        assert_eq!(preview.guides.len(), 2);
+        for guide in &preview.guides {
+            assert_eq!(guide.orientation, "vertical");
+            assert_close(guide.start, 420.0 * scale);
+            assert_close(guide.end, 1500.0 * scale);
+        }
+        assert!(preview.displays.iter().all(|d| !d.detached));
+    }
+
+    #[test]
+    fn layout_preview_marks_detached_displays() {
+        let displays = [display(1, 0, 0, 1920, 1080), display(2, 2000, 0, 1920, 1080)];
+        let preview = layout_preview(&displays);
+        assert!(preview.guides.is_empty());
+        assert!(preview.displays.iter().all(|d| d.detached));
+    }
+
+    #[test]
+    fn layout_preview_keeps_disabled_displays() {
+        let mut off = display(2, 1920, 0, 1920, 1080);
+        off.enabled = false;
+        let preview = layout_preview(&[display(1, 0, 0, 1920, 1080), off, display(3, 0, 1080, 1920, 1080)]);
+        let states: Vec<(u32, bool)> = preview.displays.iter().map(|d| (d.id, d.enabled)).collect();
+        assert_eq!(states, [(1, true), (2, false), (3, true)]);
+        assert_close(preview.content_width, 1.0);
+        // 꺼진 디스플레이와는 맞닿은 모서리를 만들지 않습니다
+        assert_eq!(preview.guides.len(), 1);
+        assert_eq!(preview.guides[0].display_ids, [1, 3]);
+        assert!(preview.displays.iter().all(|d| !d.detached));
+    }
+
+    #[test]
+    fn layout_preview_horizontal_guide_and_corner_touch() {
+        // 위아래로 맞닿은 두 디스플레이와 꼭짓점만 닿은 디스플레이
+        let displays = [
+            display(1, 0, 0, 1920, 1080),
+            display(2, 480, 1080, 1280, 720),
+            display(3, 1920, 1080, 800, 600),
+        ];
+        let preview = layout_preview(&displays);
+        assert_eq!(preview.guides.len(), 1);
+        let guide = &preview.guides[0];
+        assert_eq!(guide.orientation, "horizontal");
+        assert_eq!(guide.display_ids, [1, 2]);
+        assert_close(guide.start, 480.0 / 2720.0);
+        assert_close(guide.end, 1760.0 / 2720.0);
+        assert!(preview.displays[2].detached);
+    }
+
+    #[test]
+    fn layout_preview_uses_logical_size_for_mixed_dpi() {
+        let mut retina = display(2, 1920, 0, 2880, 1800);
+        retina.logical_width = 1440;
+        retina.logical_height = 900;
+        retina.scale_factor = 2.0;
+        let preview = layout_preview(&[display(1, 0, 0, 1920, 1080), retina]);
+        assert_close(preview.displays[1].width, 1440.0 / 3360.0);
+        assert_eq!(preview.guides.len(), 1);
+    }
+
+    #[test]
+    fn check_display_bounds_rejects_only_fully_outside() {
+        let displays = [display(1, 0, 0, 1920, 1080), display(2, -65535, 0, 1920, 1080)];
+        assert!(check_display_bounds(&displays, DEFAULT_DESKTOP_LIMIT).is_ok());
+
+        let lost = [display(1, 0, 0, 1920, 1080), display(2, -2_000_000, 0, 1920, 1080)];
+        match check_display_bounds(&lost, DEFAULT_DESKTOP_LIMIT) {
+            Err(AppError::DisplayOutOfBounds { display_id, origin, .. }) => {
+                assert_eq!(display_id, "2");
+                assert_eq!(origin, "-2000000,0");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // 오른쪽 끝이 정확히 -limit이면 범위 밖
+        let edge = [display(1, -65535 - 1920, 0, 1920, 1080)];
+        assert!(check_display_bounds(&edge, DEFAULT_DESKTOP_LIMIT).is_err());
+        assert!(check_display_bounds(&[display(1, 0, 70000, 1920, 1080)], 100_000).is_ok());
+    }
+
+    #[test]
+    fn arrange_displays_closes_gaps_around_primary() {
+        // 주 디스플레이 오른쪽에 떨어진 디스플레이와 위쪽에 겹친 디스플레이
+        let displays = vec![
+            display(1, 100, 100, 1920, 1080),
+            display(2, 2200, 150, 1920, 1080),
+            display(3, 400, -800, 1280, 1024),
+        ];
+        let arranged = arrange_displays(displays);
+        assert_eq!((arranged[0].x, arranged[0].y), (0, 0));
+        assert_eq!((arranged[1].x, arranged[1].y), (1920, 50));
+        assert_eq!((arranged[2].x, arranged[2].y), (300, -1024));
+        let preview = layout_preview(&arranged);
+        assert!(preview.displays.iter().all(|d| !d.detached));
+    }
+
+    #[test]
+    fn arrange_displays_keeps_mirrors_on_source() {
+        let mut mirror = display(2, 5000, 5000, 1920, 1080);
+        mirror.mirror_of = Some(1);
+        let arranged = arrange_displays(vec![display(1, 300, 200, 1920, 1080), mirror]);
+        assert_eq!((arranged[0].x, arranged[0].y), (0, 0));
+        assert_eq!((arranged[1].x, arranged[1].y), (0, 0));
+    }
+
+    #[test]
+    fn switches_between_mirror_and_extend() {
+        let mode = |width, height| DisplayMode {
+            width,
+            height,
+            refresh_rate: 60,
+            scale_factor: 1.0,
+            is_current: false,
+        };
+        let resolution = common_resolution(&[
+            vec![mode(2560, 1440), mode(1920, 1080), mode(1280, 720)],
+            vec![mode(1920, 1080), mode(1280, 720)],
+        ]);
+        assert_eq!(resolution, Some((1920, 1080)));
+        assert_eq!(common_resolution(&[vec![mode(2560, 1440)], vec![mode(1920, 1080)]]), None);
+
+        let mut external = display(2, 0, 0, 2560, 1440);
+        external.refresh_rate = 144;
+        let mirrored = mirror_layout(vec![display(1, 300, 200, 1920, 1080), external], resolution);
+        assert_eq!(mirrored[0].mirror_of, None);
+        assert_eq!(mirrored[1].mirror_of, Some(1));
+        assert_eq!((mirrored[1].x, mirrored[1].y, mirrored[1].width, mirrored[1].refresh_rate), (300, 200, 1920, 0));
+
+        let extended = extend_layout(mirrored);
+        assert!(extended.iter().all(|display| display.mirror_of.is_none()));
+        assert_eq!((extended[0].x, extended[0].y), (0, 0));
+        assert_eq!((extended[1].x, extended[1].y), (1920, 0));
+    }
+}
@@ -0,0 +1,566 @@
+// 그 밖의 OS: 세션에 맞는 방법으로 목록 조회, 일련번호는 DRM의 EDID로 확인
+// X11은 xrandr, Wayland는 wayland/kscreen 모듈 (XWayland의 xrandr은 가상 출력만 보여 주므로 쓰지 않음)
+
+use std::fs;
+
+use super::registry::{fallback_identities, DisplayIdentity};
+use super::{edid, DisplayApplied, DisplayInfo, DisplayMode};
+use crate::error::AppError;
+use crate::monitor::{watch_command_output, ChangeNotifier};
+use crate::tools::{
+    decode_output, run_command, template_backend, template_program, templated_command, tool_command,
+};
+
+mod kscreen;
+mod wayland;
+
+// 디스플레이 목록을 읽을 방법 (실행 중인 세션과 컴포지터로 결정)
+#[derive(Debug, Clone, PartialEq)]
+enum Session {
+    X11,
+    // Sway, Hyprland 등 zwlr_output_manager_v1을 제공하는 컴포지터
+    Wlroots,
+    // GNOME Shell (Mutter)
+    Gnome,
+    // KDE Plasma (KWin, kscreen)
+    Kde,
+    // 아직 지원하지 않는 Wayland 컴포지터 (XDG_CURRENT_DESKTOP)
+    OtherWayland(String),
+}
+
+fn detect_session() -> Session {
+    let var = |name: &str| std::env::var(name).unwrap_or_default();
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some() || var("XDG_SESSION_TYPE") == "wayland";
+    if !wayland {
+        return Session::X11;
+    }
+
+    let desktop = var("XDG_CURRENT_DESKTOP");
+    let upper = desktop.to_uppercase();
+    if std::env::var_os("SWAYSOCK").is_some()
+        || std::env::var_os("HYPRLAND_INSTANCE_SIGNATURE").is_some()
+        || ["SWAY", "HYPRLAND", "RIVER", "WAYFIRE", "LABWC"].iter().any(|name| upper.contains(name))
+    {
+        Session::Wlroots
+    } else if upper.contains("GNOME") {
+        Session::Gnome
+    } else if upper.contains("KDE") {
+        Session::Kde
+    } else {
+        Session::OtherWayland(desktop)
+    }
+}
+
+// get_system_info에 표시할 디스플레이 백엔드
+pub fn backend_name() -> String {
+    match detect_session() {
+        Session::X11 => "xrandr (X11)".to_string(),
+        Session::Wlroots => "wlr-randr (Wayland, wlroots)".to_string(),
+        Session::Gnome => "Mutter DisplayConfig (Wayland, GNOME)".to_string(),
+        Session::Kde => "kscreen-doctor (Wayland, KDE Plasma)".to_string(),
+        Session::OtherWayland(desktop) => format!("unsupported (Wayland, {})", desktop),
+    }
+}
+
+// 적용에 필요한 외부 도구 (X11은 출력 목록을 읽는 xrandr과 적용 템플릿의 프로그램)
+// 적용을 지원하지 않는 세션은 도구와 관계없이 UnsupportedSession으로 실패하므로 비워 둡니다
+pub fn apply_tools() -> Vec<String> {
+    match detect_session() {
+        Session::X11 => {
+            let mut tools = vec!["xrandr".to_string()];
+            tools.extend(template_program("display.apply").filter(|program| program != "xrandr"));
+            tools
+        }
+        Session::Gnome => vec!["busctl".to_string()],
+        Session::Kde => vec!["kscreen-doctor".to_string()],
+        Session::Wlroots | Session::OtherWayland(_) => Vec::new(),
+    }
+}
+
+// 디스플레이 연결/분리는 세션과 관계없이 DRM uevent로 감시
+// 커넥터 상태가 그대로인 변경(다른 앱에서 바꾼 해상도 등)은 알리지 않으므로 다음 연결 변경 때 반영됩니다
+pub fn watch_display_changes(notifier: ChangeNotifier) -> Result<String, AppError> {
+    watch_command_output(
+        "udevadm",
+        &["monitor", "--udev", "--subsystem-match=drm"],
+        is_drm_uevent,
+        notifier,
+    )?;
+    Ok("udevadm monitor (drm)".to_string())
+}
+
+// 예: "UDEV  [1234.5] change   /devices/pci0000:00/0000:00:02.0/drm/card1 (drm)" (머리말 줄은 제외)
+fn is_drm_uevent(line: &str) -> bool {
+    line.starts_with("UDEV ") && line.ends_with("(drm)")
+}
+
+// xrandr이 보고한 출력(output) 하나
+#[derive(Debug, Clone, Default, PartialEq)]
+struct XrandrOutput {
+    // 커넥터 이름 (예: HDMI-1)
+    name: String,
+    // Identifier (같은 세션에서는 커넥터마다 고정)
+    id: Option<u32>,
+    connected: bool,
+    primary: bool,
+    // 켜져 있으면 CRTC 배치 (width, height, x, y)
+    geometry: Option<(u32, u32, i32, i32)>,
+    rotation: u32,
+    // 현재 모드의 주사율 (Hz, 반올림, 모르면 0)
+    refresh_rate: u32,
+    // 출력이 지원하는 모드 (회전 전 크기)
+    modes: Vec<DisplayMode>,
+    edid: Vec<u8>,
+}
+
+fn unsupported_wayland(desktop: &str) -> AppError {
+    AppError::UnsupportedSession {
+        session: format!("Wayland ({})", if desktop.is_empty() { "unknown" } else { desktop }),
+    }
+}
+
+pub fn list_displays() -> Result<Vec<DisplayInfo>, AppError> {
+    match detect_session() {
+        Session::X11 => Ok(displays_from_outputs(&query_xrandr()?)),
+        Session::Wlroots => wayland::list_displays_wlroots(),
+        Session::Gnome => wayland::list_displays_mutter(),
+        Session::Kde => kscreen::list_displays_kscreen(),
+        Session::OtherWayland(desktop) => Err(unsupported_wayland(&desktop)),
+    }
+}
+
+fn query_xrandr() -> Result<Vec<XrandrOutput>, AppError> {
+    let output = run_command(tool_command("xrandr").arg("--verbose"))
+        .map_err(|e| AppError::tool_missing("xrandr", &e))?;
+    if !output.status.success() {
+        return Err(AppError::tool_failed("xrandr", &output));
+    }
+    Ok(parse_xrandr(&decode_output(&output.stdout)))
+}
+
+// Linux는 아직 밝기를 바꾸지 않으므로 밝기가 있는 디스플레이는 건너뛰었다고 알립니다
+pub fn apply_display_settings(displays: &[DisplayInfo]) -> Result<DisplayApplied, AppError> {
+    let brightness_skipped: Vec<String> = displays
+        .iter()
+        .filter(|display| display.enabled && display.brightness.is_some())
+        .map(|display| display.name.clone())
+        .collect();
+    for name in &brightness_skipped {
+        log::warn!("{}: 이 플랫폼에서는 밝기를 바꿀 수 없어 건너뜁니다", name);
+    }
+    apply_layout(displays).map(|backend| DisplayApplied { backend, brightness_skipped })
+}
+
+// X11은 xrandr 명령 하나로, GNOME은 Mutter D-Bus API로, KDE는 kscreen-doctor로 적용
+// (wlroots 계열의 적용은 아직 지원하지 않음)
+fn apply_layout(displays: &[DisplayInfo]) -> Result<String, AppError> {
+    match detect_session() {
+        Session::X11 => {}
+        // Mutter는 구성에 넣지 않은 모니터를 끕니다
+        Session::Gnome => {
+            let enabled: Vec<DisplayInfo> = displays.iter().filter(|d| d.enabled).cloned().collect();
+            return wayland::apply_displays_mutter(&enabled);
+        }
+        Session::Kde => return kscreen::apply_displays_kscreen(displays),
+        Session::Wlroots => return Err(unsupported_wayland("wlroots")),
+        Session::OtherWayland(desktop) => return Err(unsupported_wayland(&desktop)),
+    }
+
+    let args = xrandr_args(displays, &query_xrandr()?)?;
+    let (mut command, program, custom) = templated_command("display.apply", &[("args", &args)])?;
+    let output = run_command(&mut command).map_err(|e| AppError::tool_missing(&program, &e))?;
+    if !output.status.success() {
+        return Err(AppError::tool_failed(&program, &output));
+    }
+    Ok(template_backend(&program, custom))
+}
+
+// 디스플레이별 xrandr 인자 (프로필에 없는 출력은 그대로 둡니다)
+// X11 화면은 원점이 (0, 0)이므로 음수 좌표가 있으면 전체를 옮깁니다
+fn xrandr_args(displays: &[DisplayInfo], outputs: &[XrandrOutput]) -> Result<Vec<String>, AppError> {
+    let connector = |id: u32| {
+        outputs
+            .iter()
+            .enumerate()
+            .find(|(index, output)| output.connected && output_id(*index, output) == id)
+            .map(|(_, output)| output.name.clone())
+    };
+    let placed = displays.iter().filter(|display| display.enabled && display.mirror_of.is_none());
+    let min_x = placed.clone().map(|display| display.x).min().unwrap_or(0).min(0);
+    let min_y = placed.map(|display| display.y).min().unwrap_or(0).min(0);
+
+    let mut args = Vec::new();
+    for display in displays {
+        // displayplacer처럼 연결되지 않은 디스플레이가 있으면 아무것도 바꾸지 않습니다
+        let name = connector(display.id).ok_or_else(|| AppError::ToolFailed {
+            tool: "xrandr".to_string(),
+            detail: format!("Unable to find output for display {} ({})", display.id, display.name),
+        })?;
+        if !display.enabled {
+            args.extend(["--output".to_string(), name, "--off".to_string()]);
+            continue;
+        }
+        // 목록의 해상도는 회전이 반영된 값이고 --mode는 회전 전 모드입니다
+        let (width, height) = if display.rotation % 180 == 90 {
+            (display.height, display.width)
+        } else {
+            (display.width, display.height)
+        };
+        args.extend(["--output".to_string(), name]);
+        args.extend(["--mode".to_string(), format!("{}x{}", width, height)]);
+        // 주사율이 없는 이전 프로필은 xrandr이 모드의 기본 주사율을 고릅니다
+        if display.refresh_rate > 0 {
+            args.extend(["--rate".to_string(), display.refresh_rate.to_string()]);
+        }
+        match display.mirror_of.and_then(connector) {
+            Some(source) => args.extend(["--same-as".to_string(), source]),
+            None => args.extend([
+                "--pos".to_string(),
+                format!("{}x{}", display.x - min_x, display.y - min_y),
+            ]),
+        }
+        args.extend(["--rotate".to_string(), rotation_name(display.rotation).to_string()]);
+        if display.is_primary {
+            args.push("--primary".to_string());
+        }
+    }
+    Ok(args)
+}
+
+// 시계 방향 각도 → xrandr 회전 이름
+fn rotation_name(rotation: u32) -> &'static str {
+    match rotation {
+        90 => "right",
+        180 => "inverted",
+        270 => "left",
+        _ => "normal",
+    }
+}
+
+// 목록과 적용에서 같은 규칙으로 쓰는 출력 id (Identifier가 없으면 순서)
+fn output_id(index: usize, output: &XrandrOutput) -> u32 {
+    output.id.unwrap_or(index as u32 + 1)
+}
+
+// xrandr --verbose 출력 해석
+fn parse_xrandr(text: &str) -> Vec<XrandrOutput> {
+    let mut outputs: Vec<XrandrOutput> = Vec::new();
+    let mut in_edid = false;
+
+    for line in text.lines() {
+        // 들여쓰기 없는 줄은 화면(Screen) 또는 출력 머리글
+        if !line.starts_with(char::is_whitespace) {
+            in_edid = false;
+            outputs.extend(parse_output_header(line));
+            continue;
+        }
+        let Some(output) = outputs.last_mut() else {
+            continue;
+        };
+
+        let trimmed = line.trim();
+        if in_edid {
+            if !trimmed.is_empty() && trimmed.chars().all(|c| c.is_ascii_hexdigit()) {
+                output.edid.extend(edid::decode_hex(trimmed));
+                continue;
+            }
+            in_edid = false;
+        }
+        // 모드 줄 예: "1920x1080 (0x48) 138.700MHz +HSync -VSync *current +preferred"
+        // 바로 아래 "v: ... clock  60.02Hz" 줄에 주사율이 있습니다
+        if trimmed.contains("MHz") {
+            output.modes.extend(parse_mode_line(trimmed));
+        } else if trimmed.starts_with("v:") {
+            let refresh_rate = trimmed
+                .rsplit(' ')
+                .next()
+                .and_then(|clock| clock.strip_suffix("Hz"))
+                .and_then(|hz| hz.parse::<f64>().ok())
+                .map_or(0, |hz| hz.round() as u32);
+            if let Some(mode) = output.modes.last_mut() {
+                mode.refresh_rate = refresh_rate;
+                if mode.is_current {
+                    output.refresh_rate = refresh_rate;
+                }
+            }
+        } else if trimmed == "EDID:" {
+            in_edid = true;
+        } else if let Some(id) = trimmed.strip_prefix("Identifier:") {
+            output.id = u32::from_str_radix(id.trim().trim_start_matches("0x"), 16).ok();
+        }
+    }
+
+    outputs
+}
+
+// 예: "HDMI-1 connected primary 2560x1440+1920+0 (0x4a) left (normal left inverted right ...) 597mm x 336mm"
+fn parse_output_header(line: &str) -> Option<XrandrOutput> {
+    let mut tokens = line.split_whitespace();
+    let name = tokens.next()?;
+    let connected = match tokens.next()? {
+        "connected" => true,
+        "disconnected" | "unknown" => false,
+        _ => return None,
+    };
+
+    let mut output = XrandrOutput {
+        name: name.to_string(),
+        connected,
+        ..Default::default()
+    };
+    // 괄호 안의 회전 목록은 지원하는 값이므로 그 앞까지만 읽습니다
+    for token in tokens.take_while(|token| *token != "(normal") {
+        match token {
+            "primary" => output.primary = true,
+            // xrandr의 left는 반시계 방향, displayplacer와 같이 시계 방향 각도로 저장합니다
+            "normal" => output.rotation = 0,
+            "right" => output.rotation = 90,
+            "inverted" => output.rotation = 180,
+            "left" => output.rotation = 270,
+            _ => {
+                if let Some(geometry) = parse_geometry(token) {
+                    output.geometry = Some(geometry);
+                }
+            }
+        }
+    }
+    Some(output)
+}
+
+// "1920x1080 (0x48) 138.700MHz ... *current" → 크기와 현재 모드 여부 (주사율은 다음 줄에서)
+// 인터레이스 모드(예: "1920x1080i")도 크기만 읽습니다
+fn parse_mode_line(line: &str) -> Option<DisplayMode> {
+    let (width, height) = line.split_whitespace().next()?.split_once('x')?;
+    let height = height.trim_end_matches(|c: char| !c.is_ascii_digit());
+    Some(DisplayMode {
+        width: width.parse().ok()?,
+        height: height.parse().ok()?,
+        refresh_rate: 0,
+        // X11은 디스플레이별 배율이 없습니다
+        scale_factor: 1.0,
+        is_current: line.contains("*current"),
+    })
+}
+
+// "2560x1440+1920+0" → (2560, 1440, 1920, 0)
+fn parse_geometry(token: &str) -> Option<(u32, u32, i32, i32)> {
+    let (width, rest) = token.split_once('x')?;
+    let mut parts = rest.split('+');
+    let height = parts.next()?.parse().ok()?;
+    let x = parts.next()?.parse().ok()?;
+    let y = parts.next()?.parse().ok()?;
+    Some((width.parse().ok()?, height, x, y))
+}
+
+// 연결된 출력을 DisplayInfo로 (꺼진 출력은 enabled: false와 첫 모드의 크기로, 다시 켤 수 있도록)
+// 같은 위치와 크기의 출력은 먼저 나온 출력의 미러링으로 봅니다
+fn displays_from_outputs(outputs: &[XrandrOutput]) -> Vec<DisplayInfo> {
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+    for (index, output) in outputs.iter().enumerate().filter(|(_, output)| output.connected) {
+        let enabled = output.geometry.is_some();
+        let (width, height, x, y) = output.geometry.unwrap_or_else(|| {
+            output.modes.first().map_or((0, 0, 0, 0), |mode| (mode.width, mode.height, 0, 0))
+        });
+        let mirror_of = displays
+            .iter()
+            .filter(|d| enabled && d.enabled)
+            .find(|d| (d.width, d.height, d.x, d.y) == (width, height, x, y))
+            .map(|d| d.id);
+        displays.push(DisplayInfo {
+            id: output_id(index, output),
+            name: edid::name(&output.edid).unwrap_or_else(|| output.name.clone()),
+            width,
+            height,
+            // X11은 디스플레이별 배율이 없습니다
+            logical_width: width,
+            logical_height: height,
+            refresh_rate: output.refresh_rate,
+            x,
+            y,
+            scale_factor: 1.0,
+            is_primary: output.primary,
+            rotation: output.rotation,
+            mirror_of,
+            persistent_id: String::new(),
+            stable_id: None,
+            enabled,
+            brightness: None,
+        });
+    }
+    displays
+}
+
+// X11은 xrandr의 모드 목록, Wayland 백엔드는 아직 모드 목록을 읽지 않습니다
+pub fn display_modes(display_id: u32) -> Result<Vec<DisplayMode>, AppError> {
+    match detect_session() {
+        Session::X11 => modes_of_output(&query_xrandr()?, display_id),
+        _ => Err(AppError::UnsupportedPlatform { feature: "display mode list (Wayland)".to_string() }),
+    }
+}
+
+// displays_from_outputs와 같은 id 규칙으로 연결된 출력 찾기
+fn modes_of_output(outputs: &[XrandrOutput], display_id: u32) -> Result<Vec<DisplayMode>, AppError> {
+    outputs
+        .iter()
+        .enumerate()
+        .filter(|(_, output)| output.connected)
+        .find(|(index, output)| output_id(*index, output) == display_id)
+        .map(|(_, output)| output.modes.clone())
+        .ok_or_else(|| AppError::DisplayNotFound { display_id: display_id.to_string() })
+}
+
+// X11은 xrandr의 출력 이름과 EDID로 알아봅니다
+// Wayland 백엔드는 아직 출력별 EDID를 읽지 않으므로 OS id로 구분합니다
+pub fn display_identities(displays: &[DisplayInfo]) -> Result<Vec<DisplayIdentity>, AppError> {
+    match detect_session() {
+        Session::X11 => Ok(identities_from_outputs(&query_xrandr()?)),
+        _ => Ok(fallback_identities(displays)),
+    }
+}
+
+// displays_from_outputs와 같은 출력만 같은 id 규칙으로
+fn identities_from_outputs(outputs: &[XrandrOutput]) -> Vec<DisplayIdentity> {
+    outputs
+        .iter()
+        .enumerate()
+        .filter(|(_, output)| output.connected)
+        .map(|(index, output)| DisplayIdentity {
+            os_id: output_id(index, output),
+            fingerprint: edid::fingerprint(&output.edid),
+            connector: output.name.clone(),
+        })
+        .collect()
+}
+
+pub fn connected_display_serials() -> Result<Vec<String>, AppError> {
+    Ok(raw_edids()?.iter().flat_map(|blob| edid::serials(blob)).collect())
+}
+
+// DRM 커넥터마다 연결된 모니터의 EDID가 노출됩니다 (연결되지 않은 커넥터는 빈 파일)
+pub fn raw_edids() -> Result<Vec<Vec<u8>>, AppError> {
+    let drm = std::path::Path::new("/sys/class/drm");
+    let entries = fs::read_dir(drm).map_err(|e| AppError::io(drm, &e))?;
+    let mut edids: Vec<(std::path::PathBuf, Vec<u8>)> = entries
+        .flatten()
+        .filter_map(|entry| Some((entry.path(), fs::read(entry.path().join("edid")).ok()?)))
+        .filter(|(_, edid)| !edid.is_empty())
+        .collect();
+    edids.sort();
+    Ok(edids.into_iter().map(|(_, edid)| edid).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XRANDR_VERBOSE: &str = "\
+Screen 0: minimum 8 x 8, current 3360 x 2560, maximum 32767 x 32767
+eDP-1 connected primary 1920x1080+0+0 (0x48) normal (normal left inverted right x axis y axis) 344mm x 194mm
+\tIdentifier: 0x42
+\tTimestamp:  21387
+\tCRTC:       0
+\tEDID:
+\t\t00ffffffffffff000000000000000000
+\t\t00000000000000000000000000000000
+\t\t00000000000000000000000000000000
+\t\t000000000000000000fc0044454c4c20
+\t\t5532373230510a200000000000000000
+\t\t00000000000000000000000000000000
+\t\t00000000000000000000000000000000
+\t\t00000000000000000000000000000000
+\tBrightness: 1.0
+  1920x1080 (0x48) 138.700MHz +HSync -VSync *current +preferred
+        h: width  1920 start 1968 end 2000 total 2080 skew    0 clock  66.68KHz
+        v: height 1080 start 1083 end 1088 total 1111           clock  60.02Hz
+  1280x720 (0x49) 74.480MHz +HSync +VSync
+        h: width  1280 start 1336 end 1472 total 1664 skew    0 clock  44.76KHz
+        v: height  720 start  721 end  724 total  746           clock  75.00Hz
+HDMI-1 connected 1440x2560+1920+0 (0x4a) left (normal left inverted right x axis y axis) 597mm x 336mm
+\tIdentifier: 0x43
+DP-1 disconnected (normal left inverted right x axis y axis)
+\tIdentifier: 0x44
+DP-2 connected (normal left inverted right x axis y axis)
+\tIdentifier: 0x45
+";
+
+    #[test]
+    fn parses_xrandr_outputs() {
+        let outputs = parse_xrandr(XRANDR_VERBOSE);
+        assert_eq!(outputs.len(), 4);
+        assert_eq!(outputs[0].id, Some(0x42));
+        assert!(outputs[0].primary);
+        assert_eq!(outputs[0].edid.len(), 128);
+        assert_eq!(outputs[1].geometry, Some((1440, 2560, 1920, 0)));
+        assert_eq!(outputs[1].rotation, 270);
+        assert_eq!((outputs[0].refresh_rate, outputs[1].refresh_rate), (60, 0));
+        assert_eq!(
+            modes_of_output(&outputs, 0x42).unwrap(),
+            vec![
+                DisplayMode { width: 1920, height: 1080, refresh_rate: 60, scale_factor: 1.0, is_current: true },
+                DisplayMode { width: 1280, height: 720, refresh_rate: 75, scale_factor: 1.0, is_current: false },
+            ]
+        );
+        assert!(matches!(modes_of_output(&outputs, 0x44), Err(AppError::DisplayNotFound { .. })));
+        assert_eq!(modes_of_output(&outputs, 0x45).unwrap(), Vec::new());
+        assert!(!outputs[2].connected);
+        assert_eq!(outputs[3].geometry, None);
+
+        // 연결되지 않은 출력은 제외하고 꺼진 출력은 enabled: false로, 이름은 EDID를 우선 사용합니다
+        let displays = displays_from_outputs(&outputs);
+        assert_eq!(displays.len(), 3);
+        assert_eq!((displays[2].name.as_str(), displays[2].enabled), ("DP-2", false));
+        assert!(displays[..2].iter().all(|d| d.enabled));
+        assert_eq!((displays[0].id, displays[0].name.as_str()), (0x42, "DELL U2720Q"));
+        assert_eq!((displays[1].name.as_str(), displays[1].x), ("HDMI-1", 1920));
+        assert!(displays.iter().all(|d| d.mirror_of.is_none()));
+
+        // 안정 id 등록에는 같은 출력을 커넥터 이름으로 넘깁니다 (예시 EDID에는 제조사 코드가 없음)
+        let identities = identities_from_outputs(&outputs);
+        let connectors: Vec<(u32, &str)> = identities.iter().map(|i| (i.os_id, i.connector.as_str())).collect();
+        assert_eq!(connectors, vec![(0x42, "eDP-1"), (0x43, "HDMI-1"), (0x45, "DP-2")]);
+        assert!(identities.iter().all(|i| i.fingerprint.is_none()));
+    }
+
+    #[test]
+    fn builds_xrandr_command_for_profile() {
+        let outputs = parse_xrandr(XRANDR_VERBOSE);
+        let mut displays = displays_from_outputs(&outputs);
+        // 외부 모니터를 왼쪽으로 옮기면 좌표가 음수가 되어 전체를 오른쪽으로 옮깁니다
+        displays[1].x = -1440;
+        assert_eq!(
+            xrandr_args(&displays, &outputs).unwrap().join(" "),
+            "--output eDP-1 --mode 1920x1080 --rate 60 --pos 1440x0 --rotate normal --primary \
+             --output HDMI-1 --mode 2560x1440 --pos 0x0 --rotate left --output DP-2 --off"
+        );
+
+        // 켜는 출력과 끄는 출력
+        displays[0].enabled = false;
+        displays[2].enabled = true;
+        let args = xrandr_args(&displays, &outputs).unwrap().join(" ");
+        assert!(args.starts_with("--output eDP-1 --off --output HDMI-1"), "{}", args);
+        displays[0].enabled = true;
+        displays[2].enabled = false;
+
+        displays[1].mirror_of = Some(displays[0].id);
+        let args = xrandr_args(&displays, &outputs).unwrap().join(" ");
+        assert!(
+            args.ends_with("--output HDMI-1 --mode 2560x1440 --same-as eDP-1 --rotate left --output DP-2 --off"),
+            "{}",
+            args
+        );
+
+        // 연결되지 않은 출력은 적용하지 않습니다
+        displays[1].id = 0x44;
+        assert!(matches!(xrandr_args(&displays, &outputs), Err(AppError::ToolFailed { .. })));
+    }
+
+    #[test]
+    fn same_geometry_is_mirror() {
+        let text = "\
+HDMI-1 connected primary 1920x1080+0+0 (0x4a) normal (normal left inverted right x axis y axis) 0mm x 0mm
+DP-1 connected 1920x1080+0+0 (0x4a) normal (normal left inverted right x axis y axis) 0mm x 0mm
+";
+        let displays = displays_from_outputs(&parse_xrandr(text));
+        assert_eq!(displays[1].mirror_of, Some(displays[0].id));
+    }
+}
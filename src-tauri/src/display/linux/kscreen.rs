@@ -0,0 +1,252 @@
+// KDE Plasma (Wayland): kscreen-doctor로 목록 조회와 적용
+// kscreen-doctor --json은 Plasma 5.24부터, priority(주 디스플레이 순서)와 mirror는 Plasma 6부터 있습니다
+
+use serde::Deserialize;
+
+use super::wayland::{connector_id, display_info};
+use super::DisplayInfo;
+use crate::error::AppError;
+use crate::tools::{run_command, tool_command};
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct KscreenConfig {
+    outputs: Vec<KscreenOutput>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct KscreenOutput {
+    id: u32,
+    name: String,
+    connected: bool,
+    enabled: bool,
+    current_mode_id: String,
+    modes: Vec<KscreenMode>,
+    pos: KscreenPosition,
+    scale: f64,
+    // 1 = none, 2 = left, 4 = inverted, 8 = right
+    rotation: u32,
+    // Plasma 6은 priority(1이 주 디스플레이), Plasma 5는 primary
+    priority: Option<u32>,
+    primary: Option<bool>,
+    // 미러링 원본 출력의 kscreen id (0이면 미러링하지 않음)
+    replication_source: u32,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct KscreenMode {
+    id: String,
+    refresh_rate: f64,
+    size: KscreenSize,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct KscreenSize {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct KscreenPosition {
+    x: i32,
+    y: i32,
+}
+
+// kscreen 회전 값 → 시계 방향 각도 (left는 반시계 90도)
+fn rotation_from_kscreen(rotation: u32) -> u32 {
+    match rotation {
+        2 => 270,
+        4 => 180,
+        8 => 90,
+        _ => 0,
+    }
+}
+
+fn kscreen_rotation_name(rotation: u32) -> &'static str {
+    match rotation {
+        90 => "right",
+        180 => "inverted",
+        270 => "left",
+        _ => "none",
+    }
+}
+
+fn query_kscreen() -> Result<KscreenConfig, AppError> {
+    let output = run_command(tool_command("kscreen-doctor").arg("--json"))
+        .map_err(|e| AppError::tool_missing("kscreen-doctor", &e))?;
+    if !output.status.success() {
+        return Err(AppError::tool_failed("kscreen-doctor", &output));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| AppError::DisplayQueryFailed {
+        detail: format!("kscreen-doctor: {}", e),
+    })
+}
+
+pub fn list_displays_kscreen() -> Result<Vec<DisplayInfo>, AppError> {
+    Ok(kscreen_displays(&query_kscreen()?))
+}
+
+fn kscreen_displays(config: &KscreenConfig) -> Vec<DisplayInfo> {
+    let active: Vec<&KscreenOutput> =
+        config.outputs.iter().filter(|output| output.connected && output.enabled).collect();
+    let mut displays: Vec<DisplayInfo> = active
+        .iter()
+        .filter_map(|output| {
+            let mode = output.modes.iter().find(|mode| mode.id == output.current_mode_id)?;
+            let mut display = display_info(
+                &output.name,
+                output.name.clone(),
+                (mode.size.width, mode.size.height),
+                (output.pos.x, output.pos.y),
+                output.scale,
+                rotation_from_kscreen(output.rotation),
+            );
+            display.is_primary = output.priority == Some(1) || output.primary == Some(true);
+            display.mirror_of = active
+                .iter()
+                .filter(|_| output.replication_source != 0)
+                .find(|source| source.id == output.replication_source)
+                .map(|source| connector_id(&source.name));
+            Some(display)
+        })
+        .collect();
+
+    if !displays.iter().any(|display| display.is_primary) {
+        if let Some(display) = displays.first_mut() {
+            display.is_primary = true;
+        }
+    }
+    displays
+}
+
+// kscreen-doctor 인자 (output.<이름>.<설정>.<값>, 프로필에 없는 출력은 그대로 둡니다)
+fn kscreen_args(config: &KscreenConfig, displays: &[DisplayInfo]) -> Result<Vec<String>, AppError> {
+    let output_of = |id: u32| {
+        config
+            .outputs
+            .iter()
+            .find(|output| output.connected && connector_id(&output.name) == id)
+    };
+    let plasma6 = config.outputs.iter().any(|output| output.priority.is_some());
+
+    let mut args = Vec::new();
+    for display in displays {
+        let output = output_of(display.id).ok_or_else(|| AppError::ToolFailed {
+            tool: "kscreen-doctor".to_string(),
+            detail: format!("Unable to find output for display {} ({})", display.id, display.name),
+        })?;
+        if !display.enabled {
+            args.push(format!("output.{}.disable", output.name));
+            continue;
+        }
+        // 목록의 해상도는 회전이 반영된 값이고 모드는 회전 전 크기입니다
+        let size = if display.rotation % 180 == 90 {
+            (display.height, display.width)
+        } else {
+            (display.width, display.height)
+        };
+        let mode = output
+            .modes
+            .iter()
+            .filter(|mode| (mode.size.width, mode.size.height) == size)
+            .max_by(|a, b| {
+                (a.id == output.current_mode_id, a.refresh_rate)
+                    .partial_cmp(&(b.id == output.current_mode_id, b.refresh_rate))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .ok_or_else(|| AppError::ToolFailed {
+                tool: "kscreen-doctor".to_string(),
+                detail: format!("invalid mode {}x{} for {}", size.0, size.1, output.name),
+            })?;
+
+        let setting = |key: &str, value: &str| format!("output.{}.{}.{}", output.name, key, value);
+        args.push(format!("output.{}.enable", output.name));
+        args.push(setting("mode", &mode.id));
+        args.push(setting("scale", &display.scale_factor.to_string()));
+        args.push(setting("rotation", kscreen_rotation_name(display.rotation)));
+        match display.mirror_of.and_then(output_of) {
+            Some(source) if plasma6 => args.push(setting("mirror", &source.name)),
+            // Plasma 5에서는 같은 위치에 겹쳐 두면 복제로 표시됩니다
+            _ => args.push(setting("position", &format!("{},{}", display.x, display.y))),
+        }
+        if display.is_primary {
+            if plasma6 {
+                args.push(setting("priority", "1"));
+            } else {
+                args.push(format!("output.{}.primary", output.name));
+            }
+        }
+    }
+    Ok(args)
+}
+
+pub fn apply_displays_kscreen(displays: &[DisplayInfo]) -> Result<String, AppError> {
+    let args = kscreen_args(&query_kscreen()?, displays)?;
+    let output = run_command(tool_command("kscreen-doctor").args(&args))
+        .map_err(|e| AppError::tool_missing("kscreen-doctor", &e))?;
+    if !output.status.success() {
+        return Err(AppError::tool_failed("kscreen-doctor", &output));
+    }
+    Ok("kscreen-doctor".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KSCREEN_JSON: &str = r#"{"outputs": [
+        {"id": 1, "name": "eDP-1", "connected": true, "enabled": true, "currentModeId": "1",
+         "modes": [{"id": "1", "name": "2880x1800@120", "refreshRate": 120.0, "size": {"width": 2880, "height": 1800}},
+                   {"id": "2", "name": "2880x1800@60", "refreshRate": 60.0, "size": {"width": 2880, "height": 1800}}],
+         "pos": {"x": 0, "y": 0}, "scale": 1.75, "rotation": 1, "priority": 1, "replicationSource": 0},
+        {"id": 2, "name": "DP-1", "connected": true, "enabled": true, "currentModeId": "5",
+         "modes": [{"id": "5", "name": "2560x1440@144", "refreshRate": 144.0, "size": {"width": 2560, "height": 1440}}],
+         "pos": {"x": 1646, "y": 0}, "scale": 1.0, "rotation": 2, "priority": 2, "replicationSource": 0},
+        {"id": 3, "name": "HDMI-A-1", "connected": false, "enabled": false, "modes": []}
+    ]}"#;
+
+    #[test]
+    fn parses_kscreen_outputs() {
+        let config: KscreenConfig = serde_json::from_str(KSCREEN_JSON).unwrap();
+        let displays = kscreen_displays(&config);
+        assert_eq!(displays.len(), 2);
+        assert!(displays[0].is_primary);
+        assert_eq!((displays[0].logical_width, displays[0].logical_height), (1646, 1029));
+        assert_eq!((displays[1].width, displays[1].height, displays[1].rotation), (1440, 2560, 270));
+        assert_eq!(displays[1].id, connector_id("DP-1"));
+    }
+
+    #[test]
+    fn builds_kscreen_doctor_args() {
+        let config: KscreenConfig = serde_json::from_str(KSCREEN_JSON).unwrap();
+        let mut displays = kscreen_displays(&config);
+        displays[0].scale_factor = 1.5;
+        displays[1].mirror_of = Some(displays[0].id);
+
+        let args = kscreen_args(&config, &displays).unwrap();
+        assert_eq!(
+            args,
+            [
+                "output.eDP-1.enable",
+                "output.eDP-1.mode.1",
+                "output.eDP-1.scale.1.5",
+                "output.eDP-1.rotation.none",
+                "output.eDP-1.position.0,0",
+                "output.eDP-1.priority.1",
+                "output.DP-1.enable",
+                "output.DP-1.mode.5",
+                "output.DP-1.scale.1",
+                "output.DP-1.rotation.left",
+                "output.DP-1.mirror.eDP-1",
+            ]
+        );
+
+        displays[1].enabled = false;
+        let args = kscreen_args(&config, &displays).unwrap();
+        assert_eq!(args.last().map(String::as_str), Some("output.DP-1.disable"));
+    }
+}
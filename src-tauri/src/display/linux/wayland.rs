@@ -0,0 +1,530 @@
+// Wayland: wlroots 계열은 wlr-randr, GNOME은 Mutter DisplayConfig D-Bus API
+// wlroots는 zwlr_output_manager_v1 프로토콜에 직접 연결하지 않고 wlr-randr --json을 실행하므로 wlr-randr이 설치되어 있어야 합니다
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::DisplayInfo;
+use crate::error::AppError;
+use crate::tools::{decode_output, run_command, tool_command};
+
+// Wayland에는 X11의 Identifier 같은 출력 번호가 없어 커넥터 이름으로 id를 만듭니다 (FNV-1a)
+pub(super) fn connector_id(connector: &str) -> u32 {
+    connector
+        .bytes()
+        .fold(0x811c9dc5u32, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x01000193))
+}
+
+// wl_output transform은 반시계 방향이므로 시계 방향 각도로 바꿉니다 (뒤집기는 무시)
+fn rotation_from_transform(transform: u32) -> u32 {
+    match transform % 4 {
+        1 => 270,
+        2 => 180,
+        3 => 90,
+        _ => 0,
+    }
+}
+
+// 물리 해상도와 배율로 DisplayInfo 만들기 (90/270도 회전이면 가로세로를 바꿉니다)
+pub(super) fn display_info(
+    connector: &str,
+    name: String,
+    mode: (u32, u32),
+    position: (i32, i32),
+    scale: f64,
+    rotation: u32,
+) -> DisplayInfo {
+    let (width, height) = if rotation % 180 == 90 { (mode.1, mode.0) } else { mode };
+    let scale = if scale > 0.0 { scale } else { 1.0 };
+    DisplayInfo {
+        id: connector_id(connector),
+        name,
+        width,
+        height,
+        logical_width: (width as f64 / scale).round() as u32,
+        logical_height: (height as f64 / scale).round() as u32,
+        // Wayland 백엔드는 아직 주사율을 읽지 않습니다
+        refresh_rate: 0,
+        x: position.0,
+        y: position.1,
+        scale_factor: scale,
+        is_primary: false,
+        rotation,
+        mirror_of: None,
+        persistent_id: String::new(),
+        stable_id: None,
+        enabled: true,
+        brightness: None,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct WlrOutput {
+    name: String,
+    model: String,
+    enabled: bool,
+    modes: Vec<WlrMode>,
+    position: WlrPosition,
+    transform: String,
+    scale: f64,
+}
+
+impl Default for WlrOutput {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            model: String::new(),
+            enabled: true,
+            modes: Vec::new(),
+            position: WlrPosition::default(),
+            transform: "normal".to_string(),
+            scale: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct WlrMode {
+    width: u32,
+    height: u32,
+    current: bool,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct WlrPosition {
+    x: i32,
+    y: i32,
+}
+
+// wlroots 계열 (Sway, Hyprland 등)
+pub fn list_displays_wlroots() -> Result<Vec<DisplayInfo>, AppError> {
+    let output = run_command(tool_command("wlr-randr").arg("--json"))
+        .map_err(|e| AppError::tool_missing("wlr-randr", &e))?;
+    if !output.status.success() {
+        return Err(AppError::tool_failed("wlr-randr", &output));
+    }
+    parse_wlr_randr(&decode_output(&output.stdout))
+}
+
+fn parse_wlr_randr(json: &str) -> Result<Vec<DisplayInfo>, AppError> {
+    let outputs: Vec<WlrOutput> = serde_json::from_str(json)
+        .map_err(|e| AppError::DisplayQueryFailed { detail: format!("wlr-randr: {}", e) })?;
+
+    let mut displays: Vec<DisplayInfo> = outputs
+        .iter()
+        .filter(|output| output.enabled)
+        .filter_map(|output| {
+            let mode = output.modes.iter().find(|mode| mode.current)?;
+            let transform = match output.transform.trim_start_matches("flipped-") {
+                "90" => 1,
+                "180" => 2,
+                "270" => 3,
+                _ => 0,
+            };
+            let name = if output.model.is_empty() { &output.name } else { &output.model };
+            Some(display_info(
+                &output.name,
+                name.clone(),
+                (mode.width, mode.height),
+                (output.position.x, output.position.y),
+                output.scale,
+                rotation_from_transform(transform),
+            ))
+        })
+        .collect();
+
+    // wlroots에는 주 디스플레이 개념이 없어 원점에 있는 출력을 주 디스플레이로 봅니다
+    let primary = displays.iter().position(|d| (d.x, d.y) == (0, 0)).unwrap_or(0);
+    if let Some(display) = displays.get_mut(primary) {
+        display.is_primary = true;
+    }
+    Ok(displays)
+}
+
+// GNOME (Mutter)
+const MUTTER_DESTINATION: [&str; 3] = [
+    "org.gnome.Mutter.DisplayConfig",
+    "/org/gnome/Mutter/DisplayConfig",
+    "org.gnome.Mutter.DisplayConfig",
+];
+
+// ApplyMonitorsConfig의 method: 2 = persistent (monitors.xml에 저장)
+const MUTTER_APPLY_PERSISTENT: &str = "2";
+
+// 물리 모니터의 모드 하나
+#[derive(Debug, Clone, PartialEq)]
+struct MutterMode {
+    id: String,
+    width: u32,
+    height: u32,
+    refresh: f64,
+    current: bool,
+    supported_scales: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct MutterMonitor {
+    connector: String,
+    name: String,
+    modes: Vec<MutterMode>,
+}
+
+// 논리 모니터 (같은 위치와 배율을 쓰는 모니터 묶음, 둘 이상이면 미러링)
+#[derive(Debug, Clone, PartialEq)]
+struct MutterLogicalMonitor {
+    x: i32,
+    y: i32,
+    scale: f64,
+    transform: u32,
+    primary: bool,
+    connectors: Vec<String>,
+}
+
+// GetCurrentState 결과 (serial은 ApplyMonitorsConfig에 그대로 넘겨야 합니다)
+#[derive(Debug, Clone, PartialEq)]
+struct MutterState {
+    serial: u64,
+    monitors: Vec<MutterMonitor>,
+    logical_monitors: Vec<MutterLogicalMonitor>,
+}
+
+fn query_mutter_state() -> Result<MutterState, AppError> {
+    let mut args = vec!["--user", "--json=short", "call"];
+    args.extend(MUTTER_DESTINATION);
+    args.push("GetCurrentState");
+    let output = run_command(tool_command("busctl").args(args))
+        .map_err(|e| AppError::tool_missing("busctl", &e))?;
+    if !output.status.success() {
+        return Err(AppError::tool_failed("busctl", &output));
+    }
+    parse_mutter_state(&decode_output(&output.stdout))
+}
+
+pub fn list_displays_mutter() -> Result<Vec<DisplayInfo>, AppError> {
+    Ok(mutter_displays(&query_mutter_state()?))
+}
+
+// a{sv} 사전에서 값 꺼내기 (busctl은 variant를 {"type", "data"}로 출력)
+fn property<'a>(properties: &'a Value, key: &str) -> Option<&'a Value> {
+    properties.get(key).map(|variant| variant.get("data").unwrap_or(variant))
+}
+
+// GetCurrentState 반환값:
+// (serial, monitors a((ssss)a(siiddada{sv})a{sv}), logical_monitors a(iiduba(ssss)a{sv}), properties a{sv})
+fn parse_mutter_state(json: &str) -> Result<MutterState, AppError> {
+    let invalid = |detail: &str| AppError::DisplayQueryFailed {
+        detail: format!("Mutter DisplayConfig: {}", detail),
+    };
+    let value: Value = serde_json::from_str(json).map_err(|e| invalid(&e.to_string()))?;
+    let data = value["data"].as_array().ok_or_else(|| invalid("missing data"))?;
+    let serial = data.first().and_then(Value::as_u64).ok_or_else(|| invalid("missing serial"))?;
+    let monitors = data.get(1).and_then(Value::as_array).ok_or_else(|| invalid("missing monitors"))?;
+    let logical_monitors = data
+        .get(2)
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid("missing logical monitors"))?;
+
+    let monitors = monitors
+        .iter()
+        .filter_map(|monitor| {
+            let connector = monitor[0][0].as_str()?.to_string();
+            let modes = monitor[1]
+                .as_array()?
+                .iter()
+                .filter_map(|mode| {
+                    Some(MutterMode {
+                        id: mode[0].as_str()?.to_string(),
+                        width: mode[1].as_u64()? as u32,
+                        height: mode[2].as_u64()? as u32,
+                        refresh: mode[3].as_f64().unwrap_or_default(),
+                        current: property(&mode[6], "is-current").and_then(Value::as_bool)
+                            == Some(true),
+                        supported_scales: mode[5]
+                            .as_array()
+                            .map(|scales| scales.iter().filter_map(Value::as_f64).collect())
+                            .unwrap_or_default(),
+                    })
+                })
+                .collect();
+            let name = property(&monitor[2], "display-name")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| connector.clone());
+            Some(MutterMonitor { connector, name, modes })
+        })
+        .collect();
+
+    let logical_monitors = logical_monitors
+        .iter()
+        .map(|logical| MutterLogicalMonitor {
+            x: logical[0].as_i64().unwrap_or_default() as i32,
+            y: logical[1].as_i64().unwrap_or_default() as i32,
+            scale: logical[2].as_f64().unwrap_or(1.0),
+            transform: logical[3].as_u64().unwrap_or_default() as u32,
+            primary: logical[4].as_bool().unwrap_or(false),
+            connectors: logical[5]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|spec| spec[0].as_str().map(str::to_string))
+                .collect(),
+        })
+        .collect();
+
+    Ok(MutterState { serial, monitors, logical_monitors })
+}
+
+fn mutter_displays(state: &MutterState) -> Vec<DisplayInfo> {
+    // 커넥터 → (표시 이름, 현재 모드 해상도)
+    let physical = |connector: &str| -> Option<(String, (u32, u32))> {
+        let monitor = state.monitors.iter().find(|m| m.connector == connector)?;
+        let mode = monitor.modes.iter().find(|mode| mode.current)?;
+        Some((monitor.name.clone(), (mode.width, mode.height)))
+    };
+
+    let mut displays = Vec::new();
+    for logical in &state.logical_monitors {
+        let rotation = rotation_from_transform(logical.transform);
+        // 논리 모니터 하나에 여러 모니터가 있으면 미러링입니다
+        let mut mirror_source = None;
+        for connector in &logical.connectors {
+            let Some((name, mode)) = physical(connector) else {
+                continue;
+            };
+            let position = (logical.x, logical.y);
+            let mut display =
+                display_info(connector, name, mode, position, logical.scale, rotation);
+            display.is_primary = logical.primary && mirror_source.is_none();
+            display.mirror_of = mirror_source;
+            mirror_source.get_or_insert(display.id);
+            displays.push(display);
+        }
+    }
+    displays
+}
+
+// 시계 방향 각도 → wl_output transform (rotation_from_transform의 반대)
+fn transform_from_rotation(rotation: u32) -> u32 {
+    match rotation {
+        90 => 3,
+        180 => 2,
+        270 => 1,
+        _ => 0,
+    }
+}
+
+// 회전 전 해상도와 같은 모드 (현재 모드 우선, 없으면 주사율이 가장 높은 모드)
+fn matching_mode<'a>(modes: &'a [MutterMode], display: &DisplayInfo) -> Option<&'a MutterMode> {
+    let size = if display.rotation % 180 == 90 {
+        (display.height, display.width)
+    } else {
+        (display.width, display.height)
+    };
+    let candidates = modes.iter().filter(|mode| (mode.width, mode.height) == size);
+    candidates
+        .clone()
+        .find(|mode| mode.current)
+        .or_else(|| candidates.max_by(|a, b| a.refresh.total_cmp(&b.refresh)))
+}
+
+// Mutter는 모드가 지원하는 배율만 받으므로 가장 가까운 배율을 고릅니다
+fn nearest_scale(mode: &MutterMode, scale: f64) -> f64 {
+    mode.supported_scales
+        .iter()
+        .copied()
+        .min_by(|a, b| (a - scale).abs().total_cmp(&(b - scale).abs()))
+        .unwrap_or(scale)
+}
+
+// ApplyMonitorsConfig를 호출하는 busctl 인자
+// 목록에 없는 모니터는 꺼지므로 프로필에 없는 디스플레이는 현재 구성 그대로 넣습니다
+fn mutter_apply_args(
+    state: &MutterState,
+    displays: &[DisplayInfo],
+) -> Result<Vec<String>, AppError> {
+    let monitor_of = |id: u32| state.monitors.iter().find(|m| connector_id(&m.connector) == id);
+    for display in displays {
+        if monitor_of(display.id).is_none() {
+            return Err(AppError::ToolFailed {
+                tool: "busctl".to_string(),
+                detail: format!(
+                    "Unable to find monitor for display {} ({})",
+                    display.id, display.name
+                ),
+            });
+        }
+    }
+
+    let mut merged = mutter_displays(state);
+    for display in &mut merged {
+        if let Some(requested) = displays.iter().find(|d| d.id == display.id) {
+            *display = requested.clone();
+        }
+    }
+
+    // 미러링 원본마다 논리 모니터 하나 (원본이 목록에 없으면 따로 배치)
+    let is_source = |id: u32| merged.iter().any(|d| d.id == id && d.mirror_of.is_none());
+    let sources: Vec<&DisplayInfo> = merged
+        .iter()
+        .filter(|d| d.mirror_of.is_none_or(|source| !is_source(source)))
+        .collect();
+    let min_x = sources.iter().map(|d| d.x).min().unwrap_or(0);
+    let min_y = sources.iter().map(|d| d.y).min().unwrap_or(0);
+    let primary = sources.iter().position(|d| d.is_primary).unwrap_or(0);
+
+    let mut args: Vec<String> = ["--user", "call"].iter().map(|arg| arg.to_string()).collect();
+    args.extend(MUTTER_DESTINATION.iter().map(|arg| arg.to_string()));
+    args.extend([
+        "ApplyMonitorsConfig".to_string(),
+        "uua(iiduba(ssa{sv}))a{sv}".to_string(),
+        state.serial.to_string(),
+        MUTTER_APPLY_PERSISTENT.to_string(),
+        sources.len().to_string(),
+    ]);
+    for (index, source) in sources.iter().enumerate() {
+        let members: Vec<&DisplayInfo> = std::iter::once(*source)
+            .chain(merged.iter().filter(|d| d.mirror_of == Some(source.id) && d.id != source.id))
+            .collect();
+        let mut specs = Vec::new();
+        let mut scale = source.scale_factor;
+        for (position, member) in members.iter().enumerate() {
+            let monitor = monitor_of(member.id).ok_or_else(|| AppError::ToolFailed {
+                tool: "busctl".to_string(),
+                detail: format!("Unable to find monitor for display {}", member.id),
+            })?;
+            let mode = matching_mode(&monitor.modes, member).ok_or_else(|| AppError::ToolFailed {
+                tool: "busctl".to_string(),
+                detail: format!(
+                    "invalid mode {}x{} for {}",
+                    member.width, member.height, monitor.connector
+                ),
+            })?;
+            if position == 0 {
+                scale = nearest_scale(mode, source.scale_factor);
+            }
+            specs.extend([monitor.connector.clone(), mode.id.clone(), "0".to_string()]);
+        }
+        args.extend([
+            (source.x - min_x).to_string(),
+            (source.y - min_y).to_string(),
+            scale.to_string(),
+            transform_from_rotation(source.rotation).to_string(),
+            (index == primary).to_string(),
+            members.len().to_string(),
+        ]);
+        args.extend(specs);
+    }
+    // properties a{sv} (비어 있음)
+    args.push("0".to_string());
+    Ok(args)
+}
+
+pub fn apply_displays_mutter(displays: &[DisplayInfo]) -> Result<String, AppError> {
+    // 조회와 적용 사이에 구성이 바뀌면 serial이 맞지 않아 거부되므로 한 번 다시 조회합니다
+    let mut attempt = 0;
+    loop {
+        let state = query_mutter_state()?;
+        let args = mutter_apply_args(&state, displays)?;
+        let output = run_command(tool_command("busctl").args(&args))
+            .map_err(|e| AppError::tool_missing("busctl", &e))?;
+        if output.status.success() {
+            return Ok("busctl (Mutter DisplayConfig)".to_string());
+        }
+        let stale = decode_output(&output.stderr).contains("stale");
+        attempt += 1;
+        if !stale || attempt > 1 {
+            return Err(AppError::tool_failed("busctl", &output));
+        }
+        log::warn!("Mutter 구성 serial {}이 바뀌어 다시 조회합니다", state.serial);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_wlr_randr_json() {
+        let json = r#"[
+            {"name": "eDP-1", "make": "BOE", "model": "0x095F", "enabled": true,
+             "modes": [{"width": 2256, "height": 1504, "refresh": 59.999, "current": true}],
+             "position": {"x": 0, "y": 0}, "transform": "normal", "scale": 1.5},
+            {"name": "DP-1", "make": "Dell Inc.", "model": "DELL U2720Q", "enabled": true,
+             "modes": [{"width": 3840, "height": 2160, "refresh": 60.0, "current": true}],
+             "position": {"x": 1504, "y": 0}, "transform": "90", "scale": 2.0},
+            {"name": "HDMI-A-1", "make": "LG", "enabled": false, "modes": []}
+        ]"#;
+        let displays = parse_wlr_randr(json).unwrap();
+        assert_eq!(displays.len(), 2);
+        assert!(displays[0].is_primary);
+        assert_eq!((displays[0].logical_width, displays[0].logical_height), (1504, 1003));
+        assert_eq!(displays[1].name, "DELL U2720Q");
+        assert_eq!((displays[1].width, displays[1].height, displays[1].rotation), (2160, 3840, 270));
+        assert_eq!(displays[1].id, connector_id("DP-1"));
+    }
+
+    const MUTTER_STATE: &str = r#"{"type": "ua((ssss)a(siiddada{sv})a{sv})a(iiduba(ssss)a{sv})a{sv}", "data": [
+        7,
+        [
+            [["eDP-1", "BOE", "0x095f", "0x00000000"],
+             [["2256x1504@60", 2256, 1504, 60.0, 1.5, [1.0, 1.5, 2.0], {"is-current": {"type": "b", "data": true}}]],
+             {"display-name": {"type": "s", "data": "Built-in display"}}],
+            [["HDMI-1", "DEL", "DELL U2720Q", "ABC123"],
+             [["3840x2160@60", 3840, 2160, 60.0, 2.0, [1.0, 2.0], {"is-current": {"type": "b", "data": true}}],
+              ["1920x1080@60", 1920, 1080, 60.0, 1.0, [1.0], {}]],
+             {"display-name": {"type": "s", "data": "Dell 27\""}}],
+            [["DP-2", "DEL", "DELL U2720Q", "DEF456"],
+             [["3840x2160@60", 3840, 2160, 60.0, 2.0, [1.0, 2.0], {"is-current": {"type": "b", "data": true}}]],
+             {}]
+        ],
+        [
+            [0, 0, 1.5, 0, true, [["eDP-1", "BOE", "0x095f", "0x00000000"]], {}],
+            [1504, 0, 2.0, 0, false, [["HDMI-1", "DEL", "DELL U2720Q", "ABC123"], ["DP-2", "DEL", "DELL U2720Q", "DEF456"]], {}]
+        ],
+        {"layout-mode": {"type": "u", "data": 1}}
+    ]}"#;
+
+    #[test]
+    fn parses_mutter_current_state() {
+        let state = parse_mutter_state(MUTTER_STATE).unwrap();
+        assert_eq!(state.serial, 7);
+        let displays = mutter_displays(&state);
+        assert_eq!(displays.len(), 3);
+        assert_eq!(displays[0].name, "Built-in display");
+        assert!(displays[0].is_primary);
+        assert_eq!((displays[1].width, displays[1].logical_width, displays[1].x), (3840, 1920, 1504));
+        assert_eq!(displays[1].name, "Dell 27\"");
+        assert_eq!(displays[2].name, "DP-2");
+        assert_eq!(displays[2].mirror_of, Some(displays[1].id));
+        assert!(!displays[2].is_primary);
+    }
+
+    #[test]
+    fn builds_mutter_apply_config() {
+        let state = parse_mutter_state(MUTTER_STATE).unwrap();
+        let mut displays = mutter_displays(&state);
+        // DP-2를 미러링에서 빼서 오른쪽에 세로로 두고, 지원하지 않는 배율은 가까운 값으로
+        displays[2].mirror_of = None;
+        displays[2].x = 3424;
+        displays[2].rotation = 90;
+        (displays[2].width, displays[2].height) = (2160, 3840);
+        displays[2].scale_factor = 1.75;
+
+        let args = mutter_apply_args(&state, &displays[2..]).unwrap();
+        let expected = [
+            "ApplyMonitorsConfig", "uua(iiduba(ssa{sv}))a{sv}", "7", "2", "3",
+            "0", "0", "1.5", "0", "true", "1", "eDP-1", "2256x1504@60", "0",
+            "1504", "0", "2", "0", "false", "1", "HDMI-1", "3840x2160@60", "0",
+            "3424", "0", "2", "3", "false", "1", "DP-2", "3840x2160@60", "0",
+            "0",
+        ];
+        assert_eq!(args[5..], expected);
+
+        let mut missing = displays[0].clone();
+        missing.id = 1;
+        assert!(mutter_apply_args(&state, &[missing]).is_err());
+    }
+}
@@ -0,0 +1,367 @@
+// macOS: CoreGraphics로 목록을 읽고 displayplacer로 적용
+
+use core_graphics::display::{
+    CGDirectDisplayID, CGDisplay, CGDisplayBounds, CGDisplayMode, CGDisplayPixelsHigh, CGDisplayPixelsWide,
+    CGGetActiveDisplayList, CGMainDisplayID,
+};
+
+use core_foundation::base::{CFType, TCFType};
+use core_foundation::dictionary::{CFDictionary, CFDictionaryRef, CFMutableDictionaryRef};
+use core_foundation::number::CFNumber;
+use core_foundation::string::{CFString, CFStringRef};
+
+use std::ffi::{c_char, c_void};
+
+use super::registry::DisplayIdentity;
+use super::{
+    backing_scale, closest_refresh_rate, displayplacer_args, edid, refresh_rate_note, snap_rotation, DisplayApplied,
+    DisplayInfo, DisplayMode,
+};
+use crate::error::AppError;
+use crate::monitor::ChangeNotifier;
+use crate::tools::{
+    failure_detail, run_command, template_backend, template_program, templated_command, with_version_hint,
+};
+
+mod brightness;
+
+// get_system_info에 표시할 디스플레이 백엔드
+pub fn backend_name() -> String {
+    "CoreGraphics + displayplacer".to_string()
+}
+
+// 적용에 필요한 외부 도구 (기본은 displayplacer, 명령 템플릿을 바꿨으면 그 프로그램)
+pub fn apply_tools() -> Vec<String> {
+    template_program("display.apply").into_iter().collect()
+}
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGGetOnlineDisplayList(max_displays: u32, displays: *mut CGDirectDisplayID, count: *mut u32) -> i32;
+    // 미러링 중이면 미러링 원본, 아니면 kCGNullDirectDisplay (0)
+    fn CGDisplayMirrorsDisplay(display: CGDirectDisplayID) -> CGDirectDisplayID;
+}
+
+const MAX_DISPLAYS: usize = 32;
+
+// 활성 디스플레이와, 하드웨어 미러링으로 활성 목록에서 빠진 미러링 대상
+// 잠자기 중인 디스플레이는 온라인 목록에만 있으므로 포함하지 않습니다
+fn listed_display_ids() -> Result<Vec<CGDirectDisplayID>, AppError> {
+    let query = |name: &str, list: unsafe extern "C" fn(u32, *mut CGDirectDisplayID, *mut u32) -> i32| {
+        let mut ids: Vec<CGDirectDisplayID> = vec![0; MAX_DISPLAYS];
+        let mut count: u32 = 0;
+        let result = unsafe { list(MAX_DISPLAYS as u32, ids.as_mut_ptr(), &mut count) };
+        if result != 0 {
+            return Err(AppError::DisplayQueryFailed { detail: format!("{} returned {}", name, result) });
+        }
+        ids.truncate(count as usize);
+        Ok(ids)
+    };
+    let mut ids = query("CGGetActiveDisplayList", CGGetActiveDisplayList)?;
+    let online = query("CGGetOnlineDisplayList", CGGetOnlineDisplayList).unwrap_or_default();
+    let mirrors: Vec<CGDirectDisplayID> = online
+        .into_iter()
+        .filter(|id| !ids.contains(id) && ids.contains(&unsafe { CGDisplayMirrorsDisplay(*id) }))
+        .collect();
+    ids.extend(mirrors);
+    Ok(ids)
+}
+
+pub fn list_displays() -> Result<Vec<DisplayInfo>, AppError> {
+    let mut displays = Vec::new();
+    let display_ids = listed_display_ids()?;
+
+    unsafe {
+        let main_display_id = CGMainDisplayID();
+        let names = iokit_display_names();
+
+        for (i, &display_id) in display_ids.iter().enumerate() {
+            let bounds = CGDisplayBounds(display_id);
+            // 현재 모드에서 포인트 크기와 픽셀 크기를 각각 읽습니다
+            // 모드를 읽지 못하면 픽셀 크기를 포인트 크기로 사용하므로 배율은 1.0이 됩니다
+            // 내장 디스플레이는 주사율이 0으로 보고될 수 있습니다 (알 수 없음)
+            let (width, height, logical_width, logical_height, refresh_rate) =
+                match CGDisplay::new(display_id).display_mode() {
+                    Some(mode) => (
+                        mode.pixel_width(),
+                        mode.pixel_height(),
+                        mode.width(),
+                        mode.height(),
+                        mode.refresh_rate().round() as u32,
+                    ),
+                    None => {
+                        let width = CGDisplayPixelsWide(display_id) as u64;
+                        let height = CGDisplayPixelsHigh(display_id) as u64;
+                        (width, height, width, height, 0)
+                    }
+                };
+
+            displays.push(DisplayInfo {
+                id: display_id,
+                name: display_name(display_id, i as usize, &names),
+                width: width.try_into().unwrap(),
+                height: height.try_into().unwrap(),
+                logical_width: logical_width.try_into().unwrap(),
+                logical_height: logical_height.try_into().unwrap(),
+                refresh_rate,
+                x: bounds.origin.x as i32,
+                y: bounds.origin.y as i32,
+                scale_factor: backing_scale(width, logical_width),
+                is_primary: display_id == main_display_id,
+                // CGDisplayRotation (displayplacer의 degree와 같은 시계 방향 각도)
+                rotation: snap_rotation(CGDisplay::new(display_id).rotation()),
+                mirror_of: Some(CGDisplayMirrorsDisplay(display_id)).filter(|source| *source != 0),
+                persistent_id: String::new(),
+                stable_id: None,
+                enabled: true,
+                brightness: brightness::display_brightness(display_id),
+            });
+        }
+    }
+
+    Ok(displays)
+}
+
+// IODisplayCreateInfoDictionary의 옵션 (kIODisplayOnlyPreferredName, 현재 언어의 이름 하나만)
+const DISPLAY_ONLY_PREFERRED_NAME: u32 = 0x200;
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IOServiceMatching(name: *const c_char) -> CFMutableDictionaryRef;
+    fn IOServiceGetMatchingServices(main_port: u32, matching: CFDictionaryRef, existing: *mut u32) -> i32;
+    fn IOIteratorNext(iterator: u32) -> u32;
+    fn IOObjectRelease(object: u32) -> i32;
+    fn IODisplayCreateInfoDictionary(framebuffer: u32, options: u32) -> CFDictionaryRef;
+}
+
+// IODisplayConnect 서비스의 ((제조사, 제품, 일련번호), EDID 제품 이름)
+// Apple Silicon에는 IODisplayConnect가 없을 수 있으며, 그때는 빈 목록입니다
+fn iokit_display_names() -> Vec<((u32, u32, u32), String)> {
+    let mut names = Vec::new();
+    unsafe {
+        // IOServiceGetMatchingServices가 matching의 참조를 가져갑니다
+        let matching = IOServiceMatching(c"IODisplayConnect".as_ptr());
+        let mut iterator = 0;
+        if matching.is_null() || IOServiceGetMatchingServices(0, matching as CFDictionaryRef, &mut iterator) != 0 {
+            return names;
+        }
+        loop {
+            let service = IOIteratorNext(iterator);
+            if service == 0 {
+                break;
+            }
+            let info = IODisplayCreateInfoDictionary(service, DISPLAY_ONLY_PREFERRED_NAME);
+            IOObjectRelease(service);
+            if info.is_null() {
+                continue;
+            }
+            let info: CFDictionary<CFString, CFType> = CFDictionary::wrap_under_create_rule(info);
+            let number = |key: &str| {
+                info.find(CFString::new(key))
+                    .and_then(|value| value.downcast::<CFNumber>())
+                    .and_then(|number| number.to_i64())
+                    .unwrap_or(0) as u32
+            };
+            // DisplayProductName은 언어 → 이름 사전입니다
+            let name = info
+                .find(CFString::new("DisplayProductName"))
+                .and_then(|value| value.downcast::<CFDictionary>())
+                .and_then(|localized| localized.get_keys_and_values().1.first().copied())
+                .map(|name| CFString::wrap_under_get_rule(name as CFStringRef).to_string())
+                .filter(|name| !name.trim().is_empty());
+            if let Some(name) = name {
+                let key = (number("DisplayVendorID"), number("DisplayProductID"), number("DisplaySerialNumber"));
+                names.push((key, name.trim().to_string()));
+            }
+        }
+        IOObjectRelease(iterator);
+    }
+    names
+}
+
+// EDID 제품 이름 (일련번호가 다르면 제조사와 제품이 같은 모니터), 없으면 내장 디스플레이 또는 "Display N"
+fn display_name(display_id: CGDirectDisplayID, index: usize, names: &[((u32, u32, u32), String)]) -> String {
+    let cg = CGDisplay::new(display_id);
+    let key = (cg.vendor_number(), cg.model_number(), cg.serial_number());
+    names
+        .iter()
+        .find(|(found, _)| *found == key)
+        .or_else(|| names.iter().find(|((vendor, product, _), _)| (*vendor, *product) == (key.0, key.1)))
+        .map(|(_, name)| name.clone())
+        .or_else(|| cg.is_builtin().then(|| "Built-in Display".to_string()))
+        .unwrap_or_else(|| format!("Display {}", index + 1))
+}
+
+// 해상도가 같은 모드의 주사율 (모드 목록을 읽지 못하면 빈 목록)
+fn supported_refresh_rates(display: &DisplayInfo) -> Vec<u32> {
+    let (width, height) = if display.logical_width > 0 && display.logical_height > 0 {
+        (display.logical_width as u64, display.logical_height as u64)
+    } else {
+        (display.width as u64, display.height as u64)
+    };
+    CGDisplayMode::all_display_modes(display.id, std::ptr::null())
+        .unwrap_or_default()
+        .iter()
+        .filter(|mode| (mode.width(), mode.height()) == (width, height))
+        .map(|mode| mode.refresh_rate().round() as u32)
+        .filter(|rate| *rate > 0)
+        .collect()
+}
+
+// CGDisplayCopyAllDisplayModes의 모든 모드 (HiDPI 모드는 픽셀 크기와 배율 2.0)
+pub fn display_modes(display_id: u32) -> Result<Vec<DisplayMode>, AppError> {
+    let active = CGDisplay::active_displays()
+        .map_err(|e| AppError::DisplayQueryFailed { detail: e.to_string() })?;
+    if !active.contains(&display_id) {
+        return Err(AppError::DisplayNotFound { display_id: display_id.to_string() });
+    }
+    let key = |mode: &CGDisplayMode| {
+        (mode.pixel_width(), mode.pixel_height(), mode.width(), mode.refresh_rate().round() as u32)
+    };
+    let current = CGDisplay::new(display_id).display_mode().map(|mode| key(&mode));
+    let modes = CGDisplayMode::all_display_modes(display_id, std::ptr::null()).ok_or_else(|| {
+        AppError::DisplayQueryFailed { detail: "CGDisplayCopyAllDisplayModes returned no modes".to_string() }
+    })?;
+
+    Ok(modes
+        .iter()
+        .map(|mode| DisplayMode {
+            width: mode.pixel_width() as u32,
+            height: mode.pixel_height() as u32,
+            refresh_rate: mode.refresh_rate().round() as u32,
+            scale_factor: backing_scale(mode.pixel_width(), mode.width()),
+            is_current: current == Some(key(mode)),
+        })
+        .collect())
+}
+
+pub fn apply_display_settings(displays: &[DisplayInfo]) -> Result<DisplayApplied, AppError> {
+    // 모니터가 지원하지 않는 주사율은 displayplacer가 모드를 찾지 못하므로 가장 가까운 주사율로 바꿉니다
+    let mut substituted = Vec::new();
+    let displays: Vec<DisplayInfo> = displays
+        .iter()
+        .map(|display| {
+            let mut display = display.clone();
+            if display.refresh_rate > 0 {
+                let available = supported_refresh_rates(&display);
+                if let Some(rate) = closest_refresh_rate(display.refresh_rate, &available) {
+                    if rate != display.refresh_rate {
+                        log::warn!(
+                            "{}: {}Hz를 지원하지 않아 {}Hz로 적용합니다",
+                            display.name, display.refresh_rate, rate
+                        );
+                        substituted.push((display.name.clone(), display.refresh_rate, rate));
+                        display.refresh_rate = rate;
+                    }
+                }
+            }
+            display
+        })
+        .collect();
+
+    // displayplacer를 사용해서 디스플레이 설정 적용
+    let args = displayplacer_args(&displays);
+    let (mut command, program, custom) = templated_command("display.apply", &[("args", &args)])?;
+
+    match run_command(&mut command) {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(DisplayApplied {
+                    backend: template_backend(&program, custom) + &refresh_rate_note(&substituted),
+                    brightness_skipped: apply_brightness(&displays),
+                })
+            } else {
+                Err(AppError::ToolFailed {
+                    detail: with_version_hint(&program, failure_detail(&output)),
+                    tool: program,
+                })
+            }
+        }
+        Err(e) => {
+            Err(AppError::tool_missing(&program, &e))
+        }
+    }
+}
+
+// displayplacer로 배치를 바꾼 뒤 밝기 적용 (바꾸지 못한 디스플레이 이름 반환)
+fn apply_brightness(displays: &[DisplayInfo]) -> Vec<String> {
+    let mut skipped = Vec::new();
+    for display in displays.iter().filter(|display| display.enabled) {
+        let Some(percent) = display.brightness else {
+            continue;
+        };
+        if !brightness::set_display_brightness(display.id, percent) {
+            log::warn!("{}: 밝기를 바꿀 수 없어 건너뜁니다 (DDC/CI를 지원하지 않거나 응답하지 않음)", display.name);
+            skipped.push(display.name.clone());
+        }
+    }
+    skipped
+}
+
+pub fn connected_display_serials() -> Result<Vec<String>, AppError> {
+    // CoreGraphics는 EDID의 숫자 일련번호를 제공합니다
+    let ids = CGDisplay::active_displays()
+        .map_err(|e| AppError::DisplayQueryFailed { detail: e.to_string() })?;
+    Ok(ids
+        .into_iter()
+        .map(|id| CGDisplay::new(id).serial_number())
+        .filter(|serial| *serial != 0)
+        .map(|serial| serial.to_string())
+        .collect())
+}
+
+// CoreGraphics가 EDID의 제조사 / 제품 코드 / 숫자 일련번호를 따로 알려 주므로 같은 형식의 식별값을 만들고,
+// 연결 경로는 프레임 버퍼마다 정해지는 논리 장치 번호를 사용합니다
+pub fn display_identities(displays: &[DisplayInfo]) -> Result<Vec<DisplayIdentity>, AppError> {
+    Ok(displays
+        .iter()
+        .map(|display| {
+            let cg = CGDisplay::new(display.id);
+            let serial = Some(cg.serial_number()).filter(|serial| *serial != 0).map(|serial| serial.to_string());
+            DisplayIdentity {
+                os_id: display.id,
+                fingerprint: edid::fingerprint_from_ids(
+                    cg.vendor_number() as u16,
+                    cg.model_number() as u16,
+                    serial.as_deref(),
+                ),
+                connector: format!("unit {}", cg.unit_number()),
+            }
+        })
+        .collect())
+}
+
+// 구성 변경이 시작될 때의 알림 (kCGDisplayBeginConfigurationFlag, 끝난 뒤의 알림만 사용)
+const BEGIN_CONFIGURATION_FLAG: u32 = 1;
+
+type ReconfigurationCallback =
+    unsafe extern "C" fn(display: CGDirectDisplayID, flags: u32, user_info: *mut c_void);
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGDisplayRegisterReconfigurationCallback(
+        callback: ReconfigurationCallback,
+        user_info: *mut c_void,
+    ) -> i32;
+}
+
+unsafe extern "C" fn display_reconfigured(_display: CGDirectDisplayID, flags: u32, user_info: *mut c_void) {
+    if flags & BEGIN_CONFIGURATION_FLAG == 0 {
+        let notifier = &*(user_info as *const ChangeNotifier);
+        notifier.changed();
+    }
+}
+
+// 연결/분리, 해상도, 배치, 미러링 변경을 CoreGraphics 콜백으로 감시
+// 콜백은 메인 스레드의 런 루프에서 호출되며, 앱이 끝날 때까지 등록을 유지합니다
+pub fn watch_display_changes(notifier: ChangeNotifier) -> Result<String, AppError> {
+    let user_info = Box::into_raw(Box::new(notifier)) as *mut c_void;
+    let result = unsafe { CGDisplayRegisterReconfigurationCallback(display_reconfigured, user_info) };
+    if result != 0 {
+        drop(unsafe { Box::from_raw(user_info as *mut ChangeNotifier) });
+        return Err(AppError::DisplayQueryFailed {
+            detail: format!("CGDisplayRegisterReconfigurationCallback returned {}", result),
+        });
+    }
+    Ok("CGDisplayRegisterReconfigurationCallback".to_string())
+}
@@ -0,0 +1,248 @@
+// macOS 밝기: 내장 디스플레이(와 Apple 모니터)는 DisplayServices, 그 밖의 외장 모니터는 IOAVService I2C로 DDC/CI
+// 둘 다 비공개 API이므로 실행할 때 함수를 찾고, 찾지 못하면 밝기를 읽지 않습니다 (None)
+// IOAVService는 Apple Silicon에만 있어 Intel Mac의 외장 모니터는 밝기를 읽지 않습니다
+
+use core_foundation::base::{CFRelease, CFType, CFTypeRef, TCFType};
+use core_foundation::data::{CFData, CFDataRef};
+use core_foundation::dictionary::CFDictionaryRef;
+use core_foundation::string::{CFString, CFStringRef};
+use core_graphics::display::{CGDirectDisplayID, CGDisplay};
+use std::ffi::{c_char, c_void, CStr};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use super::{IOIteratorNext, IOObjectRelease, IOServiceGetMatchingServices, IOServiceMatching};
+use crate::display::brightness::ddc::{
+    parse_vcp_reply, vcp_get_request, vcp_set_request, DDC_DISPLAY_ADDRESS, DDC_HOST_ADDRESS, VCP_BRIGHTNESS,
+};
+use crate::display::brightness::{from_percent, to_percent, with_timeout, BRIGHTNESS_TIMEOUT};
+
+// dlopen의 RTLD_LAZY
+const RTLD_LAZY: i32 = 1;
+// 모니터가 DDC/CI 요청에 응답을 준비하는 시간 (MCCS 권장값 40ms)
+const DDC_REPLY_DELAY: Duration = Duration::from_millis(50);
+
+extern "C" {
+    fn dlopen(path: *const c_char, mode: i32) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+}
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IORegistryEntryCreateCFProperty(
+        entry: u32,
+        key: CFStringRef,
+        allocator: *const c_void,
+        options: u32,
+    ) -> CFTypeRef;
+}
+
+type IOAVServiceRef = *mut c_void;
+
+type GetBrightness = unsafe extern "C" fn(display: CGDirectDisplayID, brightness: *mut f32) -> i32;
+type SetBrightness = unsafe extern "C" fn(display: CGDirectDisplayID, brightness: f32) -> i32;
+type CreateService = unsafe extern "C" fn(allocator: *const c_void, service: u32) -> IOAVServiceRef;
+type CopyEdid = unsafe extern "C" fn(service: IOAVServiceRef, edid: *mut CFDataRef) -> i32;
+type ReadI2c = unsafe extern "C" fn(IOAVServiceRef, chip: u32, offset: u32, output: *mut c_void, size: u32) -> i32;
+type WriteI2c = unsafe extern "C" fn(IOAVServiceRef, chip: u32, address: u32, input: *const c_void, size: u32) -> i32;
+
+struct DisplayServices {
+    get: GetBrightness,
+    set: SetBrightness,
+}
+
+struct AvService {
+    create: CreateService,
+    copy_edid: CopyEdid,
+    read_i2c: ReadI2c,
+    write_i2c: WriteI2c,
+}
+
+// 프레임워크에서 함수 찾기 (프레임워크나 함수가 하나라도 없으면 None)
+unsafe fn lookup(framework: &CStr, symbols: &[&CStr]) -> Option<Vec<*mut c_void>> {
+    let handle = dlopen(framework.as_ptr(), RTLD_LAZY);
+    if handle.is_null() {
+        return None;
+    }
+    let found: Vec<*mut c_void> = symbols.iter().map(|symbol| dlsym(handle, symbol.as_ptr())).collect();
+    found.iter().all(|pointer| !pointer.is_null()).then_some(found)
+}
+
+fn display_services() -> Option<&'static DisplayServices> {
+    static SERVICES: OnceLock<Option<DisplayServices>> = OnceLock::new();
+    SERVICES
+        .get_or_init(|| unsafe {
+            let found = lookup(
+                c"/System/Library/PrivateFrameworks/DisplayServices.framework/DisplayServices",
+                &[c"DisplayServicesGetBrightness", c"DisplayServicesSetBrightness"],
+            )?;
+            Some(DisplayServices {
+                get: std::mem::transmute::<*mut c_void, GetBrightness>(found[0]),
+                set: std::mem::transmute::<*mut c_void, SetBrightness>(found[1]),
+            })
+        })
+        .as_ref()
+}
+
+fn av_service() -> Option<&'static AvService> {
+    static SERVICE: OnceLock<Option<AvService>> = OnceLock::new();
+    SERVICE
+        .get_or_init(|| unsafe {
+            let found = lookup(
+                c"/System/Library/Frameworks/IOKit.framework/IOKit",
+                &[
+                    c"IOAVServiceCreateWithService",
+                    c"IOAVServiceCopyEDID",
+                    c"IOAVServiceReadI2C",
+                    c"IOAVServiceWriteI2C",
+                ],
+            )?;
+            Some(AvService {
+                create: std::mem::transmute::<*mut c_void, CreateService>(found[0]),
+                copy_edid: std::mem::transmute::<*mut c_void, CopyEdid>(found[1]),
+                read_i2c: std::mem::transmute::<*mut c_void, ReadI2c>(found[2]),
+                write_i2c: std::mem::transmute::<*mut c_void, WriteI2c>(found[3]),
+            })
+        })
+        .as_ref()
+}
+
+// 밝기 (0–100), 지원하지 않거나 제한 시간 안에 응답하지 않으면 None
+pub fn display_brightness(display_id: CGDirectDisplayID) -> Option<u32> {
+    with_timeout(&format!("display {}", display_id), BRIGHTNESS_TIMEOUT, move || {
+        let mut level = 0f32;
+        if let Some(services) = display_services() {
+            if unsafe { (services.get)(display_id, &mut level) } == 0 {
+                return Some((level.clamp(0.0, 1.0) * 100.0).round() as u32);
+            }
+        }
+        if CGDisplay::new(display_id).is_builtin() {
+            return None;
+        }
+        let (current, max) = with_external_service(display_id, ddc_read)??;
+        to_percent(current as u32, 0, max as u32)
+    })
+    .flatten()
+}
+
+// 밝기를 바꿨으면 true
+pub fn set_display_brightness(display_id: CGDirectDisplayID, percent: u32) -> bool {
+    with_timeout(&format!("display {}", display_id), BRIGHTNESS_TIMEOUT, move || {
+        if let Some(services) = display_services() {
+            if unsafe { (services.set)(display_id, percent.min(100) as f32 / 100.0) } == 0 {
+                return true;
+            }
+        }
+        if CGDisplay::new(display_id).is_builtin() {
+            return false;
+        }
+        with_external_service(display_id, |av, service| {
+            // 모니터마다 최댓값이 달라 먼저 읽습니다
+            let Some((_, max)) = ddc_read(av, service) else {
+                return false;
+            };
+            let value = from_percent(percent, 0, max as u32) as u16;
+            ddc_write(av, service, &vcp_set_request(VCP_BRIGHTNESS, value))
+        })
+        .unwrap_or(false)
+    })
+    .unwrap_or(false)
+}
+
+fn ddc_write(av: &AvService, service: IOAVServiceRef, message: &[u8]) -> bool {
+    let (chip, address) = (DDC_DISPLAY_ADDRESS as u32, DDC_HOST_ADDRESS as u32);
+    unsafe { (av.write_i2c)(service, chip, address, message.as_ptr() as *const c_void, message.len() as u32) == 0 }
+}
+
+// 밝기의 (현재 값, 최댓값)
+fn ddc_read(av: &AvService, service: IOAVServiceRef) -> Option<(u16, u16)> {
+    if !ddc_write(av, service, &vcp_get_request(VCP_BRIGHTNESS)) {
+        return None;
+    }
+    std::thread::sleep(DDC_REPLY_DELAY);
+    let mut reply = [0u8; 12];
+    let (chip, offset) = (DDC_DISPLAY_ADDRESS as u32, DDC_HOST_ADDRESS as u32);
+    let read = unsafe { (av.read_i2c)(service, chip, offset, reply.as_mut_ptr() as *mut c_void, reply.len() as u32) };
+    if read != 0 {
+        return None;
+    }
+    parse_vcp_reply(&reply, VCP_BRIGHTNESS)
+}
+
+// 디스플레이의 외장 IOAVService로 call 실행 (EDID의 제조사 / 제품 / 일련번호로 찾음)
+fn with_external_service<T>(
+    display_id: CGDirectDisplayID,
+    call: impl FnOnce(&AvService, IOAVServiceRef) -> T,
+) -> Option<T> {
+    let av = av_service()?;
+    let cg = CGDisplay::new(display_id);
+    let wanted = (cg.vendor_number(), cg.model_number(), cg.serial_number());
+
+    let mut matches: Vec<(IOAVServiceRef, bool)> = Vec::new();
+    unsafe {
+        // IOServiceGetMatchingServices가 matching의 참조를 가져갑니다
+        let matching = IOServiceMatching(c"DCPAVServiceProxy".as_ptr());
+        let mut iterator = 0;
+        if matching.is_null() || IOServiceGetMatchingServices(0, matching as CFDictionaryRef, &mut iterator) != 0 {
+            return None;
+        }
+        loop {
+            let entry = IOIteratorNext(iterator);
+            if entry == 0 {
+                break;
+            }
+            let external = location(entry).as_deref() == Some("External");
+            let service = if external { (av.create)(std::ptr::null(), entry) } else { std::ptr::null_mut() };
+            IOObjectRelease(entry);
+            if service.is_null() {
+                continue;
+            }
+            match edid_ids(av, service) {
+                Some((vendor, product, serial)) if (vendor, product) == (wanted.0, wanted.1) => {
+                    matches.push((service, serial == wanted.2));
+                }
+                _ => CFRelease(service as CFTypeRef),
+            }
+        }
+        IOObjectRelease(iterator);
+    }
+
+    // 일련번호까지 같은 서비스, 없으면 같은 모델이 하나뿐일 때만
+    let chosen = matches
+        .iter()
+        .position(|(_, same_serial)| *same_serial)
+        .or_else(|| (matches.len() == 1).then_some(0));
+    let result = chosen.map(|index| call(av, matches[index].0));
+    for (service, _) in matches {
+        unsafe { CFRelease(service as CFTypeRef) };
+    }
+    result
+}
+
+// DCPAVServiceProxy의 Location ("External" / "Embedded")
+unsafe fn location(entry: u32) -> Option<String> {
+    let key = CFString::new("Location");
+    let value = IORegistryEntryCreateCFProperty(entry, key.as_concrete_TypeRef(), std::ptr::null(), 0);
+    if value.is_null() {
+        return None;
+    }
+    CFType::wrap_under_create_rule(value).downcast::<CFString>().map(|location| location.to_string())
+}
+
+// EDID의 (제조사, 제품 코드, 숫자 일련번호), CoreGraphics의 vendor / model / serial과 같은 값
+unsafe fn edid_ids(av: &AvService, service: IOAVServiceRef) -> Option<(u32, u32, u32)> {
+    let mut edid: CFDataRef = std::ptr::null();
+    if (av.copy_edid)(service, &mut edid) != 0 || edid.is_null() {
+        return None;
+    }
+    let edid = CFData::wrap_under_create_rule(edid);
+    let bytes = edid.bytes();
+    if bytes.len() < 16 {
+        return None;
+    }
+    Some((
+        u16::from_be_bytes([bytes[8], bytes[9]]) as u32,
+        u16::from_le_bytes([bytes[10], bytes[11]]) as u32,
+        u32::from_le_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]),
+    ))
+}
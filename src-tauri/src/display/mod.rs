@@ -0,0 +1,696 @@
+// 디스플레이 목록 / 적용 / 비교
+// 플랫폼별 구현은 macos, windows, linux 모듈에 있습니다
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::locale::{current_locale, Locale};
+use crate::monitor::ChangeNotifier;
+use crate::profiles::CancellationToken;
+use self::registry::{fallback_identities, DisplayIdentity};
+
+pub mod brightness;
+pub mod edid;
+pub mod layout;
+pub mod registry;
+
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "macos")]
+use self::macos as platform;
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "windows")]
+use self::windows as platform;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod linux;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+use self::linux as platform;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DisplayInfo {
+    pub id: u32,
+    pub name: String,
+    // 물리 해상도 (픽셀)
+    pub width: u32,
+    pub height: u32,
+    // 논리 해상도 (포인트, 픽셀 / scale_factor)
+    // 이전 버전에서 저장한 프로필에는 없으므로 0이면 알 수 없음
+    #[serde(default)]
+    pub logical_width: u32,
+    #[serde(default)]
+    pub logical_height: u32,
+    // 주사율 (Hz), 이전 버전의 프로필에는 없으므로 0이면 적용하지 않음
+    #[serde(default)]
+    pub refresh_rate: u32,
+    pub x: i32,
+    pub y: i32,
+    pub scale_factor: f64,
+    pub is_primary: bool,
+    // 회전을 저장하지 않던 프로필은 0
+    #[serde(default)]
+    pub rotation: u32,
+    // 미러링 원본 디스플레이 id (미러링하지 않으면 None)
+    #[serde(default)]
+    pub mirror_of: Option<u32>,
+    // 다시 연결해도 같은 EDID 식별값 (없으면 연결 경로), 목록을 읽을 때 안정 id 등록부가 채웁니다
+    #[serde(default)]
+    pub persistent_id: String,
+    // EDID 식별값 (제조사, 제품, 일련번호), 저장된 디스플레이를 id보다 먼저 이것으로 찾습니다
+    // EDID를 읽지 못한 디스플레이와 이전 버전의 프로필은 None (id로 찾음)
+    #[serde(default)]
+    pub stable_id: Option<String>,
+    // 꺼진 디스플레이 (예: 노트북 화면을 끄고 외부 모니터만), 이전 버전의 프로필은 켜짐
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    // 밝기 (0–100), 읽을 수 없는 모니터와 이전 버전의 프로필은 None (적용하지 않음)
+    #[serde(default)]
+    pub brightness: Option<u32>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+// 모니터가 지원하는 모드 하나 (width / height는 픽셀)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DisplayMode {
+    pub width: u32,
+    pub height: u32,
+    // Hz, 모르면 0
+    pub refresh_rate: u32,
+    pub scale_factor: f64,
+    pub is_current: bool,
+}
+
+// 데스크톱 전체를 미러링할지 확장할지 (set_display_mode)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DesktopMode {
+    Mirror,
+    Extend,
+}
+
+impl DesktopMode {
+    pub fn parse(mode: &str) -> Result<Self, AppError> {
+        match mode {
+            "mirror" => Ok(DesktopMode::Mirror),
+            "extend" => Ok(DesktopMode::Extend),
+            _ => Err(AppError::InvalidDesktopMode { mode: mode.to_string() }),
+        }
+    }
+}
+
+// 디스플레이 설정을 적용한 결과
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DisplayApplied {
+    // 사용한 백엔드 이름
+    pub backend: String,
+    // 밝기를 바꾸지 못해 건너뛴 디스플레이 이름 (배치는 적용됨)
+    pub brightness_skipped: Vec<String>,
+}
+
+// 디스플레이 조회/적용 백엔드 (실제 OS 또는 테스트용 mock)
+pub trait DisplayBackend: Send + Sync {
+    // 연결된 디스플레이 목록
+    fn enumerate(&self) -> Result<Vec<DisplayInfo>, AppError>;
+    // 디스플레이 설정 적용 (사용한 백엔드 이름 반환)
+    fn apply(&self, displays: &[DisplayInfo]) -> Result<String, AppError>;
+    // 적용하고 밝기를 바꾸지 못한 디스플레이도 함께 반환 (프로필 적용 보고서의 경고로 알림, 기본은 건너뜀 없음)
+    fn apply_reporting(&self, displays: &[DisplayInfo]) -> Result<DisplayApplied, AppError> {
+        self.apply(displays).map(|backend| DisplayApplied { backend, brightness_skipped: Vec::new() })
+    }
+    // 연결된 디스플레이의 EDID 일련번호 목록
+    // EDID에는 숫자 일련번호와 문자열 일련번호가 따로 있을 수 있어 둘 다 포함합니다
+    fn connected_serials(&self) -> Result<Vec<String>, AppError>;
+    // 연결된 모니터의 EDID 원본 (fixture 기록용, 읽을 수 없는 플랫폼은 빈 목록)
+    fn raw_edids(&self) -> Result<Vec<Vec<u8>>, AppError> {
+        Ok(Vec::new())
+    }
+    // 디스플레이별 EDID 식별값과 연결 경로 (안정 id 등록에 사용)
+    fn identities(&self, displays: &[DisplayInfo]) -> Result<Vec<DisplayIdentity>, AppError> {
+        Ok(fallback_identities(displays))
+    }
+    // 목록의 id를 OS가 쓰는 id로 바꾼 디스플레이 (스크립트 내보내기처럼 OS 도구에 직접 넘길 때)
+    fn native_displays(&self, displays: &[DisplayInfo]) -> Result<Vec<DisplayInfo>, AppError> {
+        Ok(displays.to_vec())
+    }
+    // 디스플레이가 지원하는 모드 (연결되지 않은 id는 DisplayNotFound)
+    fn modes(&self, display_id: u32) -> Result<Vec<DisplayMode>, AppError> {
+        let _ = display_id;
+        Err(AppError::UnsupportedPlatform { feature: "display mode list".to_string() })
+    }
+    // OS가 미러링 / 확장 전환을 직접 지원하면 전환하고 사용한 방법을 반환
+    // None이면 호출한 쪽이 배치를 계산해 apply로 적용합니다
+    fn set_desktop_mode(&self, mode: DesktopMode) -> Result<Option<String>, AppError> {
+        let _ = mode;
+        Ok(None)
+    }
+    // 적용에 필요한 외부 도구 (적용하기 전에 설치 여부를 확인, 기본은 없음)
+    fn required_tools(&self) -> Vec<String> {
+        Vec::new()
+    }
+    // 목록을 읽는 방법 (get_system_info에 표시)
+    fn name(&self) -> String;
+    // 구성이 바뀔 때 notifier로 알리는 감시 시작 (알린 방법 반환)
+    // 지원하지 않으면 오류를 반환하고, 모니터가 주기적으로 다시 읽습니다
+    fn watch(&self, notifier: ChangeNotifier) -> Result<String, AppError> {
+        drop(notifier);
+        Err(AppError::UnsupportedPlatform { feature: "display change notifications".to_string() })
+    }
+}
+
+// 현재 OS의 디스플레이 백엔드
+pub struct SystemDisplayBackend;
+
+impl DisplayBackend for SystemDisplayBackend {
+    fn enumerate(&self) -> Result<Vec<DisplayInfo>, AppError> {
+        platform::list_displays()
+    }
+
+    fn apply(&self, displays: &[DisplayInfo]) -> Result<String, AppError> {
+        self.apply_reporting(displays).map(|applied| applied.backend)
+    }
+
+    fn apply_reporting(&self, displays: &[DisplayInfo]) -> Result<DisplayApplied, AppError> {
+        platform::apply_display_settings(displays)
+    }
+
+    fn connected_serials(&self) -> Result<Vec<String>, AppError> {
+        platform::connected_display_serials()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn raw_edids(&self) -> Result<Vec<Vec<u8>>, AppError> {
+        platform::raw_edids()
+    }
+
+    fn identities(&self, displays: &[DisplayInfo]) -> Result<Vec<DisplayIdentity>, AppError> {
+        platform::display_identities(displays)
+    }
+
+    fn modes(&self, display_id: u32) -> Result<Vec<DisplayMode>, AppError> {
+        platform::display_modes(display_id).map(normalize_modes)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn set_desktop_mode(&self, mode: DesktopMode) -> Result<Option<String>, AppError> {
+        platform::set_desktop_topology(mode).map(Some)
+    }
+
+    fn required_tools(&self) -> Vec<String> {
+        platform::apply_tools()
+    }
+
+    fn name(&self) -> String {
+        platform::backend_name()
+    }
+
+    fn watch(&self, notifier: ChangeNotifier) -> Result<String, AppError> {
+        platform::watch_display_changes(notifier)
+    }
+}
+
+// 배율 100%에 해당하는 DPI (Windows)
+pub const BASE_DPI: u32 = 96;
+
+// 모니터의 유효 DPI → 배율 (120 → 1.25, 144 → 1.5), 읽지 못한 값(0)은 1.0
+pub fn scale_from_dpi(dpi: u32) -> f64 {
+    if dpi == 0 {
+        return 1.0;
+    }
+    dpi as f64 / BASE_DPI as f64
+}
+
+// 픽셀 크기 / 포인트 크기 (Retina는 보통 2.0, 일반 외장 모니터는 1.0), 알 수 없으면 1.0
+pub fn backing_scale(pixels: u64, points: u64) -> f64 {
+    if pixels == 0 || points == 0 {
+        return 1.0;
+    }
+    pixels as f64 / points as f64
+}
+
+// 배율을 적용한 논리 크기 (반올림)
+pub fn logical_size(pixels: u32, scale_factor: f64) -> u32 {
+    if scale_factor <= 0.0 {
+        return pixels;
+    }
+    (pixels as f64 / scale_factor).round() as u32
+}
+
+// 회전 각도를 가장 가까운 90도 단위로 (0/90/180/270, 음수는 반대 방향)
+pub fn snap_rotation(degrees: f64) -> u32 {
+    if !degrees.is_finite() {
+        return 0;
+    }
+    ((degrees / 90.0).round() as i64).rem_euclid(4) as u32 * 90
+}
+
+// 같은 모드는 하나로 합치고 해상도가 큰 순서로 (같은 해상도는 주사율, 배율이 높은 순)
+pub fn normalize_modes(modes: Vec<DisplayMode>) -> Vec<DisplayMode> {
+    let mut unique: Vec<DisplayMode> = Vec::new();
+    for mode in modes {
+        let same = |other: &&mut DisplayMode| {
+            (other.width, other.height, other.refresh_rate) == (mode.width, mode.height, mode.refresh_rate)
+                && other.scale_factor == mode.scale_factor
+        };
+        match unique.iter_mut().find(same) {
+            Some(existing) => existing.is_current |= mode.is_current,
+            None => unique.push(mode),
+        }
+    }
+    unique.sort_by(|a, b| {
+        let key = |mode: &DisplayMode| (mode.width as u64 * mode.height as u64, mode.width, mode.refresh_rate);
+        key(b).cmp(&key(a)).then(b.scale_factor.total_cmp(&a.scale_factor))
+    });
+    unique
+}
+
+// 오류 메시지에 쓰는 모드 이름 (예: "1920x1080@144")
+pub fn mode_label(display: &DisplayInfo) -> String {
+    match display.refresh_rate {
+        0 => format!("{}x{}", display.width, display.height),
+        rate => format!("{}x{}@{}", display.width, display.height, rate),
+    }
+}
+
+// 적용 전 확인에 표시할 디스플레이 상태 (예: "2560x1440@60 (1920,0)", "1920x1080 (0,0) 90°", "off")
+pub fn display_state_label(display: &DisplayInfo) -> String {
+    if !display.enabled {
+        return "off".to_string();
+    }
+    let mut label = format!("{} ({},{})", mode_label(display), display.x, display.y);
+    if display.rotation != 0 {
+        label.push_str(&format!(" {}°", display.rotation));
+    }
+    label
+}
+
+// 프로필의 해상도를 지원하는 모드가 있는지 (회전한 디스플레이도 비교하도록 가로세로를 바꿔서도 확인)
+// HiDPI 모드는 픽셀 크기 대신 포인트 크기로 저장했을 수 있어 논리 크기도 비교합니다
+pub fn supports_resolution(display: &DisplayInfo, modes: &[DisplayMode]) -> bool {
+    let wanted = [(display.width, display.height), (display.height, display.width)];
+    modes.iter().any(|mode| {
+        let logical = (logical_size(mode.width, mode.scale_factor), logical_size(mode.height, mode.scale_factor));
+        wanted.contains(&(mode.width, mode.height)) || wanted.contains(&logical)
+    })
+}
+
+// 적용하기 전에 모니터가 해상도를 지원하는지 확인 (도구의 알기 어려운 오류 대신 UnsupportedDisplayMode)
+// 모드 목록을 읽지 못하는 디스플레이는 확인하지 않고, 주사율은 적용할 때 가장 가까운 값으로 바꿉니다
+pub fn check_display_modes(backend: &dyn DisplayBackend, displays: &[DisplayInfo]) -> Result<(), AppError> {
+    for display in displays.iter().filter(|display| display.enabled) {
+        let modes = match backend.modes(display.id) {
+            Ok(modes) if !modes.is_empty() => modes,
+            Ok(_) => continue,
+            Err(e) => {
+                log::debug!("{}: 모드 목록을 읽지 못해 확인하지 않습니다: {}", display.name, e);
+                continue;
+            }
+        };
+        if !supports_resolution(display, &modes) {
+            return Err(AppError::UnsupportedDisplayMode { display: display.name.clone(), mode: mode_label(display) });
+        }
+    }
+    Ok(())
+}
+
+// 모니터가 지원하는 주사율 중 요청한 값에 가장 가까운 값 (같은 거리면 높은 쪽, 목록이 비면 None)
+pub fn closest_refresh_rate(requested: u32, available: &[u32]) -> Option<u32> {
+    available
+        .iter()
+        .copied()
+        .min_by_key(|rate| (rate.abs_diff(requested), std::cmp::Reverse(*rate)))
+}
+
+// 요청한 주사율을 지원하지 않아 다른 값으로 적용한 디스플레이 (백엔드 이름에 덧붙여 알림)
+pub fn refresh_rate_note(substituted: &[(String, u32, u32)]) -> String {
+    if substituted.is_empty() {
+        return String::new();
+    }
+    let items: Vec<String> = substituted
+        .iter()
+        .map(|(name, requested, actual)| format!("{} {}Hz → {}Hz", name, requested, actual))
+        .collect();
+    format!(" (지원하는 주사율로 적용: {})", items.join(", "))
+}
+
+// 디스플레이별 displayplacer 인자 (스크립트 내보내기에서도 사용)
+pub fn displayplacer_args(displays: &[DisplayInfo]) -> Vec<String> {
+    // 미러링은 원본의 인자 하나에 "id:원본+미러" 형식으로 묶습니다
+    let mirrors_of = |source: &DisplayInfo| -> Vec<u32> {
+        displays
+            .iter()
+            .filter(|d| d.enabled && d.id != source.id && d.mirror_of == Some(source.id))
+            .map(|d| d.id)
+            .collect()
+    };
+    let is_mirror = |display: &DisplayInfo| {
+        display
+            .mirror_of
+            .is_some_and(|source| source != display.id && displays.iter().any(|d| d.enabled && d.id == source))
+    };
+    displays
+        .iter()
+        .filter(|display| !(display.enabled && is_mirror(display)))
+        .map(|display| {
+            if !display.enabled {
+                return format!("id:{} enabled:false", display.id);
+            }
+            let id = std::iter::once(display.id)
+                .chain(mirrors_of(display))
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join("+");
+            // displayplacer의 res는 포인트 단위입니다 (논리 크기가 없는 이전 프로필은 width/height)
+            let (width, height) = if display.logical_width > 0 && display.logical_height > 0 {
+                (display.logical_width, display.logical_height)
+            } else {
+                (display.width, display.height)
+            };
+            // HiDPI 모드는 scaling:on으로 고릅니다 (배율이 없던 이전 프로필은 지정하지 않음)
+            let scaling = if display.scale_factor > 1.0 { " scaling:on" } else { "" };
+            let refresh = match display.refresh_rate {
+                0 => String::new(),
+                rate => format!(" hz:{}", rate),
+            };
+            format!(
+                "id:{} res:{}x{}{}{} origin:({},{}) degree:{}",
+                id,
+                width,
+                height,
+                refresh,
+                scaling,
+                display.x,
+                display.y,
+                display.rotation
+            )
+        })
+        .collect()
+}
+
+// 같은 미러링 그룹 안에서 배율이 다른 디스플레이 확인
+// 배율이 다르면 미러링된 화면의 글자가 흐리게 보일 수 있습니다
+pub fn check_mirror_scale_factors(displays: &[DisplayInfo]) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    for display in displays.iter().filter(|display| display.enabled) {
+        let Some(source_id) = display.mirror_of else {
+            continue;
+        };
+        let Some(source) = displays.iter().find(|d| d.id == source_id) else {
+            continue;
+        };
+
+        if (display.scale_factor - source.scale_factor).abs() > f64::EPSILON {
+            warnings.push(match current_locale() {
+                Locale::En => format!(
+                    "{} mirrors {} with a different scale factor ({} vs {}); mirrored text may look blurry",
+                    display.name, source.name, display.scale_factor, source.scale_factor
+                ),
+                Locale::Ko => format!(
+                    "{}이(가) 배율이 다른 {}을(를) 미러링합니다 ({} / {}). 미러링된 화면이 흐리게 보일 수 있습니다",
+                    display.name, source.name, display.scale_factor, source.scale_factor
+                ),
+            });
+        }
+    }
+
+    warnings
+}
+
+// 저장된 디스플레이와 같은 연결된 디스플레이 (stable_id가 같은 것, 같은 모니터가 여러 대면 id도 같은 것)
+// 어느 한쪽의 EDID를 읽지 못해 stable_id가 없을 때만 id로 찾습니다
+pub fn find_connected<'a>(stored: &DisplayInfo, connected: &'a [DisplayInfo]) -> Option<&'a DisplayInfo> {
+    if let Some(stable_id) = &stored.stable_id {
+        let same: Vec<&DisplayInfo> =
+            connected.iter().filter(|display| display.stable_id.as_ref() == Some(stable_id)).collect();
+        if !same.is_empty() {
+            return same.iter().find(|display| display.id == stored.id).or(same.first()).copied();
+        }
+    }
+    connected
+        .iter()
+        .find(|display| display.id == stored.id && (stored.stable_id.is_none() || display.stable_id.is_none()))
+}
+
+// 찾은 연결된 디스플레이의 id로 바꾼 저장된 디스플레이 (찾지 못한 디스플레이는 저장된 id 그대로)
+pub fn remap_connected_ids(stored: &[DisplayInfo], connected: &[DisplayInfo]) -> Vec<DisplayInfo> {
+    let id_map: Vec<(u32, u32)> = stored
+        .iter()
+        .filter_map(|display| Some((display.id, find_connected(display, connected)?.id)))
+        .collect();
+    let remap = |id: u32| id_map.iter().find(|(saved, _)| *saved == id).map_or(id, |(_, current)| *current);
+    stored
+        .iter()
+        .map(|display| {
+            let mut display = display.clone();
+            display.id = remap(display.id);
+            display.mirror_of = display.mirror_of.map(remap);
+            display
+        })
+        .collect()
+}
+
+// 저장된 디스플레이 구성과 현재 구성의 차이 (find_connected로 같은 디스플레이를 찾아 비교)
+pub fn diff_displays(expected: &[DisplayInfo], actual: &[DisplayInfo]) -> Vec<String> {
+    let mut drift = Vec::new();
+
+    for display in expected {
+        let current = find_connected(display, actual);
+        // 꺼 둔 디스플레이는 목록에 없거나 꺼져 있으면 일치 (Windows와 macOS는 꺼진 디스플레이를 나열하지 않음)
+        if !display.enabled {
+            if let Some(current) = current.filter(|current| current.enabled) {
+                drift.push(format!("{}: 꺼짐 → 켜짐", current.name));
+            }
+            continue;
+        }
+        let Some(current) = current else {
+            // Windows는 복제 중인 모니터를 원본과 하나로 나열하므로 원본이 있으면 일치로 봅니다
+            let mirrored = display.mirror_of.is_some_and(|source| actual.iter().any(|d| d.id == source && d.enabled));
+            if !(cfg!(target_os = "windows") && mirrored) {
+                drift.push(format!("{}: 연결되어 있지 않음", display.name));
+            }
+            continue;
+        };
+        if !current.enabled {
+            drift.push(format!("{}: 켜짐 → 꺼짐", display.name));
+            continue;
+        }
+
+        // 밝기는 모니터 버튼으로도 바꾸고 바뀌기까지 시간이 걸리는 모니터도 있어 비교하지 않습니다
+        if (display.width, display.height) != (current.width, current.height) {
+            drift.push(format!(
+                "{}: 해상도 {}x{} → {}x{}",
+                display.name, display.width, display.height, current.width, current.height
+            ));
+        }
+        // 주사율을 모르는 쪽이 있으면 비교하지 않습니다
+        if display.refresh_rate > 0 && current.refresh_rate > 0 && display.refresh_rate != current.refresh_rate {
+            drift.push(format!(
+                "{}: 주사율 {}Hz → {}Hz",
+                display.name, display.refresh_rate, current.refresh_rate
+            ));
+        }
+        if (display.x, display.y) != (current.x, current.y) {
+            drift.push(format!(
+                "{}: 위치 ({},{}) → ({},{})",
+                display.name, display.x, display.y, current.x, current.y
+            ));
+        }
+        if display.rotation != current.rotation {
+            drift.push(format!(
+                "{}: 회전 {}° → {}°",
+                display.name, display.rotation, current.rotation
+            ));
+        }
+        if display.mirror_of != current.mirror_of {
+            let label = |source: Option<u32>| match source.and_then(|id| actual.iter().find(|d| d.id == id)) {
+                Some(source) => source.name.clone(),
+                None => "없음".to_string(),
+            };
+            drift.push(format!(
+                "{}: 미러링 {} → {}",
+                display.name,
+                label(display.mirror_of),
+                label(current.mirror_of)
+            ));
+        }
+    }
+
+    for current in actual.iter().filter(|current| current.enabled) {
+        if !expected.iter().any(|d| d.id == current.id) {
+            drift.push(format!("{}: 추가로 연결됨", current.name));
+        }
+    }
+
+    drift
+}
+
+// 디스플레이 구성이 안정될 때까지 대기
+// 로그인 직후나 도킹 직후에는 디스플레이가 순차적으로 연결되므로
+// 연속 두 번 같은 구성이 조회될 때까지 기다립니다 (최대 timeout, 취소되면 바로 반환)
+pub async fn wait_for_stable_displays(
+    backend: &dyn DisplayBackend,
+    timeout: std::time::Duration,
+    cancel: &CancellationToken,
+) -> Result<Vec<DisplayInfo>, AppError> {
+    let interval = std::time::Duration::from_secs(1);
+    let started = std::time::Instant::now();
+    let mut previous = backend.enumerate()?;
+
+    loop {
+        tokio::time::sleep(interval).await;
+        let current = backend.enumerate()?;
+        if cancel.is_cancelled() || diff_displays(&previous, &current).is_empty() {
+            return Ok(current);
+        }
+        if started.elapsed() >= timeout {
+            log::warn!("디스플레이 구성이 {:?} 안에 안정되지 않았습니다", timeout);
+            return Ok(current);
+        }
+        previous = current;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_dpi_and_pixel_ratios_to_scale_factors() {
+        assert_eq!(scale_from_dpi(96), 1.0);
+        assert_eq!(scale_from_dpi(120), 1.25);
+        assert_eq!(scale_from_dpi(144), 1.5);
+        assert_eq!(scale_from_dpi(0), 1.0);
+
+        assert_eq!(backing_scale(2880, 1440), 2.0);
+        assert_eq!(backing_scale(2560, 2560), 1.0);
+        assert_eq!(backing_scale(2880, 0), 1.0);
+
+        // Surface 150%: 2736x1824 → 1824x1216
+        assert_eq!((logical_size(2736, 1.5), logical_size(1824, 1.5)), (1824, 1216));
+        assert_eq!(logical_size(1920, 1.25), 1536);
+        assert_eq!(logical_size(1920, 0.0), 1920);
+    }
+
+    #[test]
+    fn applies_refresh_rate_only_when_saved() {
+        // refresh_rate가 없는 이전 버전의 프로필
+        let mut display: DisplayInfo = serde_json::from_value(serde_json::json!({
+            "id": 1, "name": "DELL", "width": 2560, "height": 1440, "x": 0, "y": 0,
+            "scale_factor": 1.0, "is_primary": true, "rotation": 0
+        }))
+        .unwrap();
+        assert_eq!(display.refresh_rate, 0);
+        assert_eq!(displayplacer_args(&[display.clone()]), vec!["id:1 res:2560x1440 origin:(0,0) degree:0"]);
+
+        display.refresh_rate = 144;
+        assert_eq!(displayplacer_args(&[display.clone()]), vec!["id:1 res:2560x1440 hz:144 origin:(0,0) degree:0"]);
+
+        let mut current = display.clone();
+        current.refresh_rate = 60;
+        assert_eq!(diff_displays(&[display.clone()], &[current.clone()]), vec!["DELL: 주사율 144Hz → 60Hz"]);
+        current.refresh_rate = 0;
+        assert!(diff_displays(&[display], &[current]).is_empty());
+    }
+
+    #[test]
+    fn disabled_displays_are_turned_off_and_compared() {
+        // enabled가 없는 이전 버전의 프로필은 켜진 디스플레이
+        let mut display: DisplayInfo = serde_json::from_value(serde_json::json!({
+            "id": 1, "name": "DELL", "width": 2560, "height": 1440, "x": 0, "y": 0,
+            "scale_factor": 1.0, "is_primary": true, "rotation": 0
+        }))
+        .unwrap();
+        assert!(display.enabled);
+
+        let current = display.clone();
+        display.enabled = false;
+        assert_eq!(displayplacer_args(&[display.clone()]), vec!["id:1 enabled:false"]);
+        assert_eq!(diff_displays(&[display.clone()], &[current]), vec!["DELL: 꺼짐 → 켜짐"]);
+        // 꺼 두기로 한 디스플레이는 연결되지 않았거나 꺼져 있으면 일치합니다
+        assert!(diff_displays(&[display.clone()], &[]).is_empty());
+        assert!(diff_displays(&[display.clone()], &[display]).is_empty());
+    }
+
+    #[test]
+    fn groups_mirrors_for_displayplacer() {
+        let display: DisplayInfo = serde_json::from_value(serde_json::json!({
+            "id": 1, "name": "DELL", "width": 1920, "height": 1080, "x": 0, "y": 0,
+            "scale_factor": 1.0, "is_primary": true, "rotation": 0
+        }))
+        .unwrap();
+        let mut mirror = display.clone();
+        mirror.id = 2;
+        mirror.mirror_of = Some(1);
+        assert_eq!(
+            displayplacer_args(&[display.clone(), mirror.clone()]),
+            vec!["id:1+2 res:1920x1080 origin:(0,0) degree:0"]
+        );
+        assert!(DesktopMode::parse("clone").is_err());
+
+        // 미러링 여부도 비교합니다
+        let mut extended = mirror.clone();
+        extended.mirror_of = None;
+        assert_eq!(
+            diff_displays(&[display.clone(), mirror.clone()], &[display.clone(), extended]),
+            vec!["DELL: 미러링 DELL → 없음"]
+        );
+        assert!(diff_displays(&[display.clone(), mirror.clone()], &[display.clone(), mirror.clone()]).is_empty());
+        // 복제 중인 모니터가 원본과 하나로 나열되는 것은 Windows에서만 일치로 봅니다
+        assert_eq!(diff_displays(&[display.clone(), mirror], &[display]).is_empty(), cfg!(target_os = "windows"));
+    }
+
+    fn mode(width: u32, height: u32, refresh_rate: u32, scale_factor: f64, is_current: bool) -> DisplayMode {
+        DisplayMode { width, height, refresh_rate, scale_factor, is_current }
+    }
+
+    #[test]
+    fn normalizes_modes_and_checks_resolutions() {
+        let modes = normalize_modes(vec![
+            mode(1920, 1080, 60, 1.0, false),
+            mode(2560, 1440, 60, 1.0, false),
+            mode(1920, 1080, 144, 1.0, false),
+            mode(1920, 1080, 60, 1.0, true),
+            mode(2880, 1800, 60, 2.0, false),
+        ]);
+        let sizes: Vec<(u32, u32, u32)> = modes.iter().map(|m| (m.width, m.height, m.refresh_rate)).collect();
+        assert_eq!(sizes, vec![(2880, 1800, 60), (2560, 1440, 60), (1920, 1080, 144), (1920, 1080, 60)]);
+        assert!(modes[3].is_current);
+
+        let mut display: DisplayInfo = serde_json::from_value(serde_json::json!({
+            "id": 2, "name": "Display 2", "width": 1080, "height": 1920, "x": 0, "y": 0,
+            "scale_factor": 1.0, "is_primary": false, "rotation": 90, "refresh_rate": 144
+        }))
+        .unwrap();
+        // 세로로 돌린 모니터와 HiDPI 모드의 포인트 크기
+        assert!(supports_resolution(&display, &modes));
+        (display.width, display.height) = (1440, 900);
+        assert!(supports_resolution(&display, &modes));
+        (display.width, display.height) = (1920, 1200);
+        assert!(!supports_resolution(&display, &modes));
+        assert_eq!(mode_label(&display), "1920x1200@144");
+    }
+
+    #[test]
+    fn picks_closest_supported_refresh_rate() {
+        assert_eq!(closest_refresh_rate(144, &[60, 120, 165]), Some(165));
+        assert_eq!(closest_refresh_rate(144, &[60, 120, 144]), Some(144));
+        assert_eq!(closest_refresh_rate(90, &[60, 120]), Some(120));
+        assert_eq!(closest_refresh_rate(60, &[]), None);
+        assert_eq!(
+            refresh_rate_note(&[("DELL".to_string(), 144, 120)]),
+            " (지원하는 주사율로 적용: DELL 144Hz → 120Hz)"
+        );
+    }
+
+    #[test]
+    fn snaps_rotation_to_cardinal_angles() {
+        assert_eq!(snap_rotation(0.0), 0);
+        assert_eq!(snap_rotation(90.0), 90);
+        assert_eq!(snap_rotation(269.4), 270);
+        assert_eq!(snap_rotation(359.0), 0);
+        assert_eq!(snap_rotation(-90.0), 270);
+        assert_eq!(snap_rotation(f64::NAN), 0);
+    }
+}
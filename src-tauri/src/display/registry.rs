@@ -0,0 +1,475 @@
+// 디스플레이 안정 id 등록부
+// Windows의 HMONITOR처럼 OS가 주는 id는 세션이나 재연결마다 바뀔 수 있으므로, 물리 디스플레이마다 앱 안의 id를 정해
+// display-registry.json에 저장하고 목록을 읽거나 적용할 때 OS id와 서로 바꿉니다 (프론트엔드와 프로필은 안정 id만 사용)
+// 디스플레이는 EDID 식별값(제조사, 제품 코드, 일련번호)으로 알아보고, 읽을 수 없으면 어댑터와 출력 경로로 알아봅니다
+// 일련번호가 없는 같은 모델 두 대처럼 식별값이 겹치면 연결 경로로 구분합니다
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use super::{DesktopMode, DisplayApplied, DisplayBackend, DisplayInfo, DisplayMode};
+use crate::error::AppError;
+use crate::monitor::ChangeNotifier;
+use crate::profiles::{write_atomic, Profile};
+
+// OS가 보고한 디스플레이 하나의 물리 식별 정보
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayIdentity {
+    pub os_id: u32,
+    // EDID 식별값 (edid::fingerprint 형식, 읽을 수 없으면 None)
+    pub fingerprint: Option<String>,
+    // 어댑터와 출력 경로 (예: HDMI-1, Windows 장치 인터페이스 이름)
+    pub connector: String,
+}
+
+// 식별 정보를 읽을 수 없는 백엔드는 OS id를 경로로 사용합니다 (OS가 id를 바꾸면 새 디스플레이로 봄)
+pub fn fallback_identities(displays: &[DisplayInfo]) -> Vec<DisplayIdentity> {
+    displays
+        .iter()
+        .map(|display| DisplayIdentity {
+            os_id: display.id,
+            fingerprint: None,
+            connector: display.id.to_string(),
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RegistryEntry {
+    pub id: u32,
+    pub fingerprint: Option<String>,
+    pub connector: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DisplayRegistry {
+    pub entries: Vec<RegistryEntry>,
+    // 이전 버전에서 OS id로 저장한 프로필을 안정 id로 바꿨는지
+    pub profiles_migrated: bool,
+}
+
+impl DisplayRegistry {
+    // 연결된 디스플레이마다 안정 id를 정해 (OS id, 안정 id) 목록과 등록부가 바뀌었는지 반환
+    // 같은 구성이면 OS가 나열한 순서와 관계없이 항상 같은 결과가 나오도록 식별값과 경로 순으로 처리합니다
+    pub fn resolve(&mut self, identities: &[DisplayIdentity]) -> (Vec<(u32, u32)>, bool) {
+        let mut order: Vec<&DisplayIdentity> = identities.iter().collect();
+        order.sort_by(|a, b| (&a.fingerprint, &a.connector).cmp(&(&b.fingerprint, &b.connector)));
+
+        let mut assigned: Vec<Option<u32>> = vec![None; order.len()];
+        let mut changed = false;
+
+        // 식별값과 경로가 모두 같은 항목
+        for (index, identity) in order.iter().enumerate() {
+            let entry = self.entries.iter().find(|entry| {
+                !assigned.contains(&Some(entry.id))
+                    && entry.fingerprint == identity.fingerprint
+                    && entry.connector == identity.connector
+            });
+            assigned[index] = entry.map(|entry| entry.id);
+        }
+
+        // 식별값이 겹치지 않는 디스플레이는 다른 포트로 옮겨도 같은 id (경로만 갱신)
+        for (index, identity) in order.iter().enumerate() {
+            if assigned[index].is_some() || identity.fingerprint.is_none() {
+                continue;
+            }
+            let same_now = order.iter().filter(|other| other.fingerprint == identity.fingerprint).count();
+            let candidates: Vec<usize> = (0..self.entries.len())
+                .filter(|&i| {
+                    self.entries[i].fingerprint == identity.fingerprint && !assigned.contains(&Some(self.entries[i].id))
+                })
+                .collect();
+            if let ([entry], 1) = (candidates.as_slice(), same_now) {
+                self.entries[*entry].connector = identity.connector.clone();
+                assigned[index] = Some(self.entries[*entry].id);
+                changed = true;
+            }
+        }
+
+        // 처음 보는 디스플레이
+        for (index, identity) in order.iter().enumerate() {
+            if assigned[index].is_some() {
+                continue;
+            }
+            let id = self.entries.iter().map(|entry| entry.id).max().unwrap_or(0) + 1;
+            self.entries.push(RegistryEntry {
+                id,
+                fingerprint: identity.fingerprint.clone(),
+                connector: identity.connector.clone(),
+            });
+            assigned[index] = Some(id);
+            changed = true;
+        }
+
+        let ids = order
+            .iter()
+            .zip(assigned)
+            .filter_map(|(identity, id)| Some((identity.os_id, id?)))
+            .collect();
+        (ids, changed)
+    }
+}
+
+fn lookup(ids: &[(u32, u32)], from: impl Fn(&(u32, u32)) -> u32, to: impl Fn(&(u32, u32)) -> u32, id: u32) -> Option<u32> {
+    ids.iter().find(|pair| from(pair) == id).map(to)
+}
+
+// OS 백엔드를 감싸 목록은 안정 id로 보여 주고, 적용할 때는 다시 OS id로 바꿔 넘깁니다
+pub struct StableDisplayBackend {
+    inner: Box<dyn DisplayBackend>,
+    path: PathBuf,
+    registry: Mutex<DisplayRegistry>,
+}
+
+impl StableDisplayBackend {
+    pub fn load(inner: impl DisplayBackend + 'static, path: PathBuf) -> Self {
+        let registry = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("{}", AppError::corrupt_file(&path, &e));
+                DisplayRegistry::default()
+            }),
+            Err(_) => DisplayRegistry::default(),
+        };
+        Self { inner: Box::new(inner), path, registry: Mutex::new(registry) }
+    }
+
+    // 연결된 디스플레이의 (OS id, 안정 id) (새 디스플레이는 등록하고 저장)
+    fn current_ids(&self, displays: &[DisplayInfo]) -> Vec<(u32, u32)> {
+        self.resolve_current(displays).0
+    }
+
+    // current_ids와 함께 식별 정보도 돌려줍니다 (persistent_id에 사용)
+    fn resolve_current(&self, displays: &[DisplayInfo]) -> (Vec<(u32, u32)>, Vec<DisplayIdentity>) {
+        let identities = self.inner.identities(displays).unwrap_or_else(|e| {
+            log::warn!("디스플레이 식별 정보를 읽지 못해 OS id로 구분합니다: {}", e);
+            fallback_identities(displays)
+        });
+        let mut registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        let (ids, changed) = registry.resolve(&identities);
+        if changed {
+            self.save(&registry);
+        }
+        (ids, identities)
+    }
+
+    fn save(&self, registry: &DisplayRegistry) {
+        let result = serde_json::to_string_pretty(registry)
+            .map_err(|e| AppError::Internal { detail: format!("Failed to serialize display registry: {}", e) })
+            .and_then(|content| write_atomic(&self.path, content.as_bytes()).map_err(|e| AppError::io(&self.path, &e)));
+        if let Err(e) = result {
+            log::warn!("디스플레이 등록부 저장 실패: {}", e);
+        }
+    }
+
+    pub fn profiles_migrated(&self) -> bool {
+        self.registry.lock().unwrap_or_else(|e| e.into_inner()).profiles_migrated
+    }
+
+    // 이전 버전에서 OS id로 저장한 프로필을 안정 id로 바꿈 (업데이트 후 처음 한 번, 바꾼 프로필이 있으면 true)
+    // 지금 연결되지 않은 디스플레이는 OS id를 알 수 없어 그대로 둡니다
+    pub fn migrate_profile_ids(&self, profiles: &mut [Profile]) -> Result<bool, AppError> {
+        if self.profiles_migrated() {
+            return Ok(false);
+        }
+        let ids = self.current_ids(&self.inner.enumerate()?);
+        let to_stable = |id: u32| lookup(&ids, |pair| pair.0, |pair| pair.1, id);
+
+        let mut migrated = false;
+        for display in profiles.iter_mut().flat_map(|profile| profile.displays.iter_mut()) {
+            match to_stable(display.id) {
+                Some(id) => {
+                    migrated |= id != display.id;
+                    display.id = id;
+                }
+                None => log::info!("{}: 연결되어 있지 않아 id를 바꾸지 않습니다 ({})", display.name, display.id),
+            }
+            display.mirror_of = display.mirror_of.map(|source| to_stable(source).unwrap_or(source));
+        }
+
+        let mut registry = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+        registry.profiles_migrated = true;
+        self.save(&registry);
+        Ok(migrated)
+    }
+}
+
+impl DisplayBackend for StableDisplayBackend {
+    fn enumerate(&self) -> Result<Vec<DisplayInfo>, AppError> {
+        let mut displays = self.inner.enumerate()?;
+        let (ids, identities) = self.resolve_current(&displays);
+        let to_stable = |id: u32| lookup(&ids, |pair| pair.0, |pair| pair.1, id).unwrap_or(id);
+        for display in displays.iter_mut() {
+            if let Some(identity) = identities.iter().find(|identity| identity.os_id == display.id) {
+                display.persistent_id = identity.fingerprint.clone().unwrap_or_else(|| identity.connector.clone());
+                display.stable_id = identity.fingerprint.clone();
+            }
+            display.id = to_stable(display.id);
+            display.mirror_of = display.mirror_of.map(to_stable);
+        }
+        Ok(displays)
+    }
+
+    fn apply(&self, displays: &[DisplayInfo]) -> Result<String, AppError> {
+        self.inner.apply(&self.native_displays(displays)?)
+    }
+
+    fn apply_reporting(&self, displays: &[DisplayInfo]) -> Result<DisplayApplied, AppError> {
+        self.inner.apply_reporting(&self.native_displays(displays)?)
+    }
+
+    fn native_displays(&self, displays: &[DisplayInfo]) -> Result<Vec<DisplayInfo>, AppError> {
+        let (ids, identities) = self.resolve_current(&self.inner.enumerate()?);
+        let to_os = |id: u32| {
+            lookup(&ids, |pair| pair.1, |pair| pair.0, id).ok_or_else(|| AppError::ToolFailed {
+                tool: self.inner.name(),
+                detail: format!("Unable to find screen {}", id),
+            })
+        };
+        // stable_id가 있으면 EDID가 같은 모니터의 OS id를 먼저 찾습니다 (같은 모니터가 여러 대면 안정 id도 같은 것)
+        let by_stable_id = |display: &DisplayInfo| {
+            let stable_id = display.stable_id.as_ref()?;
+            let same: Vec<u32> = identities
+                .iter()
+                .filter(|identity| identity.fingerprint.as_ref() == Some(stable_id))
+                .map(|identity| identity.os_id)
+                .collect();
+            let own = lookup(&ids, |pair| pair.1, |pair| pair.0, display.id).filter(|os_id| same.contains(os_id));
+            own.or(same.first().copied())
+        };
+        // 꺼 둘 디스플레이가 목록에 없으면 이미 꺼졌거나 연결되지 않은 것이므로 넘기지 않습니다
+        displays
+            .iter()
+            .filter(|display| {
+                display.enabled
+                    || by_stable_id(display).is_some()
+                    || lookup(&ids, |pair| pair.1, |pair| pair.0, display.id).is_some()
+            })
+            .map(|display| {
+                let mut display = display.clone();
+                display.id = match by_stable_id(&display) {
+                    Some(os_id) => os_id,
+                    None => to_os(display.id)?,
+                };
+                display.mirror_of = display.mirror_of.map(to_os).transpose()?;
+                Ok(display)
+            })
+            .collect()
+    }
+
+    fn connected_serials(&self) -> Result<Vec<String>, AppError> {
+        self.inner.connected_serials()
+    }
+
+    fn raw_edids(&self) -> Result<Vec<Vec<u8>>, AppError> {
+        self.inner.raw_edids()
+    }
+
+    fn identities(&self, displays: &[DisplayInfo]) -> Result<Vec<DisplayIdentity>, AppError> {
+        self.inner.identities(displays)
+    }
+
+    fn modes(&self, display_id: u32) -> Result<Vec<DisplayMode>, AppError> {
+        let ids = self.current_ids(&self.inner.enumerate()?);
+        let os_id = lookup(&ids, |pair| pair.1, |pair| pair.0, display_id)
+            .ok_or_else(|| AppError::DisplayNotFound { display_id: display_id.to_string() })?;
+        self.inner.modes(os_id)
+    }
+
+    fn set_desktop_mode(&self, mode: DesktopMode) -> Result<Option<String>, AppError> {
+        self.inner.set_desktop_mode(mode)
+    }
+
+    fn required_tools(&self) -> Vec<String> {
+        self.inner.required_tools()
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn watch(&self, notifier: ChangeNotifier) -> Result<String, AppError> {
+        self.inner.watch(notifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::capture_audio_settings;
+    use crate::mock::{MockBackend, MockFixture, MockOperation};
+
+    fn identity(os_id: u32, fingerprint: Option<&str>, connector: &str) -> DisplayIdentity {
+        DisplayIdentity {
+            os_id,
+            fingerprint: fingerprint.map(str::to_string),
+            connector: connector.to_string(),
+        }
+    }
+
+    fn stable_id(ids: &[(u32, u32)], os_id: u32) -> u32 {
+        lookup(ids, |pair| pair.0, |pair| pair.1, os_id).unwrap()
+    }
+
+    #[test]
+    fn identical_serial_less_monitors_are_told_apart_by_connector() {
+        let mut registry = DisplayRegistry::default();
+        let (ids, changed) = registry.resolve(&[
+            identity(0x1001, Some("AOC2402"), "DP-2"),
+            identity(0x1002, Some("AOC2402"), "DP-1"),
+        ]);
+        assert!(changed);
+        // 나열 순서와 관계없이 경로 순으로 번호를 정합니다
+        assert_eq!((stable_id(&ids, 0x1002), stable_id(&ids, 0x1001)), (1, 2));
+
+        // 다음 세션에서 OS id가 바뀌고 나열 순서가 달라도 같은 포트의 모니터는 같은 id
+        let (ids, changed) = registry.resolve(&[
+            identity(0x2002, Some("AOC2402"), "DP-1"),
+            identity(0x2001, Some("AOC2402"), "DP-2"),
+        ]);
+        assert!(!changed);
+        assert_eq!((stable_id(&ids, 0x2002), stable_id(&ids, 0x2001)), (1, 2));
+
+        // 겹치는 모니터는 다른 포트로 옮기면 구분할 수 없으므로 새 id
+        let (ids, _) = registry.resolve(&[
+            identity(0x3001, Some("AOC2402"), "HDMI-1"),
+            identity(0x3002, Some("AOC2402"), "HDMI-2"),
+        ]);
+        assert_eq!((stable_id(&ids, 0x3001), stable_id(&ids, 0x3002)), (3, 4));
+    }
+
+    #[test]
+    fn unique_monitors_keep_their_id_on_another_port() {
+        let mut registry = DisplayRegistry::default();
+        let (ids, _) = registry.resolve(&[
+            identity(7, Some("GSM5B09-405NTABC1234"), "DP-1"),
+            identity(8, None, "eDP-1"),
+        ]);
+        let (dell, laptop) = (stable_id(&ids, 7), stable_id(&ids, 8));
+
+        let (ids, changed) = registry.resolve(&[
+            identity(3, None, "eDP-1"),
+            identity(4, Some("GSM5B09-405NTABC1234"), "HDMI-1"),
+        ]);
+        assert!(changed);
+        assert_eq!((stable_id(&ids, 4), stable_id(&ids, 3)), (dell, laptop));
+        assert_eq!(registry.entries.iter().find(|entry| entry.id == dell).unwrap().connector, "HDMI-1");
+    }
+
+    fn display(id: u32, x: i32, mirror_of: Option<u32>) -> DisplayInfo {
+        DisplayInfo {
+            id,
+            name: format!("Display {}", id),
+            width: 1920,
+            height: 1080,
+            logical_width: 1920,
+            logical_height: 1080,
+            refresh_rate: 0,
+            x,
+            y: 0,
+            scale_factor: 1.0,
+            is_primary: x == 0,
+            rotation: 0,
+            mirror_of,
+            persistent_id: String::new(),
+            stable_id: None,
+            enabled: true,
+            brightness: None,
+        }
+    }
+
+    #[test]
+    fn translates_ids_at_the_backend_boundary_and_migrates_profiles() {
+        let path = std::env::temp_dir().join(format!("dsm-display-registry-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mock = MockBackend::new(MockFixture {
+            displays: vec![display(0x42, 0, None), display(0x43, 1920, Some(0x42))],
+            ..Default::default()
+        });
+        let backend = StableDisplayBackend::load(mock.clone(), path.clone());
+
+        let mut profiles = vec![Profile {
+            id: "desk".to_string(),
+            name: "Desk".to_string(),
+            displays: vec![display(0x43, 1920, Some(0x42)), display(0x99, 3840, None)],
+            audio_settings: Some(capture_audio_settings(&[])),
+            created_at: String::new(),
+            tags: Vec::new(),
+            color: None,
+            hotkey: None,
+            pre_apply_command: None,
+            post_apply_command: None,
+            auto_apply_on_device: None,
+            output_follows_display: false,
+        }];
+        assert!(backend.migrate_profile_ids(&mut profiles).unwrap());
+        let ids: Vec<(u32, Option<u32>)> = profiles[0].displays.iter().map(|d| (d.id, d.mirror_of)).collect();
+        assert_eq!(ids, vec![(2, Some(1)), (0x99, None)]);
+        assert!(!backend.migrate_profile_ids(&mut profiles).unwrap());
+
+        let listed: Vec<(u32, Option<u32>)> = backend.enumerate().unwrap().iter().map(|d| (d.id, d.mirror_of)).collect();
+        assert_eq!(listed, vec![(1, None), (2, Some(1))]);
+        backend.apply(&[display(2, 0, None)]).unwrap();
+        assert!(matches!(&mock.operations()[..], [MockOperation::Displays(applied)] if applied[0].id == 0x43));
+        assert!(backend.apply(&[display(5, 0, None)]).is_err());
+
+        // 다시 불러와도 같은 id
+        let reloaded = StableDisplayBackend::load(mock, path.clone());
+        assert!(reloaded.profiles_migrated());
+        assert_eq!(reloaded.enumerate().unwrap()[1].id, 2);
+        // 식별값을 읽지 못하는 백엔드는 연결 경로(OS id)를 persistent_id로
+        assert_eq!(reloaded.enumerate().unwrap()[1].persistent_id, "67");
+        assert_eq!(reloaded.enumerate().unwrap()[1].stable_id, None);
+        let _ = fs::remove_file(&path);
+    }
+
+    // 디스플레이마다 EDID 식별값을 돌려주는 mock
+    struct EdidMock(MockBackend);
+
+    impl DisplayBackend for EdidMock {
+        fn enumerate(&self) -> Result<Vec<DisplayInfo>, AppError> {
+            self.0.enumerate()
+        }
+
+        fn apply(&self, displays: &[DisplayInfo]) -> Result<String, AppError> {
+            self.0.apply(displays)
+        }
+
+        fn connected_serials(&self) -> Result<Vec<String>, AppError> {
+            self.0.connected_serials()
+        }
+
+        fn identities(&self, displays: &[DisplayInfo]) -> Result<Vec<DisplayIdentity>, AppError> {
+            Ok(displays
+                .iter()
+                .map(|display| identity(display.id, Some(&format!("EDID-{}", display.id)), &display.id.to_string()))
+                .collect())
+        }
+
+        fn name(&self) -> String {
+            self.0.name()
+        }
+    }
+
+    #[test]
+    fn fills_stable_id_from_edid_and_applies_by_it() {
+        let path = std::env::temp_dir().join(format!("dsm-display-registry-edid-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mock = MockBackend::new(MockFixture {
+            displays: vec![display(0x42, 0, None), display(0x43, 1920, None)],
+            ..Default::default()
+        });
+        let backend = StableDisplayBackend::load(EdidMock(mock.clone()), path.clone());
+
+        let listed = backend.enumerate().unwrap();
+        assert_eq!(listed[1].stable_id.as_deref(), Some("EDID-67"));
+
+        // 저장된 id가 맞지 않아도 EDID 식별값이 같은 모니터에 적용합니다
+        let saved = DisplayInfo { id: 9, stable_id: Some("EDID-67".to_string()), ..listed[1].clone() };
+        backend.apply(&[saved]).unwrap();
+        assert!(matches!(&mock.operations()[..], [MockOperation::Displays(applied)] if applied[0].id == 0x43));
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,823 @@
+// Windows: EnumDisplayMonitors로 목록을 읽고 ChangeDisplaySettingsExW로 적용
+
+use std::ffi::c_void;
+use std::mem;
+use std::sync::OnceLock;
+use windows::core::{w, BOOL, PCWSTR};
+use windows::Win32::Devices::Display::{
+    DestroyPhysicalMonitors, DisplayConfigGetDeviceInfo, GetDisplayConfigBufferSizes, GetMonitorBrightness,
+    GetNumberOfPhysicalMonitorsFromHMONITOR, GetPhysicalMonitorsFromHMONITOR, QueryDisplayConfig, SetDisplayConfig,
+    SetMonitorBrightness, PHYSICAL_MONITOR,
+    DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME, DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME,
+    DISPLAYCONFIG_DEVICE_INFO_HEADER, DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_PATH_INFO, DISPLAYCONFIG_SOURCE_DEVICE_NAME,
+    DISPLAYCONFIG_TARGET_DEVICE_NAME, QDC_ONLY_ACTIVE_PATHS, SDC_ALLOW_CHANGES, SDC_APPLY, SDC_SAVE_TO_DATABASE,
+    SDC_TOPOLOGY_CLONE, SDC_TOPOLOGY_EXTEND, SDC_USE_SUPPLIED_DISPLAY_CONFIG,
+};
+use windows::Win32::Foundation::{ERROR_SUCCESS, WIN32_ERROR, HINSTANCE, HWND, LPARAM, LRESULT, POINTL, RECT, WPARAM};
+use windows::Win32::Graphics::Gdi::{
+    ChangeDisplaySettingsExW, EnumDisplayDevicesW, EnumDisplayMonitors, EnumDisplaySettingsW, GetMonitorInfoW,
+    CDS_NORESET, CDS_SET_PRIMARY, CDS_TYPE, CDS_UPDATEREGISTRY, DEVMODEW, DISPLAY_DEVICEW, DISP_CHANGE,
+    DISP_CHANGE_BADDUALVIEW, DISP_CHANGE_BADFLAGS, DISP_CHANGE_BADMODE, DISP_CHANGE_BADPARAM,
+    DISP_CHANGE_NOTUPDATED, DISP_CHANGE_RESTART, DISP_CHANGE_SUCCESSFUL, DMDO_180, DMDO_270, DMDO_90,
+    DMDO_DEFAULT, DM_DISPLAYFREQUENCY, DM_DISPLAYORIENTATION, DM_PELSHEIGHT, DM_PELSWIDTH, DM_POSITION,
+    ENUM_CURRENT_SETTINGS, ENUM_DISPLAY_SETTINGS_MODE, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW,
+    MONITORINFOF_PRIMARY,
+};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW, TranslateMessage, MSG,
+    WINDOW_EX_STYLE, WM_DEVICECHANGE, WM_DISPLAYCHANGE, WNDCLASSW, WS_OVERLAPPED,
+};
+
+use super::brightness::{from_percent, to_percent, with_timeout, BRIGHTNESS_TIMEOUT};
+use super::registry::DisplayIdentity;
+use super::{
+    closest_refresh_rate, logical_size, refresh_rate_note, scale_from_dpi, DesktopMode, DisplayApplied, DisplayInfo,
+    DisplayMode,
+};
+use crate::error::AppError;
+use crate::monitor::ChangeNotifier;
+use crate::shell::powershell;
+
+unsafe extern "system" fn monitor_enum_proc(
+    hmonitor: HMONITOR,
+    _hdc: HDC,
+    _lprect: *mut RECT,
+    lparam: LPARAM,
+) -> BOOL {
+    let displays = &mut *(lparam.0 as *mut Vec<DisplayInfo>);
+
+    // 적용할 때 쓰는 GDI 장치 이름(szDevice)도 함께 읽습니다
+    let mut monitor_info_ex = MONITORINFOEXW::default();
+    monitor_info_ex.monitorInfo.cbSize = mem::size_of::<MONITORINFOEXW>() as u32;
+
+    if GetMonitorInfoW(hmonitor, &mut monitor_info_ex as *mut MONITORINFOEXW as *mut MONITORINFO).as_bool() {
+        let monitor_info = monitor_info_ex.monitorInfo;
+        let rect = monitor_info.rcMonitor;
+        let is_primary = monitor_info.dwFlags & MONITORINFOF_PRIMARY != 0;
+        let (width, height) = ((rect.right - rect.left) as u32, (rect.bottom - rect.top) as u32);
+        let scale_factor = monitor_scale_factor(hmonitor);
+        let mode = current_mode(&wide_to_string(&monitor_info_ex.szDevice));
+
+        displays.push(DisplayInfo {
+            // HMONITOR 값을 그대로 id로 사용합니다 (winapi 시절과 같은 값)
+            id: hmonitor.0 as usize as u32,
+            name: format!("Display {}", displays.len() + 1),
+            width,
+            height,
+            logical_width: logical_size(width, scale_factor),
+            logical_height: logical_size(height, scale_factor),
+            // 0과 1은 하드웨어 기본값이라는 뜻이므로 알 수 없음(0)으로
+            refresh_rate: mode.as_ref().map(|mode| mode.dmDisplayFrequency).filter(|hz| *hz > 1).unwrap_or(0),
+            x: rect.left,
+            y: rect.top,
+            scale_factor,
+            is_primary,
+            rotation: mode.as_ref().map_or(0, mode_rotation),
+            mirror_of: None,
+            persistent_id: String::new(),
+            stable_id: None,
+            enabled: true,
+            brightness: None,
+        });
+    }
+
+    BOOL(1) // Continue enumeration
+}
+
+// GDI 장치의 현재 모드 (읽지 못하면 None)
+fn current_mode(device: &str) -> Option<DEVMODEW> {
+    let device: Vec<u16> = device.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut mode = DEVMODEW {
+        dmSize: mem::size_of::<DEVMODEW>() as u16,
+        ..Default::default()
+    };
+    unsafe { EnumDisplaySettingsW(PCWSTR(device.as_ptr()), ENUM_CURRENT_SETTINGS, &mut mode) }
+        .as_bool()
+        .then_some(mode)
+}
+
+// 모드의 방향 (DMDO_* → 0/90/180/270)
+fn mode_rotation(mode: &DEVMODEW) -> u32 {
+    match unsafe { mode.Anonymous1.Anonymous2.dmDisplayOrientation } {
+        DMDO_90 => 90,
+        DMDO_180 => 180,
+        DMDO_270 => 270,
+        _ => 0,
+    }
+}
+
+// 모니터별 유효 DPI로 구한 배율 (읽지 못하면 1.0)
+// 앱은 모니터별 DPI 인식으로 실행되므로 rcMonitor는 실제 픽셀 크기입니다
+fn monitor_scale_factor(hmonitor: HMONITOR) -> f64 {
+    let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+    match unsafe { GetDpiForMonitor(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) } {
+        Ok(()) => scale_from_dpi(dpi_x),
+        Err(e) => {
+            log::warn!("GetDpiForMonitor 실패, 배율 1.0 사용: {}", e);
+            1.0
+        }
+    }
+}
+
+// get_system_info에 표시할 디스플레이 백엔드
+pub fn backend_name() -> String {
+    "GDI (EnumDisplayMonitors)".to_string()
+}
+
+// 적용은 ChangeDisplaySettingsEx로 하므로 외부 도구가 필요 없습니다
+pub fn apply_tools() -> Vec<String> {
+    Vec::new()
+}
+
+pub fn list_displays() -> Result<Vec<DisplayInfo>, AppError> {
+    let mut displays = Vec::new();
+
+    unsafe {
+        let result = EnumDisplayMonitors(
+            None,
+            None,
+            Some(monitor_enum_proc),
+            LPARAM(&mut displays as *mut Vec<DisplayInfo> as isize),
+        );
+
+        if !result.as_bool() {
+            return Err(AppError::DisplayQueryFailed {
+                detail: "EnumDisplayMonitors failed".to_string(),
+            });
+        }
+    }
+
+    name_monitors(&mut displays);
+    // 밝기는 DDC/CI로 모니터에 직접 묻습니다 (노트북 내장 화면처럼 지원하지 않으면 None)
+    let devices = monitor_devices();
+    for display in displays.iter_mut() {
+        if let Some((_, device)) = devices.iter().find(|(id, _)| *id == display.id) {
+            display.brightness = monitor_brightness(display.id, device);
+        }
+    }
+
+    if displays.is_empty() {
+        // Fallback if enumeration fails
+        displays.push(DisplayInfo {
+            id: 1,
+            name: "Primary Display".to_string(),
+            width: 1920,
+            height: 1080,
+            logical_width: 1920,
+            logical_height: 1080,
+            refresh_rate: 0,
+            x: 0,
+            y: 0,
+            scale_factor: 1.0,
+            is_primary: true,
+            rotation: 0,
+            mirror_of: None,
+            persistent_id: String::new(),
+            stable_id: None,
+            enabled: true,
+            brightness: None,
+        });
+    }
+
+    Ok(displays)
+}
+
+// 모든 모니터의 모드를 레지스트리에 올려 두었다가 마지막에 한 번에 적용합니다
+// (모니터를 하나씩 적용하면 중간 배치에서 겹치거나 떨어져 Windows가 위치를 바꿉니다)
+// 적용하려는 사이 연결이 끊긴 모니터는 건너뛰고 반환하는 백엔드 이름에 표시합니다
+// 프로필의 안정 id는 등록부(StableDisplayBackend)가 장치 인터페이스 이름으로 찾은 HMONITOR로 바꿔서 넘깁니다
+// 미러링할 모니터는 모드를 올리지 않고, 나머지를 적용한 뒤 SetDisplayConfig로 원본 모니터의 복제로 바꿉니다
+pub fn apply_display_settings(displays: &[DisplayInfo]) -> Result<DisplayApplied, AppError> {
+    let devices = monitor_devices();
+    let mut skipped = Vec::new();
+    let mut staged = Vec::new();
+    let mut substituted = Vec::new();
+    let mut mirrors = Vec::new();
+
+    for display in displays {
+        let found = devices.iter().find(|(id, _)| *id == display.id);
+        // 꺼진 모니터는 HMONITOR가 없어 목록에 없으므로, 찾지 못했으면 이미 꺼진 것으로 봅니다
+        if !display.enabled {
+            if let Some((_, device)) = found {
+                detach_monitor(device, &display.name)?;
+                staged.push(display.name.clone());
+            }
+            continue;
+        }
+        let Some((_, device)) = found else {
+            log::warn!("{}: 연결되어 있지 않아 건너뜁니다", display.name);
+            skipped.push(display.name.clone());
+            continue;
+        };
+        // 이미 복제 중인 모니터는 원본과 HMONITOR를 같이 쓰므로 목록에 따로 나오지 않습니다
+        if let Some(source) = display.mirror_of {
+            match devices.iter().find(|(id, _)| *id == source) {
+                Some((_, source_device)) => mirrors.push((device.clone(), source_device.clone(), display.name.clone())),
+                None => {
+                    log::warn!("{}: 미러링 원본 모니터가 연결되어 있지 않아 건너뜁니다", display.name);
+                    skipped.push(display.name.clone());
+                }
+            }
+            continue;
+        }
+        let device_name: Vec<u16> = device.encode_utf16().chain(std::iter::once(0)).collect();
+
+        // 현재 모드(색 깊이 등)에서 크기, 위치, 방향, 주사율만 바꿉니다
+        let mut mode = DEVMODEW {
+            dmSize: mem::size_of::<DEVMODEW>() as u16,
+            ..Default::default()
+        };
+        unsafe {
+            if !EnumDisplaySettingsW(PCWSTR(device_name.as_ptr()), ENUM_CURRENT_SETTINGS, &mut mode).as_bool() {
+                return Err(AppError::ToolFailed {
+                    tool: CHANGE_DISPLAY_SETTINGS.to_string(),
+                    detail: format!("EnumDisplaySettingsW failed for {}", device),
+                });
+            }
+            // 위치와 방향은 디스플레이용 공용체 필드에 있습니다
+            mode.Anonymous1.Anonymous2.dmPosition = POINTL { x: display.x, y: display.y };
+            mode.Anonymous1.Anonymous2.dmDisplayOrientation = match display.rotation {
+                90 => DMDO_90,
+                180 => DMDO_180,
+                270 => DMDO_270,
+                _ => DMDO_DEFAULT,
+            };
+        }
+        // rcMonitor 기준 크기이므로 세로 방향이면 이미 가로세로가 바뀌어 있습니다
+        mode.dmPelsWidth = display.width;
+        mode.dmPelsHeight = display.height;
+        mode.dmFields = DM_PELSWIDTH | DM_PELSHEIGHT | DM_POSITION | DM_DISPLAYORIENTATION;
+        // 주사율은 프로필에 있을 때만 바꾸며, 지원하지 않으면 가장 가까운 주사율로 (0이면 현재 주사율 유지)
+        if display.refresh_rate > 0 {
+            let available = supported_refresh_rates(&device_name, display.width, display.height);
+            let rate = closest_refresh_rate(display.refresh_rate, &available).unwrap_or(display.refresh_rate);
+            if rate != display.refresh_rate {
+                log::warn!("{}: {}Hz를 지원하지 않아 {}Hz로 적용합니다", display.name, display.refresh_rate, rate);
+                substituted.push((display.name.clone(), display.refresh_rate, rate));
+            }
+            mode.dmDisplayFrequency = rate;
+            mode.dmFields |= DM_DISPLAYFREQUENCY;
+        }
+
+        // 주 모니터는 (0, 0)에 있어야 하며 CDS_SET_PRIMARY로 지정합니다
+        let mut flags = CDS_UPDATEREGISTRY | CDS_NORESET;
+        if display.is_primary {
+            flags |= CDS_SET_PRIMARY;
+        }
+        let result = unsafe {
+            ChangeDisplaySettingsExW(PCWSTR(device_name.as_ptr()), Some(&mode as *const DEVMODEW), None, flags, None)
+        };
+        if result != DISP_CHANGE_SUCCESSFUL {
+            return Err(AppError::ToolFailed {
+                tool: CHANGE_DISPLAY_SETTINGS.to_string(),
+                detail: format!("{}: {}", display.name, disp_change_message(result)),
+            });
+        }
+        staged.push(display.name.clone());
+    }
+
+    let mut backend = CHANGE_DISPLAY_SETTINGS.to_string();
+    if !skipped.is_empty() {
+        backend.push_str(&format!(" (연결되지 않아 건너뜀: {})", skipped.join(", ")));
+    }
+    if !staged.is_empty() {
+        // 올려 둔 변경을 한 번에 적용 (어느 모니터에서 실패했는지 알 수 없으므로 적용하려던 모니터를 모두 표시)
+        let result = unsafe { ChangeDisplaySettingsExW(PCWSTR::null(), None, None, CDS_TYPE(0), None) };
+        if result != DISP_CHANGE_SUCCESSFUL && result != DISP_CHANGE_RESTART {
+            return Err(AppError::ToolFailed {
+                tool: CHANGE_DISPLAY_SETTINGS.to_string(),
+                detail: format!("{}: {}", staged.join(", "), disp_change_message(result)),
+            });
+        }
+        if result == DISP_CHANGE_RESTART {
+            log::warn!("디스플레이 설정은 저장되었지만 재시작해야 적용됩니다");
+            backend.push_str(" (재시작 필요)");
+        }
+    }
+
+    if !mirrors.is_empty() {
+        apply_mirrors(&mirrors)?;
+        backend.push_str(" + SetDisplayConfig");
+    }
+    backend.push_str(&refresh_rate_note(&substituted));
+    Ok(DisplayApplied { backend, brightness_skipped: apply_brightness(displays, &devices) })
+}
+
+// 모드를 모두 바꾼 뒤 밝기 적용 (바꾸지 못한 디스플레이 이름 반환)
+// 모드를 바꾸면 HMONITOR가 바뀔 수 있어 적용 전에 찾은 GDI 장치 이름으로 다시 찾습니다
+// 복제한 모니터는 원본과 HMONITOR를 같이 쓰므로 원본의 밝기를 따릅니다
+fn apply_brightness(displays: &[DisplayInfo], before: &[(u32, String)]) -> Vec<String> {
+    let wanted: Vec<(&DisplayInfo, &String, u32)> = displays
+        .iter()
+        .filter(|display| display.enabled && display.mirror_of.is_none())
+        .filter_map(|display| {
+            let device = before.iter().find(|(id, _)| *id == display.id).map(|(_, device)| device)?;
+            Some((display, device, display.brightness?))
+        })
+        .collect();
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+    let after = monitor_devices();
+    let mut skipped = Vec::new();
+    for (display, device, percent) in wanted {
+        let current = after.iter().find(|(_, current)| current == device);
+        if !current.is_some_and(|(id, device)| set_monitor_brightness(*id, device, percent)) {
+            log::warn!("{}: 밝기를 바꿀 수 없어 건너뜁니다 (DDC/CI를 지원하지 않거나 응답하지 않음)", display.name);
+            skipped.push(display.name.clone());
+        }
+    }
+    skipped
+}
+
+// HMONITOR의 물리 모니터 핸들로 call을 실행하고 핸들을 닫습니다 (복제 중이면 물리 모니터가 여럿)
+// 제한 시간을 넘긴 호출이 스레드에 남을 수 있으므로 HMONITOR는 id로 받아 그 스레드에서 만듭니다
+fn with_physical_monitors<T>(id: u32, call: impl FnOnce(&[PHYSICAL_MONITOR]) -> T) -> Option<T> {
+    let hmonitor = HMONITOR(id as usize as *mut c_void);
+    let mut count = 0u32;
+    unsafe { GetNumberOfPhysicalMonitorsFromHMONITOR(hmonitor, &mut count) }.ok()?;
+    if count == 0 {
+        return None;
+    }
+    let mut monitors = vec![PHYSICAL_MONITOR::default(); count as usize];
+    unsafe { GetPhysicalMonitorsFromHMONITOR(hmonitor, &mut monitors) }.ok()?;
+    let result = call(&monitors);
+    let _ = unsafe { DestroyPhysicalMonitors(&monitors) };
+    Some(result)
+}
+
+// 물리 모니터의 (최솟값, 현재 값, 최댓값), 지원하지 않으면 None
+fn physical_brightness(monitor: &PHYSICAL_MONITOR) -> Option<(u32, u32, u32)> {
+    let (mut min, mut current, mut max) = (0u32, 0u32, 0u32);
+    let handle = monitor.hPhysicalMonitor;
+    let read = unsafe { GetMonitorBrightness(handle, &mut min, &mut current, &mut max) };
+    (read != 0).then_some((min, current, max))
+}
+
+// DDC/CI 밝기 (0–100), 지원하지 않거나 제한 시간 안에 응답하지 않으면 None
+fn monitor_brightness(id: u32, device: &str) -> Option<u32> {
+    with_timeout(device, BRIGHTNESS_TIMEOUT, move || {
+        with_physical_monitors(id, |monitors| {
+            let (min, current, max) = physical_brightness(&monitors[0])?;
+            to_percent(current, min, max)
+        })
+        .flatten()
+    })
+    .flatten()
+}
+
+// 물리 모니터 하나라도 바꿨으면 true
+fn set_monitor_brightness(id: u32, device: &str, percent: u32) -> bool {
+    with_timeout(device, BRIGHTNESS_TIMEOUT, move || {
+        with_physical_monitors(id, |monitors| {
+            let set = |monitor: &PHYSICAL_MONITOR| {
+                let Some((min, _, max)) = physical_brightness(monitor) else {
+                    return false;
+                };
+                let handle = monitor.hPhysicalMonitor;
+                unsafe { SetMonitorBrightness(handle, from_percent(percent, min, max)) != 0 }
+            };
+            monitors.iter().filter(|monitor| set(monitor)).count() > 0
+        })
+        .unwrap_or(false)
+    })
+    .unwrap_or(false)
+}
+
+// 활성 경로와 모드 (QueryDisplayConfig)
+fn active_display_config() -> Result<(Vec<DISPLAYCONFIG_PATH_INFO>, Vec<DISPLAYCONFIG_MODE_INFO>), WIN32_ERROR> {
+    let (mut path_count, mut mode_count) = (0u32, 0u32);
+    let sizes = unsafe { GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut path_count, &mut mode_count) };
+    if sizes != ERROR_SUCCESS {
+        return Err(sizes);
+    }
+    let mut paths = vec![DISPLAYCONFIG_PATH_INFO::default(); path_count as usize];
+    let mut modes = vec![DISPLAYCONFIG_MODE_INFO::default(); mode_count as usize];
+    let result = unsafe {
+        QueryDisplayConfig(
+            QDC_ONLY_ACTIVE_PATHS,
+            &mut path_count,
+            paths.as_mut_ptr(),
+            &mut mode_count,
+            modes.as_mut_ptr(),
+            None,
+        )
+    };
+    if result != ERROR_SUCCESS {
+        return Err(result);
+    }
+    paths.truncate(path_count as usize);
+    modes.truncate(mode_count as usize);
+    Ok((paths, modes))
+}
+
+// 경로의 GDI 장치 이름 (\\.\DISPLAY1 형식, monitor_devices와 같음)
+fn path_source_device(path: &DISPLAYCONFIG_PATH_INFO) -> Option<String> {
+    let mut source = DISPLAYCONFIG_SOURCE_DEVICE_NAME {
+        header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+            r#type: DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+            size: mem::size_of::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>() as u32,
+            adapterId: path.sourceInfo.adapterId,
+            id: path.sourceInfo.id,
+        },
+        ..Default::default()
+    };
+    if unsafe { DisplayConfigGetDeviceInfo(&mut source.header) } != 0 {
+        return None;
+    }
+    Some(wide_to_string(&source.viewGdiDeviceName))
+}
+
+// (미러링할 모니터, 원본 모니터, 이름)의 GDI 장치 이름을 받아 대상 경로가 원본 경로의 소스를 같이 쓰게 합니다
+// 복제는 같은 그래픽 어댑터에 연결된 모니터끼리만 할 수 있고, 공통 해상도는 Windows가 맞춥니다
+fn apply_mirrors(mirrors: &[(String, String, String)]) -> Result<(), AppError> {
+    let failed = |detail: String| AppError::ToolFailed { tool: "SetDisplayConfig".to_string(), detail };
+    let (mut paths, modes) = active_display_config().map_err(|e| failed(format!("QueryDisplayConfig: {:?}", e)))?;
+    let sources: Vec<Option<String>> = paths.iter().map(path_source_device).collect();
+    let find = |device: &str| sources.iter().position(|source| source.as_deref() == Some(device));
+
+    for (target, source, name) in mirrors {
+        let (Some(t), Some(s)) = (find(target), find(source)) else {
+            return Err(failed(format!("{}: display path not found", name)));
+        };
+        let (target_adapter, source_adapter) = (paths[t].sourceInfo.adapterId, paths[s].sourceInfo.adapterId);
+        if target_adapter.LowPart != source_adapter.LowPart || target_adapter.HighPart != source_adapter.HighPart {
+            return Err(failed(format!("{}: cannot mirror a display on another graphics adapter", name)));
+        }
+        paths[t].sourceInfo.id = paths[s].sourceInfo.id;
+        // 소스 모드 번호를 공용체로 같이 씁니다 (대상 모드는 그대로, 필요하면 SDC_ALLOW_CHANGES로 Windows가 조정)
+        unsafe {
+            paths[t].sourceInfo.Anonymous.modeInfoIdx = paths[s].sourceInfo.Anonymous.modeInfoIdx;
+        }
+    }
+
+    let flags = SDC_APPLY | SDC_USE_SUPPLIED_DISPLAY_CONFIG | SDC_ALLOW_CHANGES | SDC_SAVE_TO_DATABASE;
+    let result = unsafe { SetDisplayConfig(Some(&paths), Some(&modes), flags) };
+    if result != ERROR_SUCCESS.0 as i32 {
+        let names: Vec<&str> = mirrors.iter().map(|(_, _, name)| name.as_str()).collect();
+        return Err(failed(format!("{}: error {}", names.join(", "), result)));
+    }
+    Ok(())
+}
+
+// 복제 / 확장 전환은 GDI 모드로는 할 수 없어 Win+P와 같은 SetDisplayConfig 토폴로지로 바꿉니다
+// 복제할 때의 공통 해상도는 Windows가 고릅니다
+pub fn set_desktop_topology(mode: DesktopMode) -> Result<String, AppError> {
+    let topology = match mode {
+        DesktopMode::Mirror => SDC_TOPOLOGY_CLONE,
+        DesktopMode::Extend => SDC_TOPOLOGY_EXTEND,
+    };
+    let result = unsafe { SetDisplayConfig(None, None, SDC_APPLY | topology) };
+    if result != ERROR_SUCCESS.0 as i32 {
+        return Err(AppError::ToolFailed {
+            tool: "SetDisplayConfig".to_string(),
+            detail: format!("{:?}: error {}", mode, result),
+        });
+    }
+    Ok("SetDisplayConfig".to_string())
+}
+
+// 크기를 0으로 올려 두면 마지막 적용에서 데스크톱에서 분리됩니다 (모니터 끄기)
+fn detach_monitor(device: &str, name: &str) -> Result<(), AppError> {
+    let device_name: Vec<u16> = device.encode_utf16().chain(std::iter::once(0)).collect();
+    let mode = DEVMODEW {
+        dmSize: mem::size_of::<DEVMODEW>() as u16,
+        dmFields: DM_PELSWIDTH | DM_PELSHEIGHT | DM_POSITION,
+        ..Default::default()
+    };
+    let result = unsafe {
+        ChangeDisplaySettingsExW(
+            PCWSTR(device_name.as_ptr()),
+            Some(&mode as *const DEVMODEW),
+            None,
+            CDS_UPDATEREGISTRY | CDS_NORESET,
+            None,
+        )
+    };
+    if result != DISP_CHANGE_SUCCESSFUL {
+        return Err(AppError::ToolFailed {
+            tool: CHANGE_DISPLAY_SETTINGS.to_string(),
+            detail: format!("{}: {}", name, disp_change_message(result)),
+        });
+    }
+    Ok(())
+}
+
+// GDI 장치가 지원하는 모든 모드 (모드 번호를 0부터 늘려 가며 실패할 때까지)
+fn device_modes(device_name: &[u16]) -> Vec<DEVMODEW> {
+    let mut modes = Vec::new();
+    for index in 0.. {
+        let mut mode = DEVMODEW {
+            dmSize: mem::size_of::<DEVMODEW>() as u16,
+            ..Default::default()
+        };
+        let found = unsafe {
+            EnumDisplaySettingsW(PCWSTR(device_name.as_ptr()), ENUM_DISPLAY_SETTINGS_MODE(index), &mut mode)
+        };
+        if !found.as_bool() {
+            break;
+        }
+        modes.push(mode);
+    }
+    modes
+}
+
+// 해상도가 같은 모드의 주사율 (모드 목록은 가로세로 방향과 관계없이 비교합니다)
+fn supported_refresh_rates(device_name: &[u16], width: u32, height: u32) -> Vec<u32> {
+    device_modes(device_name)
+        .iter()
+        .filter(|mode| {
+            let size = (mode.dmPelsWidth, mode.dmPelsHeight);
+            (size == (width, height) || size == (height, width)) && mode.dmDisplayFrequency > 1
+        })
+        .map(|mode| mode.dmDisplayFrequency)
+        .collect()
+}
+
+// 모니터의 모든 모드 (배율은 모드와 관계없이 모니터의 현재 DPI 배율)
+pub fn display_modes(display_id: u32) -> Result<Vec<DisplayMode>, AppError> {
+    let (_, device) = monitor_devices()
+        .into_iter()
+        .find(|(id, _)| *id == display_id)
+        .ok_or_else(|| AppError::DisplayNotFound { display_id: display_id.to_string() })?;
+    let scale_factor = list_displays()?
+        .iter()
+        .find(|display| display.id == display_id)
+        .map_or(1.0, |display| display.scale_factor);
+    let current = current_mode(&device).map(|mode| (mode.dmPelsWidth, mode.dmPelsHeight, mode.dmDisplayFrequency));
+
+    let device_name: Vec<u16> = device.encode_utf16().chain(std::iter::once(0)).collect();
+    Ok(device_modes(&device_name)
+        .iter()
+        .map(|mode| DisplayMode {
+            width: mode.dmPelsWidth,
+            height: mode.dmPelsHeight,
+            // 0과 1은 하드웨어 기본값
+            refresh_rate: if mode.dmDisplayFrequency > 1 { mode.dmDisplayFrequency } else { 0 },
+            scale_factor,
+            is_current: current == Some((mode.dmPelsWidth, mode.dmPelsHeight, mode.dmDisplayFrequency)),
+        })
+        .collect())
+}
+
+const CHANGE_DISPLAY_SETTINGS: &str = "ChangeDisplaySettingsEx";
+
+// DISP_CHANGE_* 결과 → 설명
+fn disp_change_message(result: DISP_CHANGE) -> &'static str {
+    match result {
+        DISP_CHANGE_RESTART => "The computer must be restarted for the display mode to work",
+        DISP_CHANGE_BADMODE => "The display mode is not supported",
+        DISP_CHANGE_NOTUPDATED => "Unable to write settings to the registry",
+        DISP_CHANGE_BADFLAGS => "An invalid set of flags was passed in",
+        DISP_CHANGE_BADPARAM => "An invalid parameter was passed in",
+        DISP_CHANGE_BADDUALVIEW => "The settings change was unsuccessful because the system is DualView capable",
+        _ => "The display driver failed the specified graphics mode",
+    }
+}
+
+// EnumDisplayDevicesW가 장치 인터페이스 이름을 DeviceID에 채우도록 하는 플래그 (WindowsAndMessaging의 상수)
+const EDD_GET_DEVICE_INTERFACE_NAME: u32 = 0x0000_0001;
+
+// HMONITOR → GDI 장치 이름 (예: \\.\DISPLAY1)
+unsafe extern "system" fn monitor_device_proc(
+    hmonitor: HMONITOR,
+    _hdc: HDC,
+    _lprect: *mut RECT,
+    lparam: LPARAM,
+) -> BOOL {
+    let devices = &mut *(lparam.0 as *mut Vec<(u32, String)>);
+
+    let mut monitor_info = MONITORINFOEXW::default();
+    monitor_info.monitorInfo.cbSize = mem::size_of::<MONITORINFOEXW>() as u32;
+    if GetMonitorInfoW(hmonitor, &mut monitor_info as *mut MONITORINFOEXW as *mut MONITORINFO).as_bool() {
+        devices.push((hmonitor.0 as usize as u32, wide_to_string(&monitor_info.szDevice)));
+    }
+
+    BOOL(1)
+}
+
+fn wide_to_string(wide: &[u16]) -> String {
+    let len = wide.iter().position(|&c| c == 0).unwrap_or(wide.len());
+    String::from_utf16_lossy(&wide[..len])
+}
+
+// 모니터의 장치 인터페이스 이름 (예: \\?\DISPLAY#GSM5B09#5&1a2b3c&0&UID4352#{e6f07b5f-...})
+// 어댑터와 출력마다 정해지므로 HMONITOR와 달리 세션이 바뀌어도 같습니다
+fn monitor_interface_name(device: &str) -> Option<String> {
+    let device: Vec<u16> = device.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut display_device = DISPLAY_DEVICEW {
+        cb: mem::size_of::<DISPLAY_DEVICEW>() as u32,
+        ..Default::default()
+    };
+    let found = unsafe {
+        EnumDisplayDevicesW(PCWSTR(device.as_ptr()), 0, &mut display_device, EDD_GET_DEVICE_INTERFACE_NAME)
+    };
+    if !found.as_bool() {
+        return None;
+    }
+    Some(wide_to_string(&display_device.DeviceID)).filter(|id| !id.is_empty())
+}
+
+// WmiMonitorID의 인스턴스 이름(DISPLAY\GSM5B09\5&1a2b3c&0&UID4352_0)과 일련번호
+fn monitor_serials_by_instance() -> Result<Vec<(String, String)>, AppError> {
+    let script = "Get-CimInstance -Namespace root\\wmi -ClassName WmiMonitorID | ForEach-Object { $_.InstanceName + \"`t\" + (-join ($_.SerialNumberID | Where-Object { $_ -ne 0 } | ForEach-Object { [char]$_ })) }";
+    Ok(powershell()
+        .run(script)?
+        .lines()
+        .filter_map(|line| line.trim().split_once('\t'))
+        .map(|(instance, serial)| (instance.to_string(), serial.trim().to_string()))
+        .collect())
+}
+
+// 인터페이스 이름의 하드웨어 id(GSM5B09)와 WMI 일련번호로 만든 EDID 식별값 (edid::fingerprint와 같은 형식)
+fn monitor_fingerprint(interface: &str, serials: &[(String, String)]) -> Option<String> {
+    let parts: Vec<&str> = interface.trim_start_matches("\\\\?\\").split('#').collect();
+    let hardware_id = parts.get(1).filter(|id| !id.is_empty())?.to_uppercase();
+    let instance = parts.get(..3)?.join("\\").to_uppercase();
+    let serial = serials
+        .iter()
+        .find(|(name, _)| name.rsplit_once('_').map_or(name.as_str(), |(name, _)| name).to_uppercase() == instance)
+        .map(|(_, serial)| serial.as_str())
+        .filter(|serial| !serial.is_empty() && *serial != "0");
+    Some(match serial {
+        Some(serial) => format!("{}-{}", hardware_id, serial),
+        None => hardware_id,
+    })
+}
+
+// 활성 경로의 (모니터 장치 경로, EDID 모니터 이름), 읽지 못하면 빈 목록
+// 장치 경로는 monitor_interface_name과 같은 형식입니다
+fn monitor_friendly_names() -> Vec<(String, String)> {
+    let paths = match active_display_config() {
+        Ok((paths, _)) => paths,
+        Err(e) => {
+            log::warn!("QueryDisplayConfig 실패, 모니터 이름 대신 번호를 사용합니다: {:?}", e);
+            return Vec::new();
+        }
+    };
+
+    paths
+        .iter()
+        .filter_map(|path| {
+            let mut target = DISPLAYCONFIG_TARGET_DEVICE_NAME {
+                header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+                    r#type: DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME,
+                    size: mem::size_of::<DISPLAYCONFIG_TARGET_DEVICE_NAME>() as u32,
+                    adapterId: path.targetInfo.adapterId,
+                    id: path.targetInfo.id,
+                },
+                ..Default::default()
+            };
+            if unsafe { DisplayConfigGetDeviceInfo(&mut target.header) } != 0 {
+                return None;
+            }
+            let name = wide_to_string(&target.monitorFriendlyDeviceName);
+            Some((wide_to_string(&target.monitorDevicePath), name)).filter(|(_, name)| !name.is_empty())
+        })
+        .collect()
+}
+
+// 모니터 이름이 있으면 "Display N" 대신 사용 (내장 패널처럼 EDID 이름이 없으면 그대로)
+fn name_monitors(displays: &mut [DisplayInfo]) {
+    let names = monitor_friendly_names();
+    if names.is_empty() {
+        return;
+    }
+    let devices = monitor_devices();
+    for display in displays.iter_mut() {
+        let interface = devices
+            .iter()
+            .find(|(id, _)| *id == display.id)
+            .and_then(|(_, device)| monitor_interface_name(device));
+        let name = interface
+            .as_deref()
+            .and_then(|interface| names.iter().find(|(path, _)| path.eq_ignore_ascii_case(interface)));
+        if let Some((_, name)) = name {
+            display.name = name.clone();
+        }
+    }
+}
+
+// 연결된 모니터의 (id, GDI 장치 이름)
+fn monitor_devices() -> Vec<(u32, String)> {
+    let mut devices: Vec<(u32, String)> = Vec::new();
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            None,
+            None,
+            Some(monitor_device_proc),
+            LPARAM(&mut devices as *mut Vec<(u32, String)> as isize),
+        );
+    }
+    devices
+}
+
+// HMONITOR는 세션마다 바뀌므로 장치 인터페이스 이름을 연결 경로로, EDID 하드웨어 id와 일련번호를 식별값으로 사용
+pub fn display_identities(displays: &[DisplayInfo]) -> Result<Vec<DisplayIdentity>, AppError> {
+    let devices = monitor_devices();
+    let serials = monitor_serials_by_instance().unwrap_or_else(|e| {
+        log::warn!("모니터 일련번호를 읽지 못해 제품 코드로만 구분합니다: {}", e);
+        Vec::new()
+    });
+
+    Ok(displays
+        .iter()
+        .map(|display| {
+            let device = devices.iter().find(|(id, _)| *id == display.id).map(|(_, device)| device.clone());
+            let interface = device.as_deref().and_then(monitor_interface_name);
+            DisplayIdentity {
+                os_id: display.id,
+                fingerprint: interface.as_deref().and_then(|interface| monitor_fingerprint(interface, &serials)),
+                connector: interface.or(device).unwrap_or_else(|| display.id.to_string()),
+            }
+        })
+        .collect())
+}
+
+pub fn connected_display_serials() -> Result<Vec<String>, AppError> {
+    // WmiMonitorID의 SerialNumberID는 문자 코드 배열입니다
+    let script = "Get-CimInstance -Namespace root\\wmi -ClassName WmiMonitorID | ForEach-Object { -join ($_.SerialNumberID | Where-Object { $_ -ne 0 } | ForEach-Object { [char]$_ }) }";
+    Ok(powershell()
+        .run(script)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+// 디스플레이 변경 알림(WM_DISPLAYCHANGE)은 창 메시지 루프가 필요해 아직 구현하지 않았습니다
+// 모니터가 주기적으로 다시 읽습니다
+// 디스플레이 변경 알림을 받는 숨은 창의 알림 대상 (창 프로시저에서 사용)
+static DISPLAY_NOTIFIER: OnceLock<ChangeNotifier> = OnceLock::new();
+
+// WM_DEVICECHANGE의 장치 트리 변경 (Dbt.h의 DBT_DEVNODES_CHANGED)
+const DBT_DEVNODES_CHANGED: usize = 0x0007;
+
+unsafe extern "system" fn display_window_proc(hwnd: HWND, message: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    // 장치 트리 변경은 모니터가 아니어도 오지만 목록을 비교해 바뀐 경우에만 알립니다
+    if message == WM_DISPLAYCHANGE || (message == WM_DEVICECHANGE && wparam.0 == DBT_DEVNODES_CHANGED) {
+        if let Some(notifier) = DISPLAY_NOTIFIER.get() {
+            notifier.changed();
+        }
+        return LRESULT(0);
+    }
+    DefWindowProcW(hwnd, message, wparam, lparam)
+}
+
+// WM_DISPLAYCHANGE는 최상위 창에만 보내므로 메시지 전용 창 대신 표시하지 않는 최상위 창을 만듭니다
+// 창과 메시지 루프는 앱이 끝날 때까지 전용 스레드가 유지합니다
+pub fn watch_display_changes(notifier: ChangeNotifier) -> Result<String, AppError> {
+    if DISPLAY_NOTIFIER.set(notifier).is_err() {
+        return Err(AppError::Internal { detail: "display change window already created".to_string() });
+    }
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || unsafe {
+        let created = (|| -> windows::core::Result<HWND> {
+            let instance: HINSTANCE = GetModuleHandleW(None)?.into();
+            let class_name = w!("DisplaySoundManagerDisplayWatcher");
+            let class = WNDCLASSW {
+                lpfnWndProc: Some(display_window_proc),
+                hInstance: instance,
+                lpszClassName: class_name,
+                ..Default::default()
+            };
+            if RegisterClassW(&class) == 0 {
+                return Err(windows::core::Error::from_win32());
+            }
+            CreateWindowExW(
+                WINDOW_EX_STYLE(0),
+                class_name,
+                w!(""),
+                WS_OVERLAPPED,
+                0,
+                0,
+                0,
+                0,
+                None,
+                None,
+                Some(instance),
+                None,
+            )
+        })();
+        match created {
+            Ok(_window) => {
+                let _ = ready_tx.send(Ok(()));
+                let mut message = MSG::default();
+                while GetMessageW(&mut message, None, 0, 0).as_bool() {
+                    let _ = TranslateMessage(&message);
+                    DispatchMessageW(&message);
+                }
+            }
+            Err(e) => {
+                let _ = ready_tx.send(Err(AppError::ToolFailed {
+                    tool: "WM_DISPLAYCHANGE".to_string(),
+                    detail: format!("CreateWindowExW failed (HRESULT 0x{:08X})", e.code().0 as u32),
+                }));
+            }
+        }
+    });
+    ready_rx.recv().map_err(|e| AppError::Internal { detail: e.to_string() })??;
+    Ok("WM_DISPLAYCHANGE".to_string())
+}
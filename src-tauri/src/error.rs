@@ -0,0 +1,735 @@
+// 프론트엔드로 전달하는 오류 타입
+
+use serde::Serialize;
+
+use crate::locale::{current_locale, Locale};
+use crate::display::layout::LAYOUT_SLOTS;
+use crate::profiles::ValidationReport;
+use crate::tools::{command_timeout_of, failure_detail, tool_spec};
+
+// 다시 시도하면 성공할 수 있는 실패를 나타내는 도구 출력 (소문자)
+// 장치 사용 중, 시간 초과, 연결 직후 아직 준비되지 않은 디스플레이, DDC/CI(i2c) 통신 오류
+const TRANSIENT_FAILURE_MARKERS: &[&str] = &[
+    "busy",
+    "timed out",
+    "timeout",
+    "try again",
+    "temporarily unavailable",
+    "not ready",
+    "i2c",
+    "ddc",
+];
+
+// 명령 오류
+// 프론트엔드에는 { code, message, detail, ...매개변수 } 형태로 전달되며 message는 현재 언어로 번역됩니다
+// detail은 도구 출력 등 자세한 내용이 있는 오류만 문자열이고 나머지는 null입니다
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{}", self.message(current_locale()))]
+pub enum AppError {
+    ProfileNotFound { profile_id: String },
+    // 이름이 같은 프로필이 여러 개라 하나를 고를 수 없음 (명령줄의 --apply)
+    AmbiguousProfileName { name: String, profile_ids: String },
+    // 이름이 비어 있는 프로필
+    EmptyProfileName { profile_id: String },
+    // 다른 프로필이 이미 쓰는 이름 (대소문자 무시)
+    ProfileNameTaken { name: String, profile_id: String },
+    // 디스플레이와 오디오 설정이 모두 없는 프로필
+    EmptyProfile { profile_id: String },
+    // 모든 디스플레이를 끄는 프로필 (화면이 모두 꺼지므로 적용하지 않음)
+    AllDisplaysDisabled { profile_id: String },
+    // 프로필에 없는 부분만 적용하려고 함 (section: "display" / "audio")
+    ProfileSectionMissing { profile_id: String, section: String },
+    DeviceNotFound { device_type: String, pattern: String },
+    InvalidDevicePattern { pattern: String, detail: String },
+    // 단축키 형식 오류 (예: 수정 키가 없거나 알 수 없는 키)
+    InvalidHotkey { hotkey: String, detail: String },
+    // 다른 프로필(profile은 그 프로필 이름)이 같은 단축키를 사용
+    HotkeyConflict { hotkey: String, profile: String },
+    // OS가 단축키 등록을 거부함 (다른 프로그램이 사용 중 등)
+    HotkeyUnavailable { hotkey: String, detail: String },
+    // 시각별 규칙의 시각이 "HH:MM" 형식이 아님
+    InvalidScheduleTime { time: String },
+    ToolMissing { tool: String, install_hint: String, detail: String },
+    // 적용에 필요한 도구가 없어 시작하지 않음 (tools는 쉼표로 구분, report는 적용 전 확인 결과)
+    RequiredToolsMissing { profile_id: String, tools: String, report: Box<ValidationReport> },
+    // 프로필의 디스플레이나 오디오 장치가 연결되어 있지 않아 적용하지 않음 (missing은 쉼표로 구분)
+    ProfileHardwareMissing { profile_id: String, missing: String, report: Box<ValidationReport> },
+    ToolFailed { tool: String, detail: String },
+    // 제한 시간 안에 끝나지 않아 종료시킨 외부 명령
+    ToolTimeout { tool: String, command: String, timeout_secs: String },
+    InvalidLogLevel { level: String },
+    UnsupportedLocale { locale: String },
+    InstallNotSupported { name: String },
+    UnsupportedPlatform { feature: String },
+    // 이 플랫폼이지만 백엔드가 아직 다루지 못하는 세션 (예: Linux의 Wayland)
+    #[cfg_attr(any(target_os = "macos", target_os = "windows"), allow(dead_code))]
+    UnsupportedSession { session: String },
+    InvalidCommandTemplate { operation: String, detail: String },
+    NoPendingRecovery,
+    InvalidLayoutSlot { slot: String },
+    // set_display_mode에 "mirror" / "extend" 외의 값
+    InvalidDesktopMode { mode: String },
+    DisplayOutOfBounds { display_id: String, name: String, origin: String, limit: String },
+    UnknownAudioRole { role: String },
+    UnknownPermission { kind: String },
+    // 연결된 디스플레이 중 id가 같은 디스플레이가 없음
+    DisplayNotFound { display_id: String },
+    // 모니터가 지원하지 않는 해상도 (mode 예: "1920x1080@144")
+    UnsupportedDisplayMode { display: String, mode: String },
+    // 0–100을 벗어난 디스플레이 밝기
+    InvalidBrightness { display: String, brightness: String },
+    // OS API로 디스플레이 목록을 읽지 못함
+    DisplayQueryFailed { detail: String },
+    // 설정 / 프로필 / 로그 등 파일 입출력 실패
+    Io { path: String, detail: String },
+    // 파일은 읽었지만 내용을 해석할 수 없음
+    CorruptFile { path: String, detail: String },
+    NoRecoverySnapshot,
+    // 되돌릴 적용이 없음 (앱을 시작한 뒤 적용하지 않았거나 이미 되돌림)
+    NoRevertSnapshot,
+    // 더 새로운 버전의 앱이 저장한 프로필 파일
+    UnsupportedProfilesVersion { path: String, version: String },
+    // 이 버전에서 읽을 수 없는 내보내기 파일 형식
+    UnsupportedExportVersion { path: String, version: String },
+    // 확인에 통과한 구성을 아직 저장한 적이 없음
+    NoLastKnownGood,
+    RemoteServerFailed { port: String, detail: String },
+    InvalidRemoteMessage { detail: String },
+    UnknownRemoteCommand { command: String },
+    EmptyLayoutSlot { slot: String },
+    // 대기열에서 차례를 기다리던 중 다른 프로필 요청으로 교체됨
+    ApplySuperseded { profile_id: String, replaced_by: String },
+    // 다른 프로세스가 프로필 저장소를 잠그고 있어 제한 시간 안에 잠그지 못함
+    StoreLocked { path: String },
+    // OS 권한이나 관리자 권한이 없어 거부됨 (kind는 open_permission_settings에 넘길 값)
+    PermissionRequired { kind: String, action: String },
+    // 아직 구조화되지 않은 오류
+    Internal { detail: String },
+}
+
+impl AppError {
+    pub fn tool_missing(tool: &str, error: &std::io::Error) -> AppError {
+        // 실행은 되었지만 시간 초과로 종료시킨 경우
+        if let Some(timeout) = command_timeout_of(error) {
+            return AppError::ToolTimeout {
+                tool: tool.to_string(),
+                command: timeout.command_line.clone(),
+                timeout_secs: timeout.timeout.as_secs().to_string(),
+            };
+        }
+        AppError::ToolMissing {
+            tool: tool.to_string(),
+            install_hint: tool_spec(tool)
+                .map(|spec| spec.install_hint.to_string())
+                .unwrap_or_default(),
+            detail: error.to_string(),
+        }
+    }
+
+    // 실행은 되었지만 실패한 명령 (종료 코드와 stdout / stderr 요약 포함)
+    pub fn tool_failed(tool: &str, output: &std::process::Output) -> AppError {
+        AppError::ToolFailed {
+            tool: tool.to_string(),
+            detail: failure_detail(output),
+        }
+    }
+
+    pub fn io(path: &std::path::Path, error: &std::io::Error) -> AppError {
+        AppError::Io {
+            path: path.display().to_string(),
+            detail: error.to_string(),
+        }
+    }
+
+    pub fn corrupt_file(path: &std::path::Path, error: &serde_json::Error) -> AppError {
+        AppError::CorruptFile {
+            path: path.display().to_string(),
+            detail: error.to_string(),
+        }
+    }
+
+    // 다시 시도하면 성공할 수 있는 실패인지 (연결 직후 아직 준비되지 않은 디스플레이 등)
+    // 도구 오류는 TRANSIENT_FAILURE_MARKERS가 있을 때만, 나머지(권한, 찾을 수 없음, 잘못된 인자 등)는 다시 시도해도 같은 결과입니다
+    pub fn is_transient(&self) -> bool {
+        match self {
+            AppError::ToolTimeout { .. } | AppError::DisplayQueryFailed { .. } => true,
+            AppError::ToolFailed { detail, .. } => {
+                let detail = detail.to_lowercase();
+                TRANSIENT_FAILURE_MARKERS.iter().any(|marker| detail.contains(marker))
+            }
+            _ => false,
+        }
+    }
+
+    // 프론트엔드가 분기할 때 쓰는 고정 코드 (메시지 문구가 바뀌어도 유지)
+    fn code(&self) -> &'static str {
+        match self {
+            AppError::ProfileNotFound { .. } => "ProfileNotFound",
+            AppError::AmbiguousProfileName { .. } => "AmbiguousProfileName",
+            AppError::EmptyProfileName { .. } => "EmptyProfileName",
+            AppError::ProfileNameTaken { .. } => "ProfileNameTaken",
+            AppError::EmptyProfile { .. } => "EmptyProfile",
+            AppError::AllDisplaysDisabled { .. } => "AllDisplaysDisabled",
+            AppError::ProfileSectionMissing { .. } => "ProfileSectionMissing",
+            AppError::DisplayNotFound { .. } => "DisplayNotFound",
+            AppError::UnsupportedDisplayMode { .. } => "UnsupportedDisplayMode",
+            AppError::InvalidBrightness { .. } => "InvalidBrightness",
+            AppError::DeviceNotFound { .. } => "DeviceNotFound",
+            AppError::InvalidDevicePattern { .. } => "InvalidDevicePattern",
+            AppError::InvalidHotkey { .. } => "InvalidHotkey",
+            AppError::HotkeyConflict { .. } => "HotkeyConflict",
+            AppError::HotkeyUnavailable { .. } => "HotkeyUnavailable",
+            AppError::InvalidScheduleTime { .. } => "InvalidScheduleTime",
+            AppError::ToolMissing { .. } => "ToolMissing",
+            AppError::RequiredToolsMissing { .. } => "RequiredToolsMissing",
+            AppError::ProfileHardwareMissing { .. } => "ProfileHardwareMissing",
+            AppError::ToolFailed { .. } => "ToolFailed",
+            AppError::ToolTimeout { .. } => "ToolTimeout",
+            AppError::InvalidLogLevel { .. } => "InvalidLogLevel",
+            AppError::UnsupportedLocale { .. } => "UnsupportedLocale",
+            AppError::InstallNotSupported { .. } => "InstallNotSupported",
+            AppError::UnsupportedPlatform { .. } => "UnsupportedPlatform",
+            AppError::UnsupportedSession { .. } => "UnsupportedSession",
+            AppError::InvalidCommandTemplate { .. } => "InvalidCommandTemplate",
+            AppError::NoPendingRecovery => "NoPendingRecovery",
+            AppError::InvalidLayoutSlot { .. } => "InvalidLayoutSlot",
+            AppError::InvalidDesktopMode { .. } => "InvalidDesktopMode",
+            AppError::DisplayOutOfBounds { .. } => "DisplayOutOfBounds",
+            AppError::UnknownAudioRole { .. } => "UnknownAudioRole",
+            AppError::UnknownPermission { .. } => "UnknownPermission",
+            AppError::DisplayQueryFailed { .. } => "DisplayQueryFailed",
+            AppError::Io { .. } => "Io",
+            AppError::CorruptFile { .. } => "CorruptFile",
+            AppError::NoRecoverySnapshot => "NoRecoverySnapshot",
+            AppError::NoRevertSnapshot => "NoRevertSnapshot",
+            AppError::UnsupportedExportVersion { .. } => "UnsupportedExportVersion",
+            AppError::UnsupportedProfilesVersion { .. } => "UnsupportedProfilesVersion",
+            AppError::NoLastKnownGood => "NoLastKnownGood",
+            AppError::RemoteServerFailed { .. } => "RemoteServerFailed",
+            AppError::InvalidRemoteMessage { .. } => "InvalidRemoteMessage",
+            AppError::UnknownRemoteCommand { .. } => "UnknownRemoteCommand",
+            AppError::EmptyLayoutSlot { .. } => "EmptyLayoutSlot",
+            AppError::ApplySuperseded { .. } => "ApplySuperseded",
+            AppError::StoreLocked { .. } => "StoreLocked",
+            AppError::PermissionRequired { .. } => "PermissionRequired",
+            AppError::Internal { .. } => "Internal",
+        }
+    }
+
+    fn params(&self) -> Vec<(&'static str, &str)> {
+        match self {
+            AppError::ProfileNotFound { profile_id }
+            | AppError::EmptyProfileName { profile_id }
+            | AppError::EmptyProfile { profile_id }
+            | AppError::AllDisplaysDisabled { profile_id } => {
+                vec![("profile_id", profile_id)]
+            }
+            AppError::ProfileSectionMissing { profile_id, section } => {
+                vec![("profile_id", profile_id), ("section", section)]
+            }
+            AppError::AmbiguousProfileName { name, profile_ids } => vec![("name", name), ("profile_ids", profile_ids)],
+            AppError::ProfileNameTaken { name, profile_id } => vec![("name", name), ("profile_id", profile_id)],
+            AppError::DisplayNotFound { display_id } => vec![("display_id", display_id)],
+            AppError::UnsupportedDisplayMode { display, mode } => vec![("display", display), ("mode", mode)],
+            AppError::InvalidBrightness { display, brightness } => {
+                vec![("display", display), ("brightness", brightness)]
+            }
+            AppError::DeviceNotFound { device_type, pattern } => {
+                vec![("device_type", device_type), ("pattern", pattern)]
+            }
+            AppError::InvalidDevicePattern { pattern, detail } => {
+                vec![("pattern", pattern), ("detail", detail)]
+            }
+            AppError::InvalidHotkey { hotkey, detail } | AppError::HotkeyUnavailable { hotkey, detail } => {
+                vec![("hotkey", hotkey), ("detail", detail)]
+            }
+            AppError::HotkeyConflict { hotkey, profile } => vec![("hotkey", hotkey), ("profile", profile)],
+            AppError::InvalidScheduleTime { time } => vec![("time", time)],
+            AppError::RequiredToolsMissing { profile_id, tools, .. } => {
+                vec![("profile_id", profile_id), ("tools", tools)]
+            }
+            AppError::ProfileHardwareMissing { profile_id, missing, .. } => {
+                vec![("profile_id", profile_id), ("missing", missing)]
+            }
+            AppError::ToolMissing { tool, install_hint, detail } => {
+                vec![("tool", tool), ("install_hint", install_hint), ("detail", detail)]
+            }
+            AppError::ToolFailed { tool, detail } => vec![("tool", tool), ("detail", detail)],
+            AppError::ToolTimeout { tool, command, timeout_secs } => {
+                vec![("tool", tool), ("command", command), ("timeout_secs", timeout_secs)]
+            }
+            AppError::InvalidLogLevel { level } => vec![("level", level)],
+            AppError::UnsupportedLocale { locale } => vec![("locale", locale)],
+            AppError::InstallNotSupported { name } => vec![("name", name)],
+            AppError::UnsupportedPlatform { feature } => vec![("feature", feature)],
+            AppError::UnsupportedSession { session } => vec![("session", session)],
+            AppError::InvalidCommandTemplate { operation, detail } => {
+                vec![("operation", operation), ("detail", detail)]
+            }
+            AppError::NoPendingRecovery
+            | AppError::NoRecoverySnapshot
+            | AppError::NoRevertSnapshot
+            | AppError::NoLastKnownGood => {
+                Vec::new()
+            }
+            AppError::UnknownAudioRole { role } => vec![("role", role)],
+            AppError::UnknownPermission { kind } => vec![("kind", kind)],
+            AppError::DisplayQueryFailed { detail } | AppError::InvalidRemoteMessage { detail } => {
+                vec![("detail", detail)]
+            }
+            AppError::Io { path, detail } | AppError::CorruptFile { path, detail } => {
+                vec![("path", path), ("detail", detail)]
+            }
+            AppError::UnsupportedExportVersion { path, version } => vec![("path", path), ("version", version)],
+            AppError::UnsupportedProfilesVersion { path, version } => vec![("path", path), ("version", version)],
+            AppError::RemoteServerFailed { port, detail } => vec![("port", port), ("detail", detail)],
+            AppError::UnknownRemoteCommand { command } => vec![("command", command)],
+            AppError::InvalidLayoutSlot { slot } | AppError::EmptyLayoutSlot { slot } => {
+                vec![("slot", slot)]
+            }
+            AppError::InvalidDesktopMode { mode } => vec![("mode", mode)],
+            AppError::DisplayOutOfBounds { display_id, name, origin, limit } => vec![
+                ("display_id", display_id),
+                ("name", name),
+                ("origin", origin),
+                ("limit", limit),
+            ],
+            AppError::ApplySuperseded { profile_id, replaced_by } => {
+                vec![("profile_id", profile_id), ("replaced_by", replaced_by)]
+            }
+            AppError::StoreLocked { path } => vec![("path", path)],
+            AppError::PermissionRequired { kind, action } => vec![("kind", kind), ("action", action)],
+            AppError::Internal { detail } => vec![("detail", detail)],
+        }
+    }
+
+    // 메시지 카탈로그
+    fn message(&self, locale: Locale) -> String {
+        match (self, locale) {
+            (AppError::DisplayNotFound { display_id }, Locale::En) => {
+                format!("Display {} is not connected", display_id)
+            }
+            (AppError::DisplayNotFound { display_id }, Locale::Ko) => {
+                format!("디스플레이 {}이(가) 연결되어 있지 않습니다", display_id)
+            }
+            (AppError::UnsupportedDisplayMode { display, mode }, Locale::En) => {
+                format!("{} is not supported by {}", mode, display)
+            }
+            (AppError::UnsupportedDisplayMode { display, mode }, Locale::Ko) => {
+                format!("{}은(는) {}에서 지원하지 않는 해상도입니다", mode, display)
+            }
+            (AppError::InvalidBrightness { display, brightness }, Locale::En) => {
+                format!("Brightness for {} must be between 0 and 100 (got {})", display, brightness)
+            }
+            (AppError::InvalidBrightness { display, brightness }, Locale::Ko) => {
+                format!("{}의 밝기는 0에서 100 사이여야 합니다 (입력: {})", display, brightness)
+            }
+            (AppError::ProfileNotFound { profile_id }, Locale::En) => {
+                format!("Profile not found: {}", profile_id)
+            }
+            (AppError::ProfileNotFound { profile_id }, Locale::Ko) => {
+                format!("프로필을 찾을 수 없습니다: {}", profile_id)
+            }
+            (AppError::AmbiguousProfileName { name, profile_ids }, Locale::En) => {
+                format!("Several profiles are named {} ({}); use the profile id instead", name, profile_ids)
+            }
+            (AppError::AmbiguousProfileName { name, profile_ids }, Locale::Ko) => {
+                format!("이름이 {}인 프로필이 여러 개입니다 ({}). 프로필 id를 지정하세요", name, profile_ids)
+            }
+            (AppError::EmptyProfileName { profile_id }, Locale::En) => {
+                format!("Profile {} needs a name", profile_id)
+            }
+            (AppError::EmptyProfileName { profile_id }, Locale::Ko) => {
+                format!("프로필 {}의 이름을 입력하세요", profile_id)
+            }
+            (AppError::ProfileNameTaken { name, profile_id }, Locale::En) => {
+                format!("Another profile is already named {} ({})", name, profile_id)
+            }
+            (AppError::ProfileNameTaken { name, profile_id }, Locale::Ko) => {
+                format!("이름이 {}인 프로필이 이미 있습니다 ({})", name, profile_id)
+            }
+            (AppError::EmptyProfile { profile_id }, Locale::En) => {
+                format!("Profile {} has neither displays nor audio settings", profile_id)
+            }
+            (AppError::EmptyProfile { profile_id }, Locale::Ko) => {
+                format!("프로필 {}에 디스플레이와 오디오 설정이 모두 없습니다", profile_id)
+            }
+            (AppError::AllDisplaysDisabled { profile_id }, Locale::En) => {
+                format!("Profile {} turns off every display", profile_id)
+            }
+            (AppError::AllDisplaysDisabled { profile_id }, Locale::Ko) => {
+                format!("프로필 {}은(는) 모든 디스플레이를 끕니다", profile_id)
+            }
+            (AppError::ProfileSectionMissing { profile_id, section }, Locale::En) => {
+                format!("Profile {} has no {} settings", profile_id, section)
+            }
+            (AppError::ProfileSectionMissing { profile_id, section }, Locale::Ko) => {
+                format!("프로필 {}에 {} 설정이 없습니다", profile_id, section)
+            }
+            (AppError::DeviceNotFound { device_type, pattern }, Locale::En) => {
+                format!("No {} device matches '{}'", device_type, pattern)
+            }
+            (AppError::DeviceNotFound { device_type, pattern }, Locale::Ko) => {
+                format!("'{}'과 일치하는 {} 장치가 없습니다", pattern, device_type)
+            }
+            (AppError::InvalidDevicePattern { pattern, detail }, Locale::En) => {
+                format!("Invalid device pattern '{}': {}", pattern, detail)
+            }
+            (AppError::InvalidDevicePattern { pattern, detail }, Locale::Ko) => {
+                format!("잘못된 장치 패턴 '{}': {}", pattern, detail)
+            }
+            (AppError::InvalidHotkey { hotkey, detail }, Locale::En) => {
+                format!("Invalid shortcut '{}': {}", hotkey, detail)
+            }
+            (AppError::InvalidHotkey { hotkey, detail }, Locale::Ko) => {
+                format!("잘못된 단축키 '{}': {}", hotkey, detail)
+            }
+            (AppError::HotkeyConflict { hotkey, profile }, Locale::En) => {
+                format!("Shortcut {} is already used by profile {}", hotkey, profile)
+            }
+            (AppError::HotkeyConflict { hotkey, profile }, Locale::Ko) => {
+                format!("단축키 {}은(는) 이미 프로필 {}에서 사용 중입니다", hotkey, profile)
+            }
+            (AppError::HotkeyUnavailable { hotkey, detail }, Locale::En) => {
+                format!("Could not register shortcut {} (it may be used by another program): {}", hotkey, detail)
+            }
+            (AppError::HotkeyUnavailable { hotkey, detail }, Locale::Ko) => {
+                format!("단축키 {}을(를) 등록하지 못했습니다 (다른 프로그램이 사용 중일 수 있음): {}", hotkey, detail)
+            }
+            (AppError::InvalidScheduleTime { time }, Locale::En) => {
+                format!("Invalid schedule time: {} (use HH:MM, e.g. 09:00)", time)
+            }
+            (AppError::InvalidScheduleTime { time }, Locale::Ko) => {
+                format!("잘못된 시각입니다: {} (HH:MM 형식, 예: 09:00)", time)
+            }
+            (AppError::ToolMissing { tool, install_hint, detail }, Locale::En) => {
+                format!("Could not run {} ({}). Install it with: {}", tool, detail, install_hint)
+            }
+            (AppError::ToolMissing { tool, install_hint, detail }, Locale::Ko) => {
+                format!("{}를 실행할 수 없습니다 ({}). 설치 방법: {}", tool, detail, install_hint)
+            }
+            (AppError::RequiredToolsMissing { profile_id, tools, .. }, Locale::En) => {
+                format!("Profile {} was not applied because required tools are not installed: {}", profile_id, tools)
+            }
+            (AppError::RequiredToolsMissing { profile_id, tools, .. }, Locale::Ko) => {
+                format!("필요한 도구가 설치되어 있지 않아 프로필 {}을(를) 적용하지 않았습니다: {}", profile_id, tools)
+            }
+            (AppError::ProfileHardwareMissing { profile_id, missing, .. }, Locale::En) => {
+                format!("Profile {} was not applied because these are not connected: {}", profile_id, missing)
+            }
+            (AppError::ProfileHardwareMissing { profile_id, missing, .. }, Locale::Ko) => {
+                format!("연결되어 있지 않은 장치가 있어 프로필 {}을(를) 적용하지 않았습니다: {}", profile_id, missing)
+            }
+            (AppError::ToolFailed { tool, detail }, Locale::En) => {
+                format!("{} failed: {}", tool, detail)
+            }
+            (AppError::ToolFailed { tool, detail }, Locale::Ko) => {
+                format!("{} 실행 실패: {}", tool, detail)
+            }
+            (AppError::ToolTimeout { tool, command, timeout_secs }, Locale::En) => {
+                format!("{} did not finish within {}s and was stopped: {}", tool, timeout_secs, command)
+            }
+            (AppError::ToolTimeout { tool, command, timeout_secs }, Locale::Ko) => {
+                format!("{}가 {}초 안에 끝나지 않아 중지했습니다: {}", tool, timeout_secs, command)
+            }
+            (AppError::InvalidLogLevel { level }, Locale::En) => {
+                format!("Unknown log level: {}", level)
+            }
+            (AppError::InvalidLogLevel { level }, Locale::Ko) => {
+                format!("알 수 없는 로그 레벨: {}", level)
+            }
+            (AppError::UnsupportedLocale { locale }, Locale::En) => {
+                format!("Unsupported language: {}", locale)
+            }
+            (AppError::UnsupportedLocale { locale }, Locale::Ko) => {
+                format!("지원하지 않는 언어입니다: {}", locale)
+            }
+            (AppError::InstallNotSupported { name }, Locale::En) => {
+                format!("{} cannot be installed automatically", name)
+            }
+            (AppError::InstallNotSupported { name }, Locale::Ko) => {
+                format!("{}는 자동 설치를 지원하지 않습니다", name)
+            }
+            (AppError::UnsupportedPlatform { feature }, Locale::En) => {
+                format!("{} is not supported on this platform", feature)
+            }
+            (AppError::UnsupportedPlatform { feature }, Locale::Ko) => {
+                format!("이 플랫폼에서는 {}을(를) 지원하지 않습니다", feature)
+            }
+            (AppError::UnsupportedSession { session }, Locale::En) => {
+                format!("{} is not yet supported by this backend", session)
+            }
+            (AppError::UnsupportedSession { session }, Locale::Ko) => {
+                format!("이 백엔드는 아직 {} 세션을 지원하지 않습니다", session)
+            }
+            (AppError::InvalidCommandTemplate { operation, detail }, Locale::En) => {
+                format!("Invalid command template for {}: {}", operation, detail)
+            }
+            (AppError::InvalidCommandTemplate { operation, detail }, Locale::Ko) => {
+                format!("{} 명령 템플릿이 잘못되었습니다: {}", operation, detail)
+            }
+            (AppError::NoPendingRecovery, Locale::En) => "There is no interrupted apply to recover".to_string(),
+            (AppError::NoPendingRecovery, Locale::Ko) => "복구할 중단된 적용이 없습니다".to_string(),
+            (AppError::InvalidLayoutSlot { slot }, Locale::En) => {
+                format!("Layout slot {} does not exist (use 0-{})", slot, LAYOUT_SLOTS - 1)
+            }
+            (AppError::InvalidLayoutSlot { slot }, Locale::Ko) => {
+                format!("배치 슬롯 {}은(는) 없습니다 (0-{} 사용)", slot, LAYOUT_SLOTS - 1)
+            }
+            (AppError::InvalidDesktopMode { mode }, Locale::En) => {
+                format!("Unknown display mode: {} (use mirror or extend)", mode)
+            }
+            (AppError::InvalidDesktopMode { mode }, Locale::Ko) => {
+                format!("알 수 없는 디스플레이 모드: {} (mirror 또는 extend 사용)", mode)
+            }
+            (AppError::EmptyLayoutSlot { slot }, Locale::En) => {
+                format!("Nothing is stashed in layout slot {}", slot)
+            }
+            (AppError::EmptyLayoutSlot { slot }, Locale::Ko) => {
+                format!("배치 슬롯 {}에 저장된 배치가 없습니다", slot)
+            }
+            (AppError::ApplySuperseded { profile_id, replaced_by }, Locale::En) => format!(
+                "Applying {} was skipped because {} was requested while it was waiting",
+                profile_id, replaced_by
+            ),
+            (AppError::ApplySuperseded { profile_id, replaced_by }, Locale::Ko) => format!(
+                "{} 적용을 기다리는 중에 {} 적용 요청이 들어와 건너뛰었습니다",
+                profile_id, replaced_by
+            ),
+            (AppError::StoreLocked { path }, Locale::En) => format!(
+                "The profiles store is locked by another process: {} (close other instances or try again)",
+                path
+            ),
+            (AppError::StoreLocked { path }, Locale::Ko) => format!(
+                "다른 프로세스가 프로필 저장소를 잠그고 있습니다: {} (다른 인스턴스를 닫거나 다시 시도하세요)",
+                path
+            ),
+            (AppError::PermissionRequired { kind, action }, Locale::En) => match kind.as_str() {
+                "administrator" => format!(
+                    "The {} step requires administrator rights — restart the app as administrator",
+                    action
+                ),
+                _ => format!(
+                    "The {} step requires {} permission — open System Settings to allow it",
+                    action,
+                    permission_label(kind, locale)
+                ),
+            },
+            (AppError::PermissionRequired { kind, action }, Locale::Ko) => match kind.as_str() {
+                "administrator" => format!(
+                    "{} 단계에는 관리자 권한이 필요합니다 — 앱을 관리자 권한으로 다시 실행하세요",
+                    action
+                ),
+                _ => format!(
+                    "{} 단계에는 {} 권한이 필요합니다 — 시스템 설정을 열어 허용하세요",
+                    action,
+                    permission_label(kind, locale)
+                ),
+            },
+            (AppError::DisplayOutOfBounds { display_id, name, origin, limit }, Locale::En) => format!(
+                "{} (id {}) at ({}) is entirely outside the virtual desktop (±{})",
+                name, display_id, origin, limit
+            ),
+            (AppError::DisplayOutOfBounds { display_id, name, origin, limit }, Locale::Ko) => format!(
+                "{} (id {})의 위치 ({})가 가상 데스크톱 범위(±{})를 완전히 벗어났습니다",
+                name, display_id, origin, limit
+            ),
+            (AppError::UnknownAudioRole { role }, Locale::En) => format!(
+                "Unknown audio role: {} (expected console, multimedia or communications)",
+                role
+            ),
+            (AppError::UnknownAudioRole { role }, Locale::Ko) => format!(
+                "알 수 없는 오디오 역할: {} (console, multimedia, communications 중 하나)",
+                role
+            ),
+            (AppError::UnknownPermission { kind }, Locale::En) => format!(
+                "Unknown permission: {} (expected accessibility, screen_recording or full_disk_access)",
+                kind
+            ),
+            (AppError::UnknownPermission { kind }, Locale::Ko) => format!(
+                "알 수 없는 권한: {} (accessibility, screen_recording, full_disk_access 중 하나)",
+                kind
+            ),
+            (AppError::DisplayQueryFailed { detail }, Locale::En) => {
+                format!("Could not read the display list: {}", detail)
+            }
+            (AppError::DisplayQueryFailed { detail }, Locale::Ko) => {
+                format!("디스플레이 목록을 읽을 수 없습니다: {}", detail)
+            }
+            (AppError::Io { path, detail }, Locale::En) => format!("Could not access {}: {}", path, detail),
+            (AppError::Io { path, detail }, Locale::Ko) => format!("{}에 접근할 수 없습니다: {}", path, detail),
+            (AppError::CorruptFile { path, detail }, Locale::En) => {
+                format!("{} is damaged and could not be read: {}", path, detail)
+            }
+            (AppError::CorruptFile { path, detail }, Locale::Ko) => {
+                format!("{} 파일이 손상되어 읽을 수 없습니다: {}", path, detail)
+            }
+            (AppError::NoRecoverySnapshot, Locale::En) => {
+                "The interrupted apply has no snapshot to restore".to_string()
+            }
+            (AppError::NoRecoverySnapshot, Locale::Ko) => {
+                "중단된 적용에 되돌릴 이전 구성이 없습니다".to_string()
+            }
+            (AppError::NoRevertSnapshot, Locale::En) => "There is no apply to undo".to_string(),
+            (AppError::NoRevertSnapshot, Locale::Ko) => "되돌릴 적용이 없습니다".to_string(),
+            (AppError::UnsupportedProfilesVersion { path, version }, Locale::En) => {
+                format!("{} was saved by a newer version of the app (schema version {})", path, version)
+            }
+            (AppError::UnsupportedProfilesVersion { path, version }, Locale::Ko) => {
+                format!("{} 파일은 더 새로운 버전의 앱에서 저장되었습니다 (형식 버전 {})", path, version)
+            }
+            (AppError::UnsupportedExportVersion { path, version }, Locale::En) => {
+                format!("{} uses export format version {}, which this version cannot read", path, version)
+            }
+            (AppError::UnsupportedExportVersion { path, version }, Locale::Ko) => {
+                format!("{} 파일의 내보내기 형식 버전 {}은 이 버전에서 읽을 수 없습니다", path, version)
+            }
+            (AppError::NoLastKnownGood, Locale::En) => {
+                "No configuration has been verified as working yet".to_string()
+            }
+            (AppError::NoLastKnownGood, Locale::Ko) => {
+                "정상 동작이 확인된 구성이 아직 없습니다".to_string()
+            }
+            (AppError::RemoteServerFailed { port, detail }, Locale::En) => {
+                format!("Failed to start WebSocket bridge on port {}: {}", port, detail)
+            }
+            (AppError::RemoteServerFailed { port, detail }, Locale::Ko) => {
+                format!("{} 포트에서 WebSocket 브리지를 시작할 수 없습니다: {}", port, detail)
+            }
+            (AppError::InvalidRemoteMessage { detail }, Locale::En) => format!("Invalid message: {}", detail),
+            (AppError::InvalidRemoteMessage { detail }, Locale::Ko) => format!("잘못된 메시지: {}", detail),
+            (AppError::UnknownRemoteCommand { command }, Locale::En) => format!("Unknown command: {}", command),
+            (AppError::UnknownRemoteCommand { command }, Locale::Ko) => format!("알 수 없는 명령: {}", command),
+            (AppError::Internal { detail }, _) => detail.clone(),
+        }
+    }
+}
+
+// 권한 종류의 표시 이름 (시스템 설정의 항목 이름)
+fn permission_label(kind: &str, locale: Locale) -> &str {
+    match (kind, locale) {
+        ("accessibility", Locale::En) => "Accessibility",
+        ("accessibility", Locale::Ko) => "손쉬운 사용",
+        ("screen_recording", Locale::En) => "Screen Recording",
+        ("screen_recording", Locale::Ko) => "화면 기록",
+        ("full_disk_access", Locale::En) => "Full Disk Access",
+        ("full_disk_access", Locale::Ko) => "전체 디스크 접근",
+        _ => kind,
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let params = self.params();
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        if !params.iter().any(|(key, _)| *key == "detail") {
+            map.serialize_entry("detail", &None::<&str>)?;
+        }
+        for (key, value) in params {
+            map.serialize_entry(key, value)?;
+        }
+        // 문자열로 나타낼 수 없는 매개변수
+        if let AppError::RequiredToolsMissing { report, .. } | AppError::ProfileHardwareMissing { report, .. } = self {
+            map.serialize_entry("report", report)?;
+        }
+        map.serialize_entry("message", &self.message(current_locale()))?;
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::set_current_locale;
+
+    #[test]
+    fn serializes_code_params_and_message() {
+        set_current_locale(Locale::En);
+        let error = AppError::io(std::path::Path::new("/tmp/profiles.json"), &std::io::Error::other("disk full"));
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(value["code"], "Io");
+        assert_eq!(value["path"], "/tmp/profiles.json");
+        assert_eq!(value["detail"], "disk full");
+        assert_eq!(value["message"], "Could not access /tmp/profiles.json: disk full");
+
+        let value = serde_json::to_value(AppError::NoRecoverySnapshot).unwrap();
+        assert_eq!(value.as_object().unwrap().len(), 3);
+        assert_eq!(value["code"], "NoRecoverySnapshot");
+        assert_eq!(value["detail"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn only_known_transient_tool_failures_are_retried() {
+        let failed = |detail: &str| AppError::ToolFailed { tool: "ddcutil".to_string(), detail: detail.to_string() };
+        for detail in [
+            "Device or resource busy",
+            "Operation timed out",
+            "Display not ready",
+            "DDC communication failed",
+            "i2c transfer failed (EIO)",
+            "Resource temporarily unavailable, try again",
+        ] {
+            assert!(failed(detail).is_transient(), "{}", detail);
+        }
+        // 권한, 찾을 수 없음, 잘못된 인자, 알 수 없는 출력은 다시 시도하지 않습니다
+        for detail in [
+            "Permission denied",
+            "Operation not permitted (os error 1)",
+            "Access is denied.",
+            "Unable to find screen 2",
+            "No such file or directory",
+            "Unable to parse 'degree:45'",
+            "exit status: 1",
+        ] {
+            assert!(!failed(detail).is_transient(), "{}", detail);
+        }
+        let timeout = AppError::ToolTimeout {
+            tool: "xrandr".to_string(),
+            command: "xrandr --query".to_string(),
+            timeout_secs: "10".to_string(),
+        };
+        assert!(timeout.is_transient());
+    }
+
+    // 프런트엔드가 code와 필드 이름으로 화면을 고르므로 바뀌면 안 되는 형태입니다 ({ code, message, detail }과 매개변수)
+    #[test]
+    fn keeps_frontend_error_contract() {
+        set_current_locale(Locale::En);
+        let cases = [
+            (
+                AppError::ProfileNotFound { profile_id: "desk".to_string() },
+                serde_json::json!({"code": "ProfileNotFound", "detail": null, "profile_id": "desk"}),
+            ),
+            (
+                AppError::ToolMissing {
+                    tool: "displayplacer".to_string(),
+                    install_hint: "brew install displayplacer".to_string(),
+                    detail: "not found".to_string(),
+                },
+                serde_json::json!({
+                    "code": "ToolMissing",
+                    "tool": "displayplacer",
+                    "install_hint": "brew install displayplacer",
+                    "detail": "not found",
+                }),
+            ),
+            (
+                AppError::DeviceNotFound { device_type: "output".to_string(), pattern: "re:^USB".to_string() },
+                serde_json::json!({"code": "DeviceNotFound", "detail": null, "device_type": "output", "pattern": "re:^USB"}),
+            ),
+            (
+                AppError::ToolFailed { tool: "xrandr".to_string(), detail: "exit code 1".to_string() },
+                serde_json::json!({"code": "ToolFailed", "detail": "exit code 1", "tool": "xrandr"}),
+            ),
+        ];
+        for (error, expected) in cases {
+            let mut value = serde_json::to_value(&error).unwrap();
+            let message = value.as_object_mut().unwrap().remove("message").unwrap();
+            assert_eq!(message, error.to_string());
+            assert_eq!(value, expected);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+// 프로필 적용 전후에 실행하는 사용자 명령 (프로필의 pre_apply_command / post_apply_command)
+// 셸(Windows는 cmd /C, 그 밖에는 sh -c)로 실행하고, 제한 시간이 지나면 프로세스를 종료합니다
+// 결과는 apply-hook-finished 이벤트로 알리며, 실패해도 이미 적용한 설정은 되돌리지 않습니다
+
+use serde::Serialize;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::tools::{command_timeout_of, decode_output, output_with_timeout};
+
+// 설정의 hook_timeout_secs 기본값
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 30;
+// 결과에 남길 출력의 끝부분 길이 (stdout, stderr 각각, 바이트)
+const OUTPUT_TAIL_LIMIT: usize = 2000;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct HookResult {
+    pub profile_id: String,
+    // "pre" / "post"
+    pub stage: String,
+    pub command: String,
+    // 시간 초과, 실행 실패, 신호로 종료된 경우 None
+    pub exit_code: Option<i32>,
+    pub success: bool,
+    pub timed_out: bool,
+    pub stdout_tail: String,
+    pub stderr_tail: String,
+    // 명령을 실행하지 못한 이유 (셸을 찾지 못함 등)
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl HookResult {
+    // 실패했을 때 적용 결과의 경고로 남길 문장
+    pub fn warning(&self) -> Option<String> {
+        if self.success {
+            return None;
+        }
+        let reason = if self.timed_out {
+            "timed out".to_string()
+        } else if let Some(error) = &self.error {
+            error.clone()
+        } else {
+            match self.exit_code {
+                Some(code) => format!("exit code {}", code),
+                None => "terminated by signal".to_string(),
+            }
+        };
+        let stderr = match self.stderr_tail.as_str() {
+            "" => String::new(),
+            stderr => format!(": {}", stderr),
+        };
+        Some(format!("{}-apply command failed ({}){}", self.stage, reason, stderr))
+    }
+}
+
+fn shell_command(command_line: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    {
+        let mut command = Command::new("cmd");
+        command.args(["/C", command_line]);
+        command
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let mut command = Command::new("sh");
+        command.args(["-c", command_line]);
+        command
+    }
+}
+
+// 출력의 끝부분 (오류 원인은 대개 마지막 줄에 있습니다)
+fn output_tail(bytes: &[u8], limit: usize) -> String {
+    let text = decode_output(bytes);
+    let text = text.trim();
+    if text.len() <= limit {
+        return text.to_string();
+    }
+    let mut start = text.len() - limit;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("(… {} bytes truncated) {}", start, &text[start..])
+}
+
+// 명령을 실행하고 끝나거나 제한 시간이 지날 때까지 기다립니다
+pub fn run_hook(profile_id: &str, stage: &str, command_line: &str, timeout: Duration) -> HookResult {
+    log::info!("{} 적용 명령 실행 ({}): {}", stage, profile_id, command_line);
+    let started = Instant::now();
+    let mut result = HookResult {
+        profile_id: profile_id.to_string(),
+        stage: stage.to_string(),
+        command: command_line.to_string(),
+        exit_code: None,
+        success: false,
+        timed_out: false,
+        stdout_tail: String::new(),
+        stderr_tail: String::new(),
+        error: None,
+        duration_ms: 0,
+    };
+    match output_with_timeout(&mut shell_command(command_line), timeout) {
+        Ok(output) => {
+            result.exit_code = output.status.code();
+            result.success = output.status.success();
+            result.stdout_tail = output_tail(&output.stdout, OUTPUT_TAIL_LIMIT);
+            result.stderr_tail = output_tail(&output.stderr, OUTPUT_TAIL_LIMIT);
+        }
+        Err(e) if command_timeout_of(&e).is_some() => result.timed_out = true,
+        Err(e) => result.error = Some(e.to_string()),
+    }
+    result.duration_ms = started.elapsed().as_millis() as u64;
+
+    match result.warning() {
+        None => log::info!("{} 적용 명령 완료 ({}ms)", stage, result.duration_ms),
+        Some(warning) => log::warn!("{}", warning),
+    }
+    result
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_exit_status_and_output_tails() {
+        let result = run_hook("desk", "post", "echo started; echo 'no such scene' >&2; exit 3", Duration::from_secs(5));
+        assert_eq!((result.exit_code, result.success, result.timed_out), (Some(3), false, false));
+        assert_eq!(result.stdout_tail, "started");
+        assert_eq!(result.warning().unwrap(), "post-apply command failed (exit code 3): no such scene");
+
+        let ok = run_hook("desk", "pre", "true", Duration::from_secs(5));
+        assert!(ok.success && ok.warning().is_none());
+    }
+
+    #[test]
+    fn kills_commands_that_run_past_the_timeout() {
+        let result = run_hook("desk", "post", "sleep 5", Duration::from_millis(100));
+        assert!(result.timed_out);
+        assert!(result.duration_ms < 5000);
+        assert_eq!(result.warning().unwrap(), "post-apply command failed (timed out)");
+    }
+
+    #[test]
+    fn keeps_the_end_of_long_output() {
+        let tail = output_tail("x".repeat(10).as_bytes(), 4);
+        assert_eq!(tail, "(… 6 bytes truncated) xxxx");
+    }
+}
@@ -0,0 +1,828 @@
+// 프로필 전역 단축키 (프로필의 hotkey, 예: "CmdOrCtrl+Shift+1")
+// 앱을 시작할 때와 프로필이 바뀔 때마다 모든 프로필의 단축키를 OS에 다시 맞춰 등록하고,
+// 단축키를 누르면 창에 초점이 없어도 그 프로필을 적용합니다
+//
+// OS 등록은 Windows(RegisterHotKey), macOS(Carbon RegisterEventHotKey), Linux X11(XGrabKey)에서 하며,
+// X 서버에 연결할 수 없는 세션(Wayland만 있는 세션 등)은 형식과 겹침만 확인합니다
+
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{mpsc, Mutex};
+use tauri::AppHandle;
+
+use crate::commands::apply_profile_by_id;
+use crate::error::AppError;
+use crate::profiles::Profile;
+use crate::sync::LockExt;
+
+pub const MOD_CTRL: u8 = 1;
+pub const MOD_ALT: u8 = 2;
+pub const MOD_SHIFT: u8 = 4;
+// Windows 키 / macOS Command 키
+pub const MOD_SUPER: u8 = 8;
+
+// 문자, 숫자, F1~F24 외에 쓸 수 있는 키 (수정 키 없이 누르는 단축키는 입력을 가로채므로 받지 않습니다)
+const NAMED_KEYS: [&str; 15] = [
+    "Space", "Tab", "Enter", "Escape", "Backspace", "Delete", "Insert", "Home", "End", "PageUp", "PageDown",
+    "Up", "Down", "Left", "Right",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hotkey {
+    pub modifiers: u8,
+    // 정규화한 키 이름 ("A", "1", "F5", "Space" 등)
+    pub key: String,
+}
+
+impl Hotkey {
+    // "CmdOrCtrl+Shift+1" 형식 (대소문자 구분 없음, CmdOrCtrl은 macOS에서 Command, 나머지에서 Ctrl)
+    pub fn parse(accelerator: &str) -> Result<Self, AppError> {
+        let invalid = |detail: &str| AppError::InvalidHotkey {
+            hotkey: accelerator.to_string(),
+            detail: detail.to_string(),
+        };
+        let parts: Vec<&str> = accelerator.split('+').map(str::trim).collect();
+        let (key, modifiers) = parts.split_last().ok_or_else(|| invalid("empty shortcut"))?;
+
+        let mut flags = 0;
+        for modifier in modifiers {
+            let flag = match modifier.to_ascii_lowercase().as_str() {
+                "cmdorctrl" | "commandorcontrol" if cfg!(target_os = "macos") => MOD_SUPER,
+                "cmdorctrl" | "commandorcontrol" => MOD_CTRL,
+                "ctrl" | "control" => MOD_CTRL,
+                "alt" | "option" => MOD_ALT,
+                "shift" => MOD_SHIFT,
+                "super" | "meta" | "cmd" | "command" | "win" => MOD_SUPER,
+                _ => return Err(invalid(&format!("unknown modifier \"{}\"", modifier))),
+            };
+            if flags & flag != 0 {
+                return Err(invalid(&format!("repeated modifier \"{}\"", modifier)));
+            }
+            flags |= flag;
+        }
+        if flags == 0 {
+            return Err(invalid("at least one modifier (Ctrl, Alt, Shift, Super) is required"));
+        }
+
+        Ok(Hotkey { modifiers: flags, key: normalize_key(key).ok_or_else(|| invalid("unknown key"))? })
+    }
+
+    // 겹침 확인과 표시에 쓰는 형식 ("Ctrl+Alt+Shift+Super+키" 순서)
+    pub fn canonical(&self) -> String {
+        let names = [(MOD_CTRL, "Ctrl"), (MOD_ALT, "Alt"), (MOD_SHIFT, "Shift"), (MOD_SUPER, "Super")];
+        let mut parts: Vec<&str> = names
+            .iter()
+            .filter(|(flag, _)| self.modifiers & flag != 0)
+            .map(|(_, name)| *name)
+            .collect();
+        parts.push(&self.key);
+        parts.join("+")
+    }
+}
+
+fn normalize_key(key: &str) -> Option<String> {
+    let upper = key.to_ascii_uppercase();
+    if upper.len() == 1 && upper.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Some(upper);
+    }
+    if let Some(number) = upper.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+        return (1..=24).contains(&number).then(|| format!("F{}", number));
+    }
+    let alias = match upper.as_str() {
+        "RETURN" => "Enter",
+        "ESC" => "Escape",
+        "DEL" => "Delete",
+        "ARROWUP" => "Up",
+        "ARROWDOWN" => "Down",
+        "ARROWLEFT" => "Left",
+        "ARROWRIGHT" => "Right",
+        _ => key,
+    };
+    NAMED_KEYS
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(alias))
+        .map(|name| name.to_string())
+}
+
+// 프로필별 단축키 (단축키가 없는 프로필은 제외, 두 프로필이 같은 단축키를 쓰면 HotkeyConflict)
+pub fn profile_hotkeys(profiles: &[Profile]) -> Result<Vec<(Hotkey, String)>, AppError> {
+    let mut hotkeys: Vec<(Hotkey, String)> = Vec::new();
+    for profile in profiles {
+        let Some(accelerator) = profile.hotkey.as_deref().filter(|hotkey| !hotkey.trim().is_empty()) else {
+            continue;
+        };
+        let hotkey = Hotkey::parse(accelerator)?;
+        if let Some((_, other)) = hotkeys.iter().find(|(existing, _)| *existing == hotkey) {
+            let other = profiles.iter().find(|p| p.id == *other).map_or(other.as_str(), |p| p.name.as_str());
+            return Err(AppError::HotkeyConflict {
+                hotkey: hotkey.canonical(),
+                profile: other.to_string(),
+            });
+        }
+        hotkeys.push((hotkey, profile.id.clone()));
+    }
+    Ok(hotkeys)
+}
+
+struct Binding {
+    id: i32,
+    hotkey: Hotkey,
+    profile_id: String,
+}
+
+// 등록한 단축키와 그 프로필
+pub struct HotkeyManager {
+    bindings: Mutex<Vec<Binding>>,
+    next_id: AtomicI32,
+}
+
+impl HotkeyManager {
+    // 단축키가 눌리면 그 프로필을 적용하는 스레드 시작
+    pub fn start(app: AppHandle) -> Self {
+        let (sender, receiver) = mpsc::channel::<i32>();
+        platform::start(sender);
+
+        std::thread::spawn(move || {
+            use tauri::Manager;
+            while let Ok(id) = receiver.recv() {
+                let state = app.state::<crate::state::AppState>();
+                let profile_id = {
+                    let bindings = state.hotkeys.bindings.lock_or_recover();
+                    bindings.iter().find(|binding| binding.id == id).map(|binding| binding.profile_id.clone())
+                };
+                let Some(profile_id) = profile_id else {
+                    continue;
+                };
+                log::info!("단축키로 프로필 적용: {}", profile_id);
+                if let Err(e) = apply_profile_by_id(&app, &profile_id, "hotkey") {
+                    log::warn!("단축키로 프로필 적용 실패 ({}): {}", profile_id, e);
+                }
+            }
+        });
+
+        HotkeyManager { bindings: Mutex::new(Vec::new()), next_id: AtomicI32::new(1) }
+    }
+
+    // 프로필 목록에 맞춰 등록
+    // 새 단축키를 모두 등록한 뒤에만 빠진 단축키를 해제하므로, 실패하면 이전 등록이 그대로 남습니다
+    pub fn sync(&self, profiles: &[Profile]) -> Result<(), AppError> {
+        let wanted = profile_hotkeys(profiles)?;
+        let mut bindings = self.bindings.lock_or_recover();
+
+        let mut added: Vec<Binding> = Vec::new();
+        for (hotkey, profile_id) in &wanted {
+            if bindings.iter().any(|binding| binding.hotkey == *hotkey) {
+                continue;
+            }
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            if let Err(detail) = platform::register(id, hotkey) {
+                for binding in &added {
+                    platform::unregister(binding.id);
+                }
+                return Err(AppError::HotkeyUnavailable { hotkey: hotkey.canonical(), detail });
+            }
+            added.push(Binding { id, hotkey: hotkey.clone(), profile_id: profile_id.clone() });
+        }
+
+        bindings.retain_mut(|binding| match wanted.iter().find(|(hotkey, _)| *hotkey == binding.hotkey) {
+            Some((_, profile_id)) => {
+                binding.profile_id = profile_id.clone();
+                true
+            }
+            None => {
+                platform::unregister(binding.id);
+                false
+            }
+        });
+        bindings.extend(added);
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::sync::{mpsc, OnceLock};
+    use windows::Win32::Foundation::{LPARAM, WPARAM};
+    use windows::Win32::System::Threading::GetCurrentThreadId;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_ALT, MOD_CONTROL, MOD_NOREPEAT, MOD_SHIFT, MOD_WIN,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetMessageW, PeekMessageW, PostThreadMessageW, MSG, PM_NOREMOVE, WM_APP, WM_HOTKEY,
+    };
+
+    use super::Hotkey;
+
+    enum Request {
+        Register(i32, Hotkey, mpsc::Sender<Result<(), String>>),
+        Unregister(i32),
+    }
+
+    // RegisterHotKey는 호출한 스레드의 메시지 대기열로 WM_HOTKEY를 보내므로 등록과 수신을 한 스레드에서 합니다
+    static THREAD: OnceLock<(u32, mpsc::Sender<Request>)> = OnceLock::new();
+
+    pub fn start(pressed: mpsc::Sender<i32>) {
+        let (request_tx, request_rx) = mpsc::channel::<Request>();
+        let (ready_tx, ready_rx) = mpsc::channel();
+        std::thread::spawn(move || unsafe {
+            // 메시지 대기열을 만든 뒤에 스레드 id를 알려야 PostThreadMessageW가 실패하지 않습니다
+            let mut message = MSG::default();
+            let _ = PeekMessageW(&mut message, None, WM_APP, WM_APP, PM_NOREMOVE);
+            let _ = ready_tx.send(GetCurrentThreadId());
+            while GetMessageW(&mut message, None, 0, 0).as_bool() {
+                match message.message {
+                    WM_HOTKEY => {
+                        let _ = pressed.send(message.wParam.0 as i32);
+                    }
+                    WM_APP => {
+                        while let Ok(request) = request_rx.try_recv() {
+                            match request {
+                                Request::Register(id, hotkey, reply) => {
+                                    let result = RegisterHotKey(None, id, modifiers(&hotkey), virtual_key(&hotkey))
+                                        .map_err(|e| e.message());
+                                    let _ = reply.send(result);
+                                }
+                                Request::Unregister(id) => {
+                                    let _ = UnregisterHotKey(None, id);
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+        if let Ok(thread_id) = ready_rx.recv() {
+            let _ = THREAD.set((thread_id, request_tx));
+        }
+    }
+
+    fn send(request: Request) -> Result<(), String> {
+        let (thread_id, sender) = THREAD.get().ok_or("hotkey thread is not running")?;
+        sender.send(request).map_err(|e| e.to_string())?;
+        unsafe { PostThreadMessageW(*thread_id, WM_APP, WPARAM(0), LPARAM(0)) }.map_err(|e| e.message())
+    }
+
+    pub fn register(id: i32, hotkey: &Hotkey) -> Result<(), String> {
+        let (reply_tx, reply_rx) = mpsc::channel();
+        send(Request::Register(id, hotkey.clone(), reply_tx))?;
+        reply_rx.recv().map_err(|e| e.to_string())?
+    }
+
+    pub fn unregister(id: i32) {
+        let _ = send(Request::Unregister(id));
+    }
+
+    fn modifiers(hotkey: &Hotkey) -> HOT_KEY_MODIFIERS {
+        let mut flags = MOD_NOREPEAT;
+        for (flag, modifier) in [
+            (super::MOD_CTRL, MOD_CONTROL),
+            (super::MOD_ALT, MOD_ALT),
+            (super::MOD_SHIFT, MOD_SHIFT),
+            (super::MOD_SUPER, MOD_WIN),
+        ] {
+            if hotkey.modifiers & flag != 0 {
+                flags |= modifier;
+            }
+        }
+        flags
+    }
+
+    // 가상 키 코드 (문자와 숫자는 대문자 ASCII 값과 같음)
+    fn virtual_key(hotkey: &Hotkey) -> u32 {
+        if let Some(number) = hotkey.key.strip_prefix('F').and_then(|n| n.parse::<u32>().ok()) {
+            return 0x70 + number - 1;
+        }
+        match hotkey.key.as_str() {
+            "Space" => 0x20,
+            "Tab" => 0x09,
+            "Enter" => 0x0D,
+            "Escape" => 0x1B,
+            "Backspace" => 0x08,
+            "Delete" => 0x2E,
+            "Insert" => 0x2D,
+            "Home" => 0x24,
+            "End" => 0x23,
+            "PageUp" => 0x21,
+            "PageDown" => 0x22,
+            "Left" => 0x25,
+            "Up" => 0x26,
+            "Right" => 0x27,
+            "Down" => 0x28,
+            key => key.bytes().next().map_or(0, u32::from),
+        }
+    }
+}
+
+// 앱과 별도의 X 연결에서 루트 창의 키를 잡고, 같은 스레드에서 요청 처리와 키 입력 수신을 번갈아 합니다
+// Wayland 세션의 XWayland는 XWayland 창에 초점이 있을 때만 키를 받으므로 등록하지 않습니다
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::ffi::{c_int, c_uint, CString};
+    use std::sync::atomic::{AtomicU8, Ordering};
+    use std::sync::mpsc::{self, TryRecvError};
+    use std::sync::OnceLock;
+    use std::time::Duration;
+    use x11_dl::xlib;
+
+    use super::Hotkey;
+
+    enum Request {
+        Register(i32, Hotkey, mpsc::Sender<Result<(), String>>),
+        Unregister(i32),
+    }
+
+    static REQUESTS: OnceLock<mpsc::Sender<Request>> = OnceLock::new();
+    // 등록하는 동안 X 서버가 보낸 오류 (다른 프로그램이 이미 잡은 단축키는 BadAccess)
+    static GRAB_ERROR: AtomicU8 = AtomicU8::new(0);
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+    const MODIFIER_MASK: c_uint = xlib::ShiftMask | xlib::ControlMask | xlib::Mod1Mask | xlib::Mod4Mask;
+    // Caps Lock이나 Num Lock이 켜져 있어도 눌리도록 같이 잡습니다
+    const LOCK_MASKS: [c_uint; 4] = [0, xlib::LockMask, xlib::Mod2Mask, xlib::LockMask | xlib::Mod2Mask];
+
+    struct Grab {
+        id: i32,
+        keycode: c_int,
+        modifiers: c_uint,
+    }
+
+    unsafe extern "C" fn record_error(_display: *mut xlib::Display, event: *mut xlib::XErrorEvent) -> c_int {
+        GRAB_ERROR.store((*event).error_code, Ordering::Relaxed);
+        0
+    }
+
+    pub fn start(pressed: mpsc::Sender<i32>) {
+        if std::env::var("XDG_SESSION_TYPE").is_ok_and(|session| session == "wayland") {
+            log::info!("Wayland 세션에서는 전역 단축키를 등록하지 않습니다");
+            return;
+        }
+        let (request_tx, request_rx) = mpsc::channel::<Request>();
+        let (ready_tx, ready_rx) = mpsc::channel();
+        std::thread::spawn(move || unsafe {
+            let xlib = match xlib::Xlib::open() {
+                Ok(xlib) => xlib,
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e.to_string()));
+                    return;
+                }
+            };
+            let display = (xlib.XOpenDisplay)(std::ptr::null());
+            if display.is_null() {
+                let _ = ready_tx.send(Err("cannot open the X display".to_string()));
+                return;
+            }
+            let _ = ready_tx.send(Ok(()));
+            let root = (xlib.XDefaultRootWindow)(display);
+            let mut grabs: Vec<Grab> = Vec::new();
+            loop {
+                while (xlib.XPending)(display) > 0 {
+                    let mut event: xlib::XEvent = std::mem::zeroed();
+                    (xlib.XNextEvent)(display, &mut event);
+                    if event.get_type() != xlib::KeyPress {
+                        continue;
+                    }
+                    let (keycode, state) = (event.key.keycode, event.key.state & MODIFIER_MASK);
+                    if let Some(grab) = grabs.iter().find(|g| g.keycode as c_uint == keycode && g.modifiers == state) {
+                        let _ = pressed.send(grab.id);
+                    }
+                }
+                loop {
+                    match request_rx.try_recv() {
+                        Ok(Request::Register(id, hotkey, reply)) => {
+                            let result = grab_key(&xlib, display, root, &hotkey)
+                                .map(|(keycode, modifiers)| grabs.push(Grab { id, keycode, modifiers }));
+                            let _ = reply.send(result);
+                        }
+                        Ok(Request::Unregister(id)) => {
+                            if let Some(pos) = grabs.iter().position(|grab| grab.id == id) {
+                                let grab = grabs.remove(pos);
+                                ungrab_key(&xlib, display, root, grab.keycode, grab.modifiers);
+                                (xlib.XFlush)(display);
+                            }
+                        }
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            (xlib.XCloseDisplay)(display);
+                            return;
+                        }
+                    }
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        });
+        match ready_rx.recv() {
+            Ok(Ok(())) => {
+                let _ = REQUESTS.set(request_tx);
+            }
+            Ok(Err(detail)) => log::warn!("X 서버에 연결할 수 없어 전역 단축키를 등록하지 않습니다: {}", detail),
+            Err(_) => {}
+        }
+    }
+
+    // 잡은 (키 코드, 수정 키)
+    unsafe fn grab_key(
+        xlib: &xlib::Xlib,
+        display: *mut xlib::Display,
+        root: xlib::Window,
+        hotkey: &Hotkey,
+    ) -> Result<(c_int, c_uint), String> {
+        let name = CString::new(keysym_name(&hotkey.key)).map_err(|e| e.to_string())?;
+        let keysym = (xlib.XStringToKeysym)(name.as_ptr());
+        let keycode = if keysym == 0 { 0 } else { c_int::from((xlib.XKeysymToKeycode)(display, keysym)) };
+        if keycode == 0 {
+            return Err(format!("no key code for {}", hotkey.key));
+        }
+        let modifiers = modifiers(hotkey);
+
+        // 잡기 실패는 나중에 오류 이벤트로 오므로 XSync로 기다리는 동안만 오류 처리기를 바꿉니다
+        GRAB_ERROR.store(0, Ordering::Relaxed);
+        let previous = (xlib.XSetErrorHandler)(Some(record_error));
+        for lock in LOCK_MASKS {
+            let async_mode = xlib::GrabModeAsync;
+            (xlib.XGrabKey)(display, keycode, modifiers | lock, root, xlib::False, async_mode, async_mode);
+        }
+        (xlib.XSync)(display, xlib::False);
+        let error = GRAB_ERROR.load(Ordering::Relaxed);
+        if error != 0 {
+            ungrab_key(xlib, display, root, keycode, modifiers);
+            (xlib.XSync)(display, xlib::False);
+        }
+        (xlib.XSetErrorHandler)(previous);
+
+        match error {
+            0 => Ok((keycode, modifiers)),
+            xlib::BadAccess => Err("already registered by another application".to_string()),
+            code => Err(format!("X error {}", code)),
+        }
+    }
+
+    unsafe fn ungrab_key(
+        xlib: &xlib::Xlib,
+        display: *mut xlib::Display,
+        root: xlib::Window,
+        keycode: c_int,
+        modifiers: c_uint,
+    ) {
+        for lock in LOCK_MASKS {
+            (xlib.XUngrabKey)(display, keycode, modifiers | lock, root);
+        }
+    }
+
+    fn send(request: Request) -> Result<(), String> {
+        let sender = REQUESTS.get().ok_or("hotkey thread is not running")?;
+        sender.send(request).map_err(|e| e.to_string())
+    }
+
+    // X 서버에 연결하지 못했으면 등록하지 않고 성공으로 봅니다 (저장은 실패하지 않음)
+    pub fn register(id: i32, hotkey: &Hotkey) -> Result<(), String> {
+        if REQUESTS.get().is_none() {
+            log::warn!("전역 단축키를 받을 수 없는 세션이라 등록하지 않습니다: {}", hotkey.canonical());
+            return Ok(());
+        }
+        let (reply_tx, reply_rx) = mpsc::channel();
+        send(Request::Register(id, hotkey.clone(), reply_tx))?;
+        reply_rx.recv().map_err(|e| e.to_string())?
+    }
+
+    pub fn unregister(id: i32) {
+        let _ = send(Request::Unregister(id));
+    }
+
+    fn modifiers(hotkey: &Hotkey) -> c_uint {
+        [
+            (super::MOD_CTRL, xlib::ControlMask),
+            (super::MOD_ALT, xlib::Mod1Mask),
+            (super::MOD_SHIFT, xlib::ShiftMask),
+            (super::MOD_SUPER, xlib::Mod4Mask),
+        ]
+        .iter()
+        .filter(|(flag, _)| hotkey.modifiers & flag != 0)
+        .fold(0, |mask, (_, modifier)| mask | modifier)
+    }
+
+    // XStringToKeysym의 이름 (문자, 숫자, F1~F24는 그대로)
+    pub(super) fn keysym_name(key: &str) -> &str {
+        match key {
+            "Space" => "space",
+            "Enter" => "Return",
+            "Backspace" => "BackSpace",
+            "PageUp" => "Prior",
+            "PageDown" => "Next",
+            key => key,
+        }
+    }
+}
+
+// Carbon RegisterEventHotKey로 등록하고, 눌리면 앱의 메인 이벤트 루프가 처리기를 부릅니다
+// Carbon 이벤트 함수는 메인 스레드에서만 불러야 하므로 등록과 해제를 메인 큐에서 실행합니다
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::collections::HashMap;
+    use std::ffi::c_void;
+    use std::sync::{mpsc, Mutex, OnceLock};
+
+    use super::Hotkey;
+    use crate::sync::LockExt;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct EventHotKeyId {
+        signature: u32,
+        id: u32,
+    }
+
+    #[repr(C)]
+    struct EventTypeSpec {
+        event_class: u32,
+        event_kind: u32,
+    }
+
+    type EventHandlerProc = unsafe extern "C" fn(call_ref: *mut c_void, event: *mut c_void, user_data: *mut c_void) -> i32;
+
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        fn GetApplicationEventTarget() -> *mut c_void;
+        fn InstallEventHandler(
+            target: *mut c_void,
+            handler: EventHandlerProc,
+            count: u32,
+            types: *const EventTypeSpec,
+            user_data: *mut c_void,
+            handler_ref: *mut *mut c_void,
+        ) -> i32;
+        fn RegisterEventHotKey(
+            key_code: u32,
+            modifiers: u32,
+            id: EventHotKeyId,
+            target: *mut c_void,
+            options: u32,
+            hotkey_ref: *mut *mut c_void,
+        ) -> i32;
+        fn UnregisterEventHotKey(hotkey_ref: *mut c_void) -> i32;
+        fn GetEventParameter(
+            event: *mut c_void,
+            name: u32,
+            desired_type: u32,
+            actual_type: *mut u32,
+            size: usize,
+            actual_size: *mut usize,
+            data: *mut c_void,
+        ) -> i32;
+    }
+
+    // libdispatch (libSystem)
+    extern "C" {
+        static _dispatch_main_q: c_void;
+        fn dispatch_sync_f(queue: *const c_void, context: *mut c_void, work: extern "C" fn(*mut c_void));
+        fn pthread_main_np() -> i32;
+    }
+
+    const fn four_cc(code: &[u8; 4]) -> u32 {
+        u32::from_be_bytes(*code)
+    }
+
+    const SIGNATURE: u32 = four_cc(b"DSMh");
+    const EVENT_CLASS_KEYBOARD: u32 = four_cc(b"keyb");
+    const EVENT_HOTKEY_PRESSED: u32 = 5;
+    const PARAM_DIRECT_OBJECT: u32 = four_cc(b"----");
+    const TYPE_EVENT_HOTKEY_ID: u32 = four_cc(b"hkid");
+    // 다른 프로그램이 이미 등록한 단축키
+    const HOTKEY_EXISTS: i32 = -9878;
+
+    static PRESSED: OnceLock<Mutex<mpsc::Sender<i32>>> = OnceLock::new();
+    // 프로필 단축키 id → EventHotKeyRef
+    static REGISTERED: Mutex<Option<HashMap<i32, usize>>> = Mutex::new(None);
+
+    struct Job<F, T> {
+        task: Option<F>,
+        result: Option<T>,
+    }
+
+    extern "C" fn run_job<F: FnOnce() -> T, T>(context: *mut c_void) {
+        let job = unsafe { &mut *(context as *mut Job<F, T>) };
+        job.result = job.task.take().map(|task| task());
+    }
+
+    // 메인 스레드에서 실행하고 끝날 때까지 기다립니다 (이미 메인 스레드면 바로 실행)
+    fn on_main_thread<F: FnOnce() -> T + Send, T: Send>(task: F) -> T {
+        if unsafe { pthread_main_np() } != 0 {
+            return task();
+        }
+        let mut job = Job { task: Some(task), result: None };
+        unsafe {
+            dispatch_sync_f(
+                &_dispatch_main_q as *const c_void,
+                &mut job as *mut Job<F, T> as *mut c_void,
+                run_job::<F, T>,
+            );
+        }
+        job.result.expect("main queue did not run the hotkey task")
+    }
+
+    unsafe extern "C" fn hotkey_pressed(_call_ref: *mut c_void, event: *mut c_void, _user_data: *mut c_void) -> i32 {
+        let mut id = EventHotKeyId { signature: 0, id: 0 };
+        let status = GetEventParameter(
+            event,
+            PARAM_DIRECT_OBJECT,
+            TYPE_EVENT_HOTKEY_ID,
+            std::ptr::null_mut(),
+            std::mem::size_of::<EventHotKeyId>(),
+            std::ptr::null_mut(),
+            &mut id as *mut EventHotKeyId as *mut c_void,
+        );
+        if status == 0 && id.signature == SIGNATURE {
+            if let Some(pressed) = PRESSED.get() {
+                let _ = pressed.lock_or_recover().send(id.id as i32);
+            }
+        }
+        0
+    }
+
+    pub fn start(pressed: mpsc::Sender<i32>) {
+        if PRESSED.set(Mutex::new(pressed)).is_err() {
+            return;
+        }
+        let status = on_main_thread(|| unsafe {
+            let spec = EventTypeSpec { event_class: EVENT_CLASS_KEYBOARD, event_kind: EVENT_HOTKEY_PRESSED };
+            let mut handler_ref = std::ptr::null_mut();
+            InstallEventHandler(
+                GetApplicationEventTarget(),
+                hotkey_pressed,
+                1,
+                &spec,
+                std::ptr::null_mut(),
+                &mut handler_ref,
+            )
+        });
+        if status != 0 {
+            log::warn!("전역 단축키 처리기를 설치할 수 없습니다: OSStatus {}", status);
+        }
+    }
+
+    pub fn register(id: i32, hotkey: &Hotkey) -> Result<(), String> {
+        let key_code = key_code(&hotkey.key).ok_or_else(|| format!("no key code for {}", hotkey.key))?;
+        let modifiers = modifiers(hotkey);
+        let hotkey_id = EventHotKeyId { signature: SIGNATURE, id: id as u32 };
+        let (status, hotkey_ref) = on_main_thread(move || unsafe {
+            let mut hotkey_ref = std::ptr::null_mut();
+            let status =
+                RegisterEventHotKey(key_code, modifiers, hotkey_id, GetApplicationEventTarget(), 0, &mut hotkey_ref);
+            (status, hotkey_ref as usize)
+        });
+        match status {
+            0 => {
+                REGISTERED.lock_or_recover().get_or_insert_with(HashMap::new).insert(id, hotkey_ref);
+                Ok(())
+            }
+            HOTKEY_EXISTS => Err("already registered by another application".to_string()),
+            status => Err(format!("OSStatus {}", status)),
+        }
+    }
+
+    pub fn unregister(id: i32) {
+        let hotkey_ref = REGISTERED.lock_or_recover().as_mut().and_then(|registered| registered.remove(&id));
+        if let Some(hotkey_ref) = hotkey_ref {
+            on_main_thread(move || unsafe {
+                UnregisterEventHotKey(hotkey_ref as *mut c_void);
+            });
+        }
+    }
+
+    // Carbon 수정 키 (cmdKey, shiftKey, optionKey, controlKey)
+    fn modifiers(hotkey: &Hotkey) -> u32 {
+        [
+            (super::MOD_CTRL, 0x1000),
+            (super::MOD_ALT, 0x0800),
+            (super::MOD_SHIFT, 0x0200),
+            (super::MOD_SUPER, 0x0100),
+        ]
+        .iter()
+        .filter(|(flag, _)| hotkey.modifiers & flag != 0)
+        .fold(0, |mask, (_, modifier)| mask | modifier)
+    }
+
+    // ANSI 배열의 가상 키 코드 (kVK_*, F21~F24는 없음)
+    fn key_code(key: &str) -> Option<u32> {
+        const LETTERS: [u32; 26] = [
+            0x00, 0x0B, 0x08, 0x02, 0x0E, 0x03, 0x05, 0x04, 0x22, 0x26, 0x28, 0x25, 0x2E, 0x2D, 0x1F, 0x23, 0x0C,
+            0x0F, 0x01, 0x11, 0x20, 0x09, 0x0D, 0x07, 0x10, 0x06,
+        ];
+        const DIGITS: [u32; 10] = [0x1D, 0x12, 0x13, 0x14, 0x15, 0x17, 0x16, 0x1A, 0x1C, 0x19];
+        const FUNCTION_KEYS: [u32; 20] = [
+            0x7A, 0x78, 0x63, 0x76, 0x60, 0x61, 0x62, 0x64, 0x65, 0x6D, 0x67, 0x6F, 0x69, 0x6B, 0x71, 0x6A, 0x40,
+            0x4F, 0x50, 0x5A,
+        ];
+        let code = match key {
+            "Space" => 0x31,
+            "Tab" => 0x30,
+            "Enter" => 0x24,
+            "Escape" => 0x35,
+            "Backspace" => 0x33,
+            "Delete" => 0x75,
+            "Insert" => 0x72,
+            "Home" => 0x73,
+            "End" => 0x77,
+            "PageUp" => 0x74,
+            "PageDown" => 0x79,
+            "Left" => 0x7B,
+            "Right" => 0x7C,
+            "Down" => 0x7D,
+            "Up" => 0x7E,
+            key => {
+                if let Some(number) = key.strip_prefix('F').filter(|rest| !rest.is_empty()) {
+                    let index: usize = number.parse().ok()?;
+                    return FUNCTION_KEYS.get(index.checked_sub(1)?).copied();
+                }
+                let byte = *key.as_bytes().first()?;
+                match byte {
+                    b'A'..=b'Z' if key.len() == 1 => LETTERS[usize::from(byte - b'A')],
+                    b'0'..=b'9' if key.len() == 1 => DIGITS[usize::from(byte - b'0')],
+                    _ => return None,
+                }
+            }
+        };
+        Some(code)
+    }
+}
+
+// 전역 단축키를 등록할 방법이 없는 플랫폼: 저장하면 HotkeyUnavailable 오류로 알립니다
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+mod platform {
+    use std::sync::mpsc;
+
+    use super::Hotkey;
+
+    pub fn start(pressed: mpsc::Sender<i32>) {
+        drop(pressed);
+    }
+
+    pub fn register(id: i32, hotkey: &Hotkey) -> Result<(), String> {
+        let _ = (id, hotkey);
+        Err("global hotkeys are not supported on this platform".to_string())
+    }
+
+    pub fn unregister(id: i32) {
+        let _ = id;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: &str, hotkey: Option<&str>) -> Profile {
+        Profile {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            displays: Vec::new(),
+            audio_settings: None,
+            created_at: String::new(),
+            tags: Vec::new(),
+            color: None,
+            hotkey: hotkey.map(str::to_string),
+            pre_apply_command: None,
+            post_apply_command: None,
+            auto_apply_on_device: None,
+            output_follows_display: false,
+        }
+    }
+
+    #[test]
+    fn parses_and_normalizes_accelerators() {
+        let ctrl_or_cmd = if cfg!(target_os = "macos") { "Super" } else { "Ctrl" };
+        assert_eq!(Hotkey::parse("CmdOrCtrl+Shift+1").unwrap().canonical(), format!("{}+Shift+1", ctrl_or_cmd));
+        assert_eq!(Hotkey::parse("shift + alt + f5").unwrap().canonical(), "Alt+Shift+F5");
+        assert_eq!(Hotkey::parse("Ctrl+ArrowLeft").unwrap().key, "Left");
+
+        for invalid in ["", "A", "Ctrl+", "Ctrl+Ctrl+A", "Hyper+A", "Ctrl+F25", "Ctrl+Shift+Foo"] {
+            assert!(matches!(Hotkey::parse(invalid), Err(AppError::InvalidHotkey { .. })), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn reports_profiles_sharing_a_hotkey() {
+        let profiles = [
+            profile("desk", Some("Ctrl+Alt+1")),
+            profile("tv", Some("Ctrl+Alt+2")),
+            profile("plain", None),
+        ];
+        let hotkeys = profile_hotkeys(&profiles).unwrap();
+        assert_eq!(hotkeys.len(), 2);
+        assert_eq!(hotkeys[1].1, "tv");
+
+        // 표기가 달라도 같은 조합이면 겹칩니다
+        let clash = [profile("desk", Some("Ctrl+Alt+1")), profile("tv", Some("alt+control+1"))];
+        match profile_hotkeys(&clash) {
+            Err(AppError::HotkeyConflict { hotkey, profile }) => {
+                assert_eq!((hotkey.as_str(), profile.as_str()), ("Ctrl+Alt+1", "DESK"))
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn maps_keys_to_x11_keysym_names() {
+        let keys = ["A", "7", "F12", "Space", "Enter", "PageUp", "Left"];
+        let names: Vec<&str> = keys.iter().map(|key| platform::keysym_name(key)).collect();
+        assert_eq!(names, vec!["A", "7", "F12", "space", "Return", "Prior", "Left"]);
+    }
+}
@@ -0,0 +1,180 @@
+// 실행 중인 앱으로 명령줄 요청을 넘기는 로컬 연결
+// 앱은 127.0.0.1의 임의 포트에서 기다리며 포트와 토큰을 {앱 데이터}/instance.json에 적어 둡니다
+// 명령줄(--apply)은 이 파일로 앱을 찾아 요청을 보내고, 앱이 없으면 창 없이 직접 적용합니다
+//
+// 메시지 형식 (한 줄에 JSON 하나)
+//   명령줄 → 앱: {"token":"...","command":"apply","profile":"Desk Setup"}
+//   앱 → 명령줄: {"ok":true,"result":{...}} / {"ok":false,"error":{"code":...,"message":...}}
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::error::AppError;
+use crate::profiles::write_atomic_private;
+
+pub const INSTANCE_FILE_NAME: &str = "instance.json";
+// 연결이 없는 앱을 기다리지 않도록 짧게 시도합니다
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+const MAX_REQUEST_SIZE: u64 = 64 * 1024;
+// 요청 줄을 보내지 않는 연결이 스레드를 붙잡지 않도록 합니다
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct InstanceInfo {
+    pid: u32,
+    port: u16,
+    token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InstanceRequest {
+    #[serde(default)]
+    pub token: String,
+    pub command: String,
+    pub profile: String,
+}
+
+// 요청을 받을 준비를 하고 instance.json을 씁니다 (연결마다 스레드에서 handle 실행)
+// 파일에 토큰이 들어 있으므로 unix에서는 소유자만 읽을 수 있게 만듭니다
+pub fn listen(
+    instance_file: &Path,
+    handle: impl Fn(&InstanceRequest) -> Result<Value, AppError> + Send + Sync + 'static,
+) -> io::Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let info = InstanceInfo {
+        pid: std::process::id(),
+        port: listener.local_addr()?.port(),
+        token: uuid::Uuid::new_v4().simple().to_string(),
+    };
+    let content = serde_json::to_vec(&info).map_err(io::Error::other)?;
+    write_atomic_private(instance_file, &content)?;
+    log::info!("명령줄 요청 대기: 127.0.0.1:{}", info.port);
+
+    let handle = std::sync::Arc::new(handle);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::warn!("명령줄 연결 수락 실패: {}", e);
+                    continue;
+                }
+            };
+            let (handle, token) = (handle.clone(), info.token.clone());
+            std::thread::spawn(move || {
+                if let Err(e) = serve(stream, &token, &*handle) {
+                    log::warn!("명령줄 요청 처리 실패: {}", e);
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+fn serve(
+    stream: TcpStream,
+    token: &str,
+    handle: &dyn Fn(&InstanceRequest) -> Result<Value, AppError>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new((&stream).take(MAX_REQUEST_SIZE)).read_line(&mut line)?;
+    let response = match serde_json::from_str::<InstanceRequest>(&line) {
+        Ok(request) if constant_time_eq(request.token.as_bytes(), token.as_bytes()) => {
+            log::info!("명령줄 요청: {} ({})", request.command, request.profile);
+            match handle(&request) {
+                Ok(result) => json!({ "ok": true, "result": result }),
+                Err(error) => json!({ "ok": false, "error": error }),
+            }
+        }
+        Ok(_) => json!({ "ok": false, "error": { "code": "Unauthorized", "message": "invalid token" } }),
+        Err(e) => json!({ "ok": false, "error": { "code": "BadRequest", "message": e.to_string() } }),
+    };
+    let mut stream = stream;
+    writeln!(stream, "{}", response)?;
+    stream.flush()
+}
+
+// 토큰 비교 (일치하는 앞부분의 길이가 응답 시간으로 드러나지 않도록)
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// 실행 중인 앱에 요청을 보내고 응답을 기다립니다
+// 앱이 실행 중이 아니면 (파일이 없거나 연결할 수 없으면) None
+pub fn forward(instance_file: &Path, command: &str, profile: &str) -> Option<io::Result<Value>> {
+    let info: InstanceInfo = serde_json::from_slice(&std::fs::read(instance_file).ok()?).ok()?;
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, info.port));
+    let mut stream = match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) {
+        Ok(stream) => stream,
+        Err(e) => {
+            // 앱이 비정상 종료해 남은 파일
+            log::info!("실행 중인 앱에 연결할 수 없습니다 (pid {}): {}", info.pid, e);
+            return None;
+        }
+    };
+    let request = InstanceRequest { token: info.token, command: command.to_string(), profile: profile.to_string() };
+    Some((|| {
+        writeln!(stream, "{}", serde_json::to_string(&request).map_err(io::Error::other)?)?;
+        stream.flush()?;
+        // 적용이 끝날 때까지 기다립니다 (앱 쪽 외부 명령에는 제한 시간이 있습니다)
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        serde_json::from_str(&line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forwards_requests_to_the_running_instance() {
+        let dir = std::env::temp_dir().join(format!("dsm-instance-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join(INSTANCE_FILE_NAME);
+        assert!(forward(&file, "apply", "Desk").is_none());
+
+        listen(&file, |request| match request.profile.as_str() {
+            "Desk" => Ok(json!({ "profile_id": "desk" })),
+            other => Err(AppError::ProfileNotFound { profile_id: other.to_string() }),
+        })
+        .unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&file).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        let response = forward(&file, "apply", "Desk").unwrap().unwrap();
+        assert_eq!(response, json!({ "ok": true, "result": { "profile_id": "desk" } }));
+        let response = forward(&file, "apply", "Office").unwrap().unwrap();
+        assert_eq!(response["error"]["code"], "ProfileNotFound");
+
+        // 토큰이 맞지 않는 요청은 처리하지 않습니다
+        let mut info: InstanceInfo = serde_json::from_slice(&std::fs::read(&file).unwrap()).unwrap();
+        info.token = "wrong".to_string();
+        std::fs::write(&file, serde_json::to_vec(&info).unwrap()).unwrap();
+        assert_eq!(forward(&file, "apply", "Desk").unwrap().unwrap()["error"]["code"], "Unauthorized");
+
+        // 요청 줄을 보내지 않는 연결은 제한 시간 뒤에 닫습니다
+        let idle = TcpStream::connect(("127.0.0.1", info.port)).unwrap();
+        idle.set_read_timeout(Some(REQUEST_TIMEOUT * 3)).unwrap();
+        let started = std::time::Instant::now();
+        let mut buffer = Vec::new();
+        let _ = (&idle).read_to_end(&mut buffer);
+        assert!(started.elapsed() < REQUEST_TIMEOUT * 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn compares_tokens_exactly() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+        assert!(!constant_time_eq(b"", b"secret"));
+    }
+}
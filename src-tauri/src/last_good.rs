@@ -0,0 +1,308 @@
+// 마지막으로 정상 동작이 확인된 구성 (last known good)
+// 프로필과 별개로, 적용 후 확인에서 프로필과 차이가 없었던 구성을 조용히 저장해 두었다가
+// 잘못된 적용이나 불안정한 도크로 화면이 꺼졌을 때 트레이의 "디스플레이 복구"로 되돌립니다
+// 확인에 실패한 구성은 만들 수 없으므로 저장된 구성을 덮어쓰지 않습니다
+
+use serde::{Deserialize, Serialize};
+
+use crate::audio::{capture_audio_settings, AudioDevice, AudioSettings};
+use crate::display::{find_connected, DisplayInfo};
+use crate::profiles::{profile_drift, Profile};
+
+// 복구할 때 적용 대기열과 기록에 표시할 id
+pub const LAST_KNOWN_GOOD_ID: &str = "last-known-good";
+
+// 모니터를 다시 연결해 id가 바뀌어도 같은 모니터를 찾기 위한 정보
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DisplayFingerprint {
+    pub id: u32,
+    pub name: String,
+    // 이름이 같은 모니터 중 몇 번째인지 (id 순)
+    pub ordinal: usize,
+}
+
+// 장치 id가 바뀌어도 이름으로 찾기 위한 정보
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AudioFingerprint {
+    pub device_type: String,
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LastKnownGood {
+    pub saved_at: String,
+    // 확인에 사용한 프로필
+    pub profile_id: String,
+    pub displays: Vec<DisplayInfo>,
+    pub audio_settings: AudioSettings,
+    pub display_fingerprints: Vec<DisplayFingerprint>,
+    pub audio_fingerprints: Vec<AudioFingerprint>,
+}
+
+impl LastKnownGood {
+    // 현재 구성이 프로필과 일치하고 기본 출력 장치를 찾을 수 있을 때만 만듭니다
+    pub fn verified(profile: &Profile, displays: &[DisplayInfo], devices: &[AudioDevice]) -> Option<Self> {
+        match profile_drift(profile, displays, devices) {
+            Ok(drift) if drift.is_empty() => {}
+            Ok(drift) => {
+                log::info!("정상 구성으로 저장하지 않습니다 (프로필과 차이 있음): {:?}", drift);
+                return None;
+            }
+            Err(e) => {
+                log::info!("정상 구성으로 저장하지 않습니다: {}", e);
+                return None;
+            }
+        }
+        let audio_settings = capture_audio_settings(devices);
+        let has_outputs = devices.iter().any(|device| device.device_type == "output");
+        if displays.is_empty() || (has_outputs && audio_settings.output_device.is_none()) {
+            log::info!("정상 구성으로 저장하지 않습니다 (디스플레이 또는 기본 출력 장치 없음)");
+            return None;
+        }
+
+        let defaults = [&audio_settings.output_device, &audio_settings.input_device];
+        Some(LastKnownGood {
+            saved_at: chrono::Local::now().to_rfc3339(),
+            profile_id: profile.id.clone(),
+            displays: displays.to_vec(),
+            display_fingerprints: display_fingerprints(displays),
+            audio_fingerprints: devices
+                .iter()
+                .filter(|device| defaults.iter().any(|id| id.as_deref() == Some(device.id.as_str())))
+                .map(|device| AudioFingerprint {
+                    device_type: device.device_type.clone(),
+                    id: device.id.clone(),
+                    name: device.name.clone(),
+                })
+                .collect(),
+            audio_settings,
+        })
+    }
+
+    // 지금 연결된 장치의 id로 바꾼 복구용 프로필
+    // 찾지 못한 디스플레이는 저장된 id를 그대로 두므로 부분 적용에서 건너뛰고 경고로 알립니다
+    pub fn to_profile(&self, connected: &[DisplayInfo], devices: &[AudioDevice]) -> Profile {
+        let current = display_fingerprints(connected);
+        let mut claimed: Vec<u32> = Vec::new();
+        let mut id_map: Vec<(u32, u32)> = Vec::new();
+        // EDID 식별값(stable_id)이 같은 모니터를 먼저 찾고, 나머지는 이름과 순서로 찾습니다
+        for saved in self.displays.iter().filter(|display| display.stable_id.is_some()) {
+            let available: Vec<DisplayInfo> =
+                connected.iter().filter(|display| !claimed.contains(&display.id)).cloned().collect();
+            if let Some(matched) = find_connected(saved, &available).filter(|matched| matched.stable_id.is_some()) {
+                claimed.push(matched.id);
+                id_map.push((saved.id, matched.id));
+            }
+        }
+        for saved in &self.display_fingerprints {
+            if id_map.iter().any(|(id, _)| *id == saved.id) {
+                continue;
+            }
+            // 양쪽 다 EDID를 읽었는데 식별값이 다르면 이름이 같아도 다른 모니터
+            let saved_edid = self.displays.iter().any(|display| display.id == saved.id && display.stable_id.is_some());
+            let comparable = |id: u32| !saved_edid || connected.iter().any(|d| d.id == id && d.stable_id.is_none());
+            let same_name: Vec<&DisplayFingerprint> = current
+                .iter()
+                .filter(|fingerprint| fingerprint.name == saved.name && !claimed.contains(&fingerprint.id))
+                .filter(|fingerprint| comparable(fingerprint.id))
+                .collect();
+            let matched = same_name
+                .iter()
+                .find(|fingerprint| fingerprint.id == saved.id)
+                .or_else(|| same_name.iter().find(|fingerprint| fingerprint.ordinal == saved.ordinal))
+                .or_else(|| same_name.first());
+            if let Some(matched) = matched {
+                claimed.push(matched.id);
+                id_map.push((saved.id, matched.id));
+            }
+        }
+        let remap = |id: u32| id_map.iter().find(|(saved, _)| *saved == id).map(|(_, current)| *current);
+
+        let displays = self
+            .displays
+            .iter()
+            .filter_map(|display| {
+                let mut display = display.clone();
+                match remap(display.id) {
+                    Some(id) => {
+                        if id != display.id {
+                            log::info!("{}: id {} → {}", display.name, display.id, id);
+                        }
+                        display.id = id;
+                    }
+                    // 찾지 못한 모니터의 id를 다른 모니터가 쓰고 있으면 그 모니터를 바꾸지 않도록 제외
+                    None if connected.iter().any(|d| d.id == display.id) => {
+                        log::warn!("{}: 연결된 모니터에서 찾을 수 없어 복구에서 제외합니다", display.name);
+                        return None;
+                    }
+                    None => {}
+                }
+                display.mirror_of = display.mirror_of.and_then(remap);
+                Some(display)
+            })
+            .collect();
+
+        let mut audio_settings = self.audio_settings.clone();
+        audio_settings.output_device = self.current_device_id(&audio_settings.output_device, "output", devices);
+        audio_settings.input_device = self.current_device_id(&audio_settings.input_device, "input", devices);
+
+        Profile {
+            id: LAST_KNOWN_GOOD_ID.to_string(),
+            name: LAST_KNOWN_GOOD_ID.to_string(),
+            displays,
+            audio_settings: Some(audio_settings),
+            created_at: self.saved_at.clone(),
+            tags: Vec::new(),
+            color: None,
+            hotkey: None,
+            pre_apply_command: None,
+            post_apply_command: None,
+            auto_apply_on_device: None,
+            output_follows_display: false,
+        }
+    }
+
+    // 저장한 장치가 같은 id로 없으면 같은 이름의 장치, 그것도 없으면 바꾸지 않음 (None)
+    fn current_device_id(&self, saved: &Option<String>, device_type: &str, devices: &[AudioDevice]) -> Option<String> {
+        let saved = saved.as_deref()?;
+        let of_type = || devices.iter().filter(|device| device.device_type == device_type);
+        if of_type().any(|device| device.id == saved) {
+            return Some(saved.to_string());
+        }
+        let name = self
+            .audio_fingerprints
+            .iter()
+            .find(|fingerprint| fingerprint.device_type == device_type && fingerprint.id == saved)
+            .map(|fingerprint| fingerprint.name.as_str())?;
+        let found = of_type().find(|device| device.name == name).map(|device| device.id.clone());
+        if found.is_none() {
+            log::warn!("정상 구성의 {} 장치를 찾을 수 없어 그대로 둡니다: {}", device_type, name);
+        }
+        found
+    }
+}
+
+fn display_fingerprints(displays: &[DisplayInfo]) -> Vec<DisplayFingerprint> {
+    displays
+        .iter()
+        .map(|display| DisplayFingerprint {
+            id: display.id,
+            name: display.name.clone(),
+            ordinal: displays
+                .iter()
+                .filter(|other| other.name == display.name && other.id < display.id)
+                .count(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(id: u32, name: &str, x: i32, mirror_of: Option<u32>) -> DisplayInfo {
+        DisplayInfo {
+            id,
+            name: name.to_string(),
+            width: 1920,
+            height: 1080,
+            logical_width: 1920,
+            logical_height: 1080,
+            refresh_rate: 0,
+            x,
+            y: 0,
+            scale_factor: 1.0,
+            is_primary: x == 0,
+            rotation: 0,
+            mirror_of,
+            persistent_id: String::new(),
+            stable_id: None,
+            enabled: true,
+            brightness: None,
+        }
+    }
+
+    fn device(id: &str, name: &str, is_default: bool) -> AudioDevice {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "name": name, "is_default": is_default, "device_type": "output"
+        }))
+        .unwrap()
+    }
+
+    fn profile(displays: &[DisplayInfo], devices: &[AudioDevice]) -> Profile {
+        Profile {
+            id: "desk".to_string(),
+            name: "Desk".to_string(),
+            displays: displays.to_vec(),
+            audio_settings: Some(capture_audio_settings(devices)),
+            created_at: String::new(),
+            tags: Vec::new(),
+            color: None,
+            hotkey: None,
+            pre_apply_command: None,
+            post_apply_command: None,
+            auto_apply_on_device: None,
+            output_follows_display: false,
+        }
+    }
+
+    #[test]
+    fn only_verified_configurations_are_kept() {
+        let displays = [display(1, "Built-in", 0, None), display(2, "DELL", 1920, None)];
+        let devices = [device("speakers", "Speakers", true)];
+        let saved = profile(&displays, &devices);
+        assert!(LastKnownGood::verified(&saved, &displays, &devices).is_some());
+
+        // 모니터가 빠졌거나 기본 출력 장치가 없으면 저장하지 않습니다
+        assert!(LastKnownGood::verified(&saved, &displays[..1], &devices).is_none());
+        let no_default = [device("speakers", "Speakers", false)];
+        assert!(LastKnownGood::verified(&profile(&displays, &no_default), &displays, &no_default).is_none());
+    }
+
+    #[test]
+    fn follows_displays_and_devices_whose_ids_changed() {
+        let displays = [
+            display(1, "Built-in", 0, None),
+            display(2, "LG", 1920, None),
+            display(3, "LG", 3840, Some(2)),
+        ];
+        let devices = [device("usb-1", "USB Audio", true)];
+        let saved = LastKnownGood::verified(&profile(&displays, &devices), &displays, &devices).unwrap();
+
+        // 도크를 다시 연결해 같은 모니터와 장치가 다른 id로 잡힘
+        let connected = [display(1, "Built-in", 0, None), display(7, "LG", 0, None), display(8, "LG", 0, None)];
+        let recovered = saved.to_profile(&connected, &[device("usb-2", "USB Audio", false)]);
+        let ids: Vec<(u32, Option<u32>, i32)> =
+            recovered.displays.iter().map(|d| (d.id, d.mirror_of, d.x)).collect();
+        assert_eq!(ids, vec![(1, None, 0), (7, None, 1920), (8, Some(7), 3840)]);
+        assert_eq!(recovered.audio_settings.as_ref().unwrap().output_device.as_deref(), Some("usb-2"));
+
+        // 찾지 못한 모니터는 저장된 id 그대로 (부분 적용에서 건너뜀)
+        let recovered = saved.to_profile(&connected[..1], &[]);
+        assert_eq!(recovered.displays[1].id, 2);
+        assert_eq!(recovered.displays[2].mirror_of, None);
+        assert_eq!(recovered.audio_settings.unwrap().output_device, None);
+    }
+
+    #[test]
+    fn matches_identical_models_by_stable_id_before_id() {
+        let with_edid = |id: u32, x: i32, stable_id: &str| DisplayInfo {
+            stable_id: Some(stable_id.to_string()),
+            ..display(id, "LG", x, None)
+        };
+        let displays = [with_edid(2, 0, "GSM-1"), with_edid(3, 1920, "GSM-2")];
+        let devices = [device("speakers", "Speakers", true)];
+        let saved = LastKnownGood::verified(&profile(&displays, &devices), &displays, &devices).unwrap();
+
+        // 같은 모델 두 대의 id가 서로 바뀌어도 EDID 식별값으로 원래 위치를 찾습니다
+        let connected = [with_edid(2, 0, "GSM-2"), with_edid(3, 0, "GSM-1")];
+        let recovered = saved.to_profile(&connected, &devices);
+        let ids: Vec<(u32, i32)> = recovered.displays.iter().map(|d| (d.id, d.x)).collect();
+        assert_eq!(ids, vec![(3, 0), (2, 1920)]);
+
+        // 식별값이 다른 같은 이름의 모니터는 같은 모니터로 보지 않습니다
+        let recovered = saved.to_profile(&[with_edid(2, 0, "GSM-9")], &devices);
+        assert!(recovered.displays.iter().all(|d| d.id != 2));
+    }
+}
@@ -1,16 +1,43 @@
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-  tauri::Builder::default()
-    .setup(|app| {
-      if cfg!(debug_assertions) {
-        app.handle().plugin(
-          tauri_plugin_log::Builder::default()
-            .level(log::LevelFilter::Info)
-            .build(),
-        )?;
-      }
-      Ok(())
-    })
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
-}
+// Display & Sound Manager 백엔드
+// main.rs는 Tauri 연결만 맡고, 나머지는 이 라이브러리의 모듈에 있습니다
+// 디스플레이 / 오디오 백엔드, 프로필, 적용 과정은 Tauri 없이도 빌드됩니다 (--no-default-features)
+// 명령, 이벤트, 트레이 등 앱에 필요한 모듈은 gui 기능에서만 빌드합니다
+
+pub mod apply_queue;
+pub mod audio;
+pub mod cli;
+pub mod display;
+pub mod error;
+pub mod hooks;
+pub mod instance;
+pub mod last_good;
+pub mod locale;
+pub mod mock;
+pub mod monitor;
+pub mod privileges;
+pub mod profile_store;
+pub mod profiles;
+pub mod schedules;
+pub mod shell;
+pub mod store_lock;
+pub mod sync;
+pub mod system;
+pub mod tools;
+
+#[cfg(feature = "gui")]
+mod app;
+#[cfg(feature = "gui")]
+pub mod commands;
+#[cfg(feature = "gui")]
+pub mod hotkeys;
+#[cfg(feature = "gui")]
+pub mod midi;
+#[cfg(feature = "gui")]
+pub mod state;
+#[cfg(feature = "gui")]
+pub mod tray;
+#[cfg(feature = "gui")]
+pub mod ws;
+
+#[cfg(feature = "gui")]
+pub use app::setup;
@@ -0,0 +1,76 @@
+// 메시지 언어 (오류 메시지와 경고 문구에 사용)
+
+#[cfg(target_os = "macos")]
+use std::process::Command;
+
+#[cfg(target_os = "macos")]
+use crate::tools::{command_timeout, decode_output, output_with_timeout};
+
+// 메시지 언어
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Locale {
+    En,
+    Ko,
+}
+
+impl Locale {
+    // "ko", "ko-KR", "ko_KR.UTF-8" 같은 형식 허용
+    pub fn parse(value: &str) -> Option<Locale> {
+        let language = value
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::En),
+            "ko" => Some(Locale::Ko),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ko => "ko",
+        }
+    }
+}
+
+// 오류를 직렬화할 때 사용할 현재 언어
+static CURRENT_LOCALE: std::sync::RwLock<Locale> = std::sync::RwLock::new(Locale::En);
+
+pub fn current_locale() -> Locale {
+    *CURRENT_LOCALE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn set_current_locale(locale: Locale) {
+    *CURRENT_LOCALE.write().unwrap_or_else(|e| e.into_inner()) = locale;
+}
+
+// 설정에 언어가 없을 때 시스템 언어 감지
+pub fn detect_system_locale() -> Locale {
+    for key in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+        if let Some(locale) = std::env::var(key).ok().as_deref().and_then(Locale::parse) {
+            return locale;
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Ok(output) = output_with_timeout(Command::new("defaults").args(["read", "-g", "AppleLocale"]), command_timeout()) {
+            if let Some(locale) = Locale::parse(decode_output(&output.stdout).trim()) {
+                return locale;
+            }
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // LANG_KOREAN = 0x12
+        let language_id = unsafe { windows::Win32::Globalization::GetUserDefaultUILanguage() };
+        if language_id & 0x3ff == 0x12 {
+            return Locale::Ko;
+        }
+    }
+
+    Locale::En
+}
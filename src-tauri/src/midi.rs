@@ -0,0 +1,387 @@
+// MIDI 컨트롤러 매핑 (페이더 → 볼륨, 패드 → 프로필 적용)
+// 매핑된 포트가 빠지면 읽기 스레드가 끝나고, 감시 스레드가 주기적으로 다시 연결합니다
+//
+// 포트 접근은 플랫폼마다 다릅니다 (Linux는 ALSA raw MIDI 장치, macOS는 CoreMIDI, Windows는 WinMM)
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::commands::{apply_profile_by_id, set_volume_by_source};
+use crate::error::AppError;
+use crate::sync::LockExt;
+
+#[cfg(target_os = "macos")]
+mod coremidi;
+#[cfg(target_os = "macos")]
+use self::coremidi as platform;
+#[cfg(target_os = "windows")]
+mod winmm;
+#[cfg(target_os = "windows")]
+use self::winmm as platform;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod alsa;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+use self::alsa as platform;
+
+// 연결이 끊긴 포트를 다시 찾는 주기
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+// 페이더를 움직이는 동안 볼륨은 이 간격마다 마지막 값만 반영합니다
+const VOLUME_DEBOUNCE: Duration = Duration::from_millis(50);
+// 패드를 연달아 눌러도 같은 프로필을 반복 적용하지 않는 시간
+const PAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+// 매핑할 MIDI 메시지 (channel이 None이면 모든 채널)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MidiMessage {
+    ControlChange { channel: Option<u8>, controller: u8 },
+    Note { channel: Option<u8>, note: u8 },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MidiAction {
+    // 값(0~127)을 볼륨(0~100)으로 변환
+    SetVolume { device_type: String },
+    ApplyProfile { profile_id: String },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MidiMapping {
+    pub port: String,
+    pub message: MidiMessage,
+    pub action: MidiAction,
+}
+
+// 수신한 채널 메시지 (channel은 0~15)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ChannelMessage {
+    ControlChange { channel: u8, controller: u8, value: u8 },
+    NoteOn { channel: u8, note: u8, velocity: u8 },
+}
+
+impl MidiMessage {
+    // 일치하면 매핑에 넘길 값(0~127) 반환
+    fn matches(&self, message: &ChannelMessage) -> Option<u8> {
+        let channel_matches = |expected: &Option<u8>, actual: u8| expected.is_none_or(|c| c == actual);
+        match (self, message) {
+            (
+                MidiMessage::ControlChange { channel, controller },
+                ChannelMessage::ControlChange { channel: actual, controller: number, value },
+            ) if channel_matches(channel, *actual) && controller == number => Some(*value),
+            (
+                MidiMessage::Note { channel, note },
+                ChannelMessage::NoteOn { channel: actual, note: number, velocity },
+            ) if channel_matches(channel, *actual) && note == number => Some(*velocity),
+            _ => None,
+        }
+    }
+}
+
+// 0~127 → 0~100
+fn scale_to_percent(value: u8) -> u32 {
+    (value.min(127) as u32 * 100 + 63) / 127
+}
+
+// MIDI 바이트 스트림 파서 (running status 지원, 시스템 메시지는 무시)
+#[derive(Default)]
+struct MidiParser {
+    status: Option<u8>,
+    data: Vec<u8>,
+    in_sysex: bool,
+}
+
+impl MidiParser {
+    fn feed(&mut self, byte: u8) -> Option<ChannelMessage> {
+        match byte {
+            // 리얼타임 메시지는 다른 메시지 중간에도 올 수 있습니다
+            0xF8..=0xFF => return None,
+            0xF0 => {
+                self.in_sysex = true;
+                self.status = None;
+                return None;
+            }
+            0xF7 => {
+                self.in_sysex = false;
+                return None;
+            }
+            0xF1..=0xF6 => {
+                self.status = None;
+                return None;
+            }
+            0x80..=0xEF => {
+                self.in_sysex = false;
+                self.status = Some(byte);
+                self.data.clear();
+                return None;
+            }
+            _ => {}
+        }
+
+        if self.in_sysex {
+            return None;
+        }
+        let status = self.status?;
+        self.data.push(byte);
+
+        let expected = match status & 0xF0 {
+            0xC0 | 0xD0 => 1,
+            _ => 2,
+        };
+        if self.data.len() < expected {
+            return None;
+        }
+
+        let channel = status & 0x0F;
+        let message = match (status & 0xF0, self.data.as_slice()) {
+            (0xB0, &[controller, value]) => Some(ChannelMessage::ControlChange { channel, controller, value }),
+            (0x90, &[note, velocity]) if velocity > 0 => Some(ChannelMessage::NoteOn { channel, note, velocity }),
+            _ => None,
+        };
+        self.data.clear();
+        message
+    }
+}
+
+// 상태 바이트로 시작하는 짧은 메시지의 길이 (WinMM은 메시지를 4바이트 값 하나로 넘겨 줍니다)
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn short_message_len(status: u8) -> usize {
+    match status {
+        0xC0..=0xDF | 0xF1 | 0xF3 => 2,
+        0x80..=0xEF | 0xF2 => 3,
+        _ => 1,
+    }
+}
+
+// 콜백으로 받은 데이터를 포트가 목록에서 사라질 때까지 on_data로 넘깁니다 (CoreMIDI, WinMM)
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn forward_until_removed(
+    port: &str,
+    receiver: &mpsc::Receiver<Vec<u8>>,
+    on_data: &mut dyn FnMut(&[u8]) -> bool,
+) -> Result<(), AppError> {
+    loop {
+        match receiver.recv_timeout(RECONNECT_INTERVAL) {
+            Ok(data) => {
+                if !on_data(&data) {
+                    return Ok(());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if !platform::input_ports()?.iter().any(|name| name == port) {
+                    return Ok(());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+    }
+}
+
+// 사용할 수 있는 MIDI 입력 포트 이름
+pub fn list_inputs() -> Result<Vec<String>, AppError> {
+    let mut ports = platform::input_ports()?;
+    ports.sort();
+    Ok(ports)
+}
+
+// 매핑 목록을 공유하고 포트 연결을 관리하는 리스너
+pub struct MidiListener {
+    mappings: Arc<RwLock<Vec<MidiMapping>>>,
+}
+
+impl MidiListener {
+    pub fn start(app: AppHandle, mappings: Vec<MidiMapping>) -> Self {
+        let mappings = Arc::new(RwLock::new(mappings));
+        let (sender, receiver) = mpsc::channel::<(MidiAction, u8)>();
+
+        let dispatch_app = app.clone();
+        std::thread::spawn(move || dispatch_actions(dispatch_app, receiver));
+
+        let shared = mappings.clone();
+        std::thread::spawn(move || watch_ports(shared, sender));
+
+        MidiListener { mappings }
+    }
+
+    pub fn set_mappings(&self, mappings: Vec<MidiMapping>) {
+        *self.mappings.write().unwrap_or_else(|e| e.into_inner()) = mappings;
+    }
+}
+
+// 매핑된 포트 중 연결되지 않은 포트를 주기적으로 열기
+fn watch_ports(mappings: Arc<RwLock<Vec<MidiMapping>>>, sender: mpsc::Sender<(MidiAction, u8)>) {
+    let connected: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
+
+    loop {
+        let wanted: HashSet<String> = mappings
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|mapping| mapping.port.clone())
+            .collect();
+
+        if !wanted.is_empty() {
+            let ports = platform::input_ports().unwrap_or_default();
+            for port in wanted {
+                if connected.lock_or_recover().contains(&port) || !ports.contains(&port) {
+                    continue;
+                }
+
+                connected.lock_or_recover().insert(port.clone());
+                let mappings = mappings.clone();
+                let sender = sender.clone();
+                let connected = connected.clone();
+                std::thread::spawn(move || {
+                    match read_port(&port, &mappings, &sender) {
+                        Ok(()) => log::info!("MIDI 포트 연결 끊김: {}", port),
+                        Err(e) => log::warn!("MIDI 포트 열기 실패 ({}): {}", port, e),
+                    }
+                    connected.lock_or_recover().remove(&port);
+                });
+            }
+        }
+
+        std::thread::sleep(RECONNECT_INTERVAL);
+    }
+}
+
+// 포트에서 메시지를 읽어 매핑된 동작으로 보냄 (장치가 빠지면 반환)
+fn read_port(
+    port: &str,
+    mappings: &RwLock<Vec<MidiMapping>>,
+    sender: &mpsc::Sender<(MidiAction, u8)>,
+) -> Result<(), AppError> {
+    let connection = platform::Connection::open(port)?;
+    log::info!("MIDI 포트 연결: {}", port);
+
+    let mut parser = MidiParser::default();
+    connection.read(&mut |data| {
+        for &byte in data {
+            let Some(message) = parser.feed(byte) else {
+                continue;
+            };
+            let mappings = mappings.read().unwrap_or_else(|e| e.into_inner());
+            for mapping in mappings.iter().filter(|mapping| mapping.port == port) {
+                if let Some(value) = mapping.message.matches(&message) {
+                    if sender.send((mapping.action.clone(), value)).is_err() {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    })
+}
+
+// 동작 실행 (볼륨은 마지막 값만, 패드는 짧은 시간 안의 반복을 무시)
+fn dispatch_actions(app: AppHandle, receiver: mpsc::Receiver<(MidiAction, u8)>) {
+    let mut pending_volumes: HashMap<String, u8> = HashMap::new();
+    let mut flush_at: Option<Instant> = None;
+    let mut last_applied: HashMap<String, Instant> = HashMap::new();
+
+    loop {
+        let received = match flush_at {
+            None => receiver.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+            Some(flush_at) => receiver.recv_timeout(flush_at.saturating_duration_since(Instant::now())),
+        };
+
+        match received {
+            Ok((MidiAction::SetVolume { device_type }, value)) => {
+                pending_volumes.insert(device_type, value);
+                flush_at.get_or_insert_with(|| Instant::now() + VOLUME_DEBOUNCE);
+            }
+            Ok((MidiAction::ApplyProfile { profile_id }, _)) => {
+                let now = Instant::now();
+                if last_applied
+                    .get(&profile_id)
+                    .is_some_and(|last| now.duration_since(*last) < PAD_DEBOUNCE)
+                {
+                    continue;
+                }
+                last_applied.insert(profile_id.clone(), now);
+                // 대기열에서 차례를 기다리는 동안에도 볼륨 조절은 계속 처리합니다
+                let app = app.clone();
+                std::thread::spawn(move || {
+                    if let Err(e) = apply_profile_by_id(&app, &profile_id, "midi") {
+                        log::warn!("MIDI로 프로필 적용 실패 ({}): {}", profile_id, e);
+                    }
+                });
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                flush_at = None;
+                for (device_type, value) in pending_volumes.drain() {
+                    if let Err(e) = set_volume_by_source(&app, &device_type, scale_to_percent(value), "midi") {
+                        log::warn!("MIDI로 볼륨 변경 실패 ({}): {}", device_type, e);
+                    }
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(bytes: &[u8]) -> Vec<ChannelMessage> {
+        let mut parser = MidiParser::default();
+        bytes.iter().filter_map(|&byte| parser.feed(byte)).collect()
+    }
+
+    #[test]
+    fn parses_running_status() {
+        // 상태 바이트 없이 이어지는 CC 두 개
+        assert_eq!(
+            parse(&[0xB1, 7, 100, 7, 20]),
+            [
+                ChannelMessage::ControlChange { channel: 1, controller: 7, value: 100 },
+                ChannelMessage::ControlChange { channel: 1, controller: 7, value: 20 },
+            ]
+        );
+        // 프로그램 변경(데이터 1바이트)은 매핑하지 않고 바이트를 밀리게 하지도 않습니다
+        assert_eq!(parse(&[0xC0, 5, 6, 0xB0, 1, 2]), [ChannelMessage::ControlChange { channel: 0, controller: 1, value: 2 }]);
+    }
+
+    #[test]
+    fn skips_sysex_and_realtime_bytes() {
+        // 시스템 익스클루시브 안의 데이터 바이트와 메시지 중간의 클록(0xF8)은 무시합니다
+        let bytes = [0xB0, 0xF0, 0x7E, 0x10, 0x20, 0xF7, 0x90, 60, 0xF8, 100, 0xFE];
+        assert_eq!(parse(&bytes), [ChannelMessage::NoteOn { channel: 0, note: 60, velocity: 100 }]);
+        // 시스템 익스클루시브가 끝나면 running status를 다시 쓰지 않습니다
+        assert_eq!(parse(&[0xB0, 7, 1, 0xF0, 1, 0xF7, 7, 2]).len(), 1);
+    }
+
+    #[test]
+    fn treats_note_on_with_zero_velocity_as_note_off() {
+        assert!(parse(&[0x90, 60, 0]).is_empty());
+        assert_eq!(parse(&[0x92, 60, 0, 61, 1]), [ChannelMessage::NoteOn { channel: 2, note: 61, velocity: 1 }]);
+    }
+
+    #[test]
+    fn scales_values_and_matches_mappings() {
+        assert_eq!(scale_to_percent(0), 0);
+        assert_eq!(scale_to_percent(64), 50);
+        assert_eq!(scale_to_percent(127), 100);
+        assert_eq!(scale_to_percent(200), 100);
+
+        let fader = MidiMessage::ControlChange { channel: None, controller: 7 };
+        let pad = MidiMessage::Note { channel: Some(9), note: 36 };
+        assert_eq!(fader.matches(&ChannelMessage::ControlChange { channel: 3, controller: 7, value: 90 }), Some(90));
+        assert_eq!(fader.matches(&ChannelMessage::ControlChange { channel: 3, controller: 8, value: 90 }), None);
+        assert_eq!(pad.matches(&ChannelMessage::NoteOn { channel: 9, note: 36, velocity: 127 }), Some(127));
+        assert_eq!(pad.matches(&ChannelMessage::NoteOn { channel: 0, note: 36, velocity: 127 }), None);
+        assert_eq!(fader.matches(&ChannelMessage::NoteOn { channel: 0, note: 7, velocity: 1 }), None);
+    }
+
+    #[test]
+    fn knows_short_message_lengths() {
+        assert_eq!(short_message_len(0xB0), 3);
+        assert_eq!(short_message_len(0xC3), 2);
+        assert_eq!(short_message_len(0xF2), 3);
+        assert_eq!(short_message_len(0xF8), 1);
+    }
+}
@@ -0,0 +1,63 @@
+// Linux: ALSA raw MIDI 장치 (/dev/snd/midiC*D*)
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::PathBuf;
+
+use crate::error::AppError;
+
+// 포트 이름 → 장치 경로
+fn ports() -> HashMap<String, PathBuf> {
+    let mut ports = HashMap::new();
+    let Ok(entries) = std::fs::read_dir("/dev/snd") else {
+        return ports;
+    };
+
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(rest) = file_name.strip_prefix("midiC") else {
+            continue;
+        };
+        // 장치 이름은 카드 id를 사용합니다 (예: "nanoKONTROL2 (midiC1D0)")
+        let card = rest.split('D').next().unwrap_or_default();
+        let card_id = std::fs::read_to_string(format!("/proc/asound/card{}/id", card))
+            .map(|id| id.trim().to_string())
+            .unwrap_or_else(|_| format!("card{}", card));
+        ports.insert(format!("{} ({})", card_id, file_name), entry.path());
+    }
+
+    ports
+}
+
+pub fn input_ports() -> Result<Vec<String>, AppError> {
+    Ok(ports().into_keys().collect())
+}
+
+pub struct Connection {
+    file: std::fs::File,
+}
+
+impl Connection {
+    pub fn open(port: &str) -> Result<Self, AppError> {
+        let path = ports().remove(port).ok_or_else(|| AppError::DeviceNotFound {
+            device_type: "midi".to_string(),
+            pattern: port.to_string(),
+        })?;
+        let file = std::fs::File::open(&path).map_err(|e| AppError::io(&path, &e))?;
+        Ok(Connection { file })
+    }
+
+    // 장치가 빠지면 (읽기가 끝나거나 실패하면) 반환
+    pub fn read(mut self, on_data: &mut dyn FnMut(&[u8]) -> bool) -> Result<(), AppError> {
+        let mut buffer = [0u8; 256];
+        loop {
+            let read = match self.file.read(&mut buffer) {
+                Ok(0) | Err(_) => return Ok(()),
+                Ok(read) => read,
+            };
+            if !on_data(&buffer[..read]) {
+                return Ok(());
+            }
+        }
+    }
+}
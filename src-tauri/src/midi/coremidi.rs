@@ -0,0 +1,163 @@
+// macOS: CoreMIDI 입력 (포트 이름은 소스의 표시 이름)
+// 읽기 콜백은 CoreMIDI 스레드에서 호출되므로 받은 바이트를 채널로 넘깁니다
+
+use core_foundation::base::TCFType;
+use core_foundation::string::{CFString, CFStringRef};
+use std::ffi::c_void;
+use std::sync::mpsc;
+use std::sync::OnceLock;
+
+use super::forward_until_removed;
+use crate::error::AppError;
+
+type MidiObjectRef = u32;
+
+#[repr(C)]
+struct MidiPacketList {
+    num_packets: u32,
+    // 이어서 MIDIPacket이 num_packets개 (4바이트 단위로 압축된 구조체)
+}
+
+type MidiReadProc = unsafe extern "C" fn(list: *const MidiPacketList, read_context: *mut c_void, source: *mut c_void);
+
+#[link(name = "CoreMIDI", kind = "framework")]
+extern "C" {
+    static kMIDIPropertyDisplayName: CFStringRef;
+
+    fn MIDIGetNumberOfSources() -> usize;
+    fn MIDIGetSource(index: usize) -> MidiObjectRef;
+    fn MIDIObjectGetStringProperty(object: MidiObjectRef, property: CFStringRef, value: *mut CFStringRef) -> i32;
+    fn MIDIClientCreate(
+        name: CFStringRef,
+        notify: *const c_void,
+        notify_context: *mut c_void,
+        client: *mut MidiObjectRef,
+    ) -> i32;
+    fn MIDIInputPortCreate(
+        client: MidiObjectRef,
+        name: CFStringRef,
+        read: MidiReadProc,
+        read_context: *mut c_void,
+        port: *mut MidiObjectRef,
+    ) -> i32;
+    fn MIDIPortConnectSource(port: MidiObjectRef, source: MidiObjectRef, context: *mut c_void) -> i32;
+    fn MIDIPortDispose(port: MidiObjectRef) -> i32;
+}
+
+fn midi_error(call: &str, status: i32) -> AppError {
+    AppError::ToolFailed {
+        tool: "CoreMIDI".to_string(),
+        detail: format!("{} returned {}", call, status),
+    }
+}
+
+// 앱 전체에서 하나만 만드는 MIDI 클라이언트
+fn client() -> Result<MidiObjectRef, AppError> {
+    static CLIENT: OnceLock<Result<MidiObjectRef, i32>> = OnceLock::new();
+    let created = CLIENT.get_or_init(|| {
+        let name = CFString::new("Display Sound Manager");
+        let mut client = 0;
+        let status =
+            unsafe { MIDIClientCreate(name.as_concrete_TypeRef(), std::ptr::null(), std::ptr::null_mut(), &mut client) };
+        if status == 0 {
+            Ok(client)
+        } else {
+            Err(status)
+        }
+    });
+    created.clone().map_err(|status| midi_error("MIDIClientCreate", status))
+}
+
+fn display_name(object: MidiObjectRef) -> Option<String> {
+    let mut name: CFStringRef = std::ptr::null();
+    let status = unsafe { MIDIObjectGetStringProperty(object, kMIDIPropertyDisplayName, &mut name) };
+    if status != 0 || name.is_null() {
+        return None;
+    }
+    Some(unsafe { CFString::wrap_under_create_rule(name) }.to_string())
+}
+
+// (포트 이름, 소스)
+fn sources() -> Vec<(String, MidiObjectRef)> {
+    (0..unsafe { MIDIGetNumberOfSources() })
+        .map(|index| unsafe { MIDIGetSource(index) })
+        .filter(|&source| source != 0)
+        .filter_map(|source| Some((display_name(source)?, source)))
+        .collect()
+}
+
+pub fn input_ports() -> Result<Vec<String>, AppError> {
+    Ok(sources().into_iter().map(|(name, _)| name).collect())
+}
+
+// MIDIPacketList의 바이트를 채널로 보냄
+unsafe extern "C" fn read_packets(list: *const MidiPacketList, read_context: *mut c_void, _source: *mut c_void) {
+    let sender = &*(read_context as *const mpsc::Sender<Vec<u8>>);
+    let count = (*list).num_packets;
+    // MIDIPacket: timeStamp (8바이트), length (2바이트), data
+    let mut packet = (list as *const u8).add(4);
+    for _ in 0..count {
+        let length = std::ptr::read_unaligned(packet.add(8) as *const u16) as usize;
+        let data = std::slice::from_raw_parts(packet.add(10), length);
+        let _ = sender.send(data.to_vec());
+        // MIDIPacketNext: ARM에서는 다음 패킷이 4바이트 경계에서 시작합니다
+        let next = packet.add(10 + length);
+        packet = if cfg!(target_arch = "aarch64") { next.add(next.align_offset(4)) } else { next };
+    }
+}
+
+pub struct Connection {
+    name: String,
+    port: MidiObjectRef,
+    receiver: mpsc::Receiver<Vec<u8>>,
+    // 콜백의 read_context (포트를 해제한 뒤에 해제합니다)
+    sender: *mut mpsc::Sender<Vec<u8>>,
+}
+
+// sender는 이 연결만 가리키고 콜백은 포트를 해제하면 더 호출되지 않습니다
+unsafe impl Send for Connection {}
+
+impl Connection {
+    pub fn open(port: &str) -> Result<Self, AppError> {
+        let source = sources()
+            .into_iter()
+            .find(|(name, _)| name == port)
+            .map(|(_, source)| source)
+            .ok_or_else(|| AppError::DeviceNotFound {
+                device_type: "midi".to_string(),
+                pattern: port.to_string(),
+            })?;
+
+        let (sender, receiver) = mpsc::channel();
+        let sender = Box::into_raw(Box::new(sender));
+        let name = CFString::new(port);
+        let mut input = 0;
+        let status = unsafe {
+            MIDIInputPortCreate(client()?, name.as_concrete_TypeRef(), read_packets, sender as *mut c_void, &mut input)
+        };
+        if status != 0 {
+            drop(unsafe { Box::from_raw(sender) });
+            return Err(midi_error("MIDIInputPortCreate", status));
+        }
+        let connection = Connection { name: port.to_string(), port: input, receiver, sender };
+        let status = unsafe { MIDIPortConnectSource(input, source, std::ptr::null_mut()) };
+        if status != 0 {
+            return Err(midi_error("MIDIPortConnectSource", status));
+        }
+        Ok(connection)
+    }
+
+    // 소스가 목록에서 사라지면 반환
+    pub fn read(self, on_data: &mut dyn FnMut(&[u8]) -> bool) -> Result<(), AppError> {
+        forward_until_removed(&self.name, &self.receiver, on_data)
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        unsafe {
+            MIDIPortDispose(self.port);
+            drop(Box::from_raw(self.sender));
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// Windows: WinMM MIDI 입력 (포트 이름은 장치 이름, 이름이 같은 장치는 " #2"처럼 번호를 붙입니다)
+// 콜백은 WinMM 스레드에서 호출되므로 받은 메시지를 채널로 넘깁니다 (콜백 안에서는 midiIn 함수를 부르지 않음)
+
+use std::sync::mpsc;
+
+use super::{forward_until_removed, short_message_len};
+use crate::error::AppError;
+
+// MIM_DATA (짧은 메시지), CALLBACK_FUNCTION
+const MIM_DATA: u32 = 0x3C3;
+const CALLBACK_FUNCTION: u32 = 0x0003_0000;
+
+// MIDIINCAPSW (mmsystem.h의 구조체는 1바이트 단위로 압축)
+#[repr(C, packed)]
+struct MidiInCaps {
+    manufacturer: u16,
+    product: u16,
+    driver_version: u32,
+    name: [u16; 32],
+    support: u32,
+}
+
+type MidiInProc = unsafe extern "system" fn(handle: isize, message: u32, instance: usize, param1: usize, param2: usize);
+
+#[link(name = "winmm")]
+extern "system" {
+    fn midiInGetNumDevs() -> u32;
+    fn midiInGetDevCapsW(device: usize, caps: *mut MidiInCaps, size: u32) -> u32;
+    fn midiInOpen(handle: *mut isize, device: u32, callback: MidiInProc, instance: usize, flags: u32) -> u32;
+    fn midiInStart(handle: isize) -> u32;
+    fn midiInReset(handle: isize) -> u32;
+    fn midiInClose(handle: isize) -> u32;
+}
+
+fn mm_error(call: &str, result: u32) -> AppError {
+    AppError::ToolFailed {
+        tool: "WinMM".to_string(),
+        detail: format!("{} returned {}", call, result),
+    }
+}
+
+// 장치 번호 순서의 포트 이름
+fn device_names() -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for device in 0..unsafe { midiInGetNumDevs() } {
+        let mut caps: MidiInCaps = unsafe { std::mem::zeroed() };
+        let result =
+            unsafe { midiInGetDevCapsW(device as usize, &mut caps, std::mem::size_of::<MidiInCaps>() as u32) };
+        let name = if result == 0 {
+            let wide = caps.name;
+            let length = wide.iter().position(|&c| c == 0).unwrap_or(wide.len());
+            String::from_utf16_lossy(&wide[..length])
+        } else {
+            format!("MIDI In {}", device)
+        };
+        let same = names.iter().filter(|existing| existing.split(" #").next() == Some(name.as_str())).count();
+        names.push(if same == 0 { name } else { format!("{} #{}", name, same + 1) });
+    }
+    names
+}
+
+pub fn input_ports() -> Result<Vec<String>, AppError> {
+    Ok(device_names())
+}
+
+// MIM_DATA의 param1: 하위 바이트부터 상태, 데이터 1, 데이터 2
+unsafe extern "system" fn midi_in_proc(_handle: isize, message: u32, instance: usize, param1: usize, _param2: usize) {
+    if message != MIM_DATA {
+        return;
+    }
+    let sender = &*(instance as *const mpsc::Sender<Vec<u8>>);
+    let bytes = (param1 as u32).to_le_bytes();
+    let _ = sender.send(bytes[..short_message_len(bytes[0])].to_vec());
+}
+
+pub struct Connection {
+    name: String,
+    handle: isize,
+    receiver: mpsc::Receiver<Vec<u8>>,
+    // 콜백의 instance (장치를 닫은 뒤에 해제합니다)
+    sender: *mut mpsc::Sender<Vec<u8>>,
+}
+
+// sender는 이 연결만 가리키고 콜백은 장치를 닫으면 더 호출되지 않습니다
+unsafe impl Send for Connection {}
+
+impl Connection {
+    pub fn open(port: &str) -> Result<Self, AppError> {
+        let device = device_names().iter().position(|name| name == port).ok_or_else(|| AppError::DeviceNotFound {
+            device_type: "midi".to_string(),
+            pattern: port.to_string(),
+        })?;
+
+        let (sender, receiver) = mpsc::channel();
+        let sender = Box::into_raw(Box::new(sender));
+        let mut handle = 0;
+        let result = unsafe { midiInOpen(&mut handle, device as u32, midi_in_proc, sender as usize, CALLBACK_FUNCTION) };
+        if result != 0 {
+            drop(unsafe { Box::from_raw(sender) });
+            return Err(mm_error("midiInOpen", result));
+        }
+        let connection = Connection { name: port.to_string(), handle, receiver, sender };
+        let result = unsafe { midiInStart(handle) };
+        if result != 0 {
+            return Err(mm_error("midiInStart", result));
+        }
+        Ok(connection)
+    }
+
+    // 장치가 목록에서 사라지면 반환
+    pub fn read(self, on_data: &mut dyn FnMut(&[u8]) -> bool) -> Result<(), AppError> {
+        forward_until_removed(&self.name, &self.receiver, on_data)
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        unsafe {
+            midiInReset(self.handle);
+            midiInClose(self.handle);
+            drop(Box::from_raw(self.sender));
+        }
+    }
+}
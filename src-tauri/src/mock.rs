@@ -0,0 +1,223 @@
+// 테스트용 가짜 디스플레이/오디오 백엔드
+// 고정된 장치 구성(fixture)을 보여 주고, 적용 요청은 실제로 실행하지 않고 기록합니다
+// DSM_MOCK_BACKEND 환경 변수에 fixture 파일 경로를 넣으면 앱도 이 백엔드로 실행됩니다
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use crate::audio::{AudioBackend, AudioDevice, AudioSettings};
+use crate::display::{edid, DisplayBackend, DisplayInfo};
+use crate::error::AppError;
+use crate::sync::LockExt;
+
+pub const MOCK_BACKEND_ENV: &str = "DSM_MOCK_BACKEND";
+
+// mock 백엔드가 보여 줄 장치 구성
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct MockFixture {
+    pub displays: Vec<DisplayInfo>,
+    pub audio_devices: Vec<AudioDevice>,
+    // is_display_connected에서 사용할 EDID 일련번호
+    pub display_serials: Vec<String>,
+    // 연결된 모니터의 EDID 원본 (16진수, 기록한 OS에서 읽을 수 있었던 경우)
+    pub edids: Vec<String>,
+}
+
+// 지금 연결된 장치 구성을 fixture로 기록 (이상한 하드웨어를 테스트 사례로 추가할 때 사용)
+// 일련번호와 EDID는 읽을 수 있는 경우에만 포함합니다
+pub fn record_fixture(
+    displays: &dyn DisplayBackend,
+    audio: &dyn AudioBackend,
+) -> Result<MockFixture, AppError> {
+    Ok(MockFixture {
+        displays: displays.enumerate()?,
+        audio_devices: audio.enumerate()?,
+        display_serials: displays.connected_serials().unwrap_or_else(|e| {
+            log::warn!("fixture에 일련번호를 기록하지 못했습니다: {}", e);
+            Vec::new()
+        }),
+        edids: displays
+            .raw_edids()
+            .unwrap_or_else(|e| {
+                log::warn!("fixture에 EDID를 기록하지 못했습니다: {}", e);
+                Vec::new()
+            })
+            .iter()
+            .map(|blob| edid::encode_hex(blob))
+            .collect(),
+    })
+}
+
+// mock 백엔드가 받은 적용 요청
+#[derive(Debug, Serialize, Clone)]
+pub enum MockOperation {
+    Displays(Vec<DisplayInfo>),
+    Audio(AudioSettings),
+    DefaultDevice { device_type: String, device: String },
+    Volume { device_type: String, volume: Option<u32>, muted: Option<bool> },
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    fixture: MockFixture,
+    operations: Vec<MockOperation>,
+}
+
+// 복제본끼리 상태를 공유하므로 디스플레이/오디오 백엔드로 같은 mock을 넘길 수 있습니다
+#[derive(Debug, Clone, Default)]
+pub struct MockBackend {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockBackend {
+    pub fn new(fixture: MockFixture) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockState {
+                fixture,
+                operations: Vec::new(),
+            })),
+        }
+    }
+
+    pub fn load(path: &std::path::Path) -> Result<Self, AppError> {
+        let content = fs::read_to_string(path).map_err(|e| AppError::io(path, &e))?;
+        let fixture: MockFixture =
+            serde_json::from_str(&content).map_err(|e| AppError::corrupt_file(path, &e))?;
+        Ok(Self::new(fixture))
+    }
+
+    // DSM_MOCK_BACKEND가 설정되어 있으면 해당 fixture로 만든 mock
+    pub fn from_env() -> Option<Result<Self, AppError>> {
+        let path = std::env::var_os(MOCK_BACKEND_ENV)?;
+        Some(Self::load(std::path::Path::new(&path)))
+    }
+
+    // 장치 연결/분리를 흉내 내기 위해 구성 교체 (기록은 유지)
+    pub fn set_fixture(&self, fixture: MockFixture) {
+        self.state.lock_or_recover().fixture = fixture;
+    }
+
+    pub fn fixture(&self) -> MockFixture {
+        self.state.lock_or_recover().fixture.clone()
+    }
+
+    pub fn operations(&self) -> Vec<MockOperation> {
+        self.state.lock_or_recover().operations.clone()
+    }
+}
+
+impl DisplayBackend for MockBackend {
+    fn enumerate(&self) -> Result<Vec<DisplayInfo>, AppError> {
+        Ok(self.state.lock_or_recover().fixture.displays.clone())
+    }
+
+    // 연결되지 않은 id가 있으면 displayplacer처럼 실패하고 아무것도 바꾸지 않습니다
+    fn apply(&self, displays: &[DisplayInfo]) -> Result<String, AppError> {
+        let mut state = self.state.lock_or_recover();
+        if let Some(missing) = displays
+            .iter()
+            .find(|display| !state.fixture.displays.iter().any(|d| d.id == display.id))
+        {
+            return Err(AppError::ToolFailed {
+                tool: "mock".to_string(),
+                detail: format!("Unable to find screen {}", missing.id),
+            });
+        }
+
+        for display in displays {
+            if let Some(current) = state.fixture.displays.iter_mut().find(|d| d.id == display.id) {
+                *current = display.clone();
+            }
+        }
+        state.operations.push(MockOperation::Displays(displays.to_vec()));
+        Ok("mock".to_string())
+    }
+
+    fn connected_serials(&self) -> Result<Vec<String>, AppError> {
+        Ok(self.state.lock_or_recover().fixture.display_serials.clone())
+    }
+
+    fn raw_edids(&self) -> Result<Vec<Vec<u8>>, AppError> {
+        Ok(self.state.lock_or_recover().fixture.edids.iter().map(|hex| edid::decode_hex(hex)).collect())
+    }
+
+    fn name(&self) -> String {
+        "mock".to_string()
+    }
+}
+
+impl MockState {
+    // 장치 이름 또는 id로 기본 장치 지정
+    fn set_default(&mut self, device_type: &str, device: &str) -> Result<(), AppError> {
+        let devices = &mut self.fixture.audio_devices;
+        if !devices
+            .iter()
+            .any(|d| d.device_type == device_type && (d.name == device || d.id == device))
+        {
+            return Err(AppError::ToolFailed {
+                tool: "mock".to_string(),
+                detail: format!("Could not find an audio device named \"{}\"", device),
+            });
+        }
+        for d in devices.iter_mut().filter(|d| d.device_type == device_type) {
+            d.is_default = d.name == device || d.id == device;
+        }
+        Ok(())
+    }
+}
+
+impl AudioBackend for MockBackend {
+    fn enumerate(&self) -> Result<Vec<AudioDevice>, AppError> {
+        Ok(self.state.lock_or_recover().fixture.audio_devices.clone())
+    }
+
+    fn apply(&self, audio_settings: &AudioSettings) -> Result<String, AppError> {
+        let mut state = self.state.lock_or_recover();
+        if let Some(output_device) = &audio_settings.output_device {
+            state.set_default("output", output_device)?;
+        }
+        if let Some(input_device) = &audio_settings.input_device {
+            state.set_default("input", input_device)?;
+        }
+        state.operations.push(MockOperation::Audio(audio_settings.clone()));
+        Ok("mock".to_string())
+    }
+
+    fn set_default_device(&self, device_type: &str, device: &str) -> Result<String, AppError> {
+        let mut state = self.state.lock_or_recover();
+        state.set_default(device_type, device)?;
+        state.operations.push(MockOperation::DefaultDevice {
+            device_type: device_type.to_string(),
+            device: device.to_string(),
+        });
+        Ok("mock".to_string())
+    }
+
+    // 기본 장치의 볼륨과 음소거를 바꿔 다음 조회에 반영합니다
+    fn set_volume(&self, device_type: &str, volume: Option<u32>, muted: Option<bool>) -> Result<String, AppError> {
+        let mut state = self.state.lock_or_recover();
+        let device = state
+            .fixture
+            .audio_devices
+            .iter_mut()
+            .find(|device| device.device_type == device_type && device.is_default)
+            .ok_or_else(|| AppError::DeviceNotFound {
+                device_type: device_type.to_string(),
+                pattern: "default".to_string(),
+            })?;
+        if let Some(volume) = volume {
+            device.volume = Some(volume);
+        }
+        if let Some(muted) = muted {
+            device.muted = Some(muted);
+        }
+        state.operations.push(MockOperation::Volume { device_type: device_type.to_string(), volume, muted });
+        Ok("mock".to_string())
+    }
+
+    fn name(&self) -> String {
+        "mock".to_string()
+    }
+}
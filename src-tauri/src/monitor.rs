@@ -0,0 +1,374 @@
+// 디스플레이 / 오디오 장치 변경 감시
+// 운영체제의 변경 알림을 먼저 사용하고, 알림을 받을 수 없는 플랫폼이나 경로에서만 느린 주기로 다시 읽습니다
+// 트레이, 프론트엔드 이벤트 등은 subscribe 하나로 같은 변경을 받습니다
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+use crate::audio::{AudioBackend, AudioDevice};
+use crate::display::{DisplayBackend, DisplayInfo};
+use crate::error::AppError;
+use crate::sync::LockExt;
+
+// 알림을 받을 수 없을 때 다시 읽는 기본 주기 (초)
+pub const DEFAULT_POLL_INTERVAL_SECS: u64 = 30;
+// 알림이 연달아 오면 마지막 알림 뒤 이 시간 동안 잠잠해진 다음 한 번만 다시 읽습니다
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+// 구독자에게 전달하는 변경 (바뀐 뒤의 전체 목록)
+#[derive(Debug, Clone)]
+pub enum DeviceChange {
+    Displays(Vec<DisplayInfo>),
+    AudioDevices(Vec<AudioDevice>),
+}
+
+// 감시 방법 (get_system_info에 표시, 예: "events (udevadm monitor)", "poll (30s)")
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct MonitorStrategies {
+    pub displays: String,
+    pub audio: String,
+}
+
+enum Signal {
+    Changed,
+    // 알림 경로가 끊김 (주기적으로 다시 읽기로 전환)
+    Lost(String),
+    // 앱 종료 (감시 루프 끝)
+    Stop,
+}
+
+// 플랫폼 모듈이 변경을 알릴 때 쓰는 핸들
+#[derive(Clone)]
+pub struct ChangeNotifier {
+    sender: mpsc::Sender<Signal>,
+}
+
+impl ChangeNotifier {
+    // 감시가 끝났으면 false (알림 스레드도 정리하면 됩니다)
+    pub fn changed(&self) -> bool {
+        self.sender.send(Signal::Changed).is_ok()
+    }
+
+    #[cfg_attr(any(target_os = "macos", target_os = "windows"), allow(dead_code))]
+    pub fn lost(&self, reason: &str) {
+        let _ = self.sender.send(Signal::Lost(reason.to_string()));
+    }
+}
+
+type Subscriber = Box<dyn Fn(&DeviceChange) + Send + Sync>;
+
+#[derive(Default)]
+pub struct DeviceMonitor {
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+    poll_interval_secs: Arc<AtomicU64>,
+    strategies: Arc<Mutex<MonitorStrategies>>,
+    // 실행 중인 감시 루프마다 종료 신호를 보낼 곳
+    stops: Arc<Mutex<Vec<mpsc::Sender<Signal>>>>,
+}
+
+impl DeviceMonitor {
+    pub fn new(poll_interval_secs: u64) -> Self {
+        let monitor = Self::default();
+        monitor.set_poll_interval(poll_interval_secs);
+        monitor
+    }
+
+    pub fn subscribe(&self, subscriber: impl Fn(&DeviceChange) + Send + Sync + 'static) {
+        self.subscribers.lock_or_recover().push(Box::new(subscriber));
+    }
+
+    // 다음 대기부터 적용 (0은 1초로)
+    pub fn set_poll_interval(&self, secs: u64) {
+        self.poll_interval_secs.store(secs.max(1), Ordering::Relaxed);
+    }
+
+    // 앱을 끝낼 때 감시 루프를 멈추고 구독자를 지웁니다
+    // 알림 스레드는 다음 알림에서 changed()가 false가 되어 정리됩니다
+    pub fn stop(&self) {
+        self.subscribers.lock_or_recover().clear();
+        for stop in self.stops.lock_or_recover().drain(..) {
+            let _ = stop.send(Signal::Stop);
+        }
+    }
+
+    pub fn strategies(&self) -> MonitorStrategies {
+        self.strategies.lock_or_recover().clone()
+    }
+
+    // 디스플레이와 오디오 감시 스레드 시작 (구독자를 등록한 뒤 호출)
+    pub fn start(&self, displays: Arc<dyn DisplayBackend>, audio: Arc<dyn AudioBackend>) {
+        let display_watch = displays.clone();
+        self.spawn(
+            "displays",
+            |subsystem| &mut subsystem.displays,
+            move |notifier| display_watch.watch(notifier),
+            move || displays.enumerate(),
+            DeviceChange::Displays,
+        );
+        let audio_watch = audio.clone();
+        self.spawn(
+            "audio",
+            |subsystem| &mut subsystem.audio,
+            move |notifier| audio_watch.watch(notifier),
+            move || audio.enumerate(),
+            DeviceChange::AudioDevices,
+        );
+    }
+
+    fn spawn<T: Serialize + Send + 'static>(
+        &self,
+        subsystem: &'static str,
+        strategy_of: fn(&mut MonitorStrategies) -> &mut String,
+        watch: impl FnOnce(ChangeNotifier) -> Result<String, AppError> + Send + 'static,
+        read: impl Fn() -> Result<T, AppError> + Send + 'static,
+        change: fn(T) -> DeviceChange,
+    ) {
+        let watcher = Watcher {
+            subsystem,
+            poll_interval_secs: self.poll_interval_secs.clone(),
+            strategies: self.strategies.clone(),
+            strategy_of,
+            stops: self.stops.clone(),
+        };
+        let subscribers = self.subscribers.clone();
+        std::thread::spawn(move || {
+            watcher.run(watch, read, |value| {
+                let change = change(value);
+                for subscriber in subscribers.lock_or_recover().iter() {
+                    subscriber(&change);
+                }
+            })
+        });
+    }
+}
+
+// 하위 시스템 하나의 감시 루프
+struct Watcher {
+    subsystem: &'static str,
+    poll_interval_secs: Arc<AtomicU64>,
+    strategies: Arc<Mutex<MonitorStrategies>>,
+    strategy_of: fn(&mut MonitorStrategies) -> &mut String,
+    stops: Arc<Mutex<Vec<mpsc::Sender<Signal>>>>,
+}
+
+impl Watcher {
+    fn set_strategy(&self, strategy: String) {
+        log::info!("{} 변경 감시: {}", self.subsystem, strategy);
+        *(self.strategy_of)(&mut self.strategies.lock_or_recover()) = strategy;
+    }
+
+    fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.poll_interval_secs.load(Ordering::Relaxed))
+    }
+
+    fn set_polling(&self) {
+        self.set_strategy(format!("poll ({}s)", self.poll_interval().as_secs()));
+    }
+
+    fn run<T: Serialize>(
+        &self,
+        watch: impl FnOnce(ChangeNotifier) -> Result<String, AppError>,
+        read: impl Fn() -> Result<T, AppError>,
+        on_change: impl Fn(T),
+    ) {
+        let (sender, receiver) = mpsc::channel();
+        // 알림 경로가 없어도 채널이 닫히지 않도록 하나는 남겨 둡니다
+        let _keep_open = sender.clone();
+        self.stops.lock_or_recover().push(sender.clone());
+        let mut event_driven = match watch(ChangeNotifier { sender }) {
+            Ok(source) => {
+                self.set_strategy(format!("events ({})", source));
+                true
+            }
+            Err(e) => {
+                log::info!("{} 변경 알림을 사용할 수 없습니다: {}", self.subsystem, e);
+                self.set_polling();
+                false
+            }
+        };
+
+        // 처음 읽은 목록은 기준으로만 씁니다 (조회 실패는 다음 변경 때 다시 시도)
+        let mut last = read().ok().and_then(|value| serde_json::to_value(value).ok());
+        loop {
+            let signal = if event_driven {
+                receiver.recv().ok()
+            } else {
+                match receiver.recv_timeout(self.poll_interval()) {
+                    Ok(signal) => Some(signal),
+                    Err(mpsc::RecvTimeoutError::Timeout) => Some(Signal::Changed),
+                    Err(mpsc::RecvTimeoutError::Disconnected) => None,
+                }
+            };
+            match signal {
+                None | Some(Signal::Stop) => return,
+                Some(Signal::Lost(reason)) => {
+                    log::warn!("{} 변경 알림이 끊겼습니다: {}", self.subsystem, reason);
+                    event_driven = false;
+                    self.set_polling();
+                    continue;
+                }
+                Some(Signal::Changed) => {}
+            }
+            if event_driven {
+                loop {
+                    match receiver.recv_timeout(DEBOUNCE) {
+                        Ok(Signal::Changed) => {}
+                        Ok(Signal::Stop) => return,
+                        _ => break,
+                    }
+                }
+            }
+
+            let value = match read() {
+                Ok(value) => value,
+                Err(e) => {
+                    log::warn!("{} 목록 다시 읽기 실패: {}", self.subsystem, e);
+                    continue;
+                }
+            };
+            let current = serde_json::to_value(&value).ok();
+            if current != last {
+                last = current;
+                on_change(value);
+            }
+        }
+    }
+}
+
+// 외부 명령의 출력 한 줄마다 변경을 알리는 감시 (udevadm monitor, pactl subscribe 등)
+// 명령이 끝나면 알림 경로가 끊긴 것으로 알립니다
+#[cfg_attr(any(target_os = "macos", target_os = "windows"), allow(dead_code))]
+pub fn watch_command_output(
+    tool: &str,
+    args: &[&str],
+    is_change: fn(&str) -> bool,
+    notifier: ChangeNotifier,
+) -> Result<(), AppError> {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    let mut child = crate::tools::tool_command(tool)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| AppError::tool_missing(tool, &e))?;
+    let stdout = child.stdout.take().ok_or_else(|| AppError::Internal {
+        detail: format!("{} stdout unavailable", tool),
+    })?;
+
+    let tool = tool.to_string();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if is_change(&line) && !notifier.changed() {
+                // 감시가 끝났으므로 명령도 정리합니다
+                let _ = child.kill();
+                let _ = child.wait();
+                return;
+            }
+        }
+        let status = child.wait();
+        notifier.lost(&format!("{} exited ({:?})", tool, status));
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watcher(poll_interval_secs: u64) -> Watcher {
+        Watcher {
+            subsystem: "test",
+            poll_interval_secs: Arc::new(AtomicU64::new(poll_interval_secs)),
+            strategies: Arc::default(),
+            strategy_of: |strategies| &mut strategies.displays,
+            stops: Arc::default(),
+        }
+    }
+
+    #[test]
+    fn reports_only_changed_lists_after_events() {
+        let watcher = watcher(3600);
+        let strategies = watcher.strategies.clone();
+        let lists = Arc::new(Mutex::new(vec![vec![1], vec![1], vec![1, 2], vec![1, 2]]));
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let (notifier_tx, notifier_rx) = mpsc::channel();
+
+        let handle = {
+            let (lists, changes) = (lists.clone(), changes.clone());
+            std::thread::spawn(move || {
+                watcher.run(
+                    move |notifier| {
+                        notifier_tx.send(notifier).unwrap();
+                        Ok("test".to_string())
+                    },
+                    move || {
+                        let mut lists = lists.lock_or_recover();
+                        Ok(if lists.len() > 1 { lists.remove(0) } else { lists[0].clone() })
+                    },
+                    move |list: Vec<u32>| changes.lock_or_recover().push(list),
+                )
+            })
+        };
+        let notifier: ChangeNotifier = notifier_rx.recv().unwrap();
+        // 연달아 온 알림은 한 번만 다시 읽습니다
+        for _ in 0..3 {
+            notifier.changed();
+            notifier.changed();
+            std::thread::sleep(DEBOUNCE * 2);
+        }
+        assert_eq!(strategies.lock_or_recover().displays, "events (test)");
+
+        // 알림이 끊기면 주기적으로 다시 읽기로 전환
+        notifier.lost("gone");
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(strategies.lock_or_recover().displays, "poll (3600s)");
+        drop(notifier);
+        drop(handle);
+        assert_eq!(*changes.lock_or_recover(), [vec![1, 2]]);
+    }
+
+    #[test]
+    fn falls_back_to_polling_without_notifications() {
+        let watcher = watcher(1);
+        let strategies = watcher.strategies.clone();
+        let reads = Arc::new(AtomicU64::new(0));
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        {
+            let (reads, changes) = (reads.clone(), changes.clone());
+            std::thread::spawn(move || {
+                watcher.run(
+                    |_| Err(AppError::UnsupportedPlatform { feature: "test".to_string() }),
+                    move || Ok(reads.fetch_add(1, Ordering::SeqCst)),
+                    move |value| changes.lock_or_recover().push(value),
+                )
+            });
+        }
+        std::thread::sleep(Duration::from_millis(2500));
+        assert_eq!(strategies.lock_or_recover().displays, "poll (1s)");
+        assert_eq!(*changes.lock_or_recover(), [1, 2]);
+    }
+
+    #[test]
+    fn stops_watching_when_the_app_exits() {
+        let watcher = watcher(3600);
+        let stops = watcher.stops.clone();
+        let monitor = DeviceMonitor { stops, ..Default::default() };
+        let handle = std::thread::spawn(move || {
+            watcher.run(|_| Ok("test".to_string()), || Ok(0), |_: u32| {})
+        });
+        while monitor.stops.lock_or_recover().is_empty() {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        monitor.stop();
+        handle.join().unwrap();
+        assert!(monitor.stops.lock_or_recover().is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+// OS 권한 / 관리자 권한 확인
+// 권한이 없어 실패한 작업을 "이 작업에는 화면 기록 권한이 필요합니다" 같은 안내로 바꾸고,
+// 해당 시스템 설정 항목을 여는 도우미를 제공합니다
+// 확인은 앱 실행마다 한 번만 하고 결과를 보관합니다 (check_macos_permissions에서만 다시 확인)
+
+use serde::Serialize;
+use std::sync::Mutex;
+
+#[cfg(target_os = "macos")]
+use std::fs;
+#[cfg(target_os = "macos")]
+use std::path::PathBuf;
+#[cfg(target_os = "macos")]
+use std::process::Command;
+
+use crate::error::AppError;
+#[cfg(target_os = "macos")]
+use crate::tools::run_command;
+
+// macOS 개인정보 보호(TCC) 권한과 관리자 권한 상태
+// None이면 이 OS에는 없는 권한이거나 확인할 수 없음
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PermissionStatus {
+    // 손쉬운 사용 (다른 앱 제어, 이후 추가될 네이티브 호출)
+    pub accessibility: Option<bool>,
+    // 화면 기록 (macOS 10.15 이상, 다른 앱의 창 정보)
+    pub screen_recording: Option<bool>,
+    // 전체 디스크 접근 (집중 모드 상태)
+    pub full_disk_access: Option<bool>,
+    // 관리자 권한으로 실행 중인지 (Windows, 잠긴 PC의 SetDisplayConfig 등)
+    pub administrator: Option<bool>,
+}
+
+impl PermissionStatus {
+    fn granted(&self, kind: &str) -> Option<bool> {
+        match kind {
+            "accessibility" => self.accessibility,
+            "screen_recording" => self.screen_recording,
+            "full_disk_access" => self.full_disk_access,
+            "administrator" => self.administrator,
+            _ => None,
+        }
+    }
+}
+
+// 권한 종류 → 시스템 설정 > 개인정보 보호 및 보안의 항목
+const PERMISSION_PANES: &[(&str, &str)] = &[
+    ("accessibility", "Privacy_Accessibility"),
+    ("screen_recording", "Privacy_ScreenCapture"),
+    ("full_disk_access", "Privacy_AllFiles"),
+];
+
+// 적용 단계별로 필요할 수 있는 권한 (거부로 실패했을 때 이 권한이 없으면 안내로 바꿉니다)
+const STEP_PRIVILEGES: &[(&str, &str)] = &[("display", "administrator"), ("audio", "administrator")];
+
+#[cfg(target_os = "macos")]
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn AXIsProcessTrusted() -> bool;
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    // 권한 요청 창을 띄우지 않고 현재 상태만 확인합니다
+    fn CGPreflightScreenCaptureAccess() -> bool;
+}
+
+static PERMISSION_STATUS: Mutex<Option<PermissionStatus>> = Mutex::new(None);
+
+// 보관한 권한 상태 (처음 호출할 때 확인)
+pub fn permission_status() -> PermissionStatus {
+    let mut status = PERMISSION_STATUS.lock().unwrap_or_else(|e| e.into_inner());
+    status.get_or_insert_with(probe_permissions).clone()
+}
+
+// 사용자가 설정에서 권한을 바꾼 뒤 다시 확인
+pub fn refresh_permission_status() -> PermissionStatus {
+    let fresh = probe_permissions();
+    *PERMISSION_STATUS.lock().unwrap_or_else(|e| e.into_inner()) = Some(fresh.clone());
+    fresh
+}
+
+fn probe_permissions() -> PermissionStatus {
+    #[cfg(target_os = "macos")]
+    {
+        PermissionStatus {
+            accessibility: Some(unsafe { AXIsProcessTrusted() }),
+            screen_recording: Some(unsafe { CGPreflightScreenCaptureAccess() }),
+            full_disk_access: full_disk_access_macos(),
+            administrator: None,
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        PermissionStatus {
+            accessibility: None,
+            screen_recording: None,
+            full_disk_access: None,
+            administrator: Some(unsafe { windows::Win32::UI::Shell::IsUserAnAdmin() }.as_bool()),
+        }
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        PermissionStatus {
+            accessibility: None,
+            screen_recording: None,
+            full_disk_access: None,
+            administrator: None,
+        }
+    }
+}
+
+// 사용자 TCC 데이터베이스는 전체 디스크 접근 권한이 있어야 열 수 있습니다
+#[cfg(target_os = "macos")]
+fn full_disk_access_macos() -> Option<bool> {
+    let home = std::env::var_os("HOME")?;
+    let tcc_db = PathBuf::from(home).join("Library/Application Support/com.apple.TCC/TCC.db");
+    match fs::File::open(&tcc_db) {
+        Ok(_) => Some(true),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Some(false),
+        Err(_) => None,
+    }
+}
+
+// 감지 기능에 필요한 OS 권한 확인 (부족한 권한을 안내 문구로 반환)
+pub fn check_permissions() -> Vec<String> {
+    let mut user_actions = Vec::new();
+
+    // 집중 모드 상태는 전체 디스크 접근 권한이 있어야 읽을 수 있습니다
+    if permission_status().full_disk_access == Some(false) {
+        user_actions.push(
+            "시스템 설정 > 개인정보 보호 및 보안 > 전체 디스크 접근 권한에서 앱을 허용하면 집중 모드 상태를 읽을 수 있습니다"
+                .to_string(),
+        );
+    }
+
+    user_actions
+}
+
+// 권한이 없다고 확인된 경우에만 실패 (확인할 수 없으면 그대로 시도합니다)
+fn require_with(status: &PermissionStatus, kind: &str, action: &str) -> Result<(), AppError> {
+    match status.granted(kind) {
+        Some(false) => Err(AppError::PermissionRequired {
+            kind: kind.to_string(),
+            action: action.to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+// 오류 메시지가 권한 거부를 뜻하는지 (도구 출력, OS 오류 코드)
+pub fn mentions_denied_access(text: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "administrator",
+        "elevat",
+        "sudo",
+        "permission denied",
+        "access is denied",
+        "access denied",
+        "0x80070005",
+        "not permitted",
+    ];
+    let text = text.to_lowercase();
+    MARKERS.iter().any(|marker| text.contains(marker))
+}
+
+// 적용 단계의 실패가 권한 거부이고 그 단계에 필요한 권한이 없으면 권한 안내 오류로 바꿉니다
+pub fn explain_step_failure(step: &str, error: AppError) -> AppError {
+    explain_step_failure_with(&permission_status(), step, error)
+}
+
+fn explain_step_failure_with(status: &PermissionStatus, step: &str, error: AppError) -> AppError {
+    // 도구가 없는 경우 등은 설치 안내에 같은 단어가 들어 있을 수 있으므로 실행 결과만 봅니다
+    let detail = match &error {
+        AppError::ToolFailed { detail, .. }
+        | AppError::DisplayQueryFailed { detail }
+        | AppError::Io { detail, .. }
+        | AppError::Internal { detail } => detail,
+        _ => return error,
+    };
+    if !mentions_denied_access(detail) {
+        return error;
+    }
+    STEP_PRIVILEGES
+        .iter()
+        .filter(|(name, _)| *name == step)
+        .find_map(|(_, kind)| require_with(status, kind, step).err())
+        .unwrap_or(error)
+}
+
+// 권한 종류에 맞는 시스템 설정 항목 열기
+pub fn open_permission_settings(kind: &str) -> Result<(), AppError> {
+    let pane = PERMISSION_PANES
+        .iter()
+        .find(|(name, _)| *name == kind)
+        .map(|(_, pane)| *pane);
+    let Some(pane) = pane else {
+        // 관리자 권한은 설정 화면이 없고 앱을 관리자 권한으로 다시 실행해야 합니다
+        if kind == "administrator" {
+            return Err(AppError::UnsupportedPlatform { feature: "open_permission_settings".to_string() });
+        }
+        return Err(AppError::UnknownPermission { kind: kind.to_string() });
+    };
+
+    #[cfg(target_os = "macos")]
+    {
+        let url = format!("x-apple.systempreferences:com.apple.preference.security?{}", pane);
+        let output = run_command(Command::new("open").arg(&url))
+            .map_err(|e| AppError::tool_missing("open", &e))?;
+        if !output.status.success() {
+            return Err(AppError::tool_failed("open", &output));
+        }
+        Ok(())
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = pane;
+        Err(AppError::UnsupportedPlatform {
+            feature: "open_permission_settings".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(administrator: Option<bool>) -> PermissionStatus {
+        PermissionStatus {
+            accessibility: None,
+            screen_recording: Some(false),
+            full_disk_access: None,
+            administrator,
+        }
+    }
+
+    fn denied() -> AppError {
+        AppError::ToolFailed {
+            tool: "SetDisplayConfig".to_string(),
+            detail: "Access is denied. (0x80070005)".to_string(),
+        }
+    }
+
+    #[test]
+    fn explains_denied_steps_only_when_the_privilege_is_missing() {
+        let explained = explain_step_failure_with(&status(Some(false)), "display", denied());
+        assert!(matches!(
+            explained,
+            AppError::PermissionRequired { ref kind, ref action } if kind == "administrator" && action == "display"
+        ));
+
+        // 이미 관리자이거나 확인할 수 없으면 원래 오류를 그대로 보여줍니다
+        for administrator in [Some(true), None] {
+            let error = explain_step_failure_with(&status(administrator), "display", denied());
+            assert!(matches!(error, AppError::ToolFailed { .. }));
+        }
+        let unrelated = AppError::ToolFailed { tool: "displayplacer".to_string(), detail: "bad mode".to_string() };
+        let error = explain_step_failure_with(&status(Some(false)), "display", unrelated);
+        assert!(matches!(error, AppError::ToolFailed { .. }));
+    }
+
+    #[test]
+    fn requires_only_known_missing_permissions() {
+        let status = status(None);
+        assert!(matches!(
+            require_with(&status, "screen_recording", "thumbnails"),
+            Err(AppError::PermissionRequired { .. })
+        ));
+        assert!(require_with(&status, "accessibility", "thumbnails").is_ok());
+        assert!(require_with(&status, "administrator", "display").is_ok());
+    }
+}
@@ -0,0 +1,221 @@
+// 프로필 저장소 (profiles.json과 메모리의 목록)
+// 읽기(list / get)는 메모리의 목록만 보고 파일을 읽지 않습니다
+// 고치기(update / upsert / remove)는 프로세스 간 잠금을 잡고 파일의 최신 목록에서 고친 뒤,
+// 실제로 바뀐 것이 있을 때만 저장합니다 (명령줄 모드나 다른 인스턴스의 변경을 덮어쓰지 않도록)
+// 파일을 읽고 쓰므로 비동기 명령에서는 블로킹 스레드에서 호출합니다
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::error::AppError;
+use crate::profiles::{read_profiles_file_recovering, write_profiles_file, Profile, ProfilesRecovery};
+use crate::store_lock::StoreLock;
+use crate::sync::LockExt;
+
+// 마지막으로 읽거나 쓴 파일의 (수정 시각, 크기), 둘 다 같으면 목록을 다시 읽지 않습니다
+// 시각의 단위가 거친 파일 시스템에서는 놓칠 수 있으므로 고칠 때는 항상 다시 읽습니다
+type FileStamp = (SystemTime, u64);
+
+#[derive(Default)]
+struct Cache {
+    profiles: Vec<Profile>,
+    loaded: bool,
+    // 파일이 없으면 None
+    stamp: Option<FileStamp>,
+}
+
+pub struct ProfileStore {
+    path: PathBuf,
+    // 고치는 동안에도 잡고 있어 같은 프로세스의 변경이 섞이지 않습니다
+    cache: Mutex<Cache>,
+    // 손상된 파일을 백업으로 되돌린 기록 (get_profiles_recovery)
+    recovery: Mutex<Option<ProfilesRecovery>>,
+}
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+impl ProfileStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, cache: Mutex::new(Cache::default()), recovery: Mutex::new(None) }
+    }
+
+    pub fn recovery(&self) -> Option<ProfilesRecovery> {
+        self.recovery.lock_or_recover().clone()
+    }
+
+    // 메모리의 목록 (파일을 읽지 않음)
+    pub fn list(&self) -> Vec<Profile> {
+        self.cache.lock_or_recover().profiles.clone()
+    }
+
+    // 저장된 프로필 복사본 (적용하는 동안 잠금을 잡지 않도록)
+    pub fn get(&self, profile_id: &str) -> Result<Profile, AppError> {
+        self.cache
+            .lock_or_recover()
+            .profiles
+            .iter()
+            .find(|p| p.id == profile_id)
+            .cloned()
+            .ok_or_else(|| AppError::ProfileNotFound { profile_id: profile_id.to_string() })
+    }
+
+    // 마지막으로 읽은 뒤 파일이 바뀌었을 때만 다시 읽어 메모리의 목록을 돌려줍니다
+    pub fn reload(&self) -> Result<Vec<Profile>, AppError> {
+        let mut cache = self.cache.lock_or_recover();
+        self.refresh(&mut cache)?;
+        Ok(cache.profiles.clone())
+    }
+
+    // 잠금을 잡은 채 파일의 최신 목록을 고친 뒤 저장 (바뀐 것이 없으면 저장하지 않음)
+    // update가 오류를 돌려주면 파일과 메모리의 목록을 바꾸지 않습니다
+    pub fn update<T>(&self, update: impl FnOnce(&mut Vec<Profile>) -> Result<T, AppError>) -> Result<T, AppError> {
+        let mut cache = self.cache.lock_or_recover();
+        let _lock = StoreLock::acquire(&self.path)?;
+        self.read_locked(&mut cache)?;
+
+        let mut profiles = cache.profiles.clone();
+        let result = update(&mut profiles)?;
+        let changed = serde_json::to_value(&profiles).ok() != serde_json::to_value(&cache.profiles).ok();
+        if changed {
+            write_profiles_file(&self.path, &profiles)?;
+            cache.stamp = file_stamp(&self.path);
+            cache.profiles = profiles;
+        }
+        Ok(result)
+    }
+
+    // 같은 id가 있으면 바꾸고 없으면 추가
+    pub fn upsert(&self, profile: Profile) -> Result<(), AppError> {
+        self.update(|profiles| {
+            match profiles.iter().position(|p| p.id == profile.id) {
+                Some(pos) => profiles[pos] = profile,
+                None => profiles.push(profile),
+            }
+            Ok(())
+        })
+    }
+
+    // 삭제한 프로필 (없으면 None)
+    pub fn remove(&self, profile_id: &str) -> Result<Option<Profile>, AppError> {
+        self.update(|profiles| {
+            let pos = profiles.iter().position(|p| p.id == profile_id);
+            Ok(pos.map(|pos| profiles.remove(pos)))
+        })
+    }
+
+    fn refresh(&self, cache: &mut Cache) -> Result<(), AppError> {
+        if cache.loaded && file_stamp(&self.path) == cache.stamp {
+            return Ok(());
+        }
+        let _lock = StoreLock::acquire(&self.path)?;
+        self.read_locked(cache)
+    }
+
+    // 프로세스 간 잠금을 잡은 상태에서 호출
+    fn read_locked(&self, cache: &mut Cache) -> Result<(), AppError> {
+        let (profiles, recovery) = read_profiles_file_recovering(&self.path)?;
+        if recovery.is_some() {
+            *self.recovery.lock_or_recover() = recovery;
+        }
+        // 이전 형식을 다시 저장했거나 백업으로 되돌렸으면 읽은 뒤의 파일 기준
+        *cache = Cache { profiles, loaded: true, stamp: file_stamp(&self.path) };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn store_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dsm-store-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn profile(id: &str) -> Profile {
+        serde_json::from_value(serde_json::json!({ "id": id, "name": id })).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_changes_are_all_kept() {
+        let dir = store_dir();
+        let path = dir.join("profiles.json");
+        // 같은 파일을 쓰는 두 저장소 (앱과 다른 인스턴스)
+        let stores = [Arc::new(ProfileStore::new(path.clone())), Arc::new(ProfileStore::new(path.clone()))];
+
+        let tasks: Vec<_> = (0..8)
+            .map(|task| {
+                let store = stores[task % 2].clone();
+                tokio::task::spawn_blocking(move || {
+                    for n in 0..10 {
+                        store.upsert(profile(&format!("{}-{}", task, n))).unwrap();
+                    }
+                    // 짝수 번째만 남깁니다
+                    for n in (1..10).step_by(2) {
+                        assert!(store.remove(&format!("{}-{}", task, n)).unwrap().is_some());
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let mut ids: Vec<String> = ProfileStore::new(path).reload().unwrap().into_iter().map(|p| p.id).collect();
+        ids.sort();
+        assert_eq!(ids.len(), 40);
+        assert!(ids.iter().all(|id| id.ends_with(['0', '2', '4', '6', '8'])));
+        // 다른 저장소의 변경도 다시 읽으면 보입니다
+        assert_eq!(stores[0].reload().unwrap().len(), 40);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn writes_only_when_something_changed() {
+        let dir = store_dir();
+        let path = dir.join("profiles.json");
+        let store = ProfileStore::new(path.clone());
+        store.upsert(profile("desk")).unwrap();
+        store.upsert(profile("desk")).unwrap();
+        // 두 번째 저장이 파일을 썼다면 첫 번째 파일이 백업으로 남습니다
+        let backup = crate::profiles::profiles_backup_file(&path);
+        assert!(!backup.exists());
+        assert_eq!(store.remove("home").unwrap().map(|p| p.id), None);
+        assert!(!backup.exists());
+
+        store.upsert(profile("home")).unwrap();
+        assert!(backup.exists());
+        assert_eq!(store.get("home").unwrap().name, "home");
+        assert!(matches!(store.get("office"), Err(AppError::ProfileNotFound { .. })));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn keeps_working_after_a_panic_inside_an_update() {
+        let dir = store_dir();
+        let store = Arc::new(ProfileStore::new(dir.join("profiles.json")));
+        store.upsert(profile("desk")).unwrap();
+
+        let panicking = store.clone();
+        let _ = std::thread::spawn(move || {
+            let _ = panicking.update(|profiles| -> Result<(), AppError> {
+                profiles.clear();
+                panic!("명령 실패");
+            });
+        })
+        .join();
+
+        // 패닉한 변경은 저장되지 않고, 이후의 호출도 정상입니다
+        assert_eq!(store.list().len(), 1);
+        store.upsert(profile("home")).unwrap();
+        assert_eq!(store.reload().unwrap().len(), 2);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
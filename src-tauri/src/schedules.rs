@@ -0,0 +1,166 @@
+// 시각별 프로필 적용 규칙 (schedules.json)
+// 앱이 1분마다 지난 확인 이후 정해진 시각이 지나간 규칙을 찾아 그 프로필을 적용합니다
+// 잠자기 등으로 확인을 건너뛰었으면 깨어난 뒤 그날 지나간 규칙 중 가장 늦은 것 하나만 적용합니다
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::profiles::write_atomic;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Weekday {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+impl From<chrono::Weekday> for Weekday {
+    fn from(day: chrono::Weekday) -> Self {
+        match day {
+            chrono::Weekday::Mon => Weekday::Mon,
+            chrono::Weekday::Tue => Weekday::Tue,
+            chrono::Weekday::Wed => Weekday::Wed,
+            chrono::Weekday::Thu => Weekday::Thu,
+            chrono::Weekday::Fri => Weekday::Fri,
+            chrono::Weekday::Sat => Weekday::Sat,
+            chrono::Weekday::Sun => Weekday::Sun,
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Schedule {
+    // 비어 있으면 저장할 때 새로 만듭니다
+    #[serde(default)]
+    pub id: String,
+    pub profile_id: String,
+    // 로컬 시각 ("HH:MM")
+    pub time: String,
+    // 비어 있으면 매일
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+pub fn parse_schedule_time(time: &str) -> Result<NaiveTime, AppError> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M")
+        .map_err(|_| AppError::InvalidScheduleTime { time: time.to_string() })
+}
+
+impl Schedule {
+    // 이 날짜에 정해진 시각 (요일이 맞지 않거나 시각을 읽을 수 없으면 None)
+    fn occurrence_on(&self, date: NaiveDate) -> Option<NaiveDateTime> {
+        if !self.days.is_empty() && !self.days.contains(&date.weekday().into()) {
+            return None;
+        }
+        Some(date.and_time(parse_schedule_time(&self.time).ok()?))
+    }
+}
+
+// 지난 확인(since) 뒤부터 지금(now)까지 정해진 시각이 지나간 규칙 중 가장 늦은 것
+// 오늘 정해진 시각만 보므로 밤사이 잠자기에서 깨어나도 전날의 규칙은 적용하지 않습니다
+pub fn due_schedule(schedules: &[Schedule], since: NaiveDateTime, now: NaiveDateTime) -> Option<&Schedule> {
+    schedules
+        .iter()
+        .filter(|schedule| schedule.enabled)
+        .filter_map(|schedule| Some((schedule.occurrence_on(now.date())?, schedule)))
+        .filter(|(at, _)| *at > since && *at <= now)
+        .max_by_key(|(at, _)| *at)
+        .map(|(_, schedule)| schedule)
+}
+
+// 파일이 없으면 빈 목록
+pub fn read_schedules_file(path: &Path) -> Result<Vec<Schedule>, AppError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(AppError::io(path, &e)),
+    };
+    serde_json::from_str(&content).map_err(|e| AppError::corrupt_file(path, &e))
+}
+
+pub fn write_schedules_file(path: &Path, schedules: &[Schedule]) -> Result<(), AppError> {
+    let content = serde_json::to_string_pretty(schedules)
+        .map_err(|e| AppError::Internal { detail: format!("Failed to serialize schedules: {}", e) })?;
+    write_atomic(path, content.as_bytes()).map_err(|e| AppError::io(path, &e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(id: &str, time: &str, days: &[Weekday]) -> Schedule {
+        Schedule {
+            id: id.to_string(),
+            profile_id: id.to_string(),
+            time: time.to_string(),
+            days: days.to_vec(),
+            enabled: true,
+        }
+    }
+
+    // 2026-10-12는 월요일
+    fn at(day: u32, time: &str) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap().and_time(NaiveTime::parse_from_str(time, "%H:%M").unwrap())
+    }
+
+    fn due(schedules: &[Schedule], since: NaiveDateTime, now: NaiveDateTime) -> Option<&str> {
+        due_schedule(schedules, since, now).map(|schedule| schedule.id.as_str())
+    }
+
+    #[test]
+    fn fires_once_when_the_time_passes() {
+        let schedules = [schedule("work", "09:00", &[]), schedule("home", "18:30", &[])];
+        assert_eq!(due(&schedules, at(12, "08:59"), at(12, "09:00")), Some("work"));
+        // 다음 확인에서는 다시 적용하지 않습니다
+        assert_eq!(due(&schedules, at(12, "09:00"), at(12, "09:01")), None);
+        assert_eq!(due(&schedules, at(12, "18:29"), at(12, "18:30")), Some("home"));
+    }
+
+    #[test]
+    fn follows_weekdays_and_skips_disabled_schedules() {
+        let mut schedules = [schedule("weekday", "09:00", &[Weekday::Mon, Weekday::Fri])];
+        assert_eq!(due(&schedules, at(12, "08:59"), at(12, "09:00")), Some("weekday"));
+        // 화요일
+        assert_eq!(due(&schedules, at(13, "08:59"), at(13, "09:00")), None);
+        schedules[0].enabled = false;
+        assert_eq!(due(&schedules, at(12, "08:59"), at(12, "09:00")), None);
+    }
+
+    #[test]
+    fn applies_only_the_latest_missed_schedule_after_sleep() {
+        let schedules =
+            [schedule("morning", "09:00", &[]), schedule("noon", "12:00", &[]), schedule("night", "22:00", &[])];
+        // 08:00에 잠들어 13:00에 깨어남
+        assert_eq!(due(&schedules, at(12, "08:00"), at(12, "13:00")), Some("noon"));
+        // 전날 밤에 잠들었으면 전날의 22:00은 적용하지 않습니다
+        assert_eq!(due(&schedules, at(11, "21:00"), at(12, "08:00")), None);
+        assert_eq!(due(&schedules, at(11, "21:00"), at(12, "10:00")), Some("morning"));
+    }
+
+    #[test]
+    fn reads_schedules_with_defaults() {
+        let schedule: Schedule =
+            serde_json::from_value(serde_json::json!({"profile_id": "desk", "time": "07:30"})).unwrap();
+        assert!(schedule.enabled && schedule.days.is_empty() && schedule.id.is_empty());
+        let days: Vec<Weekday> = serde_json::from_value(serde_json::json!(["mon", "sun"])).unwrap();
+        assert_eq!(days, vec![Weekday::Mon, Weekday::Sun]);
+
+        assert!(parse_schedule_time("07:30").is_ok());
+        assert!(matches!(parse_schedule_time("25:00"), Err(AppError::InvalidScheduleTime { .. })));
+        assert!(parse_schedule_time("morning").is_err());
+    }
+}
@@ -0,0 +1,258 @@
+// 계속 실행해 두는 셸 프로세스
+// Windows의 PowerShell은 시작할 때마다 프로필 로드와 AMSI 검사로 1~3초가 걸리므로
+// 하나를 띄워 두고 표준 입력으로 스크립트를 보낸 뒤, 끝 표시 줄까지의 출력을 결과로 읽습니다
+// 프로세스가 끝났거나 응답하지 않으면 종료하고 다음 요청에서 새로 시작합니다
+
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Mutex, OnceLock};
+
+use crate::error::AppError;
+use crate::tools::{command_timeout, decode_output, tool_command};
+
+// 스크립트가 실패했을 때 출력하는 줄의 접두사
+const ERROR_MARKER: &str = "__DSM_ERROR__";
+const END_MARKER: &str = "__DSM_END_";
+
+// 스크립트를 감싸는 방법 (실패 표시와 끝 표시 출력)
+#[derive(Debug, Clone, Copy)]
+enum Dialect {
+    PowerShell,
+    #[cfg_attr(not(test), allow(dead_code))]
+    Posix,
+}
+
+impl Dialect {
+    fn wrap(self, script: &str, end: &str) -> String {
+        // 셸은 한 줄씩 실행하므로 스크립트를 한 줄로 보냅니다
+        let script = script.replace(['\r', '\n'], " ");
+        match self {
+            Dialect::PowerShell => format!(
+                "try {{ {} }} catch {{ Write-Output ('{}' + $_.Exception.Message) }}; Write-Output '{}'\n",
+                script, ERROR_MARKER, end
+            ),
+            Dialect::Posix => format!(
+                "{{ {} ; }} </dev/null 2>/dev/null || echo \"{}exit $?\"; echo '{}'\n",
+                script, ERROR_MARKER, end
+            ),
+        }
+    }
+}
+
+// 요청 하나가 실패한 이유
+enum Failure {
+    // 스크립트가 실패 (세션은 계속 사용)
+    Script(AppError),
+    // 세션이 끝났거나 응답하지 않음 (세션을 새로 시작)
+    Session(AppError),
+}
+
+struct Running {
+    child: Child,
+    stdin: ChildStdin,
+    lines: mpsc::Receiver<String>,
+}
+
+impl Running {
+    fn stop(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+pub struct ShellSession {
+    tool: &'static str,
+    args: &'static [&'static str],
+    dialect: Dialect,
+    running: Mutex<Option<Running>>,
+    next_id: AtomicU64,
+}
+
+impl ShellSession {
+    fn new(tool: &'static str, args: &'static [&'static str], dialect: Dialect) -> Self {
+        Self {
+            tool,
+            args,
+            dialect,
+            running: Mutex::new(None),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    fn start(&self) -> Result<Running, AppError> {
+        log::info!("셸 세션 시작: {} {:?}", self.tool, self.args);
+        let mut child = tool_command(self.tool)
+            .args(self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| AppError::tool_missing(self.tool, &e))?;
+        let (stdin, stdout) = match (child.stdin.take(), child.stdout.take()) {
+            (Some(stdin), Some(stdout)) => (stdin, stdout),
+            _ => {
+                let _ = child.kill();
+                return Err(AppError::Internal { detail: format!("{} pipes unavailable", self.tool) });
+            }
+        };
+
+        // 제한 시간을 두고 기다릴 수 있도록 출력은 별도 스레드에서 줄 단위로 넘깁니다
+        let (sender, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut reader = BufReader::new(stdout);
+            let mut line = Vec::new();
+            loop {
+                line.clear();
+                match reader.read_until(b'\n', &mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {}
+                }
+                let text = decode_output(line.strip_suffix(b"\n").unwrap_or(&line));
+                if sender.send(text.trim_end_matches('\r').to_string()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut running = Running { child, stdin, lines };
+        if let Dialect::PowerShell = self.dialect {
+            // 장치 이름이 깨지지 않도록 출력 인코딩을 UTF-8로 고정
+            let init = "[Console]::OutputEncoding = [System.Text.Encoding]::UTF8";
+            if let Err(Failure::Script(e) | Failure::Session(e)) = self.exchange(&mut running, init) {
+                running.stop();
+                return Err(e);
+            }
+        }
+        Ok(running)
+    }
+
+    // 스크립트를 보내고 끝 표시까지의 출력을 읽습니다
+    fn exchange(&self, running: &mut Running, script: &str) -> Result<String, Failure> {
+        let end = format!("{}{}__", END_MARKER, self.next_id.fetch_add(1, Ordering::Relaxed));
+        let lost = |detail: String| {
+            Failure::Session(AppError::ToolFailed { tool: self.tool.to_string(), detail })
+        };
+        running
+            .stdin
+            .write_all(self.dialect.wrap(script, &end).as_bytes())
+            .and_then(|_| running.stdin.flush())
+            .map_err(|e| lost(format!("session input closed: {}", e)))?;
+
+        let timeout = command_timeout();
+        let deadline = std::time::Instant::now() + timeout;
+        let mut output = Vec::new();
+        let mut error = None;
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            let line = match running.lines.recv_timeout(remaining) {
+                Ok(line) => line,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    return Err(Failure::Session(AppError::ToolTimeout {
+                        tool: self.tool.to_string(),
+                        command: script.to_string(),
+                        timeout_secs: timeout.as_secs().to_string(),
+                    }))
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(lost("session exited".to_string()))
+                }
+            };
+            if line.trim_end() == end {
+                break;
+            }
+            match line.strip_prefix(ERROR_MARKER) {
+                Some(detail) => error = Some(detail.trim().to_string()),
+                None => output.push(line),
+            }
+        }
+        match error {
+            Some(detail) => {
+                Err(Failure::Script(AppError::ToolFailed { tool: self.tool.to_string(), detail }))
+            }
+            None => Ok(output.join("\n")),
+        }
+    }
+
+    // 스크립트 실행 결과(표준 출력)를 반환합니다
+    // 세션이 끝났거나 응답하지 않은 경우 한 번만 새 세션으로 다시 시도합니다
+    pub fn run(&self, script: &str) -> Result<String, AppError> {
+        let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        log::info!("셸 세션 명령: {}", script);
+        for attempt in 0..2 {
+            let mut session = match running.take() {
+                Some(session) => session,
+                None => self.start()?,
+            };
+            match self.exchange(&mut session, script) {
+                // 스크립트 자체의 실패는 세션을 그대로 두고 반환합니다
+                Ok(output) => {
+                    *running = Some(session);
+                    return Ok(output);
+                }
+                Err(Failure::Script(e)) => {
+                    *running = Some(session);
+                    return Err(e);
+                }
+                Err(Failure::Session(e)) => {
+                    log::warn!("셸 세션 재시작 ({}): {}", self.tool, e);
+                    session.stop();
+                    // 시간 초과는 다시 보내도 같을 가능성이 높으므로 재시도하지 않습니다
+                    if attempt == 1 || matches!(e, AppError::ToolTimeout { .. }) {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        unreachable!("셸 세션 재시도 횟수 초과")
+    }
+}
+
+// Windows PowerShell 세션 (처음 사용할 때 시작, 앱 상태 없이 플랫폼 모듈에서 사용)
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn powershell() -> &'static ShellSession {
+    static SESSION: OnceLock<ShellSession> = OnceLock::new();
+    SESSION.get_or_init(|| {
+        ShellSession::new(
+            "powershell",
+            &["-NoProfile", "-NonInteractive", "-NoLogo", "-Command", "-"],
+            Dialect::PowerShell,
+        )
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    // 같은 방식으로 동작하는 sh 세션으로 확인합니다
+    fn posix_session() -> ShellSession {
+        ShellSession::new("sh", &[], Dialect::Posix)
+    }
+
+    #[test]
+    fn reuses_session_for_fast_round_trips() {
+        let session = posix_session();
+        assert_eq!(session.run("echo first").unwrap(), "first");
+
+        let started = Instant::now();
+        for _ in 0..10 {
+            assert_eq!(session.run("printf 'a\\nb\\n'").unwrap(), "a\nb");
+        }
+        assert!(started.elapsed() / 10 < Duration::from_millis(500));
+    }
+
+    #[test]
+    fn reports_script_failures_and_restarts_exited_sessions() {
+        let session = posix_session();
+        assert!(matches!(session.run("false"), Err(AppError::ToolFailed { .. })));
+        assert_eq!(session.run("echo still running").unwrap(), "still running");
+
+        // 셸이 끝나면 다음 요청에서 새 세션으로 다시 실행합니다 (UTF-8 장치 이름은 그대로)
+        let _ = session.run("exit 3");
+        assert_eq!(session.run("echo restarted").unwrap(), "restarted");
+        let name = "Lautsprecher (Realtek® Audio) 스피커 🎧";
+        assert_eq!(session.run(&format!("echo '{}'", name)).unwrap(), name);
+    }
+}
@@ -0,0 +1,430 @@
+// 앱 상태와 설정, 프로필 / 적용 기록 파일 저장
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::apply_queue::{ApplyQueue, ApplyQueueMode};
+use crate::audio::{
+    capture_audio_settings, AudioBackend, AudioBackendPreference, AudioDevice, AudioSettings,
+};
+use crate::display::layout::{DEFAULT_DESKTOP_LIMIT, LAYOUT_SLOTS};
+use crate::display::{DisplayBackend, DisplayInfo};
+use crate::error::AppError;
+use crate::hooks::DEFAULT_HOOK_TIMEOUT_SECS;
+use crate::last_good::LastKnownGood;
+use crate::monitor::{DeviceMonitor, DEFAULT_POLL_INTERVAL_SECS};
+use crate::profile_store::ProfileStore;
+use crate::profiles::{write_atomic, CancellationToken, Profile};
+use crate::schedules::{write_schedules_file, Schedule};
+use crate::sync::LockExt;
+use crate::tools::{RetryPolicy, DEFAULT_COMMAND_TIMEOUT_SECS};
+use crate::{hotkeys, midi, ws};
+
+// 앱 설정 (settings.json)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AppSettings {
+    pub log_level: String,
+    // None이면 시스템 언어를 따릅니다
+    pub locale: Option<String>,
+    // 앱 시작 시 적용할 프로필 id
+    pub apply_on_startup: Option<String>,
+    // 디스플레이가 연결 / 분리되면 구성이 맞는 프로필을 자동으로 적용할지
+    pub auto_apply_enabled: bool,
+    // 맞는 프로필이 여러 개일 때 목록에서 처음 것을 적용할지 (끄면 적용하지 않음)
+    pub auto_apply_first_match: bool,
+    // 트레이 아이콘 표시 여부
+    pub tray_enabled: bool,
+    // 창을 닫으면 종료하지 않고 트레이로 숨길지 (트레이 아이콘이 있을 때만)
+    pub close_to_tray: bool,
+    // 작업별 외부 도구 명령 템플릿 (없는 작업은 기본 템플릿 사용)
+    pub command_templates: HashMap<String, String>,
+    // 장치 id → 표시할 별명
+    pub device_nicknames: HashMap<String, String>,
+    // 빠른 전환 목록에서 숨길 장치 id
+    pub hidden_devices: Vec<String>,
+    pub remote_control: RemoteControlSettings,
+    pub midi_mappings: Vec<midi::MidiMapping>,
+    // 디스플레이 원점 허용 범위 (±값, 화면 전체가 범위 밖이면 프로필 거부)
+    pub desktop_limit: i32,
+    // 외부 명령 제한 시간 (초, 넘으면 종료)
+    pub command_timeout_secs: u64,
+    // 프로필의 적용 전후 명령 제한 시간 (초)
+    pub hook_timeout_secs: u64,
+    // 프로필의 오디오 장치가 연결되어 있지 않으면 그 장치만 건너뛰고 나머지를 적용할지 (아니면 적용하지 않음)
+    pub skip_missing_audio_devices: bool,
+    // 일시적인 외부 도구 실패의 재시도 횟수와 대기 시간
+    pub retry_policy: RetryPolicy,
+    // Linux 오디오 백엔드 (PipeWire 직접 사용 또는 PulseAudio 호환 계층)
+    pub audio_backend: AudioBackendPreference,
+    // 적용 중에 들어온 다른 프로필 요청을 차례로 실행할지, 마지막 요청으로 교체할지
+    pub apply_queue_mode: ApplyQueueMode,
+    // 변경 알림을 받을 수 없을 때 장치 목록을 다시 읽는 주기 (초)
+    pub monitor_poll_interval_secs: u64,
+}
+
+// 외부 연동용 로컬 서버 설정 (기본값은 꺼짐)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct RemoteControlSettings {
+    pub enabled: bool,
+    pub port: u16,
+    // Authorization: Bearer 토큰 (처음 켤 때 생성)
+    pub token: Option<String>,
+    // 연결할 수 있는 브라우저 페이지의 Origin (예: "http://localhost:5173"), Origin을 보내지 않는 클라이언트는 확인하지 않음
+    pub allowed_origins: Vec<String>,
+}
+
+impl RemoteControlSettings {
+    // 토큰이 없거나 비어 있으면 새로 만듦 (새로 만들었으면 true)
+    // 빈 토큰으로 서버를 열면 토큰 없이 누구나 연결할 수 있습니다
+    pub fn ensure_token(&mut self) -> bool {
+        if self.token.as_deref().is_some_and(|token| !token.trim().is_empty()) {
+            return false;
+        }
+        self.token = Some(uuid::Uuid::new_v4().simple().to_string());
+        true
+    }
+}
+
+impl Default for RemoteControlSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 47800,
+            token: None,
+            allowed_origins: Vec::new(),
+        }
+    }
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            log_level: "info".to_string(),
+            locale: None,
+            apply_on_startup: None,
+            auto_apply_enabled: false,
+            auto_apply_first_match: false,
+            tray_enabled: true,
+            close_to_tray: true,
+            command_templates: HashMap::new(),
+            device_nicknames: HashMap::new(),
+            hidden_devices: Vec::new(),
+            remote_control: RemoteControlSettings::default(),
+            midi_mappings: Vec::new(),
+            desktop_limit: DEFAULT_DESKTOP_LIMIT,
+            command_timeout_secs: DEFAULT_COMMAND_TIMEOUT_SECS,
+            hook_timeout_secs: DEFAULT_HOOK_TIMEOUT_SECS,
+            skip_missing_audio_devices: false,
+            retry_policy: RetryPolicy::default(),
+            audio_backend: AudioBackendPreference::Auto,
+            apply_queue_mode: ApplyQueueMode::default(),
+            monitor_poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+        }
+    }
+}
+
+pub const LOG_FILE_NAME: &str = "display-sound-manager";
+// 로그 파일 하나의 최대 크기와 보관할 이전 로그 파일 수
+pub const LOG_MAX_FILE_SIZE: u128 = 1024 * 1024;
+pub const LOG_RETAINED_FILES: usize = 3;
+
+pub struct AppState {
+    // 실제 OS 백엔드 (DSM_MOCK_BACKEND가 있으면 mock)
+    pub display_backend: Arc<dyn DisplayBackend>,
+    pub audio_backend: Arc<dyn AudioBackend>,
+    pub profiles: ProfileStore,
+    // 시각별 프로필 적용 규칙
+    pub schedules: Mutex<Vec<Schedule>>,
+    pub schedules_file: PathBuf,
+    pub settings: Mutex<AppSettings>,
+    pub settings_file: PathBuf,
+    pub log_file: PathBuf,
+    // 마지막으로 적용에 성공한 프로필 id
+    pub active_profile: Mutex<Option<String>>,
+    // 다시 시작해도 활성 프로필을 알 수 있도록 적용 시각과 함께 저장하는 파일
+    pub active_profile_file: PathBuf,
+    pub event_bus: ws::EventBus,
+    // 실행 중인 WebSocket 브리지 종료용
+    pub remote_server: Mutex<Option<tokio::sync::oneshot::Sender<()>>>,
+    pub midi: midi::MidiListener,
+    pub hotkeys: hotkeys::HotkeyManager,
+    // 마지막으로 조회한 디스플레이 / 오디오 장치 목록
+    pub last_displays: Mutex<Option<Vec<DisplayInfo>>>,
+    pub last_audio_devices: Mutex<Option<Vec<AudioDevice>>>,
+    // 적용 중 비정상 종료에 대비한 기록 파일
+    pub journal_file: PathBuf,
+    // 시작 시 발견한 이전 실행의 중단된 적용
+    pub pending_recovery: Mutex<Option<ApplyJournal>>,
+    // 마지막 적용 직전의 구성 (revert_last_apply, 저장하지 않음)
+    pub revert_point: Mutex<Option<RevertPoint>>,
+    // 마지막으로 정상 동작이 확인된 구성 (트레이의 디스플레이 복구)
+    pub last_known_good_file: PathBuf,
+    // 빠른 비교용으로 잠시 보관한 디스플레이 배치 (저장하지 않음)
+    pub layout_slots: Mutex<[Option<Vec<DisplayInfo>>; LAYOUT_SLOTS]>,
+    // 진행 중인 적용의 취소 신호 (cancel_apply에서 사용)
+    pub apply_cancel: Mutex<Option<CancellationToken>>,
+    // 모든 적용 요청이 거치는 대기열 (한 번에 하나씩 실행)
+    pub apply_queue: ApplyQueue,
+    // 디스플레이 / 오디오 장치 변경 감시
+    pub device_monitor: DeviceMonitor,
+}
+
+impl AppState {
+    // 적용 시작 전 기록 (임시 파일에 쓴 뒤 이름을 바꿔 반쯤 쓰인 파일이 남지 않게 합니다)
+    pub fn write_apply_journal(&self, journal: &ApplyJournal) -> Result<(), AppError> {
+        let content = serde_json::to_string(journal)
+            .map_err(|e| AppError::Internal { detail: format!("Failed to serialize apply journal: {}", e) })?;
+        write_atomic(&self.journal_file, content.as_bytes()).map_err(|e| AppError::io(&self.journal_file, &e))
+    }
+
+    pub fn read_apply_journal(&self) -> Option<ApplyJournal> {
+        let content = fs::read_to_string(&self.journal_file).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(journal) => Some(journal),
+            Err(e) => {
+                log::warn!("적용 기록을 읽을 수 없어 삭제합니다: {}", e);
+                self.clear_apply_journal();
+                None
+            }
+        }
+    }
+
+    pub fn clear_apply_journal(&self) {
+        if let Err(e) = fs::remove_file(&self.journal_file) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("적용 기록 삭제 실패: {}", e);
+            }
+        }
+    }
+
+    // 저장된 활성 프로필 (없거나 읽을 수 없으면 None)
+    pub fn read_active_profile(&self) -> Option<ActiveProfileRecord> {
+        let content = match fs::read_to_string(&self.active_profile_file) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                log::warn!("{}", AppError::io(&self.active_profile_file, &e));
+                return None;
+            }
+        };
+        serde_json::from_str(&content)
+            .map_err(|e| log::warn!("{}", AppError::corrupt_file(&self.active_profile_file, &e)))
+            .ok()
+    }
+
+    // 활성 프로필을 바꾸고 파일에 저장 (None이면 파일 삭제)
+    // 저장에 실패해도 이번 실행 동안은 바뀐 값을 사용합니다
+    pub fn set_active_profile(&self, profile_id: Option<String>) {
+        let mut active_profile = self.active_profile.lock_or_recover();
+        let result = match &profile_id {
+            Some(profile_id) => {
+                let record = ActiveProfileRecord {
+                    profile_id: profile_id.clone(),
+                    applied_at: chrono::Local::now().to_rfc3339(),
+                };
+                serde_json::to_string_pretty(&record)
+                    .map_err(std::io::Error::other)
+                    .and_then(|content| write_atomic(&self.active_profile_file, content.as_bytes()))
+            }
+            None => match fs::remove_file(&self.active_profile_file) {
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                result => result,
+            },
+        };
+        if let Err(e) = result {
+            log::warn!("{}", AppError::io(&self.active_profile_file, &e));
+        }
+        *active_profile = profile_id;
+    }
+
+    // 시각별 규칙을 고쳐 저장 (update가 오류를 돌려주거나 저장에 실패하면 바꾸지 않음)
+    pub fn update_schedules<T>(
+        &self,
+        update: impl FnOnce(&mut Vec<Schedule>) -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let mut schedules = self.schedules.lock_or_recover();
+        let mut updated = schedules.clone();
+        let result = update(&mut updated)?;
+        write_schedules_file(&self.schedules_file, &updated)?;
+        *schedules = updated;
+        Ok(result)
+    }
+
+    pub fn read_last_known_good(&self) -> Result<Option<LastKnownGood>, AppError> {
+        if !self.last_known_good_file.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&self.last_known_good_file)
+            .map_err(|e| AppError::io(&self.last_known_good_file, &e))?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| AppError::corrupt_file(&self.last_known_good_file, &e))
+    }
+
+    // 현재 구성이 프로필과 일치하면 정상 구성으로 저장 (저장했으면 true)
+    // 확인에 실패하면 이전에 저장한 구성을 그대로 둡니다
+    pub fn record_last_known_good(&self, profile: &Profile) -> bool {
+        let (Ok(displays), Ok(devices)) = (self.display_backend.enumerate(), self.audio_backend.enumerate()) else {
+            return false;
+        };
+        let Some(last_known_good) = LastKnownGood::verified(profile, &displays, &devices) else {
+            return false;
+        };
+        let content = match serde_json::to_string_pretty(&last_known_good) {
+            Ok(content) => content,
+            Err(e) => {
+                log::warn!("정상 구성 저장 실패: {}", e);
+                return false;
+            }
+        };
+        match write_atomic(&self.last_known_good_file, content.as_bytes()) {
+            Ok(()) => {
+                log::info!("정상 구성 저장 (프로필 {})", profile.id);
+                true
+            }
+            Err(e) => {
+                log::warn!("{}", AppError::io(&self.last_known_good_file, &e));
+                false
+            }
+        }
+    }
+
+    // 적용 기록을 남기고 적용 (완료되면 성공/실패와 관계없이 기록 삭제)
+    pub fn with_apply_journal<T>(
+        &self,
+        profile_id: &str,
+        intents: &[&str],
+        apply: impl FnOnce() -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let snapshot = match (self.display_backend.enumerate(), self.audio_backend.enumerate()) {
+            (Ok(displays), Ok(devices)) => Some(ApplySnapshot {
+                displays,
+                audio_settings: capture_audio_settings(&devices),
+            }),
+            _ => None,
+        };
+        // 되돌리기 자체는 되돌릴 지점을 바꾸지 않습니다
+        // 적용 전 구성을 읽지 못했으면 이전 지점도 지웁니다 (이번 적용 전의 구성이 아니므로)
+        if profile_id != REVERT_PROFILE_ID {
+            *self.revert_point.lock_or_recover() = snapshot.clone().map(|snapshot| RevertPoint {
+                profile_id: profile_id.to_string(),
+                previous_profile: self.active_profile.lock_or_recover().clone(),
+                saved_at: chrono::Local::now().to_rfc3339(),
+                snapshot,
+            });
+        }
+        let journal = ApplyJournal {
+            profile_id: profile_id.to_string(),
+            started_at: chrono::Local::now().to_rfc3339(),
+            snapshot,
+            intents: intents.iter().map(|intent| intent.to_string()).collect(),
+        };
+        if let Err(e) = self.write_apply_journal(&journal) {
+            log::warn!("{}", e);
+        }
+
+        let result = apply();
+        self.clear_apply_journal();
+        result
+    }
+
+    // 새 적용을 진행 중으로 등록 (이전 적용의 토큰은 교체)
+    pub fn begin_apply(&self) -> CancellationToken {
+        let token = CancellationToken::new();
+        *self.apply_cancel.lock_or_recover() = Some(token.clone());
+        token
+    }
+
+    // 적용이 끝나면 등록 해제 (그사이 다른 적용이 시작되었으면 그대로 둡니다)
+    pub fn finish_apply(&self, token: &CancellationToken) {
+        let mut current = self.apply_cancel.lock_or_recover();
+        if current.as_ref().is_some_and(|current| current.same_as(token)) {
+            *current = None;
+        }
+    }
+
+    // 배치 슬롯 번호 확인
+    pub fn layout_slot(slot: u8) -> Result<usize, AppError> {
+        let index = slot as usize;
+        if index < LAYOUT_SLOTS {
+            Ok(index)
+        } else {
+            Err(AppError::InvalidLayoutSlot { slot: slot.to_string() })
+        }
+    }
+
+    pub fn load_settings(&self) -> Result<AppSettings, AppError> {
+        if self.settings_file.exists() {
+            let content = fs::read_to_string(&self.settings_file)
+                .map_err(|e| AppError::io(&self.settings_file, &e))?;
+            let settings: AppSettings = serde_json::from_str(&content)
+                .map_err(|e| AppError::corrupt_file(&self.settings_file, &e))?;
+            Ok(settings)
+        } else {
+            Ok(AppSettings::default())
+        }
+    }
+
+    pub fn save_settings(&self, settings: &AppSettings) -> Result<(), AppError> {
+        if let Some(parent) = self.settings_file.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, &e))?;
+        }
+
+        let content = serde_json::to_string_pretty(settings)
+            .map_err(|e| AppError::Internal { detail: format!("Failed to serialize settings: {}", e) })?;
+        fs::write(&self.settings_file, content).map_err(|e| AppError::io(&self.settings_file, &e))?;
+        Ok(())
+    }
+}
+
+// 적용 직전 상태 (복구 시 되돌릴 구성)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApplySnapshot {
+    pub displays: Vec<DisplayInfo>,
+    pub audio_settings: AudioSettings,
+}
+
+// 되돌리기를 적용 대기열과 기록에 표시할 id
+pub const REVERT_PROFILE_ID: &str = "revert-last-apply";
+
+// 활성 프로필 기록 (active-profile.json)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActiveProfileRecord {
+    pub profile_id: String,
+    pub applied_at: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ActiveProfileStatus {
+    #[serde(flatten)]
+    pub record: ActiveProfileRecord,
+    // 지금 연결된 디스플레이 구성이 프로필과 같은지 (적용 뒤 직접 바꾸면 false)
+    pub matches_current: bool,
+}
+
+// 마지막 적용 전 구성 (적용이 중간에 실패해도 남아 있어 원래 배치로 되돌릴 수 있습니다)
+#[derive(Debug, Serialize, Clone)]
+pub struct RevertPoint {
+    // 적용한 프로필
+    pub profile_id: String,
+    // 적용 전 활성 프로필 (되돌린 뒤 다시 활성으로 표시)
+    pub previous_profile: Option<String>,
+    pub saved_at: String,
+    pub snapshot: ApplySnapshot,
+}
+
+// 진행 중인 적용 기록 (정상 종료 시 삭제)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApplyJournal {
+    pub profile_id: String,
+    pub started_at: String,
+    // 적용 전 구성을 읽지 못했으면 None
+    pub snapshot: Option<ApplySnapshot>,
+    // 적용하려던 부분 ("display", "audio")
+    pub intents: Vec<String>,
+}
@@ -0,0 +1,121 @@
+// 프로필 저장소의 프로세스 간 잠금
+// 명령줄 모드, 두 번째 인스턴스, 같은 컴퓨터의 다른 사용자 세션이 동시에 읽고 고쳐 쓰면
+// 한쪽 변경이 사라지므로, 저장소 옆의 잠금 파일을 권고 잠금(flock / LockFileEx)으로 잡은 뒤 읽고 씁니다
+// 잠금은 StoreLock을 버릴 때(오류로 일찍 반환하거나 패닉이 나도) 풀립니다
+
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+
+// 다른 프로세스가 잠금을 풀기를 기다리는 최대 시간
+pub const STORE_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+// 저장소의 잠금 파일 위치
+// 파일 하나에 저장하면 "profiles.json.lock", 디렉터리를 넘기면 "{디렉터리}/.lock"
+// (프로필을 파일별로 나눠 저장하는 방식은 아직 없으며, 지금은 profiles.json 하나만 이 잠금을 씁니다)
+pub fn lock_path(store: &Path) -> PathBuf {
+    if store.is_dir() {
+        return store.join(".lock");
+    }
+    let mut name = store.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    store.with_file_name(name)
+}
+
+#[derive(Debug)]
+pub struct StoreLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl StoreLock {
+    pub fn acquire(store: &Path) -> Result<StoreLock, AppError> {
+        Self::acquire_with_timeout(store, STORE_LOCK_TIMEOUT)
+    }
+
+    pub fn acquire_with_timeout(store: &Path, timeout: Duration) -> Result<StoreLock, AppError> {
+        let path = lock_path(store);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, &e))?;
+        }
+        // 잠금 파일은 지우지 않습니다 (지우면 다른 프로세스가 다른 파일을 잠글 수 있음)
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| AppError::io(&path, &e))?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(StoreLock { file, path }),
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    std::thread::sleep(RETRY_INTERVAL);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    log::warn!("저장소 잠금 대기 시간 초과: {}", path.display());
+                    return Err(AppError::StoreLocked { path: store.display().to_string() });
+                }
+                Err(TryLockError::Error(e)) => return Err(AppError::io(&path, &e)),
+            }
+        }
+    }
+}
+
+impl Drop for StoreLock {
+    fn drop(&mut self) {
+        // 파일을 닫으면 OS가 풀지만, 핸들이 남아 있는 동안에도 바로 풀리도록 명시합니다
+        if let Err(e) = self.file.unlock() {
+            log::warn!("저장소 잠금 해제 실패 ({}): {}", self.path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dsm-store-lock-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn times_out_while_another_holder_keeps_the_lock() {
+        let store = temp_store("single").join("profiles.json");
+        let held = StoreLock::acquire(&store).unwrap();
+        assert!(store.with_file_name("profiles.json.lock").exists());
+
+        let started = Instant::now();
+        let result = StoreLock::acquire_with_timeout(&store, Duration::from_millis(200));
+        assert!(matches!(result, Err(AppError::StoreLocked { .. })));
+        assert!(started.elapsed() >= Duration::from_millis(200));
+
+        drop(held);
+        StoreLock::acquire_with_timeout(&store, Duration::ZERO).unwrap();
+        let _ = fs::remove_dir_all(store.parent().unwrap());
+    }
+
+    #[test]
+    fn releases_the_lock_after_a_panic() {
+        // 디렉터리는 디렉터리 안의 .lock 하나로 잠급니다
+        let store = temp_store("directory");
+        assert_eq!(lock_path(&store), store.join(".lock"));
+
+        let panicking = store.clone();
+        let result = std::thread::spawn(move || {
+            let _lock = StoreLock::acquire(&panicking).unwrap();
+            panic!("저장 중 패닉");
+        })
+        .join();
+        assert!(result.is_err());
+        StoreLock::acquire_with_timeout(&store, Duration::ZERO).unwrap();
+        let _ = fs::remove_dir_all(&store);
+    }
+}
@@ -0,0 +1,42 @@
+// 독(poison)이 된 잠금 복구
+// 잠금을 잡은 채로 명령이 패닉하면 std::sync::Mutex는 이후의 lock()을 모두 Err로 돌려줘
+// unwrap하는 모든 명령이 연달아 패닉합니다. 상태 값은 패닉 전에 끝난 변경까지 반영된 그대로이므로
+// 기록을 남기고 잠금을 그대로 이어서 씁니다
+
+use std::sync::{Mutex, MutexGuard};
+
+pub trait LockExt<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T>;
+}
+
+impl<T> LockExt<T> for Mutex<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(|e| {
+            log::warn!("이전 패닉으로 독이 된 잠금을 복구합니다");
+            self.clear_poison();
+            e.into_inner()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recovers_a_lock_poisoned_by_a_panic() {
+        let profiles = std::sync::Arc::new(Mutex::new(vec!["desk"]));
+        let held = profiles.clone();
+        let _ = std::thread::spawn(move || {
+            let mut guard = held.lock().unwrap();
+            guard.push("home");
+            panic!("명령 실패");
+        })
+        .join();
+        assert!(profiles.is_poisoned());
+
+        // 패닉 전의 변경은 남아 있고, 복구한 뒤에는 독이 풀립니다
+        assert_eq!(*profiles.lock_or_recover(), vec!["desk", "home"]);
+        assert!(!profiles.is_poisoned());
+    }
+}
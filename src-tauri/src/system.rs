@@ -0,0 +1,140 @@
+// 운영체제 정보 (컴퓨터 이름, 집중 모드 상태)
+
+use serde::Serialize;
+
+#[cfg(not(target_os = "windows"))]
+use std::fs;
+#[cfg(target_os = "macos")]
+use std::path::PathBuf;
+#[cfg(target_os = "macos")]
+use std::process::Command;
+
+use crate::monitor::MonitorStrategies;
+use crate::privileges::{permission_status, PermissionStatus};
+#[cfg(not(target_os = "windows"))]
+use crate::tools::{decode_output, run_command};
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+use crate::tools::{tool_command, tool_missing_message};
+
+// 진단용 시스템 정보
+#[derive(Debug, Serialize, Clone)]
+pub struct SystemInfo {
+    pub os: String,
+    pub arch: String,
+    pub machine_name: String,
+    // 디스플레이 목록을 읽는 방법 (Linux는 세션과 컴포지터에 따라 자동 선택)
+    pub display_backend: String,
+    // 오디오 장치를 다루는 방법 (Linux는 PipeWire 실행 여부와 설정에 따라 선택)
+    pub audio_backend: String,
+    // 장치 변경을 알아채는 방법 (OS 알림 또는 주기적으로 다시 읽기)
+    pub device_monitor: MonitorStrategies,
+    // OS 권한 / 관리자 권한 (앱 실행 중 처음 확인한 값)
+    pub permissions: PermissionStatus,
+}
+
+pub fn system_info(
+    display_backend: String,
+    audio_backend: String,
+    device_monitor: MonitorStrategies,
+) -> SystemInfo {
+    SystemInfo {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        machine_name: machine_name(),
+        display_backend,
+        audio_backend,
+        device_monitor,
+        permissions: permission_status(),
+    }
+}
+
+// 시작 프로필 이름에 쓸 컴퓨터 이름
+pub fn machine_name() -> String {
+    #[cfg(target_os = "macos")]
+    let name = run_command(Command::new("scutil").args(["--get", "ComputerName"]))
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| decode_output(&output.stdout).trim().to_string());
+    #[cfg(target_os = "windows")]
+    let name = std::env::var("COMPUTERNAME").ok();
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let name = fs::read_to_string("/etc/hostname")
+        .ok()
+        .map(|content| content.trim().to_string());
+
+    name.filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "This computer".to_string())
+}
+
+// 방해 금지(집중 모드) 상태 (플랫폼에서 조회할 수 없으면 None)
+pub fn focus_state() -> Option<bool> {
+    #[cfg(target_os = "macos")]
+    {
+        focus_state_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // Windows의 집중 지원 상태는 공개 API로 조회할 수 없습니다
+        None
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        focus_state_linux()
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn focus_state_macos() -> Option<bool> {
+    // macOS 12 이상: 활성화된 집중 모드는 Assertions.json에 기록됩니다
+    // (전체 디스크 접근 권한이 없으면 읽을 수 없습니다)
+    if let Some(home) = std::env::var_os("HOME") {
+        let assertions = PathBuf::from(home).join("Library/DoNotDisturb/DB/Assertions.json");
+        if let Ok(content) = fs::read_to_string(&assertions) {
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
+                let active = json["data"]
+                    .as_array()
+                    .map(|entries| {
+                        entries.iter().any(|entry| {
+                            entry["storeAssertionRecords"]
+                                .as_array()
+                                .is_some_and(|records| !records.is_empty())
+                        })
+                    })
+                    .unwrap_or(false);
+                return Some(active);
+            }
+        }
+    }
+
+    // macOS 11 이하: 알림 센터 설정에서 방해 금지 값을 읽습니다
+    match run_command(Command::new("defaults")
+        .args(["-currentHost", "read", "com.apple.notificationcenterui", "doNotDisturb"]))
+    {
+        Ok(output) if output.status.success() => {
+            let value = decode_output(&output.stdout);
+            Some(value.trim() == "1")
+        }
+        _ => None,
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn focus_state_linux() -> Option<bool> {
+    // GNOME: 배너 표시가 꺼져 있으면 방해 금지 상태입니다
+    match run_command(tool_command("gsettings")
+        .args(["get", "org.gnome.desktop.notifications", "show-banners"]))
+    {
+        Ok(output) if output.status.success() => {
+            match decode_output(&output.stdout).trim() {
+                "true" => Some(false),
+                "false" => Some(true),
+                _ => None,
+            }
+        }
+        Ok(_) => None,
+        Err(e) => {
+            log::debug!("{}", tool_missing_message("gsettings", &e));
+            None
+        }
+    }
+}
@@ -0,0 +1,1387 @@
+// 외부 도구 실행 (명령 템플릿, 도구 위치, 의존성 점검과 설치)
+
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+use crate::locale::{current_locale, Locale};
+use crate::privileges::mentions_denied_access;
+
+// 외부 명령 기본 제한 시간 (설정의 command_timeout_secs로 변경)
+pub const DEFAULT_COMMAND_TIMEOUT_SECS: u64 = 15;
+
+static COMMAND_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(DEFAULT_COMMAND_TIMEOUT_SECS);
+
+pub fn set_command_timeout(secs: u64) {
+    COMMAND_TIMEOUT_SECS.store(secs.max(1), Ordering::Relaxed);
+}
+
+thread_local! {
+    // 명령 인자로 지정한 제한 시간 (이 스레드에서 실행하는 외부 명령에만 적용)
+    static COMMAND_TIMEOUT_OVERRIDE: Cell<Option<Duration>> = const { Cell::new(None) };
+}
+
+pub fn command_timeout() -> Duration {
+    COMMAND_TIMEOUT_OVERRIDE
+        .with(Cell::get)
+        .unwrap_or_else(|| Duration::from_secs(COMMAND_TIMEOUT_SECS.load(Ordering::Relaxed)))
+}
+
+// 느린 컴퓨터를 위해 한 번의 작업에서만 제한 시간을 늘립니다 (None이면 설정값 그대로)
+pub fn with_command_timeout<T>(secs: Option<u64>, task: impl FnOnce() -> T) -> T {
+    let Some(secs) = secs else {
+        return task();
+    };
+    let previous = COMMAND_TIMEOUT_OVERRIDE.with(|cell| cell.replace(Some(Duration::from_secs(secs.max(1)))));
+    // 작업이 패닉해도 이전 값으로 되돌립니다
+    struct Restore(Option<Duration>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            COMMAND_TIMEOUT_OVERRIDE.with(|cell| cell.set(self.0));
+        }
+    }
+    let _restore = Restore(previous);
+    task()
+}
+
+// 제한 시간 안에 끝나지 않아 종료시킨 명령 (io::Error의 내부 오류로 전달)
+#[derive(Debug)]
+pub struct CommandTimeout {
+    pub command_line: String,
+    pub timeout: Duration,
+}
+
+impl std::fmt::Display for CommandTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "timed out after {}s: {}", self.timeout.as_secs(), self.command_line)
+    }
+}
+
+impl std::error::Error for CommandTimeout {}
+
+// io::Error가 제한 시간 초과이면 그 정보
+pub fn command_timeout_of(error: &io::Error) -> Option<&CommandTimeout> {
+    error.get_ref()?.downcast_ref::<CommandTimeout>()
+}
+
+// 일시적인 실패의 재시도 정책 (설정의 retry_policy)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    // 첫 시도 뒤 추가로 시도할 횟수
+    pub retries: u32,
+    // 재시도 전 대기 시간 (밀리초, 횟수보다 짧으면 마지막 값을 반복)
+    pub backoff_ms: Vec<u64>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 2,
+            backoff_ms: vec![500, 1500],
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn backoff(&self, retry: u32) -> Duration {
+        let index = (retry as usize).min(self.backoff_ms.len().saturating_sub(1));
+        Duration::from_millis(self.backoff_ms.get(index).copied().unwrap_or_default())
+    }
+}
+
+static RETRY_POLICY: std::sync::RwLock<Option<RetryPolicy>> = std::sync::RwLock::new(None);
+
+pub fn set_retry_policy(policy: RetryPolicy) {
+    *RETRY_POLICY.write().unwrap_or_else(|e| e.into_inner()) = Some(policy);
+}
+
+pub fn retry_policy() -> RetryPolicy {
+    RETRY_POLICY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+// 일시적인 오류만 정책에 따라 다시 시도 (반환값: 결과, 재시도 횟수)
+// stop이 true를 돌려주면 (예: 적용 취소) 더 시도하지 않습니다
+pub fn with_retry<T>(
+    operation: &str,
+    policy: &RetryPolicy,
+    stop: impl Fn() -> bool,
+    mut run: impl FnMut() -> Result<T, AppError>,
+) -> (Result<T, AppError>, u32) {
+    let mut retries = 0;
+    loop {
+        match run() {
+            Err(e) if e.is_transient() && retries < policy.retries && !stop() => {
+                let backoff = policy.backoff(retries);
+                retries += 1;
+                log::warn!(
+                    "{} 실패, {}ms 뒤 다시 시도합니다 ({}/{}): {}",
+                    operation,
+                    backoff.as_millis(),
+                    retries,
+                    policy.retries,
+                    e
+                );
+                std::thread::sleep(backoff);
+            }
+            result => return (result, retries),
+        }
+    }
+}
+
+// 오류와 로그에 남길 출력의 최대 길이 (stdout, stderr 각각, 바이트)
+pub const OUTPUT_EXCERPT_LIMIT: usize = 2000;
+
+// 외부 명령 출력을 문자열로
+// BOM이 있으면 그 인코딩(UTF-8, UTF-16LE/BE), BOM 없는 UTF-16LE는 0 바이트 위치로 추정하고
+// 그 밖에는 UTF-8로 읽습니다 (Windows에서 UTF-8이 아니면 OEM 코드 페이지)
+// 대체 문자로 바뀐 장치 이름은 프로필에 저장된 뒤 다시 일치시킬 수 없으므로 출력은 모두 이 함수로 읽습니다
+pub fn decode_output(bytes: &[u8]) -> String {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return String::from_utf8_lossy(rest).into_owned();
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return decode_utf16(rest, u16::from_le_bytes);
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return decode_utf16(rest, u16::from_be_bytes);
+    }
+    if looks_like_utf16le(bytes) {
+        return decode_utf16(bytes, u16::from_le_bytes);
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => decode_legacy(bytes),
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| from_bytes([pair[0], pair[1]])).collect();
+    String::from_utf16_lossy(&units)
+}
+
+// UTF-8 출력에는 0 바이트가 없지만, 라틴 문자 위주의 UTF-16LE는 홀수 번째 바이트가 대부분 0입니다
+fn looks_like_utf16le(bytes: &[u8]) -> bool {
+    if bytes.len() < 2 || !bytes.len().is_multiple_of(2) {
+        return false;
+    }
+    let zeros = bytes.iter().skip(1).step_by(2).filter(|byte| **byte == 0).count();
+    zeros * 2 >= bytes.len() / 2
+}
+
+// UTF-8이 아닌 출력 (PowerShell 5.1이나 콘솔 도구의 OEM 코드 페이지)
+#[cfg(target_os = "windows")]
+fn decode_legacy(bytes: &[u8]) -> String {
+    use windows::Win32::Globalization::{MultiByteToWideChar, CP_OEMCP, MULTI_BYTE_TO_WIDE_CHAR_FLAGS};
+
+    let flags = MULTI_BYTE_TO_WIDE_CHAR_FLAGS(0);
+    let len = unsafe { MultiByteToWideChar(CP_OEMCP, flags, bytes, None) };
+    if len <= 0 {
+        return String::from_utf8_lossy(bytes).into_owned();
+    }
+    let mut wide = vec![0u16; len as usize];
+    let written = unsafe { MultiByteToWideChar(CP_OEMCP, flags, bytes, Some(&mut wide)) };
+    String::from_utf16_lossy(&wide[..written.max(0) as usize])
+}
+
+#[cfg(not(target_os = "windows"))]
+fn decode_legacy(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+// 명령 출력을 문자열로 (decode_output으로 읽고, 너무 길면 앞부분만)
+pub fn output_excerpt(bytes: &[u8], limit: usize) -> String {
+    let text = decode_output(bytes);
+    let text = text.trim();
+    if text.len() <= limit {
+        return text.to_string();
+    }
+    let mut end = limit;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}… ({} bytes truncated)", &text[..end], text.len() - end)
+}
+
+// 실패한 명령의 종료 코드와 출력 (오류와 로그에 같은 내용을 씁니다)
+pub fn failure_detail(output: &Output) -> String {
+    let mut parts = vec![match output.status.code() {
+        Some(code) => format!("exit code {}", code),
+        None => "terminated by signal".to_string(),
+    }];
+    let stderr = output_excerpt(&output.stderr, OUTPUT_EXCERPT_LIMIT);
+    if !stderr.is_empty() {
+        parts.push(format!("stderr: {}", stderr));
+    }
+    let stdout = output_excerpt(&output.stdout, OUTPUT_EXCERPT_LIMIT);
+    if !stdout.is_empty() {
+        parts.push(format!("stdout: {}", stdout));
+    }
+    parts.join("; ")
+}
+
+// 외부 명령 실행 (명령줄과 종료 상태를 로그에 기록, 실패하면 출력도 함께)
+// 제한 시간이 지나면 프로세스를 종료하고 ErrorKind::TimedOut 오류를 반환합니다
+pub fn run_command(command: &mut Command) -> io::Result<Output> {
+    log::info!("외부 명령 실행: {:?}", command);
+    let result = output_with_timeout(command, command_timeout());
+    match &result {
+        Ok(output) if output.status.success() => {
+            log::info!("외부 명령 종료: {:?} ({})", command.get_program(), output.status)
+        }
+        Ok(output) => log::warn!("외부 명령 실패: {:?} ({})", command, failure_detail(output)),
+        Err(e) => log::warn!("외부 명령 실행 실패: {:?} ({})", command, e),
+    }
+    result
+}
+
+// run_command와 같지만 기록하지 않고 제한 시간을 직접 지정합니다
+// 시간이 지나면 명령이 띄운 프로세스까지 모두 종료합니다 (셸 스크립트의 백그라운드 작업 등)
+pub fn output_with_timeout(command: &mut Command, timeout: Duration) -> io::Result<Output> {
+    let mut tree = ProcessTree::spawn(command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()))?;
+
+    // 출력이 파이프 버퍼를 채워 멈추지 않도록 별도 스레드에서 읽습니다
+    let stdout = read_pipe(tree.child.stdout.take());
+    let stderr = read_pipe(tree.child.stderr.take());
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = tree.child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            tree.kill();
+            let _ = tree.child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                CommandTimeout {
+                    command_line: format!("{:?}", command),
+                    timeout,
+                },
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+// 자식 프로세스와 그 자손 (Unix는 새 프로세스 그룹, Windows는 작업 개체로 묶음)
+struct ProcessTree {
+    child: Child,
+    // 작업 개체에 넣지 못했으면 None (자식만 종료)
+    #[cfg(windows)]
+    job: Option<windows::Win32::Foundation::HANDLE>,
+}
+
+impl ProcessTree {
+    #[cfg(unix)]
+    fn spawn(command: &mut Command) -> io::Result<Self> {
+        std::os::unix::process::CommandExt::process_group(command, 0);
+        Ok(ProcessTree { child: command.spawn()? })
+    }
+
+    // 작업 개체에 넣기 전에 자식이 띄운 프로세스는 묶이지 않지만, 외부 도구는 시작 직후 바로 띄우지 않습니다
+    #[cfg(windows)]
+    fn spawn(command: &mut Command) -> io::Result<Self> {
+        use std::os::windows::io::AsRawHandle;
+        use windows::Win32::Foundation::{CloseHandle, HANDLE};
+        use windows::Win32::System::JobObjects::{AssignProcessToJobObject, CreateJobObjectW};
+
+        let child = command.spawn()?;
+        let job = unsafe { CreateJobObjectW(None, windows::core::PCWSTR::null()) }.ok().filter(|job| {
+            let assigned = unsafe { AssignProcessToJobObject(*job, HANDLE(child.as_raw_handle())) };
+            if let Err(e) = &assigned {
+                log::debug!("외부 명령을 작업 개체에 넣지 못했습니다: {}", e);
+                let _ = unsafe { CloseHandle(*job) };
+            }
+            assigned.is_ok()
+        });
+        Ok(ProcessTree { child, job })
+    }
+
+    #[cfg(unix)]
+    fn kill(&mut self) {
+        // 그룹 id는 그룹을 만든 자식의 pid와 같습니다
+        if let Ok(group) = libc::pid_t::try_from(self.child.id()) {
+            unsafe { libc::kill(-group, libc::SIGKILL) };
+        }
+        let _ = self.child.kill();
+    }
+
+    #[cfg(windows)]
+    fn kill(&mut self) {
+        if let Some(job) = self.job {
+            let _ = unsafe { windows::Win32::System::JobObjects::TerminateJobObject(job, 1) };
+        }
+        let _ = self.child.kill();
+    }
+}
+
+#[cfg(windows)]
+impl Drop for ProcessTree {
+    fn drop(&mut self) {
+        if let Some(job) = self.job.take() {
+            let _ = unsafe { windows::Win32::Foundation::CloseHandle(job) };
+        }
+    }
+}
+
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+// 외부 도구 명령 템플릿
+// {이름} 자리표시자는 값으로 바뀌며, 단독 인자인 {args}는 여러 인자로 펼쳐집니다
+#[cfg(target_os = "macos")]
+pub const DEFAULT_COMMAND_TEMPLATES: &[(&str, &str)] = &[
+    ("display.apply", "displayplacer {args}"),
+    ("audio.output", "SwitchAudioSource -s {device}"),
+    ("audio.input", "SwitchAudioSource -t input -s {device}"),
+];
+#[cfg(target_os = "windows")]
+pub const DEFAULT_COMMAND_TEMPLATES: &[(&str, &str)] = &[
+    ("audio.output", "nircmd setdefaultsounddevice {device}"),
+    ("audio.input", "nircmd setdefaultsounddevice {device}"),
+];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub const DEFAULT_COMMAND_TEMPLATES: &[(&str, &str)] = &[("display.apply", "xrandr {args}")];
+
+// 설정에서 읽은 사용자 정의 템플릿 (적용 함수에서 앱 상태 없이 사용)
+static COMMAND_TEMPLATES: std::sync::RwLock<Option<HashMap<String, String>>> =
+    std::sync::RwLock::new(None);
+
+pub fn set_command_templates_override(templates: HashMap<String, String>) {
+    *COMMAND_TEMPLATES.write().unwrap_or_else(|e| e.into_inner()) = Some(templates);
+}
+
+// 템플릿을 프로그램과 인자 토큰으로 분리
+pub fn parse_command_template(operation: &str, template: &str) -> Result<Vec<String>, AppError> {
+    let tokens = shlex::split(template).ok_or_else(|| AppError::InvalidCommandTemplate {
+        operation: operation.to_string(),
+        detail: "unbalanced quotes".to_string(),
+    })?;
+    if tokens.is_empty() {
+        return Err(AppError::InvalidCommandTemplate {
+            operation: operation.to_string(),
+            detail: "empty template".to_string(),
+        });
+    }
+    Ok(tokens)
+}
+
+// 사용자가 명령 템플릿을 지정한 작업인지 (기본 구현 대신 템플릿을 실행해야 하는 경우)
+pub fn has_custom_template(operation: &str) -> bool {
+    COMMAND_TEMPLATES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .is_some_and(|templates| templates.contains_key(operation))
+}
+
+// 작업에 쓸 템플릿과 사용자 정의 여부
+fn operation_template(operation: &str) -> Result<(String, bool), AppError> {
+    let custom = COMMAND_TEMPLATES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|templates| templates.get(operation).cloned());
+    if let Some(template) = custom {
+        return Ok((template, true));
+    }
+    DEFAULT_COMMAND_TEMPLATES
+        .iter()
+        .find(|(name, _)| *name == operation)
+        .map(|(_, template)| (template.to_string(), false))
+        .ok_or_else(|| AppError::InvalidCommandTemplate {
+            operation: operation.to_string(),
+            detail: "no template for this operation".to_string(),
+        })
+}
+
+// 작업을 실행할 프로그램 이름 (적용 전 도구 확인용, 템플릿이 없거나 잘못되었으면 None)
+pub fn template_program(operation: &str) -> Option<String> {
+    let (template, _) = operation_template(operation).ok()?;
+    parse_command_template(operation, &template).ok()?.into_iter().next()
+}
+
+// 작업에 해당하는 명령 생성 (사용자 정의 템플릿이 있으면 우선 사용)
+// 반환값: (명령, 프로그램 이름, 사용자 정의 여부)
+pub fn templated_command(
+    operation: &str,
+    values: &[(&str, &[String])],
+) -> Result<(Command, String, bool), AppError> {
+    let (template, is_custom) = operation_template(operation)?;
+    let mut tokens = parse_command_template(operation, &template)?.into_iter();
+    let program = tokens.next().unwrap_or_default();
+    let mut command = tool_command(&program);
+    for token in tokens {
+        // 인자 전체가 자리표시자이면 값 목록을 각각의 인자로 펼칩니다
+        if let Some((_, value)) = values.iter().find(|(name, _)| token == format!("{{{}}}", name)) {
+            command.args(value.iter());
+            continue;
+        }
+
+        let mut arg = token;
+        for (name, value) in values {
+            arg = arg.replace(&format!("{{{}}}", name), &value.join(" "));
+        }
+        command.arg(arg);
+    }
+
+    Ok((command, program, is_custom))
+}
+
+// 외부 도구 의존성 점검
+#[derive(Debug, Serialize, Clone)]
+pub struct DependencyStatus {
+    pub name: String,
+    pub kind: String,
+    pub available: bool,
+    pub path: Option<String>,
+    pub source: Option<String>,
+    pub version: Option<String>,
+    pub min_version: Option<String>,
+    pub version_supported: Option<bool>,
+    pub used_for: String,
+    pub install_hint: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DependencyReport {
+    pub platform: String,
+    pub dependencies: Vec<DependencyStatus>,
+}
+
+pub struct ToolSpec {
+    pub name: &'static str,
+    // 버전 확인용 인자 (None이면 실행하지 않고 PATH만 확인)
+    pub version_args: Option<&'static [&'static str]>,
+    pub min_version: Option<&'static str>,
+    pub used_for: &'static str,
+    pub install_hint: &'static str,
+    // 최소 버전보다 오래된 경우 안내할 업그레이드 명령
+    pub upgrade_hint: &'static str,
+}
+
+#[cfg(target_os = "macos")]
+const TOOL_SPECS: &[ToolSpec] = &[
+    ToolSpec {
+        name: "displayplacer",
+        version_args: Some(&["--version"]),
+        min_version: Some("1.3.0"),
+        used_for: "디스플레이 설정 적용",
+        install_hint: "brew install displayplacer",
+        upgrade_hint: "brew upgrade displayplacer",
+    },
+    ToolSpec {
+        name: "SwitchAudioSource",
+        version_args: None,
+        min_version: None,
+        used_for: "CoreAudio를 사용할 수 없을 때 오디오 장치 목록 및 전환",
+        install_hint: "brew install switchaudio-osx",
+        upgrade_hint: "brew upgrade switchaudio-osx",
+    },
+];
+
+#[cfg(target_os = "windows")]
+const TOOL_SPECS: &[ToolSpec] = &[
+    ToolSpec {
+        name: "nircmd",
+        // 인자 없이 실행하면 GUI 창이 뜨므로 PATH만 확인합니다
+        version_args: None,
+        min_version: None,
+        used_for: "기본 오디오 장치 전환 (COM을 사용할 수 없을 때)",
+        install_hint: "winget install NirSoft.NirCmd",
+        upgrade_hint: "winget upgrade NirSoft.NirCmd",
+    },
+    ToolSpec {
+        name: "powershell",
+        version_args: Some(&["-NoProfile", "-Command", "$PSVersionTable.PSVersion.ToString()"]),
+        min_version: Some("5.1"),
+        used_for: "오디오 장치 목록 및 전환 (AudioDeviceCmdlets)",
+        install_hint: "Windows PowerShell은 Windows에 기본 포함되어 있습니다",
+        upgrade_hint: "Windows Management Framework 5.1을 설치하세요",
+    },
+];
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const TOOL_SPECS: &[ToolSpec] = &[
+    ToolSpec {
+        name: "xrandr",
+        version_args: Some(&["--version"]),
+        min_version: None,
+        used_for: "디스플레이 목록 (X11)",
+        install_hint: "배포판 패키지 관리자로 xrandr(x11-xserver-utils)를 설치하세요",
+        upgrade_hint: "배포판 패키지 관리자로 xrandr(x11-xserver-utils)를 업데이트하세요",
+    },
+    ToolSpec {
+        name: "wlr-randr",
+        version_args: None,
+        min_version: None,
+        // zwlr_output_manager_v1에 직접 연결하지 않고 이 도구를 실행합니다 (wlroots 세션에서 필요)
+        used_for: "디스플레이 목록 (Wayland, Sway/Hyprland 등 wlroots 계열)",
+        install_hint: "배포판 패키지 관리자로 wlr-randr(0.4 이상)를 설치하세요 (예: sudo apt-get install wlr-randr)",
+        upgrade_hint: "배포판 패키지 관리자로 wlr-randr를 업데이트하세요",
+    },
+    ToolSpec {
+        name: "busctl",
+        version_args: Some(&["--version"]),
+        min_version: None,
+        used_for: "디스플레이 목록/적용 (Wayland, GNOME)",
+        install_hint: "busctl은 systemd에 포함되어 있습니다",
+        upgrade_hint: "배포판 패키지 관리자로 systemd를 업데이트하세요",
+    },
+    ToolSpec {
+        name: "udevadm",
+        version_args: Some(&["--version"]),
+        min_version: None,
+        used_for: "디스플레이 연결/분리 감시",
+        install_hint: "udevadm은 systemd(udev)에 포함되어 있습니다",
+        upgrade_hint: "배포판 패키지 관리자로 systemd를 업데이트하세요",
+    },
+    ToolSpec {
+        name: "kscreen-doctor",
+        version_args: None,
+        min_version: None,
+        used_for: "디스플레이 목록/적용 (Wayland, KDE Plasma)",
+        install_hint: "배포판 패키지 관리자로 kscreen(libkscreen)을 설치하세요 (Plasma 5.24 이상)",
+        upgrade_hint: "배포판 패키지 관리자로 Plasma를 업데이트하세요",
+    },
+    ToolSpec {
+        name: "gsettings",
+        version_args: Some(&["--version"]),
+        min_version: None,
+        used_for: "방해 금지 상태 확인 (GNOME)",
+        install_hint: "배포판 패키지 관리자로 glib2(libglib2.0-bin)를 설치하세요",
+        upgrade_hint: "배포판 패키지 관리자로 glib2(libglib2.0-bin)를 업데이트하세요",
+    },
+    ToolSpec {
+        name: "pactl",
+        version_args: Some(&["--version"]),
+        // -f json 출력은 16.0부터 지원합니다
+        min_version: Some("16.0"),
+        used_for: "오디오 장치 목록/전환/변경 감시 (PulseAudio, PipeWire)",
+        install_hint: "배포판 패키지 관리자로 pactl(pulseaudio-utils)을 설치하세요",
+        upgrade_hint: "배포판 패키지 관리자로 pulseaudio-utils를 업데이트하세요",
+    },
+    // PipeWire 백엔드는 libpipewire 대신 아래 도구를 실행합니다 (PipeWire가 실행 중일 때 필요)
+    ToolSpec {
+        name: "pw-dump",
+        version_args: Some(&["--version"]),
+        min_version: None,
+        used_for: "오디오 장치 목록 (PipeWire CLI 백엔드)",
+        install_hint: "배포판 패키지 관리자로 PipeWire 도구(pipewire-bin 또는 pipewire-utils)를 설치하세요",
+        upgrade_hint: "배포판 패키지 관리자로 PipeWire를 업데이트하세요",
+    },
+    ToolSpec {
+        name: "pw-metadata",
+        version_args: Some(&["--version"]),
+        min_version: None,
+        used_for: "기본 오디오 장치 전환 (PipeWire CLI 백엔드, WirePlumber 메타데이터)",
+        install_hint: "배포판 패키지 관리자로 PipeWire 도구(pipewire-bin 또는 pipewire-utils)를 설치하세요",
+        upgrade_hint: "배포판 패키지 관리자로 PipeWire를 업데이트하세요",
+    },
+    ToolSpec {
+        name: "pw-cli",
+        version_args: Some(&["--version"]),
+        min_version: None,
+        used_for: "오디오 장치 프로필 전환 (PipeWire CLI 백엔드)",
+        install_hint: "배포판 패키지 관리자로 PipeWire 도구(pipewire-bin 또는 pipewire-utils)를 설치하세요",
+        upgrade_hint: "배포판 패키지 관리자로 PipeWire를 업데이트하세요",
+    },
+    ToolSpec {
+        name: "wpctl",
+        version_args: None,
+        min_version: None,
+        used_for: "볼륨/음소거 (PipeWire CLI 백엔드, WirePlumber)",
+        install_hint: "배포판 패키지 관리자로 wireplumber를 설치하세요",
+        upgrade_hint: "배포판 패키지 관리자로 wireplumber를 업데이트하세요",
+    },
+];
+
+// 앱 번들에 사이드카로 포함되는 도구 (tauri.macos.conf.json의 externalBin)
+#[cfg(target_os = "macos")]
+const SIDECAR_TOOLS: &[&str] = &["displayplacer", "SwitchAudioSource"];
+
+pub fn tool_spec(name: &str) -> Option<&'static ToolSpec> {
+    TOOL_SPECS.iter().find(|spec| spec.name == name)
+}
+
+// PATH에서 실행 파일 찾기
+// Finder/Dock에서 실행된 macOS 앱은 셸의 PATH를 물려받지 않으므로 Homebrew 경로를 추가로 확인합니다
+#[cfg(target_os = "macos")]
+const EXTRA_TOOL_DIRS: &[&str] = &["/opt/homebrew/bin", "/usr/local/bin"];
+#[cfg(not(target_os = "macos"))]
+const EXTRA_TOOL_DIRS: &[&str] = &[];
+
+fn find_in_path(name: &str) -> Option<PathBuf> {
+    let paths = std::env::var_os("PATH").unwrap_or_default();
+    let candidates: Vec<String> = if cfg!(target_os = "windows") {
+        vec![format!("{}.exe", name), format!("{}.cmd", name), name.to_string()]
+    } else {
+        vec![name.to_string()]
+    };
+
+    std::env::split_paths(&paths)
+        .chain(EXTRA_TOOL_DIRS.iter().map(PathBuf::from))
+        .flat_map(|dir| candidates.iter().map(move |candidate| dir.join(candidate)))
+        .find(|path| path.is_file())
+}
+
+// 외부 도구 실행 파일 위치
+#[derive(Debug, Clone)]
+struct ToolLocation {
+    path: PathBuf,
+    // "bundled": 앱에 포함된 사이드카, "system": 사용자가 설치한 도구
+    source: &'static str,
+}
+
+// Tauri 번들러는 사이드카를 메인 실행 파일과 같은 디렉터리에 배치합니다
+// (개발 모드에서는 tauri-build가 target/debug에 복사합니다)
+fn bundled_tool_path(name: &str) -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    let file_name = if cfg!(target_os = "windows") {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+    let path = exe_dir.join(file_name);
+    path.is_file().then_some(path)
+}
+
+// 번들된 사이드카를 우선 사용하고, 없으면 PATH에서 찾습니다
+fn locate_tool(name: &str) -> Option<ToolLocation> {
+    if let Some(path) = bundled_tool_path(name) {
+        return Some(ToolLocation { path, source: "bundled" });
+    }
+    find_in_path(name).map(|path| ToolLocation { path, source: "system" })
+}
+
+// 적용에 필요한 도구 중 번들과 PATH 어디에도 없는 도구
+pub fn missing_tools(names: &[String]) -> Vec<String> {
+    names.iter().filter(|name| locate_tool(name).is_none()).cloned().collect()
+}
+
+// 번들 또는 PATH에 도구가 있는지 (백엔드 자동 선택용)
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn is_tool_available(name: &str) -> bool {
+    locate_tool(name).is_some()
+}
+
+// ApplyReport에 기록할 백엔드 이름 (예: "displayplacer (bundled)")
+#[cfg(not(target_os = "windows"))]
+fn tool_backend(name: &str) -> String {
+    match locate_tool(name) {
+        Some(location) => format!("{} ({})", name, location.source),
+        None => name.to_string(),
+    }
+}
+
+// 템플릿으로 실행한 명령의 백엔드 이름
+#[cfg(not(target_os = "windows"))]
+pub fn template_backend(program: &str, custom: bool) -> String {
+    if custom {
+        format!("{} (custom)", program)
+    } else {
+        tool_backend(program)
+    }
+}
+
+// 외부 도구 실행용 Command 생성
+pub fn tool_command(name: &str) -> Command {
+    match locate_tool(name) {
+        Some(location) => {
+            log::debug!("{} 사용: {} ({})", name, location.path.display(), location.source);
+            Command::new(location.path)
+        }
+        None => Command::new(name),
+    }
+}
+
+// 번들된 사이드카의 격리(quarantine) 속성 제거
+// 다운로드한 앱에서 격리 속성이 상속되면 Gatekeeper가 사이드카 실행을 막습니다
+#[cfg(target_os = "macos")]
+pub fn clear_sidecar_quarantine() {
+    for name in SIDECAR_TOOLS {
+        let Some(path) = bundled_tool_path(name) else {
+            continue;
+        };
+
+        let quarantined = run_command(Command::new("xattr")
+            .arg("-p")
+            .arg("com.apple.quarantine")
+            .arg(&path))
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+
+        if quarantined {
+            match run_command(Command::new("xattr")
+                .arg("-d")
+                .arg("com.apple.quarantine")
+                .arg(&path))
+            {
+                Ok(output) if output.status.success() => {
+                    log::info!("{}의 격리 속성을 제거했습니다", path.display());
+                }
+                _ => log::warn!("{}의 격리 속성을 제거하지 못했습니다", path.display()),
+            }
+        }
+    }
+}
+
+// "displayplacer v1.4.0" 같은 출력에서 첫 번째 버전 문자열 추출
+fn parse_version(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut start = 0;
+    while start < bytes.len() {
+        if bytes[start].is_ascii_digit() {
+            let mut end = start;
+            while end < bytes.len() && (bytes[end].is_ascii_digit() || bytes[end] == b'.') {
+                end += 1;
+            }
+            let candidate = text[start..end].trim_end_matches('.');
+            if candidate.contains('.') {
+                return Some(candidate.to_string());
+            }
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+    None
+}
+
+fn version_at_least(version: &str, minimum: &str) -> bool {
+    let parse = |v: &str| -> Vec<u32> { v.split('.').map(|part| part.parse().unwrap_or(0)).collect() };
+    let (found, min) = (parse(version), parse(minimum));
+    for i in 0..found.len().max(min.len()) {
+        let (a, b) = (found.get(i).copied().unwrap_or(0), min.get(i).copied().unwrap_or(0));
+        if a != b {
+            return a > b;
+        }
+    }
+    true
+}
+
+// 시작할 때 점검하므로 멈춘 도구가 시작을 막지 않도록 짧게 기다립니다
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+// 버전 확인 명령의 출력에서 버전을 찾습니다 (실행하지 못했거나 제한 시간을 넘기면 None)
+fn probe_version(command: &mut Command, timeout: Duration) -> Option<String> {
+    match output_with_timeout(command, timeout) {
+        Ok(output) => parse_version(&format!("{}{}", decode_output(&output.stdout), decode_output(&output.stderr))),
+        Err(e) => {
+            log::warn!("버전 확인 실패: {:?} ({})", command.get_program(), e);
+            None
+        }
+    }
+}
+
+fn probe_tool(spec: &ToolSpec) -> DependencyStatus {
+    let location = locate_tool(spec.name);
+    let path = location.as_ref().map(|location| location.path.clone());
+
+    let version = match (&path, spec.version_args) {
+        (Some(path), Some(args)) => probe_version(Command::new(path).args(args), PROBE_TIMEOUT),
+        _ => None,
+    };
+
+    let version_supported = match (&version, spec.min_version) {
+        (Some(version), Some(min)) => Some(version_at_least(version, min)),
+        _ => None,
+    };
+
+    DependencyStatus {
+        name: spec.name.to_string(),
+        kind: "tool".to_string(),
+        available: path.is_some(),
+        path: path.map(|p| p.to_string_lossy().to_string()),
+        source: location.map(|location| location.source.to_string()),
+        version,
+        min_version: spec.min_version.map(|v| v.to_string()),
+        version_supported,
+        used_for: spec.used_for.to_string(),
+        install_hint: spec.install_hint.to_string(),
+    }
+}
+
+// 확인하던 스레드가 잘못된 경우 (찾지 못한 것으로 표시)
+fn missing_status(spec: &ToolSpec) -> DependencyStatus {
+    DependencyStatus {
+        name: spec.name.to_string(),
+        kind: "tool".to_string(),
+        available: false,
+        path: None,
+        source: None,
+        version: None,
+        min_version: spec.min_version.map(|v| v.to_string()),
+        version_supported: None,
+        used_for: spec.used_for.to_string(),
+        install_hint: spec.install_hint.to_string(),
+    }
+}
+
+// 외부 도구를 대체하는 네이티브 기능
+fn native_capabilities() -> Vec<DependencyStatus> {
+    let native = |name: &str, used_for: &str| DependencyStatus {
+        name: name.to_string(),
+        kind: "native".to_string(),
+        available: true,
+        path: None,
+        source: None,
+        version: None,
+        min_version: None,
+        version_supported: None,
+        used_for: used_for.to_string(),
+        install_hint: String::new(),
+    };
+
+    #[cfg(target_os = "macos")]
+    {
+        vec![native("CoreGraphics", "디스플레이 목록")]
+    }
+    #[cfg(target_os = "windows")]
+    {
+        vec![native("EnumDisplayMonitors", "디스플레이 목록")]
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = native;
+        Vec::new()
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn probe_audio_device_cmdlets() -> DependencyStatus {
+    // PowerShell 시작이 느리므로 다른 도구보다 조금 더 기다립니다
+    let version = probe_version(
+        Command::new("powershell").args([
+            "-NoProfile",
+            "-Command",
+            "Get-Module -ListAvailable AudioDeviceCmdlets | Select-Object -First 1 -ExpandProperty Version | ForEach-Object { $_.ToString() }",
+        ]),
+        PROBE_TIMEOUT * 2,
+    );
+
+    DependencyStatus {
+        name: "AudioDeviceCmdlets".to_string(),
+        kind: "powershell-module".to_string(),
+        available: version.is_some(),
+        path: None,
+        source: None,
+        version,
+        min_version: None,
+        version_supported: None,
+        used_for: "오디오 장치 목록 및 전환".to_string(),
+        install_hint: "Install-Module -Name AudioDeviceCmdlets -Scope CurrentUser".to_string(),
+    }
+}
+
+// 도구마다 별도 스레드에서 동시에 확인하므로 전체 시간은 가장 느린 도구 하나만큼입니다
+pub fn collect_dependency_report() -> DependencyReport {
+    let mut dependencies: Vec<DependencyStatus> = std::thread::scope(|scope| {
+        let probes: Vec<_> = TOOL_SPECS.iter().map(|spec| scope.spawn(move || probe_tool(spec))).collect();
+        #[cfg(target_os = "windows")]
+        let cmdlets = scope.spawn(probe_audio_device_cmdlets);
+
+        #[allow(unused_mut)]
+        let mut dependencies: Vec<DependencyStatus> = probes
+            .into_iter()
+            .zip(TOOL_SPECS)
+            .map(|(probe, spec)| probe.join().unwrap_or_else(|_| missing_status(spec)))
+            .collect();
+        #[cfg(target_os = "windows")]
+        dependencies.extend(cmdlets.join().ok());
+        dependencies
+    });
+
+    dependencies.extend(native_capabilities());
+
+    DependencyReport {
+        platform: std::env::consts::OS.to_string(),
+        dependencies,
+    }
+}
+
+// 외부 도구 실행 실패 시 어떤 도구가 없는지와 설치 방법을 담은 메시지
+pub fn tool_missing_message(name: &str, error: &std::io::Error) -> String {
+    match tool_spec(name) {
+        Some(spec) => format!(
+            "{}를 실행할 수 없습니다: {}. {}에 필요합니다. 설치 방법: {}",
+            name, error, spec.used_for, spec.install_hint
+        ),
+        None => format!("{}를 실행할 수 없습니다: {}", name, error),
+    }
+}
+
+// 오래된 버전의 알려진 제한 (실패 메시지에 keyword가 있으면 해당 기능 안내)
+struct VersionLimitation {
+    tool: &'static str,
+    fixed_in: &'static str,
+    keyword: &'static str,
+    feature: &'static str,
+}
+
+const KNOWN_LIMITATIONS: &[VersionLimitation] = &[
+    VersionLimitation {
+        tool: "displayplacer",
+        fixed_in: "1.3.0",
+        keyword: "degree",
+        feature: "rotation support",
+    },
+    VersionLimitation {
+        tool: "displayplacer",
+        fixed_in: "1.3.0",
+        keyword: "origin",
+        feature: "negative origins",
+    },
+];
+
+// 실패한 도구의 버전이 알려진 제한에 해당하면 안내 문구 반환
+fn version_limitation_hint(tool: &str, version: &str, detail: &str) -> Option<String> {
+    let detail = detail.to_lowercase();
+    let limitation = KNOWN_LIMITATIONS
+        .iter()
+        .filter(|limitation| limitation.tool == tool && !version_at_least(version, limitation.fixed_in))
+        .find(|limitation| detail.contains(limitation.keyword))?;
+    let upgrade = tool_spec(tool).map(|spec| spec.upgrade_hint).unwrap_or_default();
+
+    Some(match current_locale() {
+        Locale::En => format!(
+            "your {} {} is too old for {} (requires {}); upgrade with: {}",
+            tool, version, limitation.feature, limitation.fixed_in, upgrade
+        ),
+        Locale::Ko => format!(
+            "{} {} 버전은 {}을(를) 지원하지 않습니다 ({} 이상 필요). 업그레이드: {}",
+            tool, version, limitation.feature, limitation.fixed_in, upgrade
+        ),
+    })
+}
+
+// 도구 실패 메시지에 오래된 버전 안내 추가 (실패했을 때만 버전을 확인합니다)
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+pub fn with_version_hint(tool: &str, detail: String) -> String {
+    let version = tool_spec(tool).and_then(|spec| probe_tool(spec).version);
+    match version.and_then(|version| version_limitation_hint(tool, &version, &detail)) {
+        Some(hint) => format!("{} ({})", detail, hint),
+        None => detail,
+    }
+}
+
+// 최소 버전보다 오래된 도구
+#[derive(Debug, Serialize, Clone)]
+pub struct DependencyUpdate {
+    pub name: String,
+    pub version: String,
+    pub min_version: String,
+    pub upgrade_command: String,
+}
+
+// 최소 버전보다 오래된 도구 목록
+pub fn dependency_updates() -> Vec<DependencyUpdate> {
+    TOOL_SPECS
+        .iter()
+        .map(|spec| (spec, probe_tool(spec)))
+        .filter(|(_, status)| status.version_supported == Some(false))
+        .map(|(spec, status)| DependencyUpdate {
+            name: status.name,
+            version: status.version.unwrap_or_default(),
+            min_version: status.min_version.unwrap_or_default(),
+            upgrade_command: spec.upgrade_hint.to_string(),
+        })
+        .collect()
+}
+
+// 누락된 외부 도구 설치
+#[derive(Debug, Serialize, Clone)]
+pub struct InstallProgress {
+    pub name: String,
+    pub line: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct InstallResult {
+    pub name: String,
+    pub success: bool,
+    // 관리자 권한이 필요해서 설치하지 못한 경우 (앱은 권한 상승을 직접 요청하지 않습니다)
+    pub elevation_required: bool,
+    pub message: String,
+    // 설치 후 다시 점검한 상태
+    pub status: Option<DependencyStatus>,
+}
+
+// 도구별 설치 명령 (패키지 관리자, 인자)
+fn install_command(name: &str) -> Option<(&'static str, Vec<&'static str>)> {
+    #[cfg(target_os = "macos")]
+    {
+        match name {
+            "displayplacer" => Some(("brew", vec!["install", "displayplacer"])),
+            "SwitchAudioSource" => Some(("brew", vec!["install", "switchaudio-osx"])),
+            _ => None,
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        match name {
+            "nircmd" => Some((
+                "winget",
+                vec![
+                    "install", "--id", "NirSoft.NirCmd", "--exact", "--scope", "user",
+                    "--accept-source-agreements", "--accept-package-agreements",
+                ],
+            )),
+            "AudioDeviceCmdlets" => Some((
+                "powershell",
+                vec![
+                    "-NoProfile", "-NonInteractive", "-Command",
+                    "Install-Module -Name AudioDeviceCmdlets -Scope CurrentUser -Force",
+                ],
+            )),
+            _ => None,
+        }
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = name;
+        None
+    }
+}
+
+// 출력에 권한 상승 요구가 포함되어 있는지 확인
+fn output_requires_elevation(lines: &[String]) -> bool {
+    lines.iter().any(|line| mentions_denied_access(line))
+}
+
+// 설치 명령을 실행하면서 출력 줄을 on_progress로 전달
+fn run_install_command(
+    name: &str,
+    program: &std::path::Path,
+    args: &[&str],
+    on_progress: &(dyn Fn(InstallProgress) + Sync),
+) -> Result<(bool, Vec<String>), AppError> {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    let report_line = |line: &str| {
+        on_progress(InstallProgress {
+            name: name.to_string(),
+            line: line.to_string(),
+        });
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .env("NONINTERACTIVE", "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::ToolFailed {
+            tool: program.display().to_string(),
+            detail: e.to_string(),
+        })?;
+
+    let stderr = child.stderr.take();
+    let stdout = child.stdout.take();
+    let lines = std::thread::scope(|scope| {
+        let stderr_thread = scope.spawn(|| {
+            let mut lines = Vec::new();
+            if let Some(stderr) = stderr {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    report_line(&line);
+                    lines.push(line);
+                }
+            }
+            lines
+        });
+
+        let mut lines = Vec::new();
+        if let Some(stdout) = stdout {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                report_line(&line);
+                lines.push(line);
+            }
+        }
+        lines.extend(stderr_thread.join().unwrap_or_default());
+        lines
+    });
+
+    let status = child
+        .wait()
+        .map_err(|e| AppError::ToolFailed {
+            tool: program.display().to_string(),
+            detail: e.to_string(),
+        })?;
+    Ok((status.success(), lines))
+}
+
+// 패키지 관리자로 도구 설치 (진행 중인 출력 줄은 on_progress로 전달)
+pub fn install_dependency_blocking(
+    name: &str,
+    on_progress: &(dyn Fn(InstallProgress) + Sync),
+) -> Result<InstallResult, AppError> {
+    let (manager, args) = install_command(name).ok_or_else(|| AppError::InstallNotSupported {
+        name: name.to_string(),
+    })?;
+
+    let Some(manager_path) = find_in_path(manager) else {
+        let hint = match manager {
+            "brew" => "Homebrew를 먼저 설치하세요: https://brew.sh",
+            "winget" => "Microsoft Store에서 '앱 설치 관리자'를 설치하세요",
+            _ => "",
+        };
+        return Ok(InstallResult {
+            name: name.to_string(),
+            success: false,
+            elevation_required: false,
+            message: format!("패키지 관리자 {}를 찾을 수 없습니다. {}", manager, hint),
+            status: None,
+        });
+    };
+
+    log::info!("{} 설치 시작: {} {}", name, manager, args.join(" "));
+    let (succeeded, lines) = run_install_command(name, &manager_path, &args, on_progress)?;
+
+    // 설치 후 다시 점검
+    let status = collect_dependency_report()
+        .dependencies
+        .into_iter()
+        .find(|dependency| dependency.name == name);
+    let installed = status.as_ref().is_some_and(|status| status.available);
+    let elevation_required = !installed && output_requires_elevation(&lines);
+
+    let message = if installed {
+        format!("{} 설치가 완료되었습니다", name)
+    } else if elevation_required {
+        format!("{} 설치에 관리자 권한이 필요합니다. 터미널에서 직접 설치하세요: {} {}", name, manager, args.join(" "))
+    } else if succeeded {
+        format!("{} 설치 명령은 완료되었지만 도구를 찾을 수 없습니다", name)
+    } else {
+        format!("{} 설치에 실패했습니다: {}", name, lines.last().cloned().unwrap_or_default())
+    };
+    log::info!("{}", message);
+
+    Ok(InstallResult {
+        name: name.to_string(),
+        success: installed,
+        elevation_required,
+        message,
+        status,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::set_current_locale;
+
+    #[test]
+    fn decodes_device_names_in_every_output_encoding() {
+        let names = "Lautsprecher (Realtek® Audio)\r\n스피커 (USB 헤드셋)\r\nAirPods 🎧";
+        let utf16le: Vec<u8> = names.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let utf16be: Vec<u8> = names.encode_utf16().flat_map(u16::to_be_bytes).collect();
+
+        assert_eq!(decode_output(names.as_bytes()), names);
+        assert_eq!(decode_output(&[&[0xEF, 0xBB, 0xBF], names.as_bytes()].concat()), names);
+        assert_eq!(decode_output(&[&[0xFF, 0xFE][..], &utf16le].concat()), names);
+        assert_eq!(decode_output(&[&[0xFE, 0xFF][..], &utf16be].concat()), names);
+        // Out-File 등이 BOM 없이 쓴 UTF-16LE
+        assert_eq!(decode_output(&utf16le), names);
+        assert_eq!(decode_output(b""), "");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn version_probe_gives_up_on_hung_tools() {
+        let started = Instant::now();
+        assert_eq!(probe_version(Command::new("sleep").arg("5"), Duration::from_millis(100)), None);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            probe_version(Command::new("sh").args(["-c", "echo tool 1.4.2"]), PROBE_TIMEOUT).as_deref(),
+            Some("1.4.2")
+        );
+    }
+
+    #[test]
+    fn timeout_override_applies_only_inside_the_task() {
+        let configured = command_timeout();
+        let inside = with_command_timeout(Some(90), || {
+            let nested = with_command_timeout(None, command_timeout);
+            (command_timeout(), nested)
+        });
+        assert_eq!(inside, (Duration::from_secs(90), Duration::from_secs(90)));
+        assert_eq!(command_timeout(), configured);
+        // 다른 스레드에는 영향이 없습니다
+        with_command_timeout(Some(90), || {
+            assert_eq!(std::thread::spawn(command_timeout).join().unwrap(), configured);
+        });
+    }
+
+    #[test]
+    fn retries_only_transient_errors() {
+        let policy = RetryPolicy { retries: 2, backoff_ms: vec![0] };
+        let failed = || AppError::ToolFailed {
+            tool: "displayplacer".to_string(),
+            detail: "busy".to_string(),
+        };
+
+        let mut attempts = 0;
+        let (result, retries) = with_retry("display", &policy, || false, || {
+            attempts += 1;
+            if attempts < 2 { Err(failed()) } else { Ok(attempts) }
+        });
+        assert_eq!((result.unwrap(), retries), (2, 1));
+
+        let (result, retries) = with_retry("display", &policy, || false, || Err::<(), _>(failed()));
+        assert!(result.is_err());
+        assert_eq!(retries, 2);
+
+        // 도구가 없거나 인자가 잘못된 경우는 바로 실패
+        let mut attempts = 0;
+        let (result, retries) = with_retry("display", &policy, || false, || {
+            attempts += 1;
+            Err::<(), _>(AppError::ToolFailed {
+                tool: "displayplacer".to_string(),
+                detail: "Unable to parse 'degree:45'".to_string(),
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!((attempts, retries), (1, 0));
+        let missing = AppError::ToolMissing {
+            tool: "displayplacer".to_string(),
+            install_hint: String::new(),
+            detail: "not found".to_string(),
+        };
+        assert!(!missing.is_transient());
+    }
+
+    #[test]
+    fn output_excerpt_truncates_on_char_boundary() {
+        assert_eq!(output_excerpt(b"  Unable to find screen 2\n", 100), "Unable to find screen 2");
+
+        // 한글은 3바이트이므로 4바이트에서 자르면 한 글자만 남습니다
+        let excerpt = output_excerpt("화면 없음".as_bytes(), 4);
+        assert_eq!(excerpt, "화… (10 bytes truncated)");
+
+        // 잘못된 UTF-8은 대체 문자로 바뀝니다
+        let excerpt = output_excerpt(&[b'o', b'k', 0xff, 0xfe, b'!'], 100);
+        assert_eq!(excerpt, "ok\u{fffd}\u{fffd}!");
+        // 대체 문자(3바이트)로 바꾼 뒤의 길이로 자릅니다
+        let excerpt = output_excerpt(&[0x80; 5000], 10);
+        assert_eq!(excerpt, "\u{fffd}\u{fffd}\u{fffd}… (14991 bytes truncated)");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failure_detail_includes_exit_code_and_output() {
+        let output = Command::new("sh")
+            .args(["-c", "echo diagnostics; echo broken >&2; exit 3"])
+            .output()
+            .unwrap();
+        assert_eq!(failure_detail(&output), "exit code 3; stderr: broken; stdout: diagnostics");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_is_killed_after_timeout() {
+        let mut command = Command::new("sleep");
+        command.arg("5");
+        let started = Instant::now();
+        let error = output_with_timeout(&mut command, Duration::from_millis(200)).unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        let timeout = command_timeout_of(&error).expect("timeout details");
+        assert!(timeout.command_line.contains("sleep"), "{}", timeout.command_line);
+
+        let mut command = Command::new("echo");
+        command.arg("done");
+        let output = output_with_timeout(&mut command, Duration::from_secs(5)).unwrap();
+        assert_eq!(output.stdout, b"done\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn timeout_kills_background_children_too() {
+        let started = Instant::now();
+        let error = output_with_timeout(Command::new("sh").args(["-c", "sleep 30 & wait"]), Duration::from_millis(200))
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(2), "{:?}", started.elapsed());
+    }
+
+    // 셸이 띄운 sleep도 함께 종료됩니다 (남아 있으면 정리되지 않은 좀비만 허용)
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn timeout_leaves_no_background_children_running() {
+        use std::fs;
+
+        let pid_file = std::env::temp_dir().join(format!("dsm-timeout-child-{}.pid", std::process::id()));
+        let script = format!("sleep 30 & echo $! > '{}'; wait", pid_file.display());
+        output_with_timeout(Command::new("sh").args(["-c", &script]), Duration::from_millis(200)).unwrap_err();
+        let pid = fs::read_to_string(&pid_file).unwrap().trim().to_string();
+        let _ = fs::remove_file(&pid_file);
+        let alive = || {
+            fs::read_to_string(format!("/proc/{}/stat", pid))
+                .is_ok_and(|stat| stat.rsplit(')').next().is_some_and(|rest| !rest.trim_start().starts_with('Z')))
+        };
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while alive() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(!alive(), "sleep {} is still running", pid);
+    }
+
+    #[test]
+    fn parse_version_from_captured_output() {
+        assert_eq!(parse_version("displayplacer v1.4.0\n").as_deref(), Some("1.4.0"));
+        assert_eq!(parse_version("displayplacer 1.2.0\n").as_deref(), Some("1.2.0"));
+        assert_eq!(parse_version("5.1.19041.4291\r\n").as_deref(), Some("5.1.19041.4291"));
+        assert_eq!(parse_version("2.80.0\n").as_deref(), Some("2.80.0"));
+        // 버전보다 앞에 나오는 숫자 하나는 건너뜁니다
+        assert_eq!(parse_version("build 7, version 1.3.0.").as_deref(), Some("1.3.0"));
+        assert_eq!(parse_version("unknown option --version"), None);
+    }
+
+    #[test]
+    fn version_at_least_compares_numerically() {
+        assert!(version_at_least("1.4.0", "1.3.0"));
+        assert!(version_at_least("1.3", "1.3.0"));
+        assert!(version_at_least("1.10.0", "1.9.0"));
+        assert!(!version_at_least("1.2.9", "1.3.0"));
+        assert!(version_at_least("5.1.19041.4291", "5.1"));
+        assert!(!version_at_least("5.0.10586", "5.1"));
+    }
+
+    #[test]
+    fn version_limitation_hint_matches_old_versions_only() {
+        set_current_locale(Locale::En);
+        let hint = version_limitation_hint("displayplacer", "1.2.0", "Unable to parse 'degree:90'")
+            .expect("old displayplacer with degree failure should get a hint");
+        assert!(hint.contains("displayplacer 1.2.0 is too old for rotation support"));
+
+        assert!(version_limitation_hint("displayplacer", "1.4.0", "Unable to parse 'degree:90'").is_none());
+        assert!(version_limitation_hint("displayplacer", "1.2.0", "screen not found").is_none());
+        assert!(version_limitation_hint("SwitchAudioSource", "1.0", "degree").is_none());
+    }
+}
@@ -0,0 +1,367 @@
+// 트레이 아이콘: 빠른 전환 메뉴와 적용 상태 배지
+
+use tauri::menu::{CheckMenuItem, IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::TrayIconBuilder;
+use tauri::{Emitter, Listener, Manager};
+
+use crate::commands::{
+    apply_profile_by_id, restore_last_known_good_by_source, save_current_state, set_default_audio_device,
+};
+use crate::state::AppState;
+use crate::sync::LockExt;
+
+const TRAY_ID: &str = "main";
+const TRAY_AUDIO_OUTPUT_PREFIX: &str = "audio-output:";
+const TRAY_PROFILE_PREFIX: &str = "profile:";
+
+// 트레이 메뉴 구성: 프로필 / 오디오 출력 / 현재 구성 저장 / 디스플레이 복구 / 장치 새로 고침 / 창 열기 / 종료
+fn build_tray_menu(app: &tauri::AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let state = app.state::<AppState>();
+
+    let profiles = state.profiles.list().clone();
+    let profile_items = profiles
+        .iter()
+        .map(|profile| {
+            MenuItem::with_id(
+                app,
+                format!("{}{}", TRAY_PROFILE_PREFIX, profile.id),
+                &profile.name,
+                true,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let profile_refs: Vec<&dyn IsMenuItem<tauri::Wry>> =
+        profile_items.iter().map(|item| item as &dyn IsMenuItem<tauri::Wry>).collect();
+    let profiles_menu = Submenu::with_items(app, "프로필", !profile_items.is_empty(), &profile_refs)?;
+
+    // 별명을 반영하고 숨긴 장치는 제외한 출력 장치 목록
+    let devices = match state.audio_backend.enumerate() {
+        Ok(devices) => devices,
+        Err(e) => {
+            log::warn!("트레이 오디오 장치 목록 조회 실패: {}", e);
+            Vec::new()
+        }
+    };
+    let (nicknames, hidden) = {
+        let settings = state.settings.lock_or_recover();
+        (settings.device_nicknames.clone(), settings.hidden_devices.clone())
+    };
+    let output_items = devices
+        .iter()
+        .filter(|device| device.device_type == "output" && !hidden.contains(&device.id))
+        .map(|device| {
+            CheckMenuItem::with_id(
+                app,
+                format!("{}{}", TRAY_AUDIO_OUTPUT_PREFIX, device.id),
+                nicknames.get(&device.id).unwrap_or(&device.name),
+                true,
+                device.is_default,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let output_refs: Vec<&dyn IsMenuItem<tauri::Wry>> =
+        output_items.iter().map(|item| item as &dyn IsMenuItem<tauri::Wry>).collect();
+    let audio_menu = Submenu::with_items(app, "오디오 출력", !output_items.is_empty(), &output_refs)?;
+
+    Menu::with_items(
+        app,
+        &[
+            &profiles_menu,
+            &audio_menu,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "capture", "현재 구성 저장", true, None::<&str>)?,
+            // 정상 동작이 확인된 구성을 저장한 적이 있을 때만 선택 가능
+            &MenuItem::with_id(app, "recover", "디스플레이 복구", state.last_known_good_file.exists(), None::<&str>)?,
+            &MenuItem::with_id(app, "refresh", "장치 새로 고침", true, None::<&str>)?,
+            &MenuItem::with_id(app, "show", "창 열기", true, None::<&str>)?,
+            &MenuItem::with_id(app, "quit", "종료", true, None::<&str>)?,
+        ],
+    )
+}
+
+// 장치나 프로필이 바뀌면 트레이 메뉴 다시 구성
+fn refresh_tray_menu(app: &tauri::AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_tray_menu(app) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                log::warn!("트레이 메뉴 갱신 실패: {}", e);
+            }
+        }
+        Err(e) => log::warn!("트레이 메뉴 구성 실패: {}", e),
+    }
+}
+
+fn handle_tray_menu_event(app: &tauri::AppHandle, id: &str) {
+    if let Some(device_id) = id.strip_prefix(TRAY_AUDIO_OUTPUT_PREFIX) {
+        let app = app.clone();
+        let device_id = device_id.to_string();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = set_default_audio_device(app.clone(), device_id, "output".to_string()).await {
+                log::warn!("트레이에서 오디오 출력 변경 실패: {}", e);
+                // 실패하면 체크 표시를 실제 상태로 되돌립니다
+                refresh_tray_menu(&app);
+            }
+        });
+    } else if let Some(profile_id) = id.strip_prefix(TRAY_PROFILE_PREFIX) {
+        let app = app.clone();
+        let profile_id = profile_id.to_string();
+        // 다른 적용이 끝나기를 기다릴 수 있으므로 블로킹 스레드에서 실행합니다
+        tauri::async_runtime::spawn_blocking(move || {
+            match apply_profile_by_id(&app, &profile_id, "tray") {
+                Ok(_) => {
+                    let _ = app.emit("audio-devices-changed", ());
+                }
+                // 트레이 아이콘의 상태와 툴팁은 apply-failed 이벤트로 바뀝니다
+                Err(e) => log::warn!("트레이에서 프로필 적용 실패 ({}): {}", profile_id, e),
+            }
+        });
+    } else if id == "refresh" {
+        // 감시가 변경을 놓쳤을 때 직접 다시 읽어 창과 메뉴에 반영합니다
+        let app = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let state = app.state::<AppState>();
+            match state.display_backend.enumerate() {
+                Ok(displays) => {
+                    let _ = app.emit("displays-changed", displays);
+                }
+                Err(e) => log::warn!("트레이에서 디스플레이 목록 갱신 실패: {}", e),
+            }
+            match state.audio_backend.enumerate() {
+                Ok(devices) => {
+                    let _ = app.emit("audio-devices-changed", devices);
+                }
+                Err(e) => log::warn!("트레이에서 오디오 장치 목록 갱신 실패: {}", e),
+            }
+        });
+    } else if id == "capture" {
+        // 컴퓨터 이름으로 저장하며 (같은 이름이 있으면 번호를 붙임) 이름은 창에서 바꿉니다
+        let app = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            match save_current_state(&app, &app.state::<AppState>(), "") {
+                Ok(profile) => log::info!("트레이에서 현재 구성 저장: {} ({})", profile.name, profile.id),
+                Err(e) => log::warn!("트레이에서 현재 구성 저장 실패: {}", e),
+            }
+        });
+    } else if id == "recover" {
+        let app = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = restore_last_known_good_by_source(&app, "tray") {
+                log::warn!("트레이에서 디스플레이 복구 실패: {}", e);
+            }
+            let _ = app.emit("audio-devices-changed", ());
+        });
+    } else if id == "show" {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    } else if id == "quit" {
+        app.exit(0);
+    }
+}
+
+// 트레이 아이콘 생성 (설정에서 끈 경우 만들지 않음)
+pub fn setup_tray(app: &tauri::AppHandle) -> tauri::Result<()> {
+    if !app.state::<AppState>().settings.lock_or_recover().tray_enabled {
+        return Ok(());
+    }
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Display & Sound Manager")
+        .menu(&build_tray_menu(app)?)
+        .on_menu_event(|app, event| handle_tray_menu_event(app, event.id().as_ref()));
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    for event in ["audio-devices-changed", "profiles-changed", "last-known-good-changed"] {
+        let app_handle = app.clone();
+        app.listen(event, move |_| refresh_tray_menu(&app_handle));
+    }
+
+    setup_tray_status(app);
+    setup_close_to_tray(app);
+    Ok(())
+}
+
+// 트레이 아이콘이 있으면 창을 닫을 때 종료하지 않고 숨깁니다 (설정의 close_to_tray로 끌 수 있음)
+fn setup_close_to_tray(app: &tauri::AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let app_handle = app.clone();
+    let hidden = window.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+            if app_handle.state::<AppState>().settings.lock_or_recover().close_to_tray {
+                api.prevent_close();
+                let _ = hidden.hide();
+            }
+        }
+    });
+}
+
+// 트레이 아이콘 상태
+#[derive(Debug, Clone, PartialEq)]
+enum TrayStatus {
+    Idle,
+    Applying,
+    // 적용된 프로필의 배지 색상
+    Active([u8; 3]),
+    // 마지막 적용이 실패했거나 경고가 있음 (툴팁에 표시할 내용)
+    Warning(Option<String>),
+}
+
+const TRAY_APPLYING_COLOR: [u8; 3] = [0xF5, 0x9E, 0x0B];
+const TRAY_WARNING_COLOR: [u8; 3] = [0xEF, 0x44, 0x44];
+const TRAY_ACTIVE_COLOR: [u8; 3] = [0x22, 0xC5, 0x5E];
+// 상태가 연달아 바뀔 때 아이콘이 깜박이지 않도록 마지막 상태만 반영하는 대기 시간
+const TRAY_STATUS_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
+
+// "#RRGGBB" 색상 파싱
+fn parse_hex_color(value: &str) -> Option<[u8; 3]> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+// 기본 아이콘 오른쪽 아래에 색상 배지를 그린 아이콘 생성
+fn badge_icon(base: &tauri::image::Image<'_>, color: [u8; 3]) -> tauri::image::Image<'static> {
+    let (width, height) = (base.width(), base.height());
+    let mut rgba = base.rgba().to_vec();
+
+    let radius = width.min(height) as f64 * 0.22;
+    let ring = radius + width.min(height) as f64 * 0.05;
+    let (cx, cy) = (width as f64 - ring, height as f64 - ring);
+    for y in 0..height {
+        for x in 0..width {
+            let distance = ((x as f64 + 0.5 - cx).powi(2) + (y as f64 + 0.5 - cy).powi(2)).sqrt();
+            let pixel = match distance {
+                d if d <= radius => [color[0], color[1], color[2], 0xFF],
+                // 배경과 구분되도록 흰 테두리
+                d if d <= ring => [0xFF, 0xFF, 0xFF, 0xFF],
+                _ => continue,
+            };
+            let offset = ((y * width + x) * 4) as usize;
+            rgba[offset..offset + 4].copy_from_slice(&pixel);
+        }
+    }
+
+    tauri::image::Image::new_owned(rgba, width, height)
+}
+
+fn apply_tray_status(app: &tauri::AppHandle, status: &TrayStatus) {
+    let (Some(tray), Some(base)) = (app.tray_by_id(TRAY_ID), app.default_window_icon()) else {
+        return;
+    };
+
+    let (icon, tooltip) = match status {
+        TrayStatus::Idle => (base.clone().to_owned(), "Display & Sound Manager".to_string()),
+        TrayStatus::Applying => (
+            badge_icon(base, TRAY_APPLYING_COLOR),
+            "Display & Sound Manager — 적용 중".to_string(),
+        ),
+        TrayStatus::Active(color) => {
+            let state = app.state::<AppState>();
+            let active = state.active_profile.lock_or_recover().clone();
+            let name = active.and_then(|id| {
+                state
+                    .profiles
+                    .list()
+                    .iter()
+                    .find(|p| p.id == id)
+                    .map(|p| p.name.clone())
+            });
+            (
+                badge_icon(base, *color),
+                format!("Display & Sound Manager — {}", name.unwrap_or_default()),
+            )
+        }
+        TrayStatus::Warning(detail) => (
+            badge_icon(base, TRAY_WARNING_COLOR),
+            format!(
+                "Display & Sound Manager — {}",
+                detail.as_deref().unwrap_or("마지막 적용에 문제가 있습니다")
+            ),
+        ),
+    };
+
+    if let Err(e) = tray.set_icon(Some(icon)) {
+        log::warn!("트레이 아이콘 변경 실패: {}", e);
+    }
+    let _ = tray.set_tooltip(Some(tooltip));
+}
+
+type TrayStatusMapper = fn(&tauri::AppHandle, &serde_json::Value) -> Option<TrayStatus>;
+
+// 적용 이벤트를 트레이 상태로 바꿔 반영 (짧은 시간 안의 변경은 마지막 상태만 반영)
+fn setup_tray_status(app: &tauri::AppHandle) {
+    let (sender, receiver) = std::sync::mpsc::channel::<TrayStatus>();
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        let mut current = TrayStatus::Idle;
+        while let Ok(mut next) = receiver.recv() {
+            while let Ok(newer) = receiver.recv_timeout(TRAY_STATUS_DEBOUNCE) {
+                next = newer;
+            }
+            if next != current {
+                apply_tray_status(&app_handle, &next);
+                current = next;
+            }
+        }
+    });
+
+    // 이벤트 이름 → 트레이 상태 (None이면 상태를 바꾸지 않음)
+    let listeners: [(&str, TrayStatusMapper); 4] = [
+        ("apply-started", |_, _| Some(TrayStatus::Applying)),
+        ("apply-failed", |app, failed| {
+            let profile_id = failed["profile_id"].as_str().unwrap_or_default();
+            let name = app
+                .state::<AppState>()
+                .profiles
+                .list()
+                .iter()
+                .find(|p| p.id == profile_id)
+                .map_or_else(|| profile_id.to_string(), |p| p.name.clone());
+            let message = failed["error"]["message"].as_str().unwrap_or_default();
+            Some(TrayStatus::Warning(Some(format!("{} 적용 실패: {}", name, message))))
+        }),
+        ("profile-applied", |app, report| {
+            if let Some(warning) = report["warnings"].as_array().and_then(|warnings| warnings.first()) {
+                return Some(TrayStatus::Warning(warning.as_str().map(str::to_string)));
+            }
+            let profile_id = report["profile_id"].as_str()?;
+            let color = app
+                .state::<AppState>()
+                .profiles
+                .list()
+                .iter()
+                .find(|p| p.id == profile_id)
+                .and_then(|p| p.color.as_deref().and_then(parse_hex_color))
+                .unwrap_or(TRAY_ACTIVE_COLOR);
+            Some(TrayStatus::Active(color))
+        }),
+        ("active-profile-changed", |_, profile_id| profile_id.is_null().then_some(TrayStatus::Idle)),
+    ];
+
+    for (event, to_status) in listeners {
+        let app_handle = app.clone();
+        let sender = sender.clone();
+        app.listen(event, move |event| {
+            let payload = serde_json::from_str(event.payload()).unwrap_or(serde_json::Value::Null);
+            if let Some(status) = to_status(&app_handle, &payload) {
+                let _ = sender.send(status);
+            }
+        });
+    }
+}
@@ -0,0 +1,694 @@
+// 외부 연동(대시보드, Stream Deck 플러그인)용 로컬 WebSocket 브리지
+// 127.0.0.1의 /ws 경로에서 앱 이벤트를 JSON으로 보내고 간단한 명령을 받습니다
+// 단일 인스턴스 제어 포트(instance)와 달리 사용자가 정한 고정 포트에서 켠 동안만 열리고 연결이 오래 유지되므로 따로 둡니다
+//
+// 인증: Authorization: Bearer <토큰> 헤더, 또는 헤더를 붙일 수 없는 브라우저는
+//       Sec-WebSocket-Protocol에 "bearer.<토큰>" (함께 보낸 "display-sound-manager"를 선택해 응답)
+// Origin 헤더를 보내는 클라이언트(브라우저)는 허용 목록에 있는 Origin만 연결할 수 있습니다
+//
+// 메시지 형식
+//   서버 → 클라이언트: {"type":"event","event":"profile-applied","payload":{...}}
+//                      {"type":"response","id":...,"ok":true,"result":...}
+//   클라이언트 → 서버: {"id":...,"command":"apply","profile_id":"..."}
+//                      {"id":...,"command":"set_volume","device_type":"output","volume":40}
+//                      {"id":...,"command":"toggle_mute","device_type":"output"}
+
+use base64::Engine;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Listener};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+use crate::audio::VolumeState;
+use crate::commands::{apply_profile_by_id, set_volume_by_source, toggle_mute_by_source};
+use crate::error::AppError;
+use crate::instance::constant_time_eq;
+
+// 브리지로 전달하는 앱 이벤트
+const BRIDGED_EVENTS: &[&str] = &[
+    "displays-changed",
+    "audio-devices-changed",
+    "volume-changed",
+    "profile-applied",
+    "active-profile-changed",
+];
+
+// 연결마다 쌓아 둘 수 있는 이벤트 수 (넘치면 오래된 것부터 버립니다)
+const EVENT_QUEUE_SIZE: usize = 64;
+// 연결마다 쌓아 둘 수 있는 응답 수
+const RESPONSE_QUEUE_SIZE: usize = 16;
+const MAX_REQUEST_HEADER_SIZE: usize = 8 * 1024;
+// 프레임 하나와 조각을 이어 붙인 메시지 하나의 최대 크기
+const MAX_FRAME_SIZE: u64 = 64 * 1024;
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+// 브라우저가 토큰과 함께 보내고 서버가 선택해 응답하는 하위 프로토콜
+const SUBPROTOCOL: &str = "display-sound-manager";
+const TOKEN_PROTOCOL_PREFIX: &str = "bearer.";
+
+// 앱 이벤트를 모든 연결로 나눠 주는 버스
+pub struct EventBus {
+    sender: broadcast::Sender<String>,
+}
+
+impl EventBus {
+    // 브리지 대상 앱 이벤트를 버스로 전달하도록 등록
+    pub fn new(app: &AppHandle) -> Self {
+        let (sender, _) = broadcast::channel(EVENT_QUEUE_SIZE);
+        for event in BRIDGED_EVENTS {
+            let sender = sender.clone();
+            app.listen(*event, move |e| {
+                // 연결된 클라이언트가 없으면 보내지 않습니다
+                if sender.receiver_count() == 0 {
+                    return;
+                }
+                let payload = serde_json::from_str(e.payload()).unwrap_or(Value::Null);
+                let message = json!({ "type": "event", "event": event, "payload": payload });
+                let _ = sender.send(message.to_string());
+            });
+        }
+        EventBus { sender }
+    }
+}
+
+// 연결을 허용할 클라이언트 (토큰과 허용할 Origin)
+#[derive(Debug, Clone)]
+pub struct Access {
+    pub token: String,
+    pub allowed_origins: Vec<String>,
+}
+
+// 서버 시작 (반환된 Sender로 종료)
+pub async fn start(
+    app: AppHandle,
+    bus: &EventBus,
+    port: u16,
+    access: Access,
+) -> std::io::Result<oneshot::Sender<()>> {
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    log::info!("WebSocket 브리지 시작: ws://127.0.0.1:{}/ws", port);
+
+    let (shutdown, mut shutdown_rx) = oneshot::channel::<()>();
+    let events = bus.sender.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => break,
+                accepted = listener.accept() => {
+                    let Ok((stream, peer)) = accepted else {
+                        continue;
+                    };
+                    let app = app.clone();
+                    let access = access.clone();
+                    let events = events.subscribe();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = handle_connection(app, stream, &access, events).await {
+                            log::debug!("WebSocket 연결 종료 ({}): {}", peer, e);
+                        }
+                    });
+                }
+            }
+        }
+        log::info!("WebSocket 브리지 종료");
+    });
+
+    Ok(shutdown)
+}
+
+async fn handle_connection(
+    app: AppHandle,
+    mut stream: TcpStream,
+    access: &Access,
+    mut events: broadcast::Receiver<String>,
+) -> std::io::Result<()> {
+    let request = read_request_header(&mut stream).await?;
+    let accepted = match check_handshake(&request, access) {
+        Ok(accepted) => accepted,
+        Err(status) => {
+            let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+            stream.write_all(response.as_bytes()).await?;
+            return Ok(());
+        }
+    };
+
+    let protocol = accepted.protocol.map(|protocol| format!("Sec-WebSocket-Protocol: {}\r\n", protocol));
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n{}\r\n",
+        accept_key(&accepted.key),
+        protocol.unwrap_or_default()
+    );
+    stream.write_all(response.as_bytes()).await?;
+
+    let (mut reader, mut writer) = stream.into_split();
+    let (responses, mut responses_rx) = mpsc::channel::<Vec<u8>>(RESPONSE_QUEUE_SIZE);
+
+    // 쓰기 작업: 이벤트와 응답을 보냅니다
+    // 느린 클라이언트 때문에 앱이 멈추지 않도록 이벤트는 broadcast 큐에서 밀려나면 버립니다
+    let write_task = tauri::async_runtime::spawn(async move {
+        loop {
+            let frame = tokio::select! {
+                response = responses_rx.recv() => match response {
+                    Some(frame) => frame,
+                    None => break,
+                },
+                event = events.recv() => match event {
+                    Ok(message) => encode_frame(OPCODE_TEXT, message.as_bytes()),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        log::warn!("WebSocket 클라이언트가 느려 이벤트 {}개를 버렸습니다", skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            };
+            if writer.write_all(&frame).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut messages = MessageAssembler::default();
+    loop {
+        let Some(frame) = read_frame(&mut reader).await? else {
+            break;
+        };
+        let (opcode, payload) = match messages.push(frame) {
+            Ok(Some(message)) => message,
+            Ok(None) => continue,
+            Err(code) => {
+                log::debug!("WebSocket 연결을 닫습니다 (상태 코드 {})", code);
+                let _ = responses.try_send(encode_frame(OPCODE_CLOSE, &code.to_be_bytes()));
+                break;
+            }
+        };
+        match opcode {
+            OPCODE_TEXT => {
+                let app = app.clone();
+                let responses = responses.clone();
+                tauri::async_runtime::spawn(async move {
+                    let response = handle_message(app, &payload).await;
+                    let frame = encode_frame(OPCODE_TEXT, response.to_string().as_bytes());
+                    if responses.try_send(frame).is_err() {
+                        log::warn!("WebSocket 응답 큐가 가득 차 응답을 버렸습니다");
+                    }
+                });
+            }
+            OPCODE_PING => {
+                let _ = responses.try_send(encode_frame(OPCODE_PONG, &payload));
+            }
+            OPCODE_CLOSE => {
+                let _ = responses.try_send(encode_frame(OPCODE_CLOSE, &[]));
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    drop(responses);
+    let _ = write_task.await;
+    Ok(())
+}
+
+// 클라이언트 명령 처리 (응답 JSON 반환)
+async fn handle_message(app: AppHandle, payload: &[u8]) -> Value {
+    let request: Value = match serde_json::from_slice(payload) {
+        Ok(request) => request,
+        Err(e) => {
+            let error = AppError::InvalidRemoteMessage { detail: e.to_string() };
+            return json!({ "type": "response", "id": Value::Null, "ok": false, "error": error });
+        }
+    };
+    let id = request["id"].clone();
+    let command = request["command"].as_str().unwrap_or_default().to_string();
+    log::info!("WebSocket 명령: {} (id={})", command, id);
+
+    let result = match command.as_str() {
+        "apply" => {
+            let profile_id = request["profile_id"].as_str().unwrap_or_default().to_string();
+            tauri::async_runtime::spawn_blocking(move || apply_profile_by_id(&app, &profile_id, "remote"))
+                .await
+                .unwrap_or_else(|e| Err(AppError::Internal { detail: e.to_string() }))
+                .and_then(|report| {
+                    serde_json::to_value(report).map_err(|e| AppError::Internal { detail: e.to_string() })
+                })
+        }
+        "set_volume" => {
+            let device_type = request["device_type"].as_str().unwrap_or("output").to_string();
+            let volume = request["volume"].as_u64().unwrap_or_default().min(100) as u32;
+            run_volume_command(move || set_volume_by_source(&app, &device_type, volume, "remote")).await
+        }
+        "toggle_mute" => {
+            let device_type = request["device_type"].as_str().unwrap_or("output").to_string();
+            run_volume_command(move || toggle_mute_by_source(&app, &device_type, "remote")).await
+        }
+        _ => Err(AppError::UnknownRemoteCommand { command: command.clone() }),
+    };
+
+    match result {
+        Ok(result) => json!({ "type": "response", "id": id, "ok": true, "result": result }),
+        Err(error) => json!({ "type": "response", "id": id, "ok": false, "error": error }),
+    }
+}
+
+// 볼륨 명령은 외부 도구를 실행할 수 있으므로 blocking 스레드에서 (결과는 바뀐 볼륨 상태)
+async fn run_volume_command(
+    command: impl FnOnce() -> Result<VolumeState, AppError> + Send + 'static,
+) -> Result<Value, AppError> {
+    tauri::async_runtime::spawn_blocking(command)
+        .await
+        .unwrap_or_else(|e| Err(AppError::Internal { detail: e.to_string() }))
+        .and_then(|volume| serde_json::to_value(volume).map_err(|e| AppError::Internal { detail: e.to_string() }))
+}
+
+struct HandshakeRequest {
+    path: String,
+    headers: Vec<(String, String)>,
+}
+
+impl HandshakeRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+async fn read_request_header(stream: &mut TcpStream) -> std::io::Result<HandshakeRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 || buffer.len() + read > MAX_REQUEST_HEADER_SIZE {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "incomplete request header"));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    let text = String::from_utf8_lossy(&buffer);
+    let mut lines = text.split("\r\n");
+    let target = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or_default();
+    // 쿼리는 쓰지 않습니다 (토큰을 URL에 넣으면 로그와 기록에 남음)
+    let path = target.split_once('?').map_or(target, |(path, _)| path);
+
+    Ok(HandshakeRequest {
+        path: path.to_string(),
+        headers: lines
+            .take_while(|line| !line.is_empty())
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect(),
+    })
+}
+
+// 받아들인 업그레이드 요청
+#[derive(Debug, PartialEq)]
+struct AcceptedHandshake {
+    key: String,
+    // 응답의 Sec-WebSocket-Protocol (클라이언트가 하위 프로토콜을 보냈을 때만)
+    protocol: Option<String>,
+}
+
+// 업그레이드 요청 확인 (실패 시 HTTP 상태 줄 반환)
+// 브라우저는 WebSocket에 헤더를 붙일 수 없으므로 Sec-WebSocket-Protocol의 "bearer.<토큰>"도 허용합니다
+fn check_handshake(request: &HandshakeRequest, access: &Access) -> Result<AcceptedHandshake, &'static str> {
+    if request.path != "/ws" {
+        return Err("404 Not Found");
+    }
+    // 다른 사이트의 페이지가 브라우저를 통해 연결하지 못하도록 (Origin이 없는 클라이언트는 브라우저가 아님)
+    if request.header("Origin").is_some_and(|origin| !origin_allowed(origin, &access.allowed_origins)) {
+        return Err("403 Forbidden");
+    }
+    // 토큰이 비어 있으면 빈 Bearer 헤더로도 통과하므로 모두 거부합니다
+    let token = access.token.as_str();
+    if token.is_empty() {
+        return Err("401 Unauthorized");
+    }
+
+    let bearer = request
+        .header("Authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+    let protocols: Vec<&str> = request
+        .header("Sec-WebSocket-Protocol")
+        .map(|value| value.split(',').map(str::trim).collect())
+        .unwrap_or_default();
+    let protocol_token = protocols.iter().find_map(|protocol| protocol.strip_prefix(TOKEN_PROTOCOL_PREFIX));
+    let authorized = [bearer, protocol_token]
+        .into_iter()
+        .flatten()
+        .any(|candidate| constant_time_eq(candidate.as_bytes(), token.as_bytes()));
+    if !authorized {
+        return Err("401 Unauthorized");
+    }
+
+    let is_upgrade = request
+        .header("Upgrade")
+        .is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+    let key = match request.header("Sec-WebSocket-Key") {
+        Some(key) if is_upgrade => key.to_string(),
+        _ => return Err("400 Bad Request"),
+    };
+    // 하위 프로토콜을 보낸 브라우저는 그중 하나를 선택해 응답해야 연결을 엽니다 (토큰을 되돌려 보내지 않도록 SUBPROTOCOL)
+    let protocol = if protocols.is_empty() {
+        None
+    } else if protocols.contains(&SUBPROTOCOL) {
+        Some(SUBPROTOCOL.to_string())
+    } else {
+        return Err("400 Bad Request");
+    };
+    Ok(AcceptedHandshake { key, protocol })
+}
+
+// 허용 목록의 Origin과 같은지 (대소문자와 끝의 / 무시)
+fn origin_allowed(origin: &str, allowed: &[String]) -> bool {
+    let normalize = |origin: &str| origin.trim().trim_end_matches('/').to_ascii_lowercase();
+    let origin = normalize(origin);
+    allowed.iter().any(|allowed| normalize(allowed) == origin)
+}
+
+// Sec-WebSocket-Accept = base64(SHA-1(key + GUID))
+fn accept_key(key: &str) -> String {
+    base64::engine::general_purpose::STANDARD.encode(sha1(format!("{}{}", key, WEBSOCKET_GUID).as_bytes()))
+}
+
+// 핸드셰이크에만 쓰는 SHA-1 (RFC 3174, 보안 용도가 아니라 의존성을 늘리지 않으려고 직접 구현)
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (state, value) in h.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (chunk, value) in digest.chunks_mut(4).zip(h) {
+        chunk.copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+// 닫기 프레임의 상태 코드 (RFC 6455 7.4.1)
+const CLOSE_PROTOCOL_ERROR: u16 = 1002;
+const CLOSE_UNSUPPORTED_DATA: u16 = 1003;
+const CLOSE_MESSAGE_TOO_BIG: u16 = 1009;
+
+#[derive(Debug, PartialEq)]
+struct Frame {
+    // 메시지의 마지막 조각인지 (FIN)
+    fin: bool,
+    opcode: u8,
+    payload: Vec<u8>,
+}
+
+// 조각난 메시지(FIN이 없는 프레임 + 연속 프레임)를 이어 붙임
+// 제어 프레임은 조각 사이에 올 수 있으므로 바로 돌려주고, 규칙을 어기면 닫을 때 보낼 상태 코드
+#[derive(Default)]
+struct MessageAssembler {
+    // 이어 붙이는 중인 메시지 (첫 프레임의 opcode와 지금까지의 내용)
+    partial: Option<(u8, Vec<u8>)>,
+}
+
+impl MessageAssembler {
+    fn push(&mut self, frame: Frame) -> Result<Option<(u8, Vec<u8>)>, u16> {
+        match frame.opcode {
+            OPCODE_CLOSE | OPCODE_PING | OPCODE_PONG => {
+                if !frame.fin || frame.payload.len() > 125 {
+                    return Err(CLOSE_PROTOCOL_ERROR);
+                }
+                Ok(Some((frame.opcode, frame.payload)))
+            }
+            OPCODE_TEXT | OPCODE_BINARY => {
+                if self.partial.is_some() {
+                    return Err(CLOSE_PROTOCOL_ERROR);
+                }
+                if frame.opcode == OPCODE_BINARY {
+                    return Err(CLOSE_UNSUPPORTED_DATA);
+                }
+                if frame.fin {
+                    return Ok(Some((frame.opcode, frame.payload)));
+                }
+                self.partial = Some((frame.opcode, frame.payload));
+                Ok(None)
+            }
+            OPCODE_CONTINUATION => {
+                let Some((_, message)) = self.partial.as_mut() else {
+                    return Err(CLOSE_PROTOCOL_ERROR);
+                };
+                if (message.len() + frame.payload.len()) as u64 > MAX_FRAME_SIZE {
+                    return Err(CLOSE_MESSAGE_TOO_BIG);
+                }
+                message.extend_from_slice(&frame.payload);
+                if !frame.fin {
+                    return Ok(None);
+                }
+                Ok(self.partial.take())
+            }
+            _ => Err(CLOSE_PROTOCOL_ERROR),
+        }
+    }
+}
+
+// 클라이언트 프레임 읽기 (연결이 끊기면 None)
+async fn read_frame<R: AsyncReadExt + Unpin>(reader: &mut R) -> std::io::Result<Option<Frame>> {
+    let mut header = [0u8; 2];
+    if reader.read_exact(&mut header).await.is_err() {
+        return Ok(None);
+    }
+    let fin = header[0] & 0x80 != 0;
+    let opcode = header[0] & 0x0F;
+    let masked = header[1] & 0x80 != 0;
+    let mut length = (header[1] & 0x7F) as u64;
+    if length == 126 {
+        length = reader.read_u16().await? as u64;
+    } else if length == 127 {
+        length = reader.read_u64().await?;
+    }
+    if length > MAX_FRAME_SIZE {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "frame too large"));
+    }
+
+    let mut mask = [0u8; 4];
+    if masked {
+        reader.read_exact(&mut mask).await?;
+    }
+    let mut payload = vec![0u8; length as usize];
+    reader.read_exact(&mut payload).await?;
+    if masked {
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+    }
+
+    Ok(Some(Frame { fin, opcode, payload }))
+}
+
+// 서버 프레임 생성 (서버 → 클라이언트는 마스킹하지 않습니다)
+fn encode_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(path: &str, headers: &[(&str, &str)]) -> HandshakeRequest {
+        let headers = headers.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        HandshakeRequest { path: path.to_string(), headers }
+    }
+
+    fn access(token: &str) -> Access {
+        Access { token: token.to_string(), allowed_origins: vec!["http://localhost:5173".to_string()] }
+    }
+
+    fn key(handshake: Result<AcceptedHandshake, &'static str>) -> Result<String, &'static str> {
+        handshake.map(|accepted| accepted.key)
+    }
+
+    const UPGRADE: [(&str, &str); 2] = [("Upgrade", "websocket"), ("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ==")];
+
+    // 업그레이드 헤더와 authorization 헤더
+    fn with(authorization: &'static str) -> Vec<(&'static str, &'static str)> {
+        [&[("Authorization", authorization)], &UPGRADE[..]].concat()
+    }
+
+    #[test]
+    fn computes_the_rfc_6455_accept_key() {
+        assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+        // FIPS 180-1의 예 ("abc")
+        let digest: String = sha1(b"abc").iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert_eq!(digest, "a9993e364706816aba3e25717850c26c9cd0d89d");
+    }
+
+    #[test]
+    fn handshake_requires_the_token() {
+        let bearer = with("Bearer secret");
+        let secret = access("secret");
+        assert_eq!(key(check_handshake(&request("/ws", &bearer), &secret)).as_deref(), Ok("dGhlIHNhbXBsZSBub25jZQ=="));
+
+        // 토큰 없음, 틀린 토큰, 빈 토큰
+        assert_eq!(key(check_handshake(&request("/ws", &UPGRADE), &secret)), Err("401 Unauthorized"));
+        let wrong = with("Bearer guess");
+        assert_eq!(key(check_handshake(&request("/ws", &wrong), &secret)), Err("401 Unauthorized"));
+        let empty = with("Bearer ");
+        assert_eq!(key(check_handshake(&request("/ws", &empty), &secret)), Err("401 Unauthorized"));
+        // 서버 토큰이 비어 있으면 빈 토큰도 통과하지 않습니다
+        assert_eq!(key(check_handshake(&request("/ws", &empty), &access(""))), Err("401 Unauthorized"));
+
+        assert_eq!(key(check_handshake(&request("/", &bearer), &secret)), Err("404 Not Found"));
+        let no_upgrade = [("Authorization", "Bearer secret")];
+        assert_eq!(key(check_handshake(&request("/ws", &no_upgrade), &secret)), Err("400 Bad Request"));
+    }
+
+    #[test]
+    fn browsers_send_the_token_as_a_subprotocol_from_an_allowed_origin() {
+        let secret = access("secret");
+        let browser = |origin: &'static str, protocols: &'static str| {
+            [&[("Origin", origin), ("Sec-WebSocket-Protocol", protocols)], &UPGRADE[..]].concat()
+        };
+        let accepted =
+            check_handshake(&request("/ws", &browser("http://localhost:5173", "display-sound-manager, bearer.secret")), &secret)
+                .unwrap();
+        // 토큰이 아니라 앱의 하위 프로토콜을 선택해 응답합니다
+        assert_eq!(accepted.protocol.as_deref(), Some(SUBPROTOCOL));
+        // Origin은 대소문자와 끝의 /를 무시하고 비교합니다
+        let upper = browser("HTTP://LOCALHOST:5173/", "display-sound-manager, bearer.secret");
+        assert!(check_handshake(&request("/ws", &upper), &secret).is_ok());
+
+        let other_site = browser("https://example.com", "display-sound-manager, bearer.secret");
+        assert_eq!(key(check_handshake(&request("/ws", &other_site), &secret)), Err("403 Forbidden"));
+        let wrong = browser("http://localhost:5173", "display-sound-manager, bearer.guess");
+        assert_eq!(key(check_handshake(&request("/ws", &wrong), &secret)), Err("401 Unauthorized"));
+        // 선택할 하위 프로토콜이 없으면 브라우저가 연결을 닫으므로 거부합니다
+        let token_only = browser("http://localhost:5173", "bearer.secret");
+        assert_eq!(key(check_handshake(&request("/ws", &token_only), &secret)), Err("400 Bad Request"));
+    }
+
+    // 클라이언트처럼 마스킹한 프레임
+    fn masked_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x12, 0x34, 0x56, 0x78];
+        let mut frame = encode_frame(opcode, payload);
+        let header = frame.len() - payload.len();
+        frame[1] |= 0x80;
+        for (i, byte) in frame[header..].iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+        frame.splice(header..header, mask);
+        frame
+    }
+
+    #[tokio::test]
+    async fn frames_round_trip_at_length_boundaries() {
+        for (length, header) in [(125, 2), (126, 4), (65535, 4), (65536, 10)] {
+            let payload: Vec<u8> = (0..length).map(|i| i as u8).collect();
+            let frame = encode_frame(OPCODE_TEXT, &payload);
+            assert_eq!(frame.len(), header + length, "length {}", length);
+            assert_eq!(frame[0], 0x80 | OPCODE_TEXT);
+
+            let mut reader = &masked_frame(OPCODE_TEXT, &payload)[..];
+            if length as u64 > MAX_FRAME_SIZE {
+                assert!(read_frame(&mut reader).await.is_err());
+                continue;
+            }
+            let frame = read_frame(&mut reader).await.unwrap().unwrap();
+            assert_eq!((frame.fin, frame.opcode, frame.payload.len()), (true, OPCODE_TEXT, length));
+            assert_eq!(frame.payload, payload);
+        }
+
+        // FIN이 없는 첫 조각
+        let mut first = masked_frame(OPCODE_TEXT, b"ab");
+        first[0] &= 0x7F;
+        let frame = read_frame(&mut &first[..]).await.unwrap().unwrap();
+        assert_eq!((frame.fin, frame.opcode, frame.payload), (false, OPCODE_TEXT, b"ab".to_vec()));
+
+        // 연결이 끊기면 None
+        let mut closed: &[u8] = &[];
+        assert!(read_frame(&mut closed).await.unwrap().is_none());
+    }
+
+    fn frame(fin: bool, opcode: u8, payload: &[u8]) -> Frame {
+        Frame { fin, opcode, payload: payload.to_vec() }
+    }
+
+    #[test]
+    fn reassembles_fragmented_messages() {
+        let mut messages = MessageAssembler::default();
+        assert_eq!(messages.push(frame(false, OPCODE_TEXT, b"{\"id\":")), Ok(None));
+        // 조각 사이의 ping은 바로 처리합니다
+        assert_eq!(messages.push(frame(true, OPCODE_PING, b"p")), Ok(Some((OPCODE_PING, b"p".to_vec()))));
+        assert_eq!(messages.push(frame(false, OPCODE_CONTINUATION, b"1,")), Ok(None));
+        let message = messages.push(frame(true, OPCODE_CONTINUATION, b"\"command\":\"apply\"}"));
+        assert_eq!(message, Ok(Some((OPCODE_TEXT, br#"{"id":1,"command":"apply"}"#.to_vec()))));
+        assert_eq!(messages.push(frame(true, OPCODE_TEXT, b"{}")), Ok(Some((OPCODE_TEXT, b"{}".to_vec()))));
+
+        // 시작 없는 연속 프레임, 끝나기 전의 새 메시지, 조각난 제어 프레임, 바이너리, 너무 큰 메시지
+        let mut messages = MessageAssembler::default();
+        assert_eq!(messages.push(frame(true, OPCODE_CONTINUATION, b"x")), Err(CLOSE_PROTOCOL_ERROR));
+        messages.push(frame(false, OPCODE_TEXT, b"a")).unwrap();
+        assert_eq!(messages.push(frame(true, OPCODE_TEXT, b"b")), Err(CLOSE_PROTOCOL_ERROR));
+        let mut messages = MessageAssembler::default();
+        assert_eq!(messages.push(frame(false, OPCODE_PING, b"")), Err(CLOSE_PROTOCOL_ERROR));
+        assert_eq!(messages.push(frame(true, OPCODE_BINARY, b"\0")), Err(CLOSE_UNSUPPORTED_DATA));
+        messages.push(frame(false, OPCODE_TEXT, &vec![b' '; MAX_FRAME_SIZE as usize])).unwrap();
+        assert_eq!(messages.push(frame(true, OPCODE_CONTINUATION, b"}")), Err(CLOSE_MESSAGE_TOO_BIG));
+    }
+}
@@ -0,0 +1,184 @@
+// tests/fixtures의 모든 장치 구성으로 EDID 해석, 장치 매칭, 검사, 비교, 적용 과정을 점검합니다
+// 새 fixture는 앱의 record_fixture 명령으로 기록해 tests/fixtures에 넣으면 자동으로 포함됩니다
+// 결과는 tests/fixtures/expected/{이름}.json과 비교하며, 처음 추가했거나 결과가 의도대로 바뀌었으면
+//   DSM_UPDATE_FIXTURES=1 cargo test --test fixtures
+// 로 다시 기록합니다
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use display_sound_manager_lib::audio::{capture_audio_settings, resolve_audio_device, AudioBackend};
+use display_sound_manager_lib::display::layout::{arrange_displays, layout_preview, DEFAULT_DESKTOP_LIMIT};
+use display_sound_manager_lib::display::{edid, DisplayBackend};
+use display_sound_manager_lib::error::AppError;
+use display_sound_manager_lib::mock::MockBackend;
+use display_sound_manager_lib::profiles::{
+    apply_profile_partial_settings, apply_profile_settings, profile_drift, validate_profile,
+    verify_profile, ApplyReport, CancellationToken, Profile,
+};
+use display_sound_manager_lib::tools::{set_retry_policy, RetryPolicy};
+
+const UPDATE_ENV: &str = "DSM_UPDATE_FIXTURES";
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+// 기대 결과 디렉터리(expected)는 제외하고 fixture 파일만 이름순으로
+fn fixture_names() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(fixtures_dir())
+        .unwrap()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .collect();
+    names.sort();
+    names
+}
+
+fn load(name: &str) -> MockBackend {
+    MockBackend::load(&fixtures_dir().join(format!("{}.json", name))).unwrap()
+}
+
+// fixture에 기록된 구성을 그대로 저장한 프로필
+fn captured_profile(name: &str, mock: &MockBackend) -> Profile {
+    Profile {
+        id: name.to_string(),
+        name: name.to_string(),
+        displays: DisplayBackend::enumerate(mock).unwrap(),
+        audio_settings: Some(capture_audio_settings(&AudioBackend::enumerate(mock).unwrap())),
+        created_at: "2024-01-01T00:00:00+09:00".to_string(),
+        tags: Vec::new(),
+        color: None,
+        hotkey: None,
+        pre_apply_command: None,
+        post_apply_command: None,
+        auto_apply_on_device: None,
+        output_follows_display: false,
+    }
+}
+
+// 오류 메시지는 언어 설정에 따라 달라지므로 코드만 비교합니다
+fn error_code(error: &AppError) -> Value {
+    serde_json::to_value(error).unwrap()["code"].clone()
+}
+
+// 실행 시간은 매번 달라지므로 단계 결과, 경고, 오류 코드만 비교합니다
+fn report_summary(report: &ApplyReport) -> Value {
+    let errors: serde_json::Map<String, Value> = report
+        .errors
+        .iter()
+        .map(|(step, error)| (step.clone(), error_code(error)))
+        .collect();
+    json!({
+        "steps": report.steps,
+        "warnings": report.warnings,
+        "errors": errors,
+    })
+}
+
+fn snapshot(name: &str, names: &[String]) -> Value {
+    let mock = load(name);
+    let fixture = mock.fixture();
+    let profile = captured_profile(name, &mock);
+
+    let edids: Vec<Value> = mock
+        .raw_edids()
+        .unwrap()
+        .iter()
+        .map(|blob| json!({
+            "name": edid::name(blob),
+            "serials": edid::serials(blob),
+            "fingerprint": edid::fingerprint(blob),
+        }))
+        .collect();
+
+    // 이름이 같은 장치가 여러 개면 패턴은 항상 처음 나온 장치를 고릅니다
+    let patterns: Vec<Value> = fixture
+        .audio_devices
+        .iter()
+        .map(|device| {
+            let pattern = format!("re:^{}$", regex::escape(&device.name));
+            let resolved = resolve_audio_device(&pattern, &device.device_type, &fixture.audio_devices);
+            json!({
+                "device_type": device.device_type,
+                "name": device.name,
+                "resolved": resolved.map_err(|e| error_code(&e)).map_or_else(|code| code, Value::from),
+            })
+        })
+        .collect();
+
+    let validation = validate_profile(&profile, DEFAULT_DESKTOP_LIMIT).map_or_else(|e| error_code(&e), |()| json!("ok"));
+
+    assert_eq!(profile_drift(&profile, &fixture.displays, &fixture.audio_devices).unwrap(), Vec::<String>::new());
+
+    let arranged: Vec<Value> = arrange_displays(fixture.displays.clone())
+        .iter()
+        .map(|display| json!({ "id": display.id, "x": display.x, "y": display.y }))
+        .collect();
+
+    // 저장한 구성을 같은 장치에 다시 적용하면 차이가 없어야 합니다
+    let target = load(name);
+    let report = apply_profile_settings(&profile, &target, &target, &CancellationToken::new()).unwrap();
+    assert!(report.error().is_none(), "{}: {:?}", name, report.errors);
+    assert_eq!(verify_profile(&profile, &target, &target).unwrap(), Vec::<String>::new(), "{}", name);
+
+    // 다른 장치 구성에 이 프로필을 적용할 때의 차이와 부분 적용 결과
+    let against: serde_json::Map<String, Value> = names
+        .iter()
+        .filter(|other| other.as_str() != name)
+        .map(|other| {
+            let target = load(other);
+            let current = target.fixture();
+            let drift = profile_drift(&profile, &current.displays, &current.audio_devices)
+                .map_or_else(|e| error_code(&e), |drift| json!(drift));
+            let partial = apply_profile_partial_settings(&profile, &target, &target, &CancellationToken::new())
+                .map_or_else(|e| error_code(&e), |report| report_summary(&report));
+            (other.clone(), json!({ "drift": drift, "partial": partial }))
+        })
+        .collect();
+
+    json!({
+        "edids": edids,
+        "patterns": patterns,
+        "validation": validation,
+        "arranged": arranged,
+        "preview": layout_preview(&fixture.displays),
+        "apply": report_summary(&report),
+        "against": against,
+    })
+}
+
+fn compare(path: &Path, actual: &Value) {
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, serde_json::to_string_pretty(actual).unwrap() + "\n").unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(path)
+        .unwrap_or_else(|_| panic!("{} 없음 ({}=1로 기록하세요)", path.display(), UPDATE_ENV));
+    let expected: Value = serde_json::from_str(&expected).unwrap();
+    assert_eq!(
+        &expected,
+        actual,
+        "{}와 결과가 다릅니다 (의도한 변경이면 {}=1로 다시 기록하세요)\n{}",
+        path.display(),
+        UPDATE_ENV,
+        serde_json::to_string_pretty(actual).unwrap()
+    );
+}
+
+#[test]
+fn every_fixture_matches_its_snapshot() {
+    // 재시도는 대기 없이 (다른 구성에 적용할 때 "Unable to find screen"이 재시도됩니다)
+    set_retry_policy(RetryPolicy { retries: 2, backoff_ms: vec![0] });
+    let names = fixture_names();
+    assert!(!names.is_empty());
+
+    for name in &names {
+        let actual = snapshot(name, &names);
+        compare(&fixtures_dir().join("expected").join(format!("{}.json", name)), &actual);
+    }
+}
@@ -0,0 +1,493 @@
+// mock 백엔드로 프로필 적용 → 확인 과정 전체를 점검합니다
+// fixture는 tests/fixtures에 있으며, 앱도 DSM_MOCK_BACKEND로 같은 파일을 불러올 수 있습니다
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+use display_sound_manager_lib::audio::{
+    self, default_volume_state, set_device_volume, toggle_device_mute, AudioDevice, AudioSettings,
+};
+use display_sound_manager_lib::display::{DisplayApplied, DisplayBackend, DisplayInfo};
+use display_sound_manager_lib::error::AppError;
+use display_sound_manager_lib::mock::{MockBackend, MockOperation};
+use display_sound_manager_lib::tools::{set_retry_policy, RetryPolicy};
+use display_sound_manager_lib::profiles::{
+    apply_profile_partial_settings, apply_profile_settings, validate_profile_against_system, verify_profile,
+    AudioCheck, CancellationToken, DisplayCheck, Profile, StepStatus,
+};
+
+fn fixture(name: &str) -> MockBackend {
+    // 재시도는 대기 없이 (mock의 "Unable to find screen"은 일시적인 실패로 분류됩니다)
+    set_retry_policy(RetryPolicy { retries: 2, backoff_ms: vec![0] });
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(format!("{}.json", name));
+    MockBackend::load(&path).unwrap()
+}
+
+// desk.json 구성을 저장해 둔 프로필 (헤드셋으로 출력)
+fn desk_profile(output_device: &str) -> Profile {
+    Profile {
+        id: "desk".to_string(),
+        name: "Desk".to_string(),
+        displays: fixture("desk").fixture().displays,
+        audio_settings: Some(AudioSettings {
+            output_device: Some(output_device.to_string()),
+            input_device: None,
+            output_volume: 50,
+            input_volume: 50,
+            output_roles: None,
+            output_muted: None,
+            input_muted: None,
+            device_profiles: None,
+        }),
+        created_at: "2024-01-01T00:00:00+09:00".to_string(),
+        tags: Vec::new(),
+        color: None,
+        hotkey: None,
+        pre_apply_command: None,
+        post_apply_command: None,
+        auto_apply_on_device: None,
+        output_follows_display: false,
+    }
+}
+
+#[test]
+fn apply_then_verify_matches() {
+    let mock = fixture("desk");
+    let profile = desk_profile("re:^USB Audio");
+
+    let drift = verify_profile(&profile, &mock, &mock).unwrap();
+    assert_eq!(drift.len(), 1, "{:?}", drift);
+
+    let report = apply_profile_settings(&profile, &mock, &mock, &CancellationToken::new()).unwrap();
+    assert_eq!(report.backend_used["display"], "mock");
+    assert_eq!(report.backend_used["audio"], "mock");
+
+    // 패턴은 적용 전에 실제 장치 이름으로 바뀌어야 합니다 (두 단계는 동시에 실행되어 순서는 정해지지 않음)
+    let operations = mock.operations();
+    assert_eq!(operations.len(), 2);
+    assert!(operations
+        .iter()
+        .any(|op| matches!(op, MockOperation::Displays(displays) if displays.len() == 2)));
+    assert!(operations.iter().any(|op| matches!(
+        op,
+        MockOperation::Audio(settings) if settings.output_device.as_deref() == Some("USB Audio Headset")
+    )));
+
+    assert!(verify_profile(&profile, &mock, &mock).unwrap().is_empty());
+}
+
+#[test]
+fn moved_display_is_reported_and_restored() {
+    let mock = fixture("desk");
+    let profile = desk_profile("MacBook Pro Speakers");
+    let mut moved = mock.fixture();
+    moved.displays[1].x = -1920;
+    mock.set_fixture(moved);
+
+    let drift = verify_profile(&profile, &mock, &mock).unwrap();
+    assert_eq!(drift, ["DELL U2720Q: 위치 (1512,-98) → (-1920,-98)"]);
+
+    apply_profile_settings(&profile, &mock, &mock, &CancellationToken::new()).unwrap();
+    assert!(verify_profile(&profile, &mock, &mock).unwrap().is_empty());
+}
+
+#[test]
+fn monitor_replugged_on_different_port() {
+    // 같은 모니터가 다른 포트에 연결되어 id가 바뀐 경우
+    let mock = fixture("desk-replugged");
+    let profile = desk_profile("USB Audio Headset");
+
+    let drift = verify_profile(&profile, &mock, &mock).unwrap();
+    assert!(drift.contains(&"DELL U2720Q: 연결되어 있지 않음".to_string()), "{:?}", drift);
+
+    // 없는 id로는 배치를 적용하지 않지만, 오디오는 따로 적용됩니다
+    let report = apply_profile_settings(&profile, &mock, &mock, &CancellationToken::new()).unwrap();
+    assert!(matches!(
+        report.error(),
+        Some(AppError::ToolFailed { detail, .. }) if detail.contains("screen 2")
+    ));
+    assert_eq!(report.steps["display"], StepStatus::Failed);
+    assert_eq!(report.steps["audio"], StepStatus::Applied);
+    let operations = mock.operations();
+    assert_eq!(operations.len(), 1);
+    assert!(matches!(operations[0], MockOperation::Audio(_)));
+}
+
+#[test]
+fn audio_only_profile_leaves_displays_alone() {
+    let mock = fixture("desk");
+    let mut profile = desk_profile("USB Audio Headset");
+    profile.displays.clear();
+
+    // 오디오만 있는 프로필은 배치를 적용하지도, 비교하지도 않습니다
+    let mut moved = mock.fixture();
+    moved.displays[1].x = -1920;
+    mock.set_fixture(moved);
+    let report = apply_profile_settings(&profile, &mock, &mock, &CancellationToken::new()).unwrap();
+    assert!(!report.steps.contains_key("display"));
+    assert!(matches!(&mock.operations()[..], [MockOperation::Audio(_)]));
+    assert!(verify_profile(&profile, &mock, &mock).unwrap().is_empty());
+
+    // 디스플레이만 있는 프로필은 오디오 장치를 바꾸지 않습니다
+    let mut profile = desk_profile("USB Audio Headset");
+    profile.audio_settings = None;
+    let report = apply_profile_settings(&profile, &mock, &mock, &CancellationToken::new()).unwrap();
+    assert_eq!(report.steps.keys().collect::<Vec<_>>(), ["display"]);
+}
+
+#[test]
+fn partial_apply_skips_disconnected_displays() {
+    let mock = fixture("desk-replugged");
+    let mut profile = desk_profile("USB Audio Headset");
+    profile.displays[0].x = 100;
+
+    let report =
+        apply_profile_partial_settings(&profile, &mock, &mock, &CancellationToken::new()).unwrap();
+    assert_eq!(report.warnings, ["DELL U2720Q: 연결되어 있지 않아 건너뜁니다"]);
+    let operations = mock.operations();
+    match operations.iter().find(|op| matches!(op, MockOperation::Displays(_))) {
+        Some(MockOperation::Displays(displays)) => {
+            assert_eq!(displays.iter().map(|d| d.id).collect::<Vec<_>>(), [1]);
+        }
+        other => panic!("unexpected operations: {:?}", other),
+    }
+
+    // 프로필에 없는 디스플레이(id 3)는 그대로입니다
+    let current = mock.fixture().displays;
+    assert_eq!(current[0].x, 100);
+    assert_eq!((current[1].id, current[1].x, current[1].y), (3, 1512, 0));
+}
+
+#[test]
+fn audio_device_missing() {
+    let mock = fixture("desk-no-headset");
+
+    // 패턴과 일치하는 장치가 없으면 확인 단계에서도 DeviceNotFound
+    let profile = desk_profile("re:^USB Audio");
+    assert!(matches!(
+        verify_profile(&profile, &mock, &mock),
+        Err(AppError::DeviceNotFound { .. })
+    ));
+
+    // 디스플레이는 적용되고 오디오 단계에서 실패합니다
+    let report = apply_profile_settings(&profile, &mock, &mock, &CancellationToken::new()).unwrap();
+    assert!(matches!(report.errors["audio"], AppError::DeviceNotFound { .. }));
+    assert_eq!(report.steps["display"], StepStatus::Applied);
+    let operations = mock.operations();
+    assert_eq!(operations.len(), 1);
+    assert!(matches!(operations[0], MockOperation::Displays(_)));
+
+    // 이름으로 지정한 장치가 없으면 백엔드가 실패를 알립니다
+    let profile = desk_profile("USB Audio Headset");
+    let report = apply_profile_settings(&profile, &mock, &mock, &CancellationToken::new()).unwrap();
+    assert!(matches!(report.error(), Some(AppError::ToolFailed { .. })));
+    let current = mock.fixture();
+    assert!(current
+        .audio_devices
+        .iter()
+        .any(|d| d.name == "MacBook Pro Speakers" && d.is_default));
+}
+
+// 오디오가 먼저 적용될 때까지 디스플레이 적용을 끝내지 않는 백엔드
+struct WaitForAudio {
+    mock: MockBackend,
+}
+
+impl DisplayBackend for WaitForAudio {
+    fn enumerate(&self) -> Result<Vec<DisplayInfo>, AppError> {
+        self.mock.enumerate()
+    }
+
+    fn apply(&self, displays: &[DisplayInfo]) -> Result<String, AppError> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !self.mock.operations().iter().any(|op| matches!(op, MockOperation::Audio(_))) {
+            if Instant::now() > deadline {
+                return Err(AppError::Internal { detail: "audio step did not run".to_string() });
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        self.mock.apply(displays)
+    }
+
+    fn connected_serials(&self) -> Result<Vec<String>, AppError> {
+        self.mock.connected_serials()
+    }
+
+    fn name(&self) -> String {
+        self.mock.name()
+    }
+}
+
+#[test]
+fn display_and_audio_apply_concurrently() {
+    let mock = fixture("desk");
+    let profile = desk_profile("USB Audio Headset");
+    let displays = WaitForAudio { mock: mock.clone() };
+
+    let report =
+        apply_profile_settings(&profile, &displays, &mock, &CancellationToken::new()).unwrap();
+    assert!(report.errors.is_empty(), "{:?}", report.errors);
+    assert!(matches!(mock.operations()[..], [MockOperation::Audio(_), MockOperation::Displays(_)]));
+}
+
+// 디스플레이 적용이 끝난 뒤에만 오디오를 적용하는 백엔드 (모니터의 HDMI 출력처럼 디스플레이를 켜야 나타나는 장치)
+struct AudioAfterDisplay {
+    mock: MockBackend,
+}
+
+impl audio::AudioBackend for AudioAfterDisplay {
+    fn enumerate(&self) -> Result<Vec<AudioDevice>, AppError> {
+        audio::AudioBackend::enumerate(&self.mock)
+    }
+
+    fn apply(&self, audio_settings: &AudioSettings) -> Result<String, AppError> {
+        if !self.mock.operations().iter().any(|op| matches!(op, MockOperation::Displays(_))) {
+            return Err(AppError::Internal { detail: "audio started before the display step finished".to_string() });
+        }
+        audio::AudioBackend::apply(&self.mock, audio_settings)
+    }
+
+    fn set_default_device(&self, device_type: &str, device: &str) -> Result<String, AppError> {
+        self.mock.set_default_device(device_type, device)
+    }
+
+    fn set_volume(&self, device_type: &str, volume: Option<u32>, muted: Option<bool>) -> Result<String, AppError> {
+        self.mock.set_volume(device_type, volume, muted)
+    }
+
+    fn name(&self) -> String {
+        audio::AudioBackend::name(&self.mock)
+    }
+}
+
+// 느린 디스플레이 적용 (동시에 실행하면 오디오가 먼저 끝납니다)
+struct SlowDisplays {
+    mock: MockBackend,
+}
+
+impl DisplayBackend for SlowDisplays {
+    fn enumerate(&self) -> Result<Vec<DisplayInfo>, AppError> {
+        self.mock.enumerate()
+    }
+
+    fn apply(&self, displays: &[DisplayInfo]) -> Result<String, AppError> {
+        std::thread::sleep(Duration::from_millis(100));
+        self.mock.apply(displays)
+    }
+
+    fn connected_serials(&self) -> Result<Vec<String>, AppError> {
+        self.mock.connected_serials()
+    }
+
+    fn name(&self) -> String {
+        self.mock.name()
+    }
+}
+
+#[test]
+fn output_follows_display_waits_for_the_display_step() {
+    let mock = fixture("desk");
+    let mut profile = desk_profile("USB Audio Headset");
+    profile.output_follows_display = true;
+    let displays = SlowDisplays { mock: mock.clone() };
+    let audio = AudioAfterDisplay { mock: mock.clone() };
+
+    let report = apply_profile_settings(&profile, &displays, &audio, &CancellationToken::new()).unwrap();
+    assert!(report.errors.is_empty(), "{:?}", report.errors);
+    assert!(matches!(mock.operations()[..], [MockOperation::Displays(_), MockOperation::Audio(_)]));
+}
+
+#[test]
+fn cancel_skips_remaining_steps() {
+    let mock = fixture("desk");
+    let profile = desk_profile("USB Audio Headset");
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+
+    // 시작 전에 취소되었으면 아무것도 적용하지 않습니다
+    let report = apply_profile_settings(&profile, &mock, &mock, &cancel).unwrap();
+    assert!(report.cancelled());
+    assert!(report.steps.values().all(|status| *status == StepStatus::Cancelled));
+    assert!(!report.backend_used.contains_key("audio"));
+    assert!(mock.operations().is_empty());
+
+    // 새 토큰으로는 바로 다시 적용할 수 있습니다
+    let report = apply_profile_settings(&profile, &mock, &mock, &CancellationToken::new()).unwrap();
+    assert!(!report.cancelled());
+    assert_eq!(mock.operations().len(), 2);
+}
+
+// 연결 직후처럼 첫 적용만 실패하는 백엔드
+struct FlakyDisplays {
+    mock: MockBackend,
+    failures: AtomicU32,
+}
+
+impl DisplayBackend for FlakyDisplays {
+    fn enumerate(&self) -> Result<Vec<DisplayInfo>, AppError> {
+        self.mock.enumerate()
+    }
+
+    fn apply(&self, displays: &[DisplayInfo]) -> Result<String, AppError> {
+        if self.failures.load(Ordering::SeqCst) > 0 {
+            self.failures.fetch_sub(1, Ordering::SeqCst);
+            return Err(AppError::ToolFailed {
+                tool: "mock".to_string(),
+                detail: "display service not ready".to_string(),
+            });
+        }
+        self.mock.apply(displays)
+    }
+
+    fn connected_serials(&self) -> Result<Vec<String>, AppError> {
+        self.mock.connected_serials()
+    }
+
+    fn name(&self) -> String {
+        self.mock.name()
+    }
+}
+
+#[test]
+fn transient_display_failure_is_retried() {
+    let mock = fixture("desk");
+    let profile = desk_profile("USB Audio Headset");
+    let displays = FlakyDisplays { mock: mock.clone(), failures: 1.into() };
+
+    let report =
+        apply_profile_settings(&profile, &displays, &mock, &CancellationToken::new()).unwrap();
+    assert_eq!(report.retries["display"], 1);
+    assert_eq!(report.retries["audio"], 0);
+    assert_eq!(mock.operations().len(), 2);
+}
+
+// 적용 도구가 설치되어 있지 않은 백엔드
+struct MissingTool {
+    mock: MockBackend,
+}
+
+impl DisplayBackend for MissingTool {
+    fn enumerate(&self) -> Result<Vec<DisplayInfo>, AppError> {
+        self.mock.enumerate()
+    }
+
+    fn apply(&self, displays: &[DisplayInfo]) -> Result<String, AppError> {
+        self.mock.apply(displays)
+    }
+
+    fn connected_serials(&self) -> Result<Vec<String>, AppError> {
+        self.mock.connected_serials()
+    }
+
+    fn required_tools(&self) -> Vec<String> {
+        vec!["dsm-missing-tool".to_string()]
+    }
+
+    fn name(&self) -> String {
+        self.mock.name()
+    }
+}
+
+#[test]
+fn missing_tools_stop_apply_before_any_step() {
+    let mock = fixture("desk");
+    let profile = desk_profile("USB Audio Headset");
+    let report = validate_profile_against_system(&profile, &mock, &mock).unwrap();
+    assert!(report.displays.iter().all(|display| display.status == DisplayCheck::Unchanged));
+    assert!(report.audio.iter().all(|device| device.status == AudioCheck::Ok));
+    assert!(report.missing_tools.is_empty());
+
+    let displays = MissingTool { mock: mock.clone() };
+    let result = apply_profile_settings(&profile, &displays, &mock, &CancellationToken::new());
+    let Err(AppError::RequiredToolsMissing { tools, report, .. }) = result else {
+        panic!("expected RequiredToolsMissing: {:?}", result);
+    };
+    assert_eq!(tools, "dsm-missing-tool");
+    assert_eq!(report.missing_tools, ["dsm-missing-tool"]);
+    assert_eq!(report.displays.len(), profile.displays.len());
+    // 오디오도 바꾸지 않습니다
+    assert!(mock.operations().is_empty());
+}
+
+// 배치는 적용하지만 밝기는 바꾸지 못하는 백엔드 (DDC/CI를 지원하지 않는 모니터)
+struct NoBrightness {
+    mock: MockBackend,
+}
+
+impl DisplayBackend for NoBrightness {
+    fn enumerate(&self) -> Result<Vec<DisplayInfo>, AppError> {
+        self.mock.enumerate()
+    }
+
+    fn apply(&self, displays: &[DisplayInfo]) -> Result<String, AppError> {
+        self.mock.apply(displays)
+    }
+
+    fn apply_reporting(&self, displays: &[DisplayInfo]) -> Result<DisplayApplied, AppError> {
+        Ok(DisplayApplied {
+            backend: self.mock.apply(displays)?,
+            brightness_skipped: displays
+                .iter()
+                .filter(|display| display.brightness.is_some())
+                .map(|display| display.name.clone())
+                .collect(),
+        })
+    }
+
+    fn connected_serials(&self) -> Result<Vec<String>, AppError> {
+        self.mock.connected_serials()
+    }
+
+    fn name(&self) -> String {
+        self.mock.name()
+    }
+}
+
+#[test]
+fn skipped_brightness_is_a_warning_per_display() {
+    let mock = fixture("desk");
+    let mut profile = desk_profile("USB Audio Headset");
+    for display in profile.displays.iter_mut() {
+        display.brightness = Some(70);
+    }
+    let names: Vec<String> = profile.displays.iter().map(|display| display.name.clone()).collect();
+    let displays = NoBrightness { mock: mock.clone() };
+
+    let report = apply_profile_settings(&profile, &displays, &mock, &CancellationToken::new()).unwrap();
+    assert_eq!(report.backend_used["display"], "mock");
+    let expected: Vec<String> = names.iter().map(|name| format!("{}: 밝기를 바꿀 수 없어 건너뜁니다", name)).collect();
+    assert_eq!(report.warnings, expected);
+
+    let report = apply_profile_partial_settings(&profile, &displays, &mock, &CancellationToken::new()).unwrap();
+    assert_eq!(report.warnings, expected);
+}
+
+#[test]
+fn volume_changes_only_the_default_device() {
+    let mock = fixture("desk");
+    set_device_volume(&mock, "output", 140).unwrap();
+
+    let devices = mock.fixture().audio_devices;
+    let volumes: Vec<Option<u32>> = devices.iter().map(|device| device.volume).collect();
+    assert_eq!(volumes, [Some(100), None, None, None]);
+    assert!(matches!(
+        &mock.operations()[..],
+        [MockOperation::Volume { device_type, volume: Some(100), muted: None }] if device_type == "output"
+    ));
+}
+
+#[test]
+fn toggle_mute_flips_the_default_device() {
+    let mock = fixture("desk");
+    toggle_device_mute(&mock, "input").unwrap();
+    assert_eq!(default_volume_state(&mock, "input").unwrap().muted, Some(true));
+    toggle_device_mute(&mock, "input").unwrap();
+
+    let state = default_volume_state(&mock, "input").unwrap();
+    assert_eq!(state.device.as_deref(), Some("MacBook Pro Microphone"));
+    assert_eq!(state.muted, Some(false));
+    assert_eq!(default_volume_state(&mock, "output").unwrap().muted, None);
+}
@@ -1,5 +1,6 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import './App.css';
 
@@ -33,7 +34,8 @@ interface Profile {
   id: string;
   name: string;
   displays: DisplayInfo[];
-  audio_settings: AudioSettings;
+  // 없으면 오디오 장치를 바꾸지 않는 프로필
+  audio_settings: AudioSettings | null;
   created_at: string;
 }
 
@@ -48,8 +50,17 @@ function App() {
     loadSystemInfo();
     loadProfiles();
     
+    // 모니터 연결/분리 시 백엔드가 보내는 최신 디스플레이 목록으로 갱신
+    const unlistenDisplays = listen<DisplayInfo[]>('displays-changed', (event) => {
+      setDisplays(event.payload);
+    });
+
     // 창 표시
     getCurrentWindow().show();
+
+    return () => {
+      unlistenDisplays.then((unlisten) => unlisten());
+    };
   }, []);
 
   const loadSystemInfo = async () => {
@@ -81,23 +92,9 @@ function App() {
     const name = prompt('프로필 이름을 입력하세요:');
     if (!name) return;
 
-    const outputDevice = audioDevices.find(d => d.device_type === 'output' && d.is_default);
-    const inputDevice = audioDevices.find(d => d.device_type === 'input' && d.is_default);
-
-    const newProfile: Profile = {
-      id: Date.now().toString(),
-      name,
-      displays: [...displays],
-      audio_settings: {
-        output_device: outputDevice?.id,
-        input_device: inputDevice?.id,
-        output_volume: 50,
-        input_volume: 50,
-      },
-      created_at: new Date().toISOString(),
-    };
-
     try {
+      // 백엔드가 적용할 때와 같은 방법으로 읽은 현재 구성
+      const newProfile = await invoke<Profile>('capture_current_profile', { name });
       await invoke('save_profile', { profile: newProfile });
       await loadProfiles();
     } catch (error) {
@@ -112,7 +109,15 @@ function App() {
       alert('프로필이 적용되었습니다!');
     } catch (error) {
       console.error('프로필 적용 실패:', error);
-      alert('프로필 적용에 실패했습니다.');
+      // 일부만 적용되었을 수 있으므로 적용 전 구성으로 되돌릴지 묻습니다
+      if (confirm('프로필 적용에 실패했습니다. 적용 전 구성으로 되돌리시겠습니까?')) {
+        try {
+          await invoke('revert_last_apply');
+          await loadSystemInfo();
+        } catch (revertError) {
+          console.error('되돌리기 실패:', revertError);
+        }
+      }
     }
   };
 
@@ -199,10 +204,14 @@ function App() {
                 <p>생성일: {new Date(profile.created_at).toLocaleDateString()}</p>
                 
                 <div className="profile-details">
-                  <small>
-                    출력: {profile.audio_settings.output_device || '기본'}<br/>
-                    입력: {profile.audio_settings.input_device || '기본'}
-                  </small>
+                  {profile.audio_settings ? (
+                    <small>
+                      출력: {profile.audio_settings.output_device || '기본'}<br/>
+                      입력: {profile.audio_settings.input_device || '기본'}
+                    </small>
+                  ) : (
+                    <small>오디오: 바꾸지 않음</small>
+                  )}
                 </div>
                 
                 <div className="profile-actions">